@@ -1,14 +1,18 @@
 use crate::config::get_individual_spacing;
+use crate::config::AutoHideConfig;
 use crate::config::KomobarConfig;
 use crate::config::KomobarTheme;
 use crate::config::MonitorConfigOrIndex;
 use crate::config::Position;
 use crate::config::PositionConfig;
+use crate::mark_activity;
 use crate::process_hwnd;
 use crate::render::Color32Ext;
 use crate::render::Grouping;
 use crate::render::RenderConfig;
 use crate::render::RenderExt;
+use crate::render::ICON_FONT_FAMILY;
+use crate::widgets::group::Group;
 use crate::widgets::komorebi::Komorebi;
 use crate::widgets::komorebi::KomorebiNotificationState;
 use crate::widgets::widget::BarWidget;
@@ -16,10 +20,10 @@ use crate::widgets::widget::WidgetConfig;
 use crate::KomorebiEvent;
 use crate::BAR_HEIGHT;
 use crate::DEFAULT_PADDING;
+use crate::IDLE_REPAINT_INTERVAL;
+use crate::IDLE_TIMEOUT;
 use crate::MAX_LABEL_WIDTH;
-use crate::MONITOR_LEFT;
-use crate::MONITOR_RIGHT;
-use crate::MONITOR_TOP;
+use crate::REPAINT_INTERVAL;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::TryRecvError;
 use eframe::egui::Align;
@@ -34,12 +38,18 @@ use eframe::egui::FontFamily;
 use eframe::egui::FontId;
 use eframe::egui::Frame;
 use eframe::egui::Id;
+use eframe::egui::Label;
 use eframe::egui::Layout;
 use eframe::egui::Margin;
+use eframe::egui::Order;
 use eframe::egui::Rgba;
+use eframe::egui::RichText;
+use eframe::egui::Sense;
 use eframe::egui::Style;
 use eframe::egui::TextStyle;
 use eframe::egui::Vec2;
+use eframe::egui::ViewportBuilder;
+use eframe::egui::ViewportId;
 use eframe::egui::Visuals;
 use font_loader::system_fonts;
 use font_loader::system_fonts::FontPropertyBuilder;
@@ -47,6 +57,7 @@ use komorebi_client::KomorebiTheme;
 use komorebi_client::MonitorNotification;
 use komorebi_client::NotificationEvent;
 use komorebi_client::SocketMessage;
+use komorebi_client::WindowsApi;
 use komorebi_themes::catppuccin_egui;
 use komorebi_themes::Base16Value;
 use komorebi_themes::Catppuccin;
@@ -57,24 +68,42 @@ use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 pub struct Komobar {
+    /// Title of the window of this bar, used to find its handle
+    pub title: String,
+    pub viewport_id: ViewportId,
     pub hwnd: Option<isize>,
     pub monitor_index: Option<usize>,
     pub disabled: bool,
     pub config: KomobarConfig,
     pub render_config: Rc<RefCell<RenderConfig>>,
     pub komorebi_notification_state: Option<Rc<RefCell<KomorebiNotificationState>>>,
+    pub monitor_size: komorebi_client::Rect,
     pub left_widgets: Vec<Box<dyn BarWidget>>,
     pub center_widgets: Vec<Box<dyn BarWidget>>,
     pub right_widgets: Vec<Box<dyn BarWidget>>,
     pub rx_gui: Receiver<KomorebiEvent>,
-    pub rx_config: Receiver<KomobarConfig>,
+    pub rx_config: Receiver<Result<KomobarConfig, String>>,
     pub bg_color: Rc<RefCell<Color32>>,
     pub bg_color_with_alpha: Rc<RefCell<Color32>>,
     pub scale_factor: f32,
     pub size_rect: komorebi_client::Rect,
     pub work_area_offset: komorebi_client::Rect,
+    /// Bars shown on other monitors, which are drawn in their own viewports
+    pub bars: Vec<Komobar>,
+    /// Why the configuration file could not be parsed, shown in the bar until it is fixed
+    pub config_error: Option<String>,
+    /// Whether any of the auto-hide reveal conditions was true for the latest komorebi state
+    reveal_condition: bool,
+    /// The last time an auto-hiding bar had a reason to be revealed
+    revealed_at: Option<Instant>,
+    /// How far an auto-hiding bar has slid in, from 0.0 (hidden) to 1.0 (revealed)
+    reveal_progress: f32,
+    /// The style of this bar, which is restored before it is drawn since all bars share a context
+    style: Arc<Style>,
     applied_theme_on_first_frame: bool,
 }
 
@@ -197,14 +226,46 @@ impl Komobar {
         ctx: &Context,
         previous_notification_state: Option<Rc<RefCell<KomorebiNotificationState>>>,
     ) {
+        // start from the style of this bar, since another bar may have been drawn last
+        ctx.set_style(self.style.clone());
+
         MAX_LABEL_WIDTH.store(
             self.config.max_label_width.unwrap_or(400.0) as i32,
             Ordering::SeqCst,
         );
 
+        let refresh = self.config.refresh.unwrap_or_default();
+
+        REPAINT_INTERVAL.store(refresh.repaint_interval.unwrap_or(1000), Ordering::SeqCst);
+        IDLE_REPAINT_INTERVAL.store(
+            refresh.idle_repaint_interval.unwrap_or(10000),
+            Ordering::SeqCst,
+        );
+        IDLE_TIMEOUT.store(refresh.idle_timeout.unwrap_or_default(), Ordering::SeqCst);
+
+        if let Some(data_refresh_interval) = refresh.data_refresh_interval {
+            self.config
+                .left_widgets
+                .iter_mut()
+                .chain(self.config.center_widgets.iter_mut().flatten())
+                .chain(self.config.right_widgets.iter_mut())
+                .for_each(|widget| widget.set_default_data_refresh_interval(data_refresh_interval));
+        }
+
         if let Some(font_family) = &self.config.font_family {
             tracing::info!("attempting to add custom font family: {font_family}");
-            Self::add_custom_font(ctx, font_family);
+        }
+
+        if let Some(icon_font_family) = &self.config.icon_font_family {
+            tracing::info!("attempting to add custom icon font family: {icon_font_family}");
+        }
+
+        if self.config.font_family.is_some() || self.config.icon_font_family.is_some() {
+            Self::add_custom_fonts(
+                ctx,
+                self.config.font_family.as_deref(),
+                self.config.icon_font_family.as_deref(),
+            );
         }
 
         // Update the `size_rect` so that the bar position can be changed on the EGUI update
@@ -218,88 +279,21 @@ impl Komobar {
             Self::set_font_size(ctx, *font_size);
         }
 
-        self.render_config.replace((&self.config).new_renderconfig(
-            ctx,
-            *self.bg_color.borrow(),
-            self.config.icon_scale,
-        ));
+        let mut render_config =
+            (&self.config).new_renderconfig(ctx, *self.bg_color.borrow(), self.config.icon_scale);
+        render_config.monitor_top = self.monitor_size.top;
+        self.render_config.replace(render_config);
 
         let mut komorebi_notification_state = previous_notification_state;
-        let mut komorebi_widgets = Vec::new();
-
-        for (idx, widget_config) in self.config.left_widgets.iter().enumerate() {
-            if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Left));
-            }
-        }
 
-        if let Some(center_widgets) = &self.config.center_widgets {
-            for (idx, widget_config) in center_widgets.iter().enumerate() {
-                if let WidgetConfig::Komorebi(config) = widget_config {
-                    komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Center));
-                }
-            }
-        }
-
-        for (idx, widget_config) in self.config.right_widgets.iter().enumerate() {
-            if let WidgetConfig::Komorebi(config) = widget_config {
-                komorebi_widgets.push((Komorebi::from(config), idx, Alignment::Right));
-            }
-        }
-
-        let mut left_widgets = self
-            .config
-            .left_widgets
-            .iter()
-            .filter(|config| config.enabled())
-            .map(|config| config.as_boxed_bar_widget())
-            .collect::<Vec<Box<dyn BarWidget>>>();
-
-        let mut center_widgets = match &self.config.center_widgets {
-            Some(center_widgets) => center_widgets
-                .iter()
-                .filter(|config| config.enabled())
-                .map(|config| config.as_boxed_bar_widget())
-                .collect::<Vec<Box<dyn BarWidget>>>(),
-            None => vec![],
-        };
-
-        let mut right_widgets = self
-            .config
-            .right_widgets
-            .iter()
-            .filter(|config| config.enabled())
-            .map(|config| config.as_boxed_bar_widget())
-            .collect::<Vec<Box<dyn BarWidget>>>();
-
-        if !komorebi_widgets.is_empty() {
-            komorebi_widgets
-                .into_iter()
-                .for_each(|(mut widget, idx, side)| {
-                    match komorebi_notification_state {
-                        None => {
-                            komorebi_notification_state =
-                                Some(widget.komorebi_notification_state.clone());
-                        }
-                        Some(ref previous) => {
-                            if widget.workspaces.is_some_and(|w| w.enable) {
-                                previous.borrow_mut().update_from_config(
-                                    &widget.komorebi_notification_state.borrow(),
-                                );
-                            }
-
-                            widget.komorebi_notification_state = previous.clone();
-                        }
-                    }
-
-                    let boxed: Box<dyn BarWidget> = Box::new(widget);
-                    match side {
-                        Alignment::Left => left_widgets[idx] = boxed,
-                        Alignment::Center => center_widgets[idx] = boxed,
-                        Alignment::Right => right_widgets[idx] = boxed,
-                    }
-                });
-        }
+        let left_widgets =
+            boxed_widgets(&self.config.left_widgets, &mut komorebi_notification_state);
+        let center_widgets = boxed_widgets(
+            self.config.center_widgets.as_deref().unwrap_or_default(),
+            &mut komorebi_notification_state,
+        );
+        let mut right_widgets =
+            boxed_widgets(&self.config.right_widgets, &mut komorebi_notification_state);
 
         right_widgets.reverse();
 
@@ -329,7 +323,7 @@ impl Komobar {
                 if new_rect != prev_rect {
                     self.work_area_offset = *new_rect;
                     if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, *new_rect),
+                        &SocketMessage::MonitorWorkAreaOffset(usr_monitor_index, *new_rect),
                     ) {
                         tracing::error!(
                             "error applying work area offset to monitor '{}': {}",
@@ -341,28 +335,32 @@ impl Komobar {
                     }
                 }
             } else if let Some(height) = self.config.height.or(Some(BAR_HEIGHT)) {
+                // An auto-hiding bar only reserves space for the strip it leaves on screen
+                let height = match &self.config.auto_hide {
+                    Some(auto_hide) if auto_hide.enable => auto_hide.hidden_height.unwrap_or(2.0),
+                    _ => height,
+                } * self.dpi_scale();
+
                 // We only add the `bottom_margin` to the work_area_offset since the top margin is
                 // already considered on the `size_rect.top`
-                let bottom_margin = self
-                    .config
-                    .margin
-                    .as_ref()
-                    .map_or(0, |v| v.to_individual(0.0).bottom as i32);
+                let bottom_margin = self.config.margin.as_ref().map_or(0, |v| {
+                    (v.to_individual(0.0).bottom * self.dpi_scale()) as i32
+                });
                 let new_rect = komorebi_client::Rect {
                     left: 0,
                     top: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
+                        + (self.size_rect.top - self.monitor_size.top)
                         + bottom_margin,
                     right: 0,
                     bottom: (height as i32)
-                        + (self.size_rect.top - MONITOR_TOP.load(Ordering::SeqCst))
+                        + (self.size_rect.top - self.monitor_size.top)
                         + bottom_margin,
                 };
 
                 if new_rect != self.work_area_offset {
                     self.work_area_offset = new_rect;
                     if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, new_rect),
+                        &SocketMessage::MonitorWorkAreaOffset(usr_monitor_index, new_rect),
                     ) {
                         tracing::error!(
                             "error applying work area offset to monitor '{monitor_index}': {error}"
@@ -383,28 +381,83 @@ impl Komobar {
         tracing::info!("widget configuration options applied");
 
         self.komorebi_notification_state = komorebi_notification_state;
+        self.style = ctx.style();
+    }
+
+    /// Creates, updates or removes the bars shown on other monitors to match `config.bars`
+    fn update_secondary_bars(&mut self, ctx: &Context) {
+        let mut previous = std::mem::take(&mut self.bars);
+
+        let bars = self
+            .config
+            .bars
+            .iter()
+            .flatten()
+            .map(|overrides| {
+                let config = self.config.with_overrides(overrides);
+                let usr_monitor_index = match &config.monitor {
+                    MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
+                    MonitorConfigOrIndex::Index(idx) => *idx,
+                };
+                let title = format!("komorebi-bar-{usr_monitor_index}");
+
+                match previous.iter().position(|bar| bar.title == title) {
+                    Some(idx) => {
+                        let mut bar = previous.swap_remove(idx);
+                        bar.config = config;
+                        bar.apply_config(ctx, bar.komorebi_notification_state.clone());
+                        bar
+                    }
+                    None => Self::secondary(
+                        ctx,
+                        title,
+                        config,
+                        monitor_size(usr_monitor_index).unwrap_or_default(),
+                    ),
+                }
+            })
+            .collect();
+
+        self.bars = bars;
+    }
+
+    fn viewport_builder(&self) -> ViewportBuilder {
+        ViewportBuilder::default()
+            .with_title(&self.title)
+            .with_decorations(false)
+            .with_transparent(true)
+            .with_taskbar(false)
+    }
+
+    /// The factor by which the height and margins of the bar are scaled on its monitor
+    fn dpi_scale(&self) -> f32 {
+        if self.config.dpi_scaling.unwrap_or_default() {
+            self.scale_factor
+        } else {
+            1.0
+        }
     }
 
     /// Updates the `size_rect` field. Returns a bool indicating if the field was changed or not
     fn update_size_rect(&mut self) {
         let position = self.config.position.clone().unwrap_or(PositionConfig {
             start: Some(Position {
-                x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
-                y: MONITOR_TOP.load(Ordering::SeqCst) as f32,
+                x: self.monitor_size.left as f32,
+                y: self.monitor_size.top as f32,
             }),
             end: Some(Position {
-                x: MONITOR_RIGHT.load(Ordering::SeqCst) as f32,
+                x: self.monitor_size.right as f32,
                 y: BAR_HEIGHT,
             }),
         });
 
         let mut start = position.start.unwrap_or(Position {
-            x: MONITOR_LEFT.load(Ordering::SeqCst) as f32,
-            y: MONITOR_TOP.load(Ordering::SeqCst) as f32,
+            x: self.monitor_size.left as f32,
+            y: self.monitor_size.top as f32,
         });
 
         let mut end = position.end.unwrap_or(Position {
-            x: MONITOR_RIGHT.load(Ordering::SeqCst) as f32,
+            x: self.monitor_size.right as f32,
             y: BAR_HEIGHT,
         });
 
@@ -412,11 +465,13 @@ impl Komobar {
             end.y = height;
         }
 
+        let scale = self.dpi_scale();
         let margin = get_individual_spacing(0.0, &self.config.margin);
 
-        start.y += margin.top;
-        start.x += margin.left;
-        end.x -= margin.left + margin.right;
+        end.y *= scale;
+        start.y += margin.top * scale;
+        start.x += margin.left * scale;
+        end.x -= (margin.left + margin.right) * scale;
 
         if end.y == 0.0 {
             tracing::warn!("position.end.y is set to 0.0 which will make your bar invisible on a config reload - this is usually set to 50.0 by default")
@@ -520,16 +575,64 @@ impl Komobar {
     pub fn new(
         cc: &eframe::CreationContext<'_>,
         rx_gui: Receiver<KomorebiEvent>,
-        rx_config: Receiver<KomobarConfig>,
+        rx_config: Receiver<Result<KomobarConfig, String>>,
+        config: KomobarConfig,
+        monitor_size: komorebi_client::Rect,
+    ) -> Self {
+        // this is the title eframe gives the root viewport, after the app name
+        let mut komobar = Self::with_channels(
+            &cc.egui_ctx,
+            String::from("komorebi-bar"),
+            ViewportId::ROOT,
+            rx_gui,
+            rx_config,
+            config,
+            monitor_size,
+        );
+
+        komobar.update_secondary_bars(&cc.egui_ctx);
+
+        komobar
+    }
+
+    /// A bar shown on another monitor, which receives its configuration and komorebi
+    /// notifications from the root bar rather than from channels of its own
+    fn secondary(
+        ctx: &Context,
+        title: String,
+        config: KomobarConfig,
+        monitor_size: komorebi_client::Rect,
+    ) -> Self {
+        Self::with_channels(
+            ctx,
+            title.clone(),
+            ViewportId::from_hash_of(title),
+            crossbeam_channel::never(),
+            crossbeam_channel::never(),
+            config,
+            monitor_size,
+        )
+    }
+
+    fn with_channels(
+        ctx: &Context,
+        title: String,
+        viewport_id: ViewportId,
+        rx_gui: Receiver<KomorebiEvent>,
+        rx_config: Receiver<Result<KomobarConfig, String>>,
         config: KomobarConfig,
+        monitor_size: komorebi_client::Rect,
     ) -> Self {
         let mut komobar = Self {
-            hwnd: process_hwnd(),
+            hwnd: process_hwnd(&title),
+            title,
+            viewport_id,
             monitor_index: None,
             disabled: false,
             config,
             render_config: Rc::new(RefCell::new(RenderConfig::new())),
             komorebi_notification_state: None,
+            monitor_size,
             left_widgets: vec![],
             center_widgets: vec![],
             right_widgets: vec![],
@@ -537,15 +640,21 @@ impl Komobar {
             rx_config,
             bg_color: Rc::new(RefCell::new(Style::default().visuals.panel_fill)),
             bg_color_with_alpha: Rc::new(RefCell::new(Style::default().visuals.panel_fill)),
-            scale_factor: cc.egui_ctx.native_pixels_per_point().unwrap_or(1.0),
+            scale_factor: ctx.native_pixels_per_point().unwrap_or(1.0),
             size_rect: komorebi_client::Rect::default(),
             work_area_offset: komorebi_client::Rect::default(),
+            bars: vec![],
+            config_error: None,
+            reveal_condition: false,
+            revealed_at: None,
+            reveal_progress: 1.0,
+            style: ctx.style(),
             applied_theme_on_first_frame: false,
         };
 
-        komobar.apply_config(&cc.egui_ctx, None);
+        komobar.apply_config(ctx, None);
         // needs a double apply the first time for some reason
-        komobar.apply_config(&cc.egui_ctx, None);
+        komobar.apply_config(ctx, None);
 
         komobar
     }
@@ -575,7 +684,7 @@ impl Komobar {
         });
     }
 
-    fn add_custom_font(ctx: &Context, name: &str) {
+    fn add_custom_fonts(ctx: &Context, font_family: Option<&str>, icon_font_family: Option<&str>) {
         let mut fonts = FontDefinitions::default();
         egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Regular);
 
@@ -601,20 +710,49 @@ impl Komobar {
             }
         }
 
-        let property = FontPropertyBuilder::new().family(name).build();
+        if let Some(name) = font_family {
+            let property = FontPropertyBuilder::new().family(name).build();
 
-        if let Some((font, _)) = system_fonts::get(&property) {
-            fonts
-                .font_data
-                .insert(name.to_owned(), Arc::new(FontData::from_owned(font)));
+            if let Some((font, _)) = system_fonts::get(&property) {
+                fonts
+                    .font_data
+                    .insert(name.to_owned(), Arc::new(FontData::from_owned(font)));
+
+                for family in [FontFamily::Proportional, FontFamily::Monospace] {
+                    fonts
+                        .families
+                        .entry(family)
+                        .or_default()
+                        .insert(0, name.to_owned());
+                }
+            }
+        }
+
+        if let Some(name) = icon_font_family {
+            // the text fonts, which include the bundled icons, are the fallbacks of the icon font;
+            // the family has to exist even if the font can't be found since it is referenced by
+            // the icon font id
+            let mut icon_fonts = fonts
+                .families
+                .get(&FontFamily::Proportional)
+                .cloned()
+                .unwrap_or_default();
+
+            let property = FontPropertyBuilder::new().family(name).build();
 
-            for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            if let Some((font, _)) = system_fonts::get(&property) {
                 fonts
-                    .families
-                    .entry(family)
-                    .or_default()
-                    .insert(0, name.to_owned());
+                    .font_data
+                    .insert(name.to_owned(), Arc::new(FontData::from_owned(font)));
+
+                icon_fonts.insert(0, name.to_owned());
+            } else {
+                tracing::warn!("could not find the icon font family: {name}");
             }
+
+            fonts
+                .families
+                .insert(FontFamily::Name(ICON_FONT_FAMILY.into()), icon_fonts);
         }
 
         // Tell egui to use these fonts:
@@ -624,7 +762,7 @@ impl Komobar {
     pub fn position_bar(&self) {
         if let Some(hwnd) = self.hwnd {
             let window = komorebi_client::Window::from(hwnd);
-            match window.set_position(&self.size_rect, false) {
+            match window.set_position(&self.window_rect(), false) {
                 Ok(_) => {
                     tracing::info!("updated bar position");
                 }
@@ -635,11 +773,68 @@ impl Komobar {
         }
     }
 
+    /// Reveals an auto-hiding bar while the mouse is over it or one of its reveal conditions is
+    /// true, and hides it again once `hide_delay` has passed without either
+    fn update_auto_hide(&mut self, ctx: &Context, auto_hide: &AutoHideConfig) {
+        // a hidden bar receives no input, so the cursor has to be polled
+        let hovered = WindowsApi::cursor_pos().is_ok_and(|cursor| {
+            let rect = self.window_rect();
+            cursor.x >= rect.left
+                && cursor.x < rect.left + rect.right
+                && cursor.y >= rect.top
+                && cursor.y < rect.top + rect.bottom
+        });
+
+        if hovered || self.reveal_condition {
+            self.revealed_at = Some(Instant::now());
+        }
+
+        let hide_delay = Duration::from_millis(auto_hide.hide_delay.unwrap_or(500));
+        let revealed = self
+            .revealed_at
+            .is_some_and(|revealed_at| revealed_at.elapsed() < hide_delay);
+
+        self.reveal_progress = ctx.animate_bool_with_time(
+            Id::new(("auto_hide", self.viewport_id)),
+            revealed,
+            auto_hide.animation_duration.unwrap_or(150) as f32 / 1000.0,
+        );
+
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    /// The rect of the window of this bar, which is shorter than `size_rect` while an
+    /// auto-hiding bar is hidden or sliding in
+    fn window_rect(&self) -> komorebi_client::Rect {
+        let Some(auto_hide) = self.config.auto_hide.as_ref().filter(|a| a.enable) else {
+            return self.size_rect;
+        };
+
+        // windows can't be 0 pixels high, so a fully hidden bar keeps a transparent pixel
+        let hidden_height =
+            ((auto_hide.hidden_height.unwrap_or(2.0) * self.dpi_scale()) as i32).max(1);
+        let height = hidden_height
+            + ((self.size_rect.bottom - hidden_height) as f32 * self.reveal_progress) as i32;
+
+        // bars in the bottom half of the monitor slide up from its bottom edge
+        let at_bottom = self.size_rect.top + self.size_rect.bottom / 2
+            > self.monitor_size.top + self.monitor_size.bottom / 2;
+
+        komorebi_client::Rect {
+            left: self.size_rect.left,
+            top: if at_bottom {
+                self.size_rect.top + self.size_rect.bottom - height
+            } else {
+                self.size_rect.top
+            },
+            right: self.size_rect.right,
+            bottom: height,
+        }
+    }
+
     fn update_monitor_coordinates(&mut self, monitor_size: &komorebi_client::Rect) {
         // Store the new monitor coordinates
-        MONITOR_TOP.store(monitor_size.top, Ordering::SeqCst);
-        MONITOR_LEFT.store(monitor_size.left, Ordering::SeqCst);
-        MONITOR_RIGHT.store(monitor_size.right, Ordering::SeqCst);
+        self.monitor_size = *monitor_size;
 
         // Since the `config.position` is changed on `main.rs` we need to update it here.
         // If the user had set up some `start` position, that will be overriden here
@@ -659,30 +854,81 @@ impl eframe::App for Komobar {
     }
 
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        if let Ok(updated_config) = self.rx_config.try_recv() {
+            mark_activity();
+
+            match updated_config {
+                Ok(updated_config) => {
+                    self.config = updated_config;
+                    self.apply_config(ctx, self.komorebi_notification_state.clone());
+                    self.update_secondary_bars(ctx);
+                    self.set_config_error(None);
+                }
+                // keep the current configuration but let the user know that their changes
+                // could not be applied
+                Err(error) => self.set_config_error(Some(error)),
+            }
+        }
+
+        let event = match self.rx_gui.try_recv() {
+            Ok(event) => Some(event),
+            Err(error) => {
+                if let TryRecvError::Disconnected = error {
+                    tracing::error!(
+                        "failed to receive komorebi notification on gui thread: {error}"
+                    );
+                }
+
+                None
+            }
+        };
+
+        self.update_bar(ctx, event.as_ref());
+
+        for bar in &mut self.bars {
+            ctx.show_viewport_immediate(bar.viewport_id, bar.viewport_builder(), |ctx, _| {
+                bar.update_bar(ctx, event.as_ref());
+            });
+        }
+    }
+}
+
+impl Komobar {
+    fn set_config_error(&mut self, error: Option<String>) {
+        for bar in &mut self.bars {
+            bar.config_error.clone_from(&error);
+        }
+
+        self.config_error = error;
+    }
+
+    /// Handles a komorebi event, if there is one, and draws this bar
+    fn update_bar(&mut self, ctx: &Context, event: Option<&KomorebiEvent>) {
+        // all bars share the same context, so each of them has to bring its own style
+        ctx.set_style(self.style.clone());
+
         if self.hwnd.is_none() {
-            self.hwnd = process_hwnd();
+            self.hwnd = process_hwnd(&self.title);
         }
 
         if self.scale_factor != ctx.native_pixels_per_point().unwrap_or(1.0) {
             self.scale_factor = ctx.native_pixels_per_point().unwrap_or(1.0);
+            tracing::info!("scale factor changed to {}", self.scale_factor);
             self.apply_config(ctx, self.komorebi_notification_state.clone());
         }
 
-        if let Ok(updated_config) = self.rx_config.try_recv() {
-            self.config = updated_config;
-            self.apply_config(ctx, self.komorebi_notification_state.clone());
+        if ctx.input(|input| {
+            input.pointer.is_moving()
+                || input.pointer.any_down()
+                || input.raw_scroll_delta != Vec2::ZERO
+        }) {
+            mark_activity();
         }
 
-        match self.rx_gui.try_recv() {
-            Err(error) => match error {
-                TryRecvError::Empty => {}
-                TryRecvError::Disconnected => {
-                    tracing::error!(
-                        "failed to receive komorebi notification on gui thread: {error}"
-                    );
-                }
-            },
-            Ok(KomorebiEvent::Notification(notification)) => {
+        match event {
+            None => {}
+            Some(KomorebiEvent::Notification(notification)) => {
+                mark_activity();
                 let state = &notification.state;
                 let usr_monitor_index = match &self.config.monitor {
                     MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
@@ -747,19 +993,22 @@ impl eframe::App for Komobar {
                     return;
                 }
 
+                let monitor_index = self.monitor_index.expect("should have a monitor index");
+                self.reveal_condition = self
+                    .config
+                    .auto_hide
+                    .as_ref()
+                    .and_then(|auto_hide| auto_hide.reveal_when.as_ref())
+                    .is_some_and(|conditions| {
+                        conditions
+                            .iter()
+                            .any(|condition| condition.is_met(state, monitor_index))
+                    });
+
                 // Check if monitor coordinates/size has changed
                 if let Some(monitor_index) = self.monitor_index {
                     let monitor_size = state.monitors.elements()[monitor_index].size();
-                    let top = MONITOR_TOP.load(Ordering::SeqCst);
-                    let left = MONITOR_LEFT.load(Ordering::SeqCst);
-                    let right = MONITOR_RIGHT.load(Ordering::SeqCst);
-                    let rect = komorebi_client::Rect {
-                        top,
-                        left,
-                        bottom: monitor_size.bottom,
-                        right,
-                    };
-                    if *monitor_size != rect {
+                    if *monitor_size != self.monitor_size {
                         tracing::info!(
                             "Monitor coordinates/size has changed, storing new coordinates: {:#?}",
                             monitor_size
@@ -794,11 +1043,19 @@ impl eframe::App for Komobar {
                     self.position_bar();
                 }
             }
-            Ok(KomorebiEvent::Reconnect) => {
+            Some(KomorebiEvent::Reconnect) => {
                 if let Some(monitor_index) = self.monitor_index {
-                    if let Err(error) = komorebi_client::send_message(
-                        &SocketMessage::MonitorWorkAreaOffset(monitor_index, self.work_area_offset),
-                    ) {
+                    let usr_monitor_index = match &self.config.monitor {
+                        MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
+                        MonitorConfigOrIndex::Index(idx) => *idx,
+                    };
+
+                    if let Err(error) =
+                        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+                            usr_monitor_index,
+                            self.work_area_offset,
+                        ))
+                    {
                         tracing::error!(
                             "error applying work area offset to monitor '{}': {}",
                             monitor_index,
@@ -822,6 +1079,11 @@ impl eframe::App for Komobar {
             self.applied_theme_on_first_frame = true;
         }
 
+        let auto_hide = self.config.auto_hide.clone().filter(|a| a.enable);
+        if let Some(auto_hide) = &auto_hide {
+            self.update_auto_hide(ctx, auto_hide);
+        }
+
         // Check if egui's Window size is the expected one, if not, update it
         if let Some(current_rect) = ctx.input(|i| i.viewport().outer_rect) {
             // Get the correct size according to scale factor
@@ -832,11 +1094,22 @@ impl eframe::App for Komobar {
                 bottom: ((current_rect.max.y - current_rect.min.y) * self.scale_factor) as i32,
             };
 
-            if self.size_rect != current_rect {
+            if self.window_rect() != current_rect {
                 self.position_bar();
             }
         }
 
+        if self.reveal_progress == 0.0 {
+            // only the strip is left on screen while the bar is hidden
+            if auto_hide.is_some_and(|a| a.hidden_height.unwrap_or(2.0) > 0.0) {
+                CentralPanel::default()
+                    .frame(Frame::NONE.fill(*self.bg_color_with_alpha.borrow()))
+                    .show(ctx, |_| {});
+            }
+
+            return;
+        }
+
         let frame = match &self.config.padding {
             None => {
                 if let Some(frame) = &self.config.frame {
@@ -865,6 +1138,8 @@ impl eframe::App for Komobar {
             }
         };
 
+        self.style = ctx.style();
+
         let mut render_config = self.render_config.borrow_mut();
 
         let frame = render_config.change_frame_on_bar(frame, &ctx.style());
@@ -1000,9 +1275,104 @@ impl eframe::App for Komobar {
                     });
             }
         });
+
+        if let Some(error) = &self.config_error {
+            let max_width = ctx.screen_rect().width() * 0.8;
+            let text = RichText::new(format!(
+                "{} configuration error: {error}",
+                egui_phosphor::regular::WARNING
+            ))
+            .color(ctx.style().visuals.panel_fill);
+
+            // drawn over the widgets, which still use the last valid configuration
+            let dismissed = Area::new(Id::new("config_error"))
+                .order(Order::Foreground)
+                .anchor(Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.set_max_width(max_width);
+
+                    Frame::NONE
+                        .fill(ctx.style().visuals.error_fg_color)
+                        .corner_radius(ctx.style().visuals.widgets.noninteractive.corner_radius)
+                        .inner_margin(Margin::symmetric(8, 2))
+                        .show(ui, |ui| {
+                            ui.add(Label::new(text).truncate().sense(Sense::click()))
+                                .on_hover_text(format!("{error}\n\nclick to dismiss"))
+                                .clicked()
+                        })
+                        .inner
+                })
+                .inner;
+
+            if dismissed {
+                self.config_error = None;
+            }
+        }
     }
 }
 
+/// Builds the enabled widgets of a widget list. All komorebi widgets, including those inside of
+/// groups, share a single notification state, which is created by the first one unless there is
+/// a previous one to carry over
+fn boxed_widgets(
+    configs: &[WidgetConfig],
+    komorebi_notification_state: &mut Option<Rc<RefCell<KomorebiNotificationState>>>,
+) -> Vec<Box<dyn BarWidget>> {
+    configs
+        .iter()
+        .filter(|config| config.enabled())
+        .map(|config| -> Box<dyn BarWidget> {
+            match config {
+                WidgetConfig::Komorebi(config) => {
+                    let mut widget = Komorebi::from(config);
+
+                    match komorebi_notification_state {
+                        None => {
+                            *komorebi_notification_state =
+                                Some(widget.komorebi_notification_state.clone());
+                        }
+                        Some(previous) => {
+                            if widget.workspaces.is_some_and(|w| w.enable) {
+                                previous.borrow_mut().update_from_config(
+                                    &widget.komorebi_notification_state.borrow(),
+                                );
+                            }
+
+                            widget.komorebi_notification_state = previous.clone();
+                        }
+                    }
+
+                    Box::new(widget)
+                }
+                WidgetConfig::Group(group) => Box::new(Group::new(
+                    group,
+                    boxed_widgets(&group.widgets, komorebi_notification_state),
+                )),
+                config => config.as_boxed_bar_widget(),
+            }
+        })
+        .collect()
+}
+
+/// The size of the monitor with the given user index, according to komorebi
+fn monitor_size(usr_monitor_index: usize) -> Option<komorebi_client::Rect> {
+    let state = serde_json::from_str::<komorebi_client::State>(
+        &komorebi_client::send_query(&SocketMessage::State).ok()?,
+    )
+    .ok()?;
+
+    let monitor_index = state
+        .monitor_usr_idx_map
+        .get(&usr_monitor_index)
+        .map_or(usr_monitor_index, |i| *i);
+
+    state
+        .monitors
+        .elements()
+        .get(monitor_index)
+        .map(|monitor| *monitor.size())
+}
+
 #[derive(Copy, Clone)]
 pub enum Alignment {
     Left,
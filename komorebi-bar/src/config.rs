@@ -80,6 +80,14 @@ pub struct KomobarConfig {
     pub font_size: Option<f32>,
     /// Scale of the icons relative to the font_size [[1.0-2.0]]. (default: 1.4)
     pub icon_scale: Option<f32>,
+    /// Font family of the icons, e.g. a Nerd Font, which falls back to the bundled icons for any
+    /// glyph it doesn't have (default: font_family)
+    pub icon_font_family: Option<String>,
+    /// Font size of the icons, which takes precedence over icon_scale (default: font_size * icon_scale)
+    pub icon_font_size: Option<f32>,
+    /// Scale the height and margins of the bar by the DPI scaling of its monitor, so that it has
+    /// the same size relative to its text on every monitor (default: false)
+    pub dpi_scaling: Option<bool>,
     /// Max label width before text truncation (default: 400.0)
     pub max_label_width: Option<f32>,
     /// Theme
@@ -96,6 +104,14 @@ pub struct KomobarConfig {
     pub center_widgets: Option<Vec<WidgetConfig>>,
     /// Right side widgets (ordered left-to-right)
     pub right_widgets: Vec<WidgetConfig>,
+    /// Repaint and data refresh options, e.g. to save battery on laptops
+    pub refresh: Option<RefreshConfig>,
+    /// Hide the bar until the mouse touches the edge of the screen it is on
+    pub auto_hide: Option<AutoHideConfig>,
+    /// Additional bars shown on other monitors by this same process. Each bar inherits every
+    /// option of this configuration which it does not override, except for the position, which
+    /// defaults to the top of its own monitor
+    pub bars: Option<Vec<BarConfig>>,
 }
 
 impl KomobarConfig {
@@ -126,13 +142,141 @@ impl KomobarConfig {
         }
     }
 
+    /// The configuration of an additional bar, with its overrides applied on top of this one
+    pub fn with_overrides(&self, overrides: &BarConfig) -> Self {
+        Self {
+            height: overrides.height.or(self.height),
+            padding: overrides.padding.clone().or(self.padding.clone()),
+            margin: overrides.margin.clone().or(self.margin.clone()),
+            position: overrides.position.clone(),
+            frame: overrides.frame.clone().or(self.frame.clone()),
+            monitor: overrides.monitor.clone(),
+            font_size: overrides.font_size.or(self.font_size),
+            icon_scale: overrides.icon_scale.or(self.icon_scale),
+            icon_font_size: overrides.icon_font_size.or(self.icon_font_size),
+            theme: overrides.theme.or(self.theme),
+            transparency_alpha: overrides.transparency_alpha.or(self.transparency_alpha),
+            widget_spacing: overrides.widget_spacing.or(self.widget_spacing),
+            grouping: overrides.grouping.or(self.grouping),
+            auto_hide: overrides.auto_hide.clone().or(self.auto_hide.clone()),
+            left_widgets: overrides
+                .left_widgets
+                .clone()
+                .unwrap_or_else(|| self.left_widgets.clone()),
+            center_widgets: overrides
+                .center_widgets
+                .clone()
+                .or(self.center_widgets.clone()),
+            right_widgets: overrides
+                .right_widgets
+                .clone()
+                .unwrap_or_else(|| self.right_widgets.clone()),
+            bars: None,
+            ..self.clone()
+        }
+    }
+
     pub fn show_all_icons_on_komorebi_workspace(widgets: &[WidgetConfig]) -> bool {
         widgets
             .iter()
             .any(|w| matches!(w, WidgetConfig::Komorebi(config) if config.workspaces.is_some_and(|w| w.enable && w.display.is_some_and(|s| matches!(s,
             WorkspacesDisplayFormat::AllIcons
             | WorkspacesDisplayFormat::AllIconsAndText
-            | WorkspacesDisplayFormat::AllIconsAndTextOnSelected)))))
+            | WorkspacesDisplayFormat::AllIconsAndTextOnSelected))))
+            || matches!(w, WidgetConfig::Group(config) if Self::show_all_icons_on_komorebi_workspace(&config.widgets)))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct RefreshConfig {
+    /// How often the bar is repainted (default: 1000 milliseconds)
+    pub repaint_interval: Option<u64>,
+    /// Data refresh interval of the system widgets (Battery, Cpu, Exec, Gpu, Keyboard, Memory,
+    /// Network, Notifications, Storage, Volume, Vpn) which don't set their own
+    pub data_refresh_interval: Option<u64>,
+    /// Seconds without pointer interaction or komorebi state changes after which the bar goes
+    /// idle (default: never)
+    pub idle_timeout: Option<u64>,
+    /// How often the bar is repainted while idle (default: 10000 milliseconds)
+    pub idle_repaint_interval: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BarConfig {
+    /// The monitor index or the full monitor options of this bar
+    pub monitor: MonitorConfigOrIndex,
+    /// Bar height (default: inherited)
+    pub height: Option<f32>,
+    /// Bar padding (default: inherited)
+    pub padding: Option<Padding>,
+    /// Bar margin (default: inherited)
+    pub margin: Option<Margin>,
+    /// Bar positioning options (default: the top of the monitor)
+    pub position: Option<PositionConfig>,
+    /// Frame options (default: inherited)
+    pub frame: Option<FrameConfig>,
+    /// Font size (default: inherited)
+    pub font_size: Option<f32>,
+    /// Scale of the icons relative to the font_size [[1.0-2.0]]. (default: inherited)
+    pub icon_scale: Option<f32>,
+    /// Font size of the icons, which takes precedence over icon_scale (default: inherited)
+    pub icon_font_size: Option<f32>,
+    /// Theme (default: inherited)
+    pub theme: Option<KomobarTheme>,
+    /// Alpha value for the color transparency [[0-255]] (default: inherited)
+    pub transparency_alpha: Option<u8>,
+    /// Spacing between widgets (default: inherited)
+    pub widget_spacing: Option<f32>,
+    /// Visual grouping for widgets (default: inherited)
+    pub grouping: Option<Grouping>,
+    /// Left side widgets (ordered left-to-right) (default: inherited)
+    pub left_widgets: Option<Vec<WidgetConfig>>,
+    /// Center widgets (ordered left-to-right) (default: inherited)
+    pub center_widgets: Option<Vec<WidgetConfig>>,
+    /// Right side widgets (ordered left-to-right) (default: inherited)
+    pub right_widgets: Option<Vec<WidgetConfig>>,
+    /// Auto-hide options (default: inherited)
+    pub auto_hide: Option<AutoHideConfig>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AutoHideConfig {
+    /// Enable auto-hide
+    pub enable: bool,
+    /// Height of the strip which stays on screen while the bar is hidden, where 0 hides the bar
+    /// completely (default: 2)
+    pub hidden_height: Option<f32>,
+    /// How long the bar stays revealed after the mouse leaves it (default: 500 milliseconds)
+    pub hide_delay: Option<u64>,
+    /// Duration of the slide animation (default: 150 milliseconds)
+    pub animation_duration: Option<u64>,
+    /// Conditions which keep the bar revealed for as long as any of them is true
+    pub reveal_when: Option<Vec<RevealCondition>>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum RevealCondition {
+    /// The focused workspace on the monitor of the bar has no windows
+    EmptyWorkspace,
+    /// komorebi is paused
+    Paused,
+}
+
+impl RevealCondition {
+    pub fn is_met(self, state: &komorebi_client::State, monitor_index: usize) -> bool {
+        match self {
+            RevealCondition::EmptyWorkspace => state
+                .monitors
+                .elements()
+                .get(monitor_index)
+                .and_then(|monitor| monitor.focused_workspace())
+                .is_some_and(|workspace| workspace.is_empty()),
+            RevealCondition::Paused => state.is_paused,
+        }
     }
 }
 
@@ -327,12 +471,32 @@ pub fn get_individual_spacing(
 
 impl KomobarConfig {
     pub fn read(path: &PathBuf) -> color_eyre::Result<Self> {
-        let content = std::fs::read_to_string(path)?;
-        let mut value: Self = match path.extension().unwrap().to_string_lossy().as_str() {
-            "json" => serde_json::from_str(&content)?,
-            _ => panic!("unsupported format"),
+        let Some(extension) = path.extension() else {
+            color_eyre::eyre::bail!(
+                "{} has no file extension, but it must be a .json file",
+                path.display()
+            );
         };
 
+        match extension.to_string_lossy().as_ref() {
+            "json" => Ok(Self::from_json(&std::fs::read_to_string(path)?)?),
+            extension => color_eyre::eyre::bail!(
+                "{} is a .{extension} file, but it must be a .json file",
+                path.display()
+            ),
+        }
+    }
+
+    /// The example configuration which ships with the bar
+    pub fn example() -> color_eyre::Result<Self> {
+        Ok(Self::from_json(include_str!(
+            "../../docs/komorebi.bar.example.json"
+        ))?)
+    }
+
+    fn from_json(content: &str) -> serde_json::Result<Self> {
+        let mut value: Self = serde_json::from_str(content)?;
+
         if value.frame.is_none() {
             value.frame = Some(FrameConfig {
                 inner_margin: Position {
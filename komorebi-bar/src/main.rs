@@ -1,5 +1,6 @@
 mod bar;
 mod config;
+mod popup;
 mod render;
 mod selected_frame;
 mod ui;
@@ -23,11 +24,13 @@ use std::io::BufReader;
 use std::io::Read;
 use std::path::PathBuf;
 use std::sync::atomic::AtomicI32;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::time::Duration;
+use std::time::Instant;
 use tracing_subscriber::EnvFilter;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::Foundation::LPARAM;
@@ -36,13 +39,11 @@ use windows::Win32::System::Threading::GetCurrentThreadId;
 use windows::Win32::UI::HiDpi::SetProcessDpiAwarenessContext;
 use windows::Win32::UI::HiDpi::DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2;
 use windows::Win32::UI::WindowsAndMessaging::EnumThreadWindows;
+use windows::Win32::UI::WindowsAndMessaging::GetWindowTextW;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
 use windows_core::BOOL;
 
 pub static MAX_LABEL_WIDTH: AtomicI32 = AtomicI32::new(400);
-pub static MONITOR_LEFT: AtomicI32 = AtomicI32::new(0);
-pub static MONITOR_TOP: AtomicI32 = AtomicI32::new(0);
-pub static MONITOR_RIGHT: AtomicI32 = AtomicI32::new(0);
 pub static MONITOR_INDEX: AtomicUsize = AtomicUsize::new(0);
 pub static BAR_HEIGHT: f32 = 50.0;
 pub static DEFAULT_PADDING: f32 = 10.0;
@@ -50,6 +51,22 @@ pub static DEFAULT_PADDING: f32 = 10.0;
 pub static ICON_CACHE: LazyLock<Mutex<HashMap<isize, RgbaImage>>> =
     LazyLock::new(|| Mutex::new(HashMap::new()));
 
+pub static REPAINT_INTERVAL: AtomicU64 = AtomicU64::new(1000);
+pub static IDLE_REPAINT_INTERVAL: AtomicU64 = AtomicU64::new(10000);
+/// Seconds of inactivity before the bar goes idle, where 0 means never
+pub static IDLE_TIMEOUT: AtomicU64 = AtomicU64::new(0);
+pub static LAST_ACTIVITY: LazyLock<Mutex<Instant>> = LazyLock::new(|| Mutex::new(Instant::now()));
+
+/// Keeps the bar out of idle mode for another `IDLE_TIMEOUT` seconds
+pub fn mark_activity() {
+    *LAST_ACTIVITY.lock().unwrap() = Instant::now();
+}
+
+fn is_idle() -> bool {
+    let idle_timeout = IDLE_TIMEOUT.load(Ordering::SeqCst);
+    idle_timeout != 0 && LAST_ACTIVITY.lock().unwrap().elapsed() > Duration::from_secs(idle_timeout)
+}
+
 #[derive(Parser)]
 #[clap(author, about, version)]
 struct Opts {
@@ -71,13 +88,25 @@ struct Opts {
     aliases: bool,
 }
 
+struct WindowSearch<'a> {
+    title: &'a str,
+    hwnd: HWND,
+}
+
 extern "system" fn enum_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
     unsafe {
+        let search = &mut *(lparam.0 as *mut WindowSearch);
+
         let mut process_id = 0;
         GetWindowThreadProcessId(hwnd, Some(&mut process_id));
 
-        if process_id == GetCurrentProcessId() {
-            *(lparam.0 as *mut HWND) = hwnd;
+        let mut title = [0; 256];
+        let len = GetWindowTextW(hwnd, &mut title);
+
+        if process_id == GetCurrentProcessId()
+            && String::from_utf16_lossy(&title[..len as usize]) == search.title
+        {
+            search.hwnd = hwnd;
             BOOL::from(false) // Stop enumeration
         } else {
             BOOL::from(true) // Continue enumeration
@@ -85,19 +114,24 @@ extern "system" fn enum_window(hwnd: HWND, lparam: LPARAM) -> BOOL {
     }
 }
 
-fn process_hwnd() -> Option<isize> {
+/// Finds the window of this process with the given title, since every bar has its own window
+fn process_hwnd(title: &str) -> Option<isize> {
     unsafe {
-        let mut hwnd = HWND::default();
+        let mut search = WindowSearch {
+            title,
+            hwnd: HWND::default(),
+        };
+
         let _ = EnumThreadWindows(
             GetCurrentThreadId(),
             Some(enum_window),
-            LPARAM(&mut hwnd as *mut HWND as isize),
+            LPARAM(&mut search as *mut WindowSearch as isize),
         );
 
-        if hwnd.0 as isize == 0 {
+        if search.hwnd.0 as isize == 0 {
             None
         } else {
-            Some(hwnd.0 as isize)
+            Some(search.hwnd.0 as isize)
         }
     }
 }
@@ -189,7 +223,7 @@ fn main() -> color_eyre::Result<()> {
         Option::from,
     );
 
-    let mut config = match config_path {
+    let (mut config, config_error) = match config_path {
         None => {
             let komorebi_bar_json =
                 include_str!("../../docs/komorebi.bar.example.json").to_string();
@@ -200,7 +234,7 @@ fn main() -> color_eyre::Result<()> {
                 default_config_path.as_path().display()
             );
 
-            KomobarConfig::read(&default_config_path)?
+            (KomobarConfig::read(&default_config_path)?, None)
         }
         Some(ref config) => {
             if !opts.aliases {
@@ -210,7 +244,17 @@ fn main() -> color_eyre::Result<()> {
                 );
             }
 
-            KomobarConfig::read(config)?
+            match KomobarConfig::read(config) {
+                Ok(config) => (config, None),
+                // the bar is still shown so that the error can be shown in it
+                Err(error) => {
+                    tracing::error!(
+                        "could not parse configuration file, falling back to the example configuration: {error}"
+                    );
+
+                    (KomobarConfig::example()?, Some(error.to_string()))
+                }
+            }
         }
     };
 
@@ -236,20 +280,7 @@ fn main() -> color_eyre::Result<()> {
         .get(&usr_monitor_index)
         .map_or(usr_monitor_index, |i| *i);
 
-    MONITOR_RIGHT.store(
-        state.monitors.elements()[monitor_index].size().right,
-        Ordering::SeqCst,
-    );
-
-    MONITOR_TOP.store(
-        state.monitors.elements()[monitor_index].size().top,
-        Ordering::SeqCst,
-    );
-
-    MONITOR_LEFT.store(
-        state.monitors.elements()[monitor_index].size().left,
-        Ordering::SeqCst,
-    );
+    let monitor_size = *state.monitors.elements()[monitor_index].size();
 
     MONITOR_INDEX.store(monitor_index, Ordering::SeqCst);
 
@@ -294,13 +325,22 @@ fn main() -> color_eyre::Result<()> {
     };
 
     if let Some(rect) = &work_area_offset {
-        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(monitor_index, *rect))?;
+        komorebi_client::send_message(&SocketMessage::MonitorWorkAreaOffset(
+            usr_monitor_index,
+            *rect,
+        ))?;
         tracing::info!("work area offset applied to monitor: {}", monitor_index);
     }
 
     let (tx_gui, rx_gui) = crossbeam_channel::unbounded();
     let (tx_config, rx_config) = crossbeam_channel::unbounded();
 
+    if let Some(error) = config_error {
+        if let Err(error) = tx_config.send(Err(error)) {
+            tracing::error!("could not send configuration error to gui: {error}")
+        }
+    }
+
     let mut hotwatch = Hotwatch::new()?;
     let config_path_cl = config_path.clone();
 
@@ -312,12 +352,16 @@ fn main() -> color_eyre::Result<()> {
                     config_path_cl.as_path().to_string_lossy()
                 );
 
-                if let Err(error) = tx_config.send(updated) {
+                if let Err(error) = tx_config.send(Ok(updated)) {
                     tracing::error!("could not send configuration update to gui: {error}")
                 }
             }
             Err(error) => {
                 tracing::error!("{error}");
+
+                if let Err(error) = tx_config.send(Err(error.to_string())) {
+                    tracing::error!("could not send configuration error to gui: {error}")
+                }
             }
         },
         _ => {}
@@ -330,9 +374,27 @@ fn main() -> color_eyre::Result<()> {
         native_options,
         Box::new(|cc| {
             let ctx_repainter = cc.egui_ctx.clone();
-            std::thread::spawn(move || loop {
-                std::thread::sleep(Duration::from_secs(1));
-                ctx_repainter.request_repaint();
+            std::thread::spawn(move || {
+                let mut last_repaint = Instant::now();
+
+                loop {
+                    let repaint_interval =
+                        Duration::from_millis(REPAINT_INTERVAL.load(Ordering::SeqCst));
+                    std::thread::sleep(repaint_interval);
+
+                    // while idle, wake up at the regular interval but only repaint at the slower
+                    // one, so that the bar picks up speed again as soon as there is activity
+                    let interval = if is_idle() {
+                        Duration::from_millis(IDLE_REPAINT_INTERVAL.load(Ordering::SeqCst))
+                    } else {
+                        repaint_interval
+                    };
+
+                    if last_repaint.elapsed() >= interval {
+                        ctx_repainter.request_repaint();
+                        last_repaint = Instant::now();
+                    }
+                }
             });
 
             let ctx_komorebi = cc.egui_ctx.clone();
@@ -341,6 +403,7 @@ fn main() -> color_eyre::Result<()> {
 
                 let listener = komorebi_client::subscribe_with_options(&subscriber_name, SubscribeOptions {
                     filter_state_changes: true,
+                    ..Default::default()
                 })
                     .expect("could not subscribe to komorebi notifications");
 
@@ -412,7 +475,13 @@ fn main() -> color_eyre::Result<()> {
                 }
             });
 
-            Ok(Box::new(Komobar::new(cc, rx_gui, rx_config, config)))
+            Ok(Box::new(Komobar::new(
+                cc,
+                rx_gui,
+                rx_config,
+                config,
+                monitor_size,
+            )))
         }),
     )
     .map_err(|error| color_eyre::eyre::Error::msg(error.to_string()))
@@ -0,0 +1,127 @@
+use eframe::egui::CentralPanel;
+use eframe::egui::Context;
+use eframe::egui::Key;
+use eframe::egui::Rect;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use eframe::egui::ViewportBuilder;
+use eframe::egui::ViewportId;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How long after being dismissed by a click elsewhere a popup ignores being toggled, so that
+/// clicking the widget which opened it closes it instead of opening it again
+const DISMISS_GRACE_PERIOD: Duration = Duration::from_millis(250);
+
+/// A flyout which a widget opens on click, shown in its own borderless viewport below the
+/// widget, or above it when the bar is at the bottom of the screen. It is dismissed by clicking
+/// anywhere else, pressing Escape or toggling it again from the widget.
+pub struct Popup {
+    name: &'static str,
+    open: bool,
+    /// Whether the popup has had focus since it was opened, after which losing it dismisses it
+    focused: bool,
+    dismissed_at: Option<Instant>,
+}
+
+impl Popup {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            open: false,
+            focused: false,
+            dismissed_at: None,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens the popup if it is closed and closes it if it is open, returning whether it is open
+    pub fn toggle(&mut self) -> bool {
+        if self.open {
+            self.close();
+        } else if self
+            .dismissed_at
+            .is_none_or(|dismissed_at| dismissed_at.elapsed() > DISMISS_GRACE_PERIOD)
+        {
+            self.open = true;
+            self.focused = false;
+        }
+
+        self.open
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Shows the popup next to `anchor`, a rect of the bar viewport, if it is open. Returns
+    /// whatever `add_contents` returns, or `None` if the popup is closed.
+    pub fn show<R>(
+        &mut self,
+        ctx: &Context,
+        anchor: Rect,
+        monitor_top: i32,
+        size: Vec2,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<R> {
+        if !self.open {
+            return None;
+        }
+
+        let (outer_rect, monitor_size) =
+            ctx.input(|input| (input.viewport().outer_rect, input.viewport().monitor_size));
+        let origin = outer_rect.map(|rect| rect.min).unwrap_or_default();
+        let monitor_top = monitor_top as f32 / ctx.pixels_per_point();
+
+        let mut position = origin + anchor.left_bottom().to_vec2();
+        if monitor_size.is_some_and(|monitor| position.y + size.y > monitor_top + monitor.y) {
+            position.y = origin.y + anchor.top() - size.y;
+        }
+
+        let mut add_contents = Some(add_contents);
+
+        ctx.show_viewport_immediate(
+            // every bar has its own popups
+            ViewportId::from_hash_of((self.name, ctx.viewport_id())),
+            ViewportBuilder::default()
+                .with_title(format!("komorebi-bar {}", self.name))
+                .with_position(position)
+                .with_inner_size(size)
+                .with_decorations(false)
+                .with_resizable(false)
+                .with_always_on_top()
+                .with_active(true)
+                .with_taskbar(false),
+            |ctx, _| {
+                let inner = add_contents
+                    .take()
+                    .map(|add_contents| CentralPanel::default().show(ctx, add_contents).inner);
+
+                let (focused, dismissed) = ctx.input(|input| {
+                    (
+                        input.viewport().focused,
+                        input.key_pressed(Key::Escape) || input.viewport().close_requested(),
+                    )
+                });
+
+                match focused {
+                    Some(true) => self.focused = true,
+                    Some(false) if self.focused => {
+                        self.dismissed_at = Some(Instant::now());
+                        self.close();
+                    }
+                    _ => {}
+                }
+
+                if dismissed {
+                    self.close();
+                }
+
+                inner
+            },
+        )
+    }
+}
@@ -4,6 +4,7 @@ use crate::config::MonitorConfigOrIndex;
 use eframe::egui::Color32;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
+use eframe::egui::FontFamily;
 use eframe::egui::FontId;
 use eframe::egui::Frame;
 use eframe::egui::InnerResponse;
@@ -19,6 +20,9 @@ use std::sync::Arc;
 
 static SHOW_KOMOREBI_LAYOUT_OPTIONS: AtomicUsize = AtomicUsize::new(0);
 
+/// Name of the font family which holds the `icon_font_family` font, if one is configured
+pub const ICON_FONT_FAMILY: &str = "icons";
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "kind")]
@@ -37,6 +41,8 @@ pub enum Grouping {
 pub struct RenderConfig {
     /// Komorebi monitor index of the monitor on which to render the bar
     pub monitor_idx: usize,
+    /// Top edge of the monitor on which to render the bar, in physical pixels
+    pub monitor_top: i32,
     /// Spacing between widgets
     pub spacing: f32,
     /// Sets how widgets are grouped
@@ -81,7 +87,13 @@ impl RenderExt for &KomobarConfig {
             .unwrap_or_else(FontId::default);
 
         let mut icon_font_id = text_font_id.clone();
-        icon_font_id.size *= icon_scale.unwrap_or(1.4).clamp(1.0, 2.0);
+        icon_font_id.size = self
+            .icon_font_size
+            .unwrap_or_else(|| text_font_id.size * icon_scale.unwrap_or(1.4).clamp(1.0, 2.0));
+
+        if self.icon_font_family.is_some() {
+            icon_font_id.family = FontFamily::Name(ICON_FONT_FAMILY.into());
+        }
 
         let monitor_idx = match &self.monitor {
             MonitorConfigOrIndex::MonitorConfig(monitor_config) => monitor_config.index,
@@ -99,6 +111,7 @@ impl RenderExt for &KomobarConfig {
 
         RenderConfig {
             monitor_idx,
+            monitor_top: 0,
             spacing: self.widget_spacing.unwrap_or(10.0),
             grouping: self.grouping.unwrap_or(Grouping::None),
             background_color,
@@ -124,6 +137,7 @@ impl RenderConfig {
     pub fn new() -> Self {
         Self {
             monitor_idx: 0,
+            monitor_top: 0,
             spacing: 0.0,
             grouping: Grouping::None,
             background_color: Color32::BLACK,
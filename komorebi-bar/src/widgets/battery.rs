@@ -4,13 +4,16 @@ use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
+use eframe::egui::Color32;
 use eframe::egui::Context;
 use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
+use komorebi_client::Colour;
 use serde::Deserialize;
 use serde::Serialize;
 use starship_battery::units::ratio::percent;
+use starship_battery::units::time::second;
 use starship_battery::Manager;
 use starship_battery::State;
 use std::process::Command;
@@ -28,6 +31,16 @@ pub struct BatteryConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Show the estimated time until the battery is empty or fully charged (default: false)
+    pub show_time_remaining: Option<bool>,
+    /// Charge percentage at or below which the widget is shown in the low battery colour (default: 20)
+    pub low_threshold: Option<u8>,
+    /// Charge percentage at or below which the widget is shown in the critical battery colour (default: 10)
+    pub critical_threshold: Option<u8>,
+    /// Colour used when the charge is at or below the low threshold (default: theme warning colour)
+    pub low_colour: Option<Colour>,
+    /// Colour used when the charge is at or below the critical threshold (default: theme error colour)
+    pub critical_colour: Option<Colour>,
 }
 
 impl From<BatteryConfig> for Battery {
@@ -41,7 +54,13 @@ impl From<BatteryConfig> for Battery {
             last_state: String::new(),
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            show_time_remaining: value.show_time_remaining.unwrap_or(false),
+            low_threshold: value.low_threshold.unwrap_or(20),
+            critical_threshold: value.critical_threshold.unwrap_or(10),
+            low_colour: value.low_colour.map(Color32::from),
+            critical_colour: value.critical_colour.map(Color32::from),
             state: BatteryState::Discharging,
+            percentage: 100.0,
             last_updated: Instant::now()
                 .checked_sub(Duration::from_secs(data_refresh_interval))
                 .unwrap(),
@@ -61,6 +80,12 @@ pub struct Battery {
     pub state: BatteryState,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
+    show_time_remaining: bool,
+    low_threshold: u8,
+    critical_threshold: u8,
+    low_colour: Option<Color32>,
+    critical_colour: Option<Color32>,
+    percentage: f32,
     last_state: String,
     last_updated: Instant,
 }
@@ -76,6 +101,7 @@ impl Battery {
             if let Ok(mut batteries) = self.manager.batteries() {
                 if let Some(Ok(first)) = batteries.nth(0) {
                     let percentage = first.state_of_charge().get::<percent>();
+                    self.percentage = percentage;
 
                     if percentage == 100.0 && self.hide_on_full_charge {
                         output = String::new()
@@ -91,6 +117,20 @@ impl Battery {
                                 format!("BAT: {percentage:.0}%")
                             }
                             LabelPrefix::None | LabelPrefix::Icon => format!("{percentage:.0}%"),
+                        };
+
+                        if self.show_time_remaining {
+                            let remaining = match self.state {
+                                BatteryState::Charging => first.time_to_full(),
+                                BatteryState::Discharging => first.time_to_empty(),
+                            };
+
+                            if let Some(remaining) = remaining {
+                                output.push_str(&format!(
+                                    " ({})",
+                                    format_time_remaining(remaining.get::<second>())
+                                ));
+                            }
                         }
                     }
                 }
@@ -102,6 +142,51 @@ impl Battery {
 
         output
     }
+
+    fn icon(&self) -> &'static str {
+        match self.state {
+            BatteryState::Charging => egui_phosphor::regular::BATTERY_CHARGING,
+            BatteryState::Discharging => match self.percentage {
+                p if p <= f32::from(self.critical_threshold) => {
+                    egui_phosphor::regular::BATTERY_WARNING
+                }
+                p if p <= f32::from(self.low_threshold) => egui_phosphor::regular::BATTERY_LOW,
+                p if p <= 60.0 => egui_phosphor::regular::BATTERY_MEDIUM,
+                p if p <= 90.0 => egui_phosphor::regular::BATTERY_HIGH,
+                _ => egui_phosphor::regular::BATTERY_FULL,
+            },
+        }
+    }
+
+    /// The colour to draw the widget in when the battery is running low, or `None` if the
+    /// regular theme colours should be used
+    fn warning_colour(&self, ctx: &Context) -> Option<Color32> {
+        if matches!(self.state, BatteryState::Charging) {
+            return None;
+        }
+
+        if self.percentage <= f32::from(self.critical_threshold) {
+            Some(
+                self.critical_colour
+                    .unwrap_or(ctx.style().visuals.error_fg_color),
+            )
+        } else if self.percentage <= f32::from(self.low_threshold) {
+            Some(self.low_colour.unwrap_or(ctx.style().visuals.warn_fg_color))
+        } else {
+            None
+        }
+    }
+}
+
+fn format_time_remaining(seconds: f32) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let minutes = (seconds / 60.0).round() as u64;
+
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}m")
+    }
 }
 
 impl BarWidget for Battery {
@@ -109,10 +194,8 @@ impl BarWidget for Battery {
         if self.enable {
             let output = self.output();
             if !output.is_empty() {
-                let emoji = match self.state {
-                    BatteryState::Charging => egui_phosphor::regular::BATTERY_CHARGING,
-                    BatteryState::Discharging => egui_phosphor::regular::BATTERY_FULL,
-                };
+                let emoji = self.icon();
+                let warning_colour = self.warning_colour(ctx);
 
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
@@ -120,7 +203,7 @@ impl BarWidget for Battery {
                         LabelPrefix::None | LabelPrefix::Text => String::new(),
                     },
                     config.icon_font_id.clone(),
-                    ctx.style().visuals.selection.stroke.color,
+                    warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
                     100.0,
                 );
 
@@ -129,7 +212,7 @@ impl BarWidget for Battery {
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: warning_colour.unwrap_or(ctx.style().visuals.text_color()),
                         valign: Align::Center,
                         ..Default::default()
                     },
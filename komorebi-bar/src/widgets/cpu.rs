@@ -1,6 +1,10 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
+use crate::widgets::resource_usage::paint_bars;
+use crate::widgets::resource_usage::ResourceDisplayFormat;
+use crate::widgets::resource_usage::UsageHistory;
+use crate::widgets::resource_usage::UsageThresholds;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -25,6 +29,12 @@ pub struct CpuConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Display format of the current usage (default: Text)
+    pub display_format: Option<ResourceDisplayFormat>,
+    /// Show a small usage bar for each logical core (default: false)
+    pub show_per_core: Option<bool>,
+    /// Usage thresholds at which the widget changes colour
+    pub thresholds: Option<UsageThresholds>,
 }
 
 impl From<CpuConfig> for Cpu {
@@ -38,6 +48,10 @@ impl From<CpuConfig> for Cpu {
             ),
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            display_format: value.display_format.unwrap_or_default(),
+            show_per_core: value.show_per_core.unwrap_or(false),
+            thresholds: value.thresholds,
+            history: UsageHistory::default(),
             last_updated: Instant::now()
                 .checked_sub(Duration::from_secs(data_refresh_interval))
                 .unwrap(),
@@ -50,6 +64,10 @@ pub struct Cpu {
     system: System,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
+    display_format: ResourceDisplayFormat,
+    show_per_core: bool,
+    thresholds: Option<UsageThresholds>,
+    history: UsageHistory,
     last_updated: Instant,
 }
 
@@ -58,9 +76,17 @@ impl Cpu {
         let now = Instant::now();
         if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
             self.system.refresh_cpu_usage();
+            self.history.push(self.system.global_cpu_usage());
             self.last_updated = now;
         }
 
+        if !self.display_format.shows_text() {
+            return match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => String::from("CPU:"),
+                LabelPrefix::None | LabelPrefix::Icon => String::new(),
+            };
+        }
+
         let used = self.system.global_cpu_usage();
         match self.label_prefix {
             LabelPrefix::Text | LabelPrefix::IconAndText => format!("CPU: {:.0}%", used),
@@ -73,43 +99,66 @@ impl BarWidget for Cpu {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
             let output = self.output();
-            if !output.is_empty() {
-                let mut layout_job = LayoutJob::simple(
-                    match self.label_prefix {
-                        LabelPrefix::Icon | LabelPrefix::IconAndText => {
-                            egui_phosphor::regular::CPU.to_string()
+            let used = self.system.global_cpu_usage();
+            let warning_colour = self
+                .thresholds
+                .and_then(|thresholds| thresholds.colour(ctx, used));
+
+            let mut layout_job = LayoutJob::simple(
+                match self.label_prefix {
+                    LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                        egui_phosphor::regular::CPU.to_string()
+                    }
+                    LabelPrefix::None | LabelPrefix::Text => String::new(),
+                },
+                config.icon_font_id.clone(),
+                warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
+                100.0,
+            );
+
+            layout_job.append(
+                &output,
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: warning_colour.unwrap_or(ctx.style().visuals.text_color()),
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+
+            let graph_height = config.text_font_id.size;
+            let graph_colour = warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color);
+            let per_core = self
+                .system
+                .cpus()
+                .iter()
+                .map(|cpu| cpu.cpu_usage())
+                .collect::<Vec<_>>();
+
+            config.apply_on_widget(false, ui, |ui| {
+                if SelectableFrame::new(false)
+                    .show(ui, |ui| {
+                        if !layout_job.text.is_empty() {
+                            ui.add(Label::new(layout_job).selectable(false));
                         }
-                        LabelPrefix::None | LabelPrefix::Text => String::new(),
-                    },
-                    config.icon_font_id.clone(),
-                    ctx.style().visuals.selection.stroke.color,
-                    100.0,
-                );
 
-                layout_job.append(
-                    &output,
-                    10.0,
-                    TextFormat {
-                        font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
-                        valign: Align::Center,
-                        ..Default::default()
-                    },
-                );
+                        if self.display_format.shows_graph() {
+                            self.history.paint(ui, graph_height, graph_colour);
+                        }
 
-                config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
-                        .clicked()
-                    {
-                        if let Err(error) =
-                            Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
-                        {
-                            eprintln!("{}", error)
+                        if self.show_per_core {
+                            paint_bars(ui, graph_height, &per_core, graph_colour);
                         }
+                    })
+                    .clicked()
+                {
+                    if let Err(error) = Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
+                    {
+                        eprintln!("{}", error)
                     }
-                });
-            }
+                }
+            });
         }
     }
 }
@@ -0,0 +1,232 @@
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ExecConfig {
+    /// Enable the Exec widget
+    pub enable: bool,
+    /// Command to run with cmd.exe; the first line of its output is displayed, unless the output is a JSON object with "text", "icon", "color" and "tooltip" fields
+    pub command: String,
+    /// Data refresh interval (default: 10 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Command to run when the widget is clicked
+    pub on_click: Option<String>,
+    /// Command to run when the widget is right-clicked
+    pub on_right_click: Option<String>,
+    /// Command to run when the widget is middle-clicked
+    pub on_middle_click: Option<String>,
+    /// Command to run when scrolling up over the widget
+    pub on_scroll_up: Option<String>,
+    /// Command to run when scrolling down over the widget
+    pub on_scroll_down: Option<String>,
+}
+
+impl From<ExecConfig> for Exec {
+    fn from(value: ExecConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
+
+        Self {
+            enable: value.enable,
+            command: value.command,
+            data_refresh_interval,
+            on_click: value.on_click,
+            on_right_click: value.on_right_click,
+            on_middle_click: value.on_middle_click,
+            on_scroll_up: value.on_scroll_up,
+            on_scroll_down: value.on_scroll_down,
+            output: Arc::new(Mutex::new(None)),
+            running: Arc::new(AtomicBool::new(false)),
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+pub struct Exec {
+    pub enable: bool,
+    command: String,
+    data_refresh_interval: u64,
+    on_click: Option<String>,
+    on_right_click: Option<String>,
+    on_middle_click: Option<String>,
+    on_scroll_up: Option<String>,
+    on_scroll_down: Option<String>,
+    output: Arc<Mutex<Option<ExecOutput>>>,
+    running: Arc<AtomicBool>,
+    last_updated: Instant,
+}
+
+/// The output of the command, either parsed from JSON or taken from the first line of plain text
+#[derive(Clone, Debug, Default, Deserialize)]
+struct ExecOutput {
+    #[serde(default)]
+    text: String,
+    icon: Option<String>,
+    #[serde(alias = "color")]
+    colour: Option<Colour>,
+    tooltip: Option<String>,
+}
+
+impl From<&str> for ExecOutput {
+    fn from(value: &str) -> Self {
+        let value = value.trim();
+
+        match serde_json::from_str(value) {
+            Ok(output) => output,
+            Err(_) => Self {
+                text: value.lines().next().unwrap_or_default().to_string(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Runs a user-provided command through cmd.exe without flashing up a console window
+pub fn shell_command(command: &str) -> Command {
+    let mut shell_command = Command::new("cmd.exe");
+    shell_command
+        .arg("/C")
+        .raw_arg(command)
+        .creation_flags(CREATE_NO_WINDOW);
+    shell_command
+}
+
+fn run(command: &str) -> std::io::Result<std::process::Output> {
+    shell_command(command).output()
+}
+
+impl Exec {
+    /// Runs the command on a background thread so that slow scripts don't block the bar, after
+    /// running the given action first if there is one
+    fn refresh(&mut self, ctx: &Context, action: Option<String>) {
+        // an action always runs, but there is no point in queuing up plain refreshes
+        if self.running.swap(true, Ordering::SeqCst) && action.is_none() {
+            return;
+        }
+
+        self.last_updated = Instant::now();
+
+        let command = self.command.clone();
+        let output = self.output.clone();
+        let running = self.running.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            if let Some(action) = action {
+                if let Err(error) = run(&action) {
+                    tracing::error!("could not run '{action}': {error}");
+                }
+            }
+
+            match run(&command) {
+                Ok(result) => {
+                    let stdout = String::from_utf8_lossy(&result.stdout);
+                    *output.lock().unwrap() = Some(ExecOutput::from(stdout.as_ref()));
+                }
+                Err(error) => {
+                    tracing::error!("could not run '{command}': {error}");
+                    *output.lock().unwrap() = None;
+                }
+            }
+
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+        });
+    }
+}
+
+impl BarWidget for Exec {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        let now = Instant::now();
+        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
+            self.refresh(ctx, None);
+        }
+
+        let Some(output) = self.output.lock().unwrap().clone() else {
+            return;
+        };
+
+        if output.text.is_empty() && output.icon.is_none() {
+            return;
+        }
+
+        let colour = output.colour.map(Color32::from);
+
+        let mut layout_job = LayoutJob::simple(
+            output.icon.unwrap_or_default(),
+            config.icon_font_id.clone(),
+            colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
+            100.0,
+        );
+
+        layout_job.append(
+            &output.text,
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: colour.unwrap_or(ctx.style().visuals.text_color()),
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        config.apply_on_widget(false, ui, |ui| {
+            let mut response = SelectableFrame::new(false)
+                .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)));
+
+            if let Some(tooltip) = &output.tooltip {
+                response = response.on_hover_text(tooltip);
+            }
+
+            let action = if response.clicked() {
+                self.on_click.clone()
+            } else if response.secondary_clicked() {
+                self.on_right_click.clone()
+            } else if response.middle_clicked() {
+                self.on_middle_click.clone()
+            } else if response.hovered() {
+                let scroll_delta = ui.input(|input| input.raw_scroll_delta.y);
+                if scroll_delta > 0.0 {
+                    self.on_scroll_up.clone()
+                } else if scroll_delta < 0.0 {
+                    self.on_scroll_down.clone()
+                } else {
+                    None
+                }
+            } else {
+                None
+            };
+
+            if action.is_some() {
+                self.refresh(ctx, action);
+            }
+        });
+    }
+}
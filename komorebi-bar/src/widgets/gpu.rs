@@ -0,0 +1,449 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::resource_usage::to_gib;
+use crate::widgets::resource_usage::ResourceDisplayFormat;
+use crate::widgets::resource_usage::UsageHistory;
+use crate::widgets::resource_usage::UsageThresholds;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::process::Command;
+use std::time::Duration;
+use std::time::Instant;
+use windows::core::w;
+use windows::core::Interface;
+use windows::core::PCWSTR;
+use windows::Wdk::Graphics::Direct3D::D3DKMTCloseAdapter;
+use windows::Wdk::Graphics::Direct3D::D3DKMTOpenAdapterFromLuid;
+use windows::Wdk::Graphics::Direct3D::D3DKMTQueryAdapterInfo;
+use windows::Wdk::Graphics::Direct3D::D3DKMT_ADAPTER_PERFDATA;
+use windows::Wdk::Graphics::Direct3D::D3DKMT_CLOSEADAPTER;
+use windows::Wdk::Graphics::Direct3D::D3DKMT_OPENADAPTERFROMLUID;
+use windows::Wdk::Graphics::Direct3D::D3DKMT_QUERYADAPTERINFO;
+use windows::Wdk::Graphics::Direct3D::KMTQAITYPE_ADAPTERPERFDATA;
+use windows::Win32::Foundation::LUID;
+use windows::Win32::Graphics::Dxgi::CreateDXGIFactory1;
+use windows::Win32::Graphics::Dxgi::IDXGIAdapter3;
+use windows::Win32::Graphics::Dxgi::IDXGIFactory1;
+use windows::Win32::Graphics::Dxgi::DXGI_ADAPTER_FLAG_SOFTWARE;
+use windows::Win32::Graphics::Dxgi::DXGI_MEMORY_SEGMENT_GROUP_LOCAL;
+use windows::Win32::Graphics::Dxgi::DXGI_QUERY_VIDEO_MEMORY_INFO;
+use windows::Win32::System::Performance::PdhAddEnglishCounterW;
+use windows::Win32::System::Performance::PdhCloseQuery;
+use windows::Win32::System::Performance::PdhCollectQueryData;
+use windows::Win32::System::Performance::PdhGetFormattedCounterArrayW;
+use windows::Win32::System::Performance::PdhOpenQueryW;
+use windows::Win32::System::Performance::PDH_FMT_COUNTERVALUE_ITEM_W;
+use windows::Win32::System::Performance::PDH_FMT_DOUBLE;
+use windows::Win32::System::Performance::PDH_HCOUNTER;
+use windows::Win32::System::Performance::PDH_HQUERY;
+use windows::Win32::System::Performance::PDH_MORE_DATA;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GpuConfig {
+    /// Enable the Gpu widget
+    pub enable: bool,
+    /// Data refresh interval (default: 10 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Part of the name of the adapter to show, e.g. "NVIDIA" (default: the first hardware adapter)
+    pub adapter: Option<String>,
+    /// Display format of the current utilization (default: Text)
+    pub display_format: Option<ResourceDisplayFormat>,
+    /// Show used and total dedicated video memory (default: true)
+    pub show_vram: Option<bool>,
+    /// Show the temperature, if the driver reports it (default: true)
+    pub show_temperature: Option<bool>,
+    /// Utilization thresholds at which the widget changes colour
+    pub thresholds: Option<UsageThresholds>,
+}
+
+impl From<GpuConfig> for Gpu {
+    fn from(value: GpuConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(10);
+
+        let adapter = match GpuAdapter::find(value.adapter.as_deref()) {
+            Ok(adapter) => adapter,
+            Err(error) => {
+                tracing::error!("could not enumerate the graphics adapters: {error}");
+                None
+            }
+        };
+
+        let counters = match EngineCounters::new() {
+            Ok(counters) => Some(counters),
+            Err(error) => {
+                tracing::error!("could not open the gpu engine performance counters: {error}");
+                None
+            }
+        };
+
+        Self {
+            enable: value.enable,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            display_format: value.display_format.unwrap_or_default(),
+            show_vram: value.show_vram.unwrap_or(true),
+            show_temperature: value.show_temperature.unwrap_or(true),
+            thresholds: value.thresholds,
+            adapter,
+            counters,
+            reading: GpuReading::default(),
+            history: UsageHistory::default(),
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+pub struct Gpu {
+    pub enable: bool,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    display_format: ResourceDisplayFormat,
+    show_vram: bool,
+    show_temperature: bool,
+    thresholds: Option<UsageThresholds>,
+    adapter: Option<GpuAdapter>,
+    counters: Option<EngineCounters>,
+    reading: GpuReading,
+    history: UsageHistory,
+    last_updated: Instant,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct GpuReading {
+    utilization: f32,
+    vram_used: u64,
+    vram_total: u64,
+    temperature: Option<f32>,
+}
+
+struct GpuAdapter {
+    name: String,
+    luid: LUID,
+    dxgi: IDXGIAdapter3,
+    vram_total: u64,
+}
+
+impl GpuAdapter {
+    /// Picks the first hardware adapter whose name contains `name`, or the first hardware adapter
+    /// if no name is given
+    fn find(name: Option<&str>) -> windows::core::Result<Option<Self>> {
+        let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1()? };
+        let name = name.map(str::to_lowercase);
+
+        let mut i = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(i) } {
+            i += 1;
+
+            let desc = unsafe { adapter.GetDesc1()? };
+            if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE.0 as u32 != 0 {
+                continue;
+            }
+
+            let len = desc
+                .Description
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(desc.Description.len());
+            let description = String::from_utf16_lossy(&desc.Description[..len]);
+
+            if name
+                .as_ref()
+                .is_some_and(|name| !description.to_lowercase().contains(name))
+            {
+                continue;
+            }
+
+            return Ok(Some(Self {
+                name: description,
+                luid: desc.AdapterLuid,
+                dxgi: adapter.cast()?,
+                vram_total: desc.DedicatedVideoMemory as u64,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn vram_used(&self) -> windows::core::Result<u64> {
+        let mut info = DXGI_QUERY_VIDEO_MEMORY_INFO::default();
+        unsafe {
+            self.dxgi
+                .QueryVideoMemoryInfo(0, DXGI_MEMORY_SEGMENT_GROUP_LOCAL, &mut info)?;
+        }
+
+        Ok(info.CurrentUsage)
+    }
+
+    /// The temperature in degrees Celsius, as shown in Task Manager; not every driver reports one
+    fn temperature(&self) -> Option<f32> {
+        let mut open = D3DKMT_OPENADAPTERFROMLUID {
+            AdapterLuid: self.luid,
+            ..Default::default()
+        };
+
+        unsafe { D3DKMTOpenAdapterFromLuid(&mut open).ok().ok()? };
+
+        let mut perf_data = D3DKMT_ADAPTER_PERFDATA::default();
+        let query = D3DKMT_QUERYADAPTERINFO {
+            hAdapter: open.hAdapter,
+            Type: KMTQAITYPE_ADAPTERPERFDATA,
+            pPrivateDriverData: &mut perf_data as *mut _ as *mut c_void,
+            PrivateDriverDataSize: std::mem::size_of::<D3DKMT_ADAPTER_PERFDATA>() as u32,
+        };
+
+        let result = unsafe { D3DKMTQueryAdapterInfo(&query) };
+
+        unsafe {
+            let _ = D3DKMTCloseAdapter(&D3DKMT_CLOSEADAPTER {
+                hAdapter: open.hAdapter,
+            });
+        }
+
+        // the temperature is reported in tenths of a degree
+        (result.is_ok() && perf_data.Temperature != 0).then(|| perf_data.Temperature as f32 / 10.0)
+    }
+
+    /// Prefix of the performance counter instance names belonging to this adapter
+    fn instance_prefix(&self) -> String {
+        format!(
+            "luid_0x{:08x}_0x{:08x}",
+            self.luid.HighPart, self.luid.LowPart
+        )
+    }
+}
+
+/// The "GPU Engine" performance counters which Task Manager reads utilization from
+struct EngineCounters {
+    query: PDH_HQUERY,
+    counter: PDH_HCOUNTER,
+}
+
+impl EngineCounters {
+    fn new() -> windows::core::Result<Self> {
+        let mut query = PDH_HQUERY::default();
+        let mut counter = PDH_HCOUNTER::default();
+
+        unsafe {
+            pdh_result(PdhOpenQueryW(PCWSTR::null(), 0, &mut query))?;
+            pdh_result(PdhAddEnglishCounterW(
+                query,
+                w!("\\GPU Engine(*)\\Utilization Percentage"),
+                0,
+                &mut counter,
+            ))?;
+
+            // utilization is a rate, so the first sample only serves as a baseline
+            pdh_result(PdhCollectQueryData(query))?;
+        }
+
+        Ok(Self { query, counter })
+    }
+
+    /// Utilization of the busiest engine type (3D, copy, video decode...) of the adapter, which
+    /// is also how Task Manager reports it
+    fn utilization(&self, adapter: &GpuAdapter) -> windows::core::Result<f32> {
+        let mut buffer_size = 0;
+        let mut item_count = 0;
+
+        unsafe {
+            pdh_result(PdhCollectQueryData(self.query))?;
+
+            let status = PdhGetFormattedCounterArrayW(
+                self.counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                None,
+            );
+
+            if status != PDH_MORE_DATA {
+                pdh_result(status)?;
+                return Ok(0.0);
+            }
+
+            // the item names are stored in the same buffer after the items themselves, so the
+            // buffer is sized in bytes rather than items
+            let item_size = std::mem::size_of::<PDH_FMT_COUNTERVALUE_ITEM_W>();
+            let mut buffer = vec![
+                PDH_FMT_COUNTERVALUE_ITEM_W::default();
+                (buffer_size as usize).div_ceil(item_size)
+            ];
+            let items = buffer.as_mut_ptr();
+
+            pdh_result(PdhGetFormattedCounterArrayW(
+                self.counter,
+                PDH_FMT_DOUBLE,
+                &mut buffer_size,
+                &mut item_count,
+                Some(items),
+            ))?;
+
+            let prefix = adapter.instance_prefix();
+            let mut engines: HashMap<String, f64> = HashMap::new();
+
+            for item in std::slice::from_raw_parts(items, item_count as usize) {
+                let name = item.szName.to_string().unwrap_or_default().to_lowercase();
+                if !name.contains(&prefix) {
+                    continue;
+                }
+
+                if let Some((_, engine_type)) = name.split_once("engtype_") {
+                    *engines.entry(engine_type.to_string()).or_default() +=
+                        item.FmtValue.Anonymous.doubleValue;
+                }
+            }
+
+            Ok(engines.into_values().fold(0.0, f64::max).min(100.0) as f32)
+        }
+    }
+}
+
+impl Drop for EngineCounters {
+    fn drop(&mut self) {
+        unsafe {
+            PdhCloseQuery(self.query);
+        }
+    }
+}
+
+fn pdh_result(status: u32) -> windows::core::Result<()> {
+    windows::core::HRESULT(status as i32).ok()
+}
+
+impl Gpu {
+    fn output(&mut self) -> String {
+        let now = Instant::now();
+        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
+            self.refresh();
+            self.history.push(self.reading.utilization);
+            self.last_updated = now;
+        }
+
+        let mut reading = vec![];
+
+        if self.display_format.shows_text() {
+            reading.push(format!("{:.0}%", self.reading.utilization));
+        }
+
+        if self.show_vram && self.reading.vram_total > 0 {
+            reading.push(format!(
+                "{:.1}/{:.1} GiB",
+                to_gib(self.reading.vram_used),
+                to_gib(self.reading.vram_total)
+            ));
+        }
+
+        if let (true, Some(temperature)) = (self.show_temperature, self.reading.temperature) {
+            reading.push(format!("{temperature:.0}°C"));
+        }
+
+        let reading = reading.join(" ");
+
+        match self.label_prefix {
+            LabelPrefix::Text | LabelPrefix::IconAndText => {
+                format!("GPU: {reading}").trim_end().to_string()
+            }
+            LabelPrefix::None | LabelPrefix::Icon => reading,
+        }
+    }
+
+    fn refresh(&mut self) {
+        let Some(adapter) = &self.adapter else {
+            return;
+        };
+
+        let utilization = match &self.counters {
+            Some(counters) => counters.utilization(adapter).unwrap_or_else(|error| {
+                tracing::error!("could not read the gpu utilization: {error}");
+                0.0
+            }),
+            None => 0.0,
+        };
+
+        self.reading = GpuReading {
+            utilization,
+            vram_used: adapter.vram_used().unwrap_or_default(),
+            vram_total: adapter.vram_total,
+            temperature: adapter.temperature(),
+        };
+    }
+}
+
+impl BarWidget for Gpu {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable || self.adapter.is_none() {
+            return;
+        }
+
+        let output = self.output();
+        let warning_colour = self
+            .thresholds
+            .and_then(|thresholds| thresholds.colour(ctx, self.reading.utilization));
+
+        let mut layout_job = LayoutJob::simple(
+            match self.label_prefix {
+                LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                    egui_phosphor::regular::GRAPHICS_CARD.to_string()
+                }
+                LabelPrefix::None | LabelPrefix::Text => String::new(),
+            },
+            config.icon_font_id.clone(),
+            warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
+            100.0,
+        );
+
+        layout_job.append(
+            &output,
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: warning_colour.unwrap_or(ctx.style().visuals.text_color()),
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        let graph_height = config.text_font_id.size;
+        let graph_colour = warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color);
+        let adapter_name = self
+            .adapter
+            .as_ref()
+            .map(|adapter| adapter.name.clone())
+            .unwrap_or_default();
+
+        config.apply_on_widget(false, ui, |ui| {
+            if SelectableFrame::new(false)
+                .show(ui, |ui| {
+                    if !layout_job.text.is_empty() {
+                        ui.add(Label::new(layout_job).selectable(false));
+                    }
+
+                    if self.display_format.shows_graph() {
+                        self.history.paint(ui, graph_height, graph_colour);
+                    }
+                })
+                .on_hover_text(adapter_name)
+                .clicked()
+            {
+                if let Err(error) = Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn() {
+                    eprintln!("{}", error)
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,127 @@
+use crate::bar::Alignment;
+use crate::render::Grouping;
+use crate::render::GroupingConfig;
+use crate::render::RenderConfig;
+use crate::widgets::widget::BarWidget;
+use crate::widgets::widget::WidgetConfig;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::RichText;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GroupConfig {
+    /// Enable the Group widget
+    pub enable: bool,
+    /// Widgets in the group (ordered left-to-right)
+    pub widgets: Vec<WidgetConfig>,
+    /// Background of the group, which is drawn whatever the grouping of the bar (default: the
+    /// grouping of the bar, if it groups widgets individually)
+    pub style: Option<GroupingConfig>,
+    /// Spacing between the widgets of the group (default: widget_spacing)
+    pub spacing: Option<f32>,
+    /// Text or icon font glyph drawn between the widgets of the group, e.g. "|"
+    pub separator: Option<String>,
+    /// Colour of the separator (default: theme weak text colour)
+    pub separator_colour: Option<Colour>,
+}
+
+impl From<GroupConfig> for Group {
+    fn from(value: GroupConfig) -> Self {
+        let widgets = value
+            .widgets
+            .iter()
+            .filter(|config| config.enabled())
+            .map(|config| config.as_boxed_bar_widget())
+            .collect();
+
+        Self::new(&value, widgets)
+    }
+}
+
+pub struct Group {
+    pub enable: bool,
+    widgets: Vec<Box<dyn BarWidget>>,
+    style: Option<GroupingConfig>,
+    spacing: Option<f32>,
+    separator: Option<String>,
+    separator_colour: Option<Colour>,
+}
+
+impl Group {
+    /// A group of widgets which have already been built, e.g. so that komorebi widgets inside of
+    /// the group can share the notification state of the bar
+    pub fn new(config: &GroupConfig, widgets: Vec<Box<dyn BarWidget>>) -> Self {
+        Self {
+            enable: config.enable,
+            widgets,
+            style: config.style,
+            spacing: config.spacing,
+            separator: config.separator.clone(),
+            separator_colour: config.separator_colour,
+        }
+    }
+}
+
+impl BarWidget for Group {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable || self.widgets.is_empty() {
+            return;
+        }
+
+        // the widgets share the background of the group instead of getting one each
+        let mut widget_config = config.clone();
+        widget_config.grouping = Grouping::None;
+
+        let spacing = self.spacing.unwrap_or(config.spacing);
+        let separator = self.separator.as_ref().map(|separator| {
+            RichText::new(separator)
+                .font(config.text_font_id.clone())
+                .color(
+                    self.separator_colour
+                        .map_or(ctx.style().visuals.weak_text_color(), Color32::from),
+                )
+        });
+
+        // widgets are laid out from right to left on the right side of the bar
+        let is_reversed = matches!(config.alignment, Some(Alignment::Right));
+
+        let grouping = config.grouping;
+        if let Some(style) = self.style {
+            config.grouping = Grouping::Widget(style);
+        }
+
+        config.apply_on_widget(true, ui, |ui| {
+            let gap = (spacing - ui.spacing().item_spacing.x).max(0.0);
+
+            let widgets: Box<dyn Iterator<Item = &mut Box<dyn BarWidget>>> = if is_reversed {
+                Box::new(self.widgets.iter_mut().rev())
+            } else {
+                Box::new(self.widgets.iter_mut())
+            };
+
+            for (i, widget) in widgets.enumerate() {
+                if i > 0 {
+                    match &separator {
+                        Some(separator) => {
+                            ui.add_space(gap / 2.0);
+                            ui.add(Label::new(separator.clone()).selectable(false));
+                            ui.add_space(gap / 2.0);
+                        }
+                        None => ui.add_space(gap),
+                    }
+                }
+
+                widget_config.applied_on_widget = false;
+                widget.render(ctx, ui, &mut widget_config);
+            }
+        });
+
+        config.grouping = grouping;
+    }
+}
@@ -1,5 +1,6 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -10,14 +11,21 @@ use eframe::egui::Ui;
 use eframe::egui::WidgetText;
 use serde::Deserialize;
 use serde::Serialize;
+use std::ffi::c_void;
 use std::time::Duration;
 use std::time::Instant;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::WPARAM;
 use windows::Win32::Globalization::LCIDToLocaleName;
 use windows::Win32::Globalization::LOCALE_ALLOW_NEUTRAL_NAMES;
 use windows::Win32::System::SystemServices::LOCALE_NAME_MAX_LENGTH;
 use windows::Win32::UI::Input::KeyboardAndMouse::GetKeyboardLayout;
+use windows::Win32::UI::Input::KeyboardAndMouse::HKL_NEXT;
 use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+use windows::Win32::UI::WindowsAndMessaging::WM_INPUTLANGCHANGEREQUEST;
 
 const DEFAULT_DATA_REFRESH_INTERVAL: u64 = 1;
 const ERROR_TEXT: &str = "Error";
@@ -31,6 +39,8 @@ pub struct KeyboardConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Only show the language of the layout in upper case, e.g. EN instead of en-US (default: false)
+    pub short_name: Option<bool>,
 }
 
 impl From<KeyboardConfig> for Keyboard {
@@ -38,13 +48,16 @@ impl From<KeyboardConfig> for Keyboard {
         let data_refresh_interval = value
             .data_refresh_interval
             .unwrap_or(DEFAULT_DATA_REFRESH_INTERVAL);
+        let last_foreground_window = foreground_window_outside_bar();
 
         Self {
             enable: value.enable,
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            short_name: value.short_name.unwrap_or(false),
             last_updated: Instant::now(),
-            lang_name: get_lang(),
+            lang_name: get_lang(last_foreground_window),
+            last_foreground_window,
         }
     }
 }
@@ -53,15 +66,39 @@ pub struct Keyboard {
     pub enable: bool,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
+    short_name: bool,
     last_updated: Instant,
     lang_name: String,
+    /// The most recent foreground window which does not belong to the bar, as clicking the
+    /// widget makes the bar itself the foreground window
+    last_foreground_window: Option<isize>,
 }
 
-/// Retrieves the name of the active keyboard layout for the current foreground window.
+/// The foreground window, unless it belongs to the bar
+fn foreground_window_outside_bar() -> Option<isize> {
+    let foreground_window = unsafe { GetForegroundWindow() };
+    if foreground_window.is_invalid() {
+        return None;
+    }
+
+    let mut process_id = 0;
+    unsafe {
+        GetWindowThreadProcessId(foreground_window, Some(std::ptr::addr_of_mut!(process_id)))
+    };
+
+    if process_id == std::process::id() {
+        None
+    } else {
+        Option::from(foreground_window.0 as isize)
+    }
+}
+
+/// Retrieves the name of the active keyboard layout for the given window, or for the current
+/// foreground window if there is none.
 ///
 /// This function determines the active keyboard layout by querying the system for the
-/// foreground window's thread ID and its associated keyboard layout. It then attempts
-/// to retrieve the locale name corresponding to the keyboard layout.
+/// window's thread ID and its associated keyboard layout. It then attempts to retrieve
+/// the locale name corresponding to the keyboard layout.
 ///
 /// # Failure Cases
 ///
@@ -80,9 +117,14 @@ pub struct Keyboard {
 /// - `Ok(String)`: The name of the active keyboard layout as a valid UTF-8 string.
 /// - `Err(())`: Indicates that the function failed to retrieve the locale name or encountered
 ///   invalid UTF-16 characters during conversion.
-fn get_active_keyboard_layout() -> Result<String, ()> {
-    let foreground_window_tid = unsafe { GetWindowThreadProcessId(GetForegroundWindow(), None) };
-    let lcid = unsafe { GetKeyboardLayout(foreground_window_tid) };
+fn get_active_keyboard_layout(window: Option<isize>) -> Result<String, ()> {
+    let window = match window {
+        Some(window) => HWND(window as *mut c_void),
+        None => unsafe { GetForegroundWindow() },
+    };
+
+    let window_tid = unsafe { GetWindowThreadProcessId(window, None) };
+    let lcid = unsafe { GetKeyboardLayout(window_tid) };
 
     // Extract the low word (language identifier) from the keyboard layout handle.
     let lang_id = (lcid.0 as u32) & 0xFFFF;
@@ -119,23 +161,52 @@ fn get_active_keyboard_layout() -> Result<String, ()> {
 /// A `String` representing either:
 /// - The name of the active keyboard layout, or
 /// - The fallback error message (`ERROR_TEXT`) if the layout name cannot be retrieved.
-fn get_lang() -> String {
-    get_active_keyboard_layout()
+fn get_lang(window: Option<isize>) -> String {
+    get_active_keyboard_layout(window)
         .map(|l| l.trim_end_matches('\0').to_string())
         .unwrap_or_else(|_| ERROR_TEXT.to_string())
 }
 
+/// Asks a window to switch to the next installed keyboard layout, which is the same request that
+/// is sent when pressing the input language hotkey
+fn cycle_keyboard_layout(window: isize) {
+    if let Err(error) = unsafe {
+        PostMessageW(
+            Some(HWND(window as *mut c_void)),
+            WM_INPUTLANGCHANGEREQUEST,
+            WPARAM(0),
+            LPARAM(HKL_NEXT as isize),
+        )
+    } {
+        eprintln!("{}", error)
+    }
+}
+
 impl Keyboard {
     fn output(&mut self) -> String {
+        if let Some(window) = foreground_window_outside_bar() {
+            self.last_foreground_window = Option::from(window);
+        }
+
         let now = Instant::now();
         if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
             self.last_updated = now;
-            self.lang_name = get_lang();
+            self.lang_name = get_lang(self.last_foreground_window);
         }
 
+        let lang_name = if self.short_name {
+            self.lang_name
+                .split('-')
+                .next()
+                .unwrap_or_default()
+                .to_uppercase()
+        } else {
+            self.lang_name.clone()
+        };
+
         match self.label_prefix {
-            LabelPrefix::Text | LabelPrefix::IconAndText => format!("KB: {}", self.lang_name),
-            LabelPrefix::None | LabelPrefix::Icon => self.lang_name.clone(),
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("KB: {lang_name}"),
+            LabelPrefix::None | LabelPrefix::Icon => lang_name,
         }
     }
 }
@@ -169,7 +240,25 @@ impl BarWidget for Keyboard {
                 );
 
                 config.apply_on_widget(true, ui, |ui| {
-                    ui.add(Label::new(WidgetText::LayoutJob(layout_job.clone())).selectable(false))
+                    if SelectableFrame::new(false)
+                        .show(ui, |ui| {
+                            ui.add(
+                                Label::new(WidgetText::LayoutJob(layout_job.clone()))
+                                    .selectable(false),
+                            )
+                        })
+                        .clicked()
+                    {
+                        if let Some(window) = self.last_foreground_window {
+                            cycle_keyboard_layout(window);
+                        }
+
+                        // pick up the new layout on the next frame instead of waiting for
+                        // the refresh interval to pass
+                        self.last_updated = Instant::now()
+                            .checked_sub(Duration::from_secs(self.data_refresh_interval))
+                            .unwrap_or_else(Instant::now);
+                    }
                 });
             }
         }
@@ -11,7 +11,9 @@ use crate::widgets::widget::BarWidget;
 use crate::ICON_CACHE;
 use crate::MAX_LABEL_WIDTH;
 use crate::MONITOR_INDEX;
+use eframe::egui::text::LayoutJob;
 use eframe::egui::vec2;
+use eframe::egui::Align;
 use eframe::egui::Color32;
 use eframe::egui::ColorImage;
 use eframe::egui::Context;
@@ -24,12 +26,15 @@ use eframe::egui::RichText;
 use eframe::egui::Sense;
 use eframe::egui::Stroke;
 use eframe::egui::StrokeKind;
+use eframe::egui::TextFormat;
 use eframe::egui::TextureHandle;
 use eframe::egui::TextureOptions;
 use eframe::egui::Ui;
 use eframe::egui::Vec2;
 use image::RgbaImage;
 use komorebi_client::Container;
+use komorebi_client::CycleDirection;
+use komorebi_client::FocusFollowsMouseImplementation;
 use komorebi_client::NotificationEvent;
 use komorebi_client::PathExt;
 use komorebi_client::Rect;
@@ -37,6 +42,7 @@ use komorebi_client::SocketMessage;
 use komorebi_client::Window;
 use komorebi_client::Workspace;
 use komorebi_client::WorkspaceLayer;
+use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use std::cell::RefCell;
@@ -57,8 +63,12 @@ pub struct KomorebiConfig {
     pub workspace_layer: Option<KomorebiWorkspaceLayerConfig>,
     /// Configure the Focused Window widget
     pub focused_window: Option<KomorebiFocusedWindowConfig>,
+    /// Configure the Window List widget
+    pub window_list: Option<KomorebiWindowListConfig>,
     /// Configure the Configuration Switcher widget
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
+    /// Configure the Status widget
+    pub status: Option<KomorebiStatusConfig>,
 }
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
@@ -70,6 +80,9 @@ pub struct KomorebiWorkspacesConfig {
     pub hide_empty_workspaces: bool,
     /// Display format of the workspace
     pub display: Option<WorkspacesDisplayFormat>,
+    /// Maximum number of application icons shown per workspace with the AllIcons display
+    /// formats, after which the number of remaining windows is shown (default: no limit)
+    pub max_icons: Option<usize>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -94,7 +107,7 @@ pub struct KomorebiWorkspaceLayerConfig {
     pub show_when_tiling: Option<bool>,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct KomorebiFocusedWindowConfig {
     /// Enable the Komorebi Focused Window widget
@@ -103,6 +116,52 @@ pub struct KomorebiFocusedWindowConfig {
     pub show_icon: Option<bool>,
     /// Display format of the currently focused window
     pub display: Option<DisplayFormat>,
+    /// Maximum number of characters of a window title to show before it is cut off with an ellipsis
+    pub max_length: Option<usize>,
+    /// Rules to rewrite window titles before they are displayed, applied in order
+    pub title_rewrites: Option<Vec<KomorebiFocusedWindowTitleRewrite>>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiWindowListConfig {
+    /// Enable the Komorebi Window List widget
+    pub enable: bool,
+    /// Display format of the windows on the visible workspace (default: Icon)
+    pub display: Option<DisplayFormat>,
+    /// Maximum number of characters of a window title to show before it is cut off with an ellipsis
+    pub max_length: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiFocusedWindowTitleRewrite {
+    /// Executable name of the application this rule applies to, e.g. "firefox.exe" (default: all applications)
+    pub exe: Option<String>,
+    /// Regular expression to search for in the window title
+    pub pattern: String,
+    /// Replacement for each match, which can refer to capture groups such as $1 (default: "")
+    pub replacement: Option<String>,
+}
+
+/// A title rewrite rule with its pattern compiled
+#[derive(Clone, Debug)]
+pub struct TitleRewrite {
+    exe: Option<String>,
+    pattern: Regex,
+    replacement: String,
+}
+
+impl TitleRewrite {
+    fn apply(&self, exe: &str, title: &str) -> String {
+        match &self.exe {
+            Some(rule_exe) if !rule_exe.eq_ignore_ascii_case(exe) => title.to_string(),
+            _ => self
+                .pattern
+                .replace_all(title, self.replacement.as_str())
+                .to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -114,6 +173,21 @@ pub struct KomorebiConfigurationSwitcherConfig {
     pub configurations: BTreeMap<String, String>,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KomorebiStatusConfig {
+    /// Enable the Komorebi Status widget
+    pub enable: bool,
+    /// Display format of the status (default: IconAndText)
+    pub display: Option<DisplayFormat>,
+    /// Show the active focus follows mouse implementation (default: true)
+    pub show_focus_follows_mouse: Option<bool>,
+    /// Show the number of managed windows (default: true)
+    pub show_window_count: Option<bool>,
+    /// Show the active keybinding mode (default: true)
+    pub show_keybinding_mode: Option<bool>,
+}
+
 impl From<&KomorebiConfig> for Komorebi {
     fn from(value: &KomorebiConfig) -> Self {
         let configuration_switcher =
@@ -129,6 +203,31 @@ impl From<&KomorebiConfig> for Komorebi {
                 None
             };
 
+        let title_rewrites = value
+            .focused_window
+            .as_ref()
+            .and_then(|focused_window| focused_window.title_rewrites.as_ref())
+            .map(|rewrites| {
+                rewrites
+                    .iter()
+                    .filter_map(|rewrite| match Regex::new(&rewrite.pattern) {
+                        Ok(pattern) => Some(TitleRewrite {
+                            exe: rewrite.exe.clone(),
+                            pattern,
+                            replacement: rewrite.replacement.clone().unwrap_or_default(),
+                        }),
+                        Err(error) => {
+                            tracing::error!(
+                                "invalid focused window title pattern '{}': {error}",
+                                rewrite.pattern
+                            );
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Self {
             komorebi_notification_state: Rc::new(RefCell::new(KomorebiNotificationState {
                 selected_workspace: String::new(),
@@ -141,15 +240,23 @@ impl From<&KomorebiConfig> for Komorebi {
                 mouse_follows_focus: true,
                 work_area_offset: None,
                 focused_container_information: KomorebiNotificationStateContainerInformation::EMPTY,
+                visible_windows: vec![],
                 stack_accent: None,
                 monitor_index: MONITOR_INDEX.load(Ordering::SeqCst),
                 monitor_usr_idx_map: HashMap::new(),
+                is_paused: false,
+                focus_follows_mouse: None,
+                managed_windows: 0,
+                keybinding_mode: None,
             })),
             workspaces: value.workspaces,
             layout: value.layout.clone(),
-            focused_window: value.focused_window,
+            focused_window: value.focused_window.clone(),
+            window_list: value.window_list,
+            title_rewrites,
             workspace_layer: value.workspace_layer,
             configuration_switcher,
+            status: value.status,
         }
     }
 }
@@ -160,8 +267,30 @@ pub struct Komorebi {
     pub workspaces: Option<KomorebiWorkspacesConfig>,
     pub layout: Option<KomorebiLayoutConfig>,
     pub focused_window: Option<KomorebiFocusedWindowConfig>,
+    pub window_list: Option<KomorebiWindowListConfig>,
+    pub title_rewrites: Vec<TitleRewrite>,
     pub workspace_layer: Option<KomorebiWorkspaceLayerConfig>,
     pub configuration_switcher: Option<KomorebiConfigurationSwitcherConfig>,
+    pub status: Option<KomorebiStatusConfig>,
+}
+
+impl Komorebi {
+    /// The title of a window after applying the rewrite rules and the maximum length
+    fn focused_window_title(&self, exe: &str, title: &str, max_length: Option<usize>) -> String {
+        let title = self
+            .title_rewrites
+            .iter()
+            .fold(title.to_string(), |title, rewrite| {
+                rewrite.apply(exe, &title)
+            });
+
+        match max_length {
+            Some(max_length) if title.chars().count() > max_length => {
+                format!("{}…", title.chars().take(max_length).collect::<String>())
+            }
+            _ => title,
+        }
+    }
 }
 
 impl BarWidget for Komorebi {
@@ -173,17 +302,16 @@ impl BarWidget for Komorebi {
         if let Some(workspaces) = self.workspaces {
             if workspaces.enable {
                 let mut update = None;
+                let mut scroll_delta = 0.0;
 
                 if !komorebi_notification_state.workspaces.is_empty() {
                     let format = workspaces.display.unwrap_or(DisplayFormat::Text.into());
 
                     config.apply_on_widget(false, ui, |ui| {
-                        for (i, (ws, containers, _)) in
-                            komorebi_notification_state.workspaces.iter().enumerate()
-                        {
+                        for (ws, containers, _, &i) in komorebi_notification_state.workspaces.iter() {
                             let is_selected = komorebi_notification_state.selected_workspace.eq(ws);
 
-                            if SelectableFrame::new(
+                            let response = SelectableFrame::new(
                                 is_selected,
                             )
                             .show(ui, |ui| {
@@ -207,14 +335,28 @@ impl BarWidget for Komorebi {
                                                 ui.style().spacing.button_padding.y as i8,
                                             ))
                                             .show(ui, |ui| {
-                                                for (is_focused, container) in containers {
-                                                    for icon in container.icons.iter().flatten().collect::<Vec<_>>() {
-                                                        ui.add(
-                                                            Image::from(&img_to_texture(ctx, icon))
-                                                                .maintain_aspect_ratio(true)
-                                                                .fit_to_exact_size(if *is_focused { icon_size } else { text_size }),
-                                                        );
-                                                    }
+                                                let icons = containers
+                                                    .iter()
+                                                    .flat_map(|(is_focused, container)| {
+                                                        container.icons.iter().flatten().map(move |icon| (*is_focused, icon))
+                                                    })
+                                                    .collect::<Vec<_>>();
+                                                let max_icons = workspaces.max_icons.unwrap_or(usize::MAX);
+
+                                                for (is_focused, icon) in icons.iter().take(max_icons) {
+                                                    ui.add(
+                                                        Image::from(&img_to_texture(ctx, icon))
+                                                            .maintain_aspect_ratio(true)
+                                                            .fit_to_exact_size(if *is_focused { icon_size } else { text_size }),
+                                                    );
+                                                }
+
+                                                // the windows which didn't fit are shown as a count
+                                                if icons.len() > max_icons {
+                                                    ui.add(
+                                                        Label::new(format!("+{}", icons.len() - max_icons))
+                                                            .selectable(false),
+                                                    );
                                                 }
                                             });
                                     }
@@ -257,16 +399,16 @@ impl BarWidget for Komorebi {
                                 } else {
                                     ui.response()
                                 }
-                            })
-                            .clicked()
-                            {
+                            });
+
+                            if response.clicked() {
                                 update = Some(ws.to_string());
 
                                 if komorebi_notification_state.mouse_follows_focus {
                                     if komorebi_client::send_batch([
                                         SocketMessage::MouseFollowsFocus(false),
                                         SocketMessage::FocusMonitorWorkspaceNumber(
-                                            komorebi_notification_state.monitor_index,
+                                            komorebi_notification_state.usr_monitor_index(),
                                             i,
                                         ),
                                         SocketMessage::RetileWithResizeDimensions,
@@ -286,7 +428,7 @@ impl BarWidget for Komorebi {
                                     }
                                 } else if komorebi_client::send_batch([
                                     SocketMessage::FocusMonitorWorkspaceNumber(
-                                        komorebi_notification_state.monitor_index,
+                                        komorebi_notification_state.usr_monitor_index(),
                                         i,
                                     ),
                                     SocketMessage::RetileWithResizeDimensions,
@@ -301,9 +443,59 @@ impl BarWidget for Komorebi {
                                         i,
                                     );
                                 }
+                            } else if response.middle_clicked()
+                                && komorebi_client::send_message(
+                                    &SocketMessage::MoveContainerToMonitorWorkspaceNumber(
+                                        komorebi_notification_state.usr_monitor_index(),
+                                        i,
+                                    ),
+                                )
+                                .is_err()
+                            {
+                                tracing::error!(
+                                    "could not send message to komorebi: MoveContainerToMonitorWorkspaceNumber({}, {})",
+                                    komorebi_notification_state.monitor_index,
+                                    i,
+                                );
+                            }
+
+                            if response.hovered() {
+                                scroll_delta = ui.input(|input| input.raw_scroll_delta.y);
                             }
                         }
                     });
+
+                    // scrolling up goes to the previous workspace, scrolling down to the next one
+                    let direction = if scroll_delta > 0.0 {
+                        Some(CycleDirection::Previous)
+                    } else if scroll_delta < 0.0 {
+                        Some(CycleDirection::Next)
+                    } else {
+                        None
+                    };
+
+                    if let Some(direction) = direction {
+                        if komorebi_client::send_batch([
+                            SocketMessage::FocusMonitorAtCursor,
+                            SocketMessage::MouseFollowsFocus(false),
+                            SocketMessage::CycleFocusWorkspace(direction),
+                            SocketMessage::MouseFollowsFocus(
+                                komorebi_notification_state.mouse_follows_focus,
+                            ),
+                        ])
+                        .is_err()
+                        {
+                            tracing::error!(
+                                "could not send the following batch of messages to komorebi:\n
+                                FocusMonitorAtCursor\n
+                                MouseFollowsFocus(false)\n
+                                CycleFocusWorkspace({})\n
+                                MouseFollowsFocus({})",
+                                direction,
+                                komorebi_notification_state.mouse_follows_focus,
+                            );
+                        }
+                    }
                 }
 
                 if let Some(update) = update {
@@ -318,7 +510,7 @@ impl BarWidget for Komorebi {
                     .workspaces
                     .iter()
                     .find(|o| komorebi_notification_state.selected_workspace.eq(&o.0))
-                    .map(|(_, _, layer)| layer);
+                    .map(|(_, _, layer, _)| layer);
 
                 if let Some(layer) = layer {
                     if (layer_config.show_when_tiling.unwrap_or_default()
@@ -476,7 +668,8 @@ impl BarWidget for Komorebi {
                                             if let Ok(monitor_idx) = idx.parse::<usize>() {
                                                 if komorebi_client::send_message(
                                                     &SocketMessage::MonitorWorkAreaOffset(
-                                                        monitor_idx,
+                                                        komorebi_notification_state
+                                                            .usr_monitor_index_of(monitor_idx),
                                                         rect,
                                                     ),
                                                 )
@@ -502,7 +695,7 @@ impl BarWidget for Komorebi {
             }
         }
 
-        if let Some(focused_window) = self.focused_window {
+        if let Some(focused_window) = &self.focused_window {
             if focused_window.enable {
                 let titles = &komorebi_notification_state
                     .focused_container_information
@@ -512,14 +705,18 @@ impl BarWidget for Komorebi {
                         let icons = &komorebi_notification_state
                             .focused_container_information
                             .icons;
+                        let exes = &komorebi_notification_state
+                            .focused_container_information
+                            .exes;
                         let focused_window_idx = komorebi_notification_state
                             .focused_container_information
                             .focused_window_idx;
 
-                        let iter = titles.iter().zip(icons.iter());
+                        let iter = titles.iter().zip(icons.iter()).zip(exes.iter());
                         let len = iter.len();
 
-                        for (i, (title, icon)) in iter.enumerate() {
+                        for (i, ((title, icon), exe)) in iter.enumerate() {
+                            let title = self.focused_window_title(exe, title, focused_window.max_length);
                             let selected = i == focused_window_idx && len != 1;
                             let text_color = if selected { ctx.style().visuals.selection.stroke.color} else { ui.style().visuals.text_color() };
 
@@ -553,7 +750,7 @@ impl BarWidget for Komorebi {
                                                     );
 
                                                     if let DisplayFormat::Icon = format {
-                                                        response.on_hover_text(title);
+                                                        response.on_hover_text(&title);
                                                     }
                                                 });
                                         }
@@ -573,17 +770,32 @@ impl BarWidget for Komorebi {
                                                 MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
                                                 available_height,
                                             ),
-                                            Label::new(RichText::new( title).color(text_color)).selectable(false).truncate(),
+                                            Label::new(RichText::new(&title).color(text_color)).selectable(false).truncate(),
                                         );
                                     }
                                 })
                                 .clicked()
                             {
-                                if selected {
-                                    return;
-                                }
-
-                                if komorebi_notification_state.mouse_follows_focus {
+                                // clicking the focused window toggles monocle for its container
+                                if i == focused_window_idx {
+                                    if komorebi_client::send_batch([
+                                        SocketMessage::FocusMonitorAtCursor,
+                                        SocketMessage::MouseFollowsFocus(false),
+                                        SocketMessage::ToggleMonocle,
+                                        SocketMessage::MouseFollowsFocus(
+                                            komorebi_notification_state.mouse_follows_focus,
+                                        ),
+                                    ]).is_err() {
+                                        tracing::error!(
+                                            "could not send the following batch of messages to komorebi:\n
+                                            FocusMonitorAtCursor\n
+                                            MouseFollowsFocus(false)\n
+                                            ToggleMonocle\n
+                                            MouseFollowsFocus({})\n",
+                                            komorebi_notification_state.mouse_follows_focus,
+                                        );
+                                    }
+                                } else if komorebi_notification_state.mouse_follows_focus {
                                     if komorebi_client::send_batch([
                                         SocketMessage::MouseFollowsFocus(false),
                                         SocketMessage::FocusStackWindow(i),
@@ -610,10 +822,173 @@ impl BarWidget for Komorebi {
                 }
             }
         }
+
+        if let Some(window_list) = self.window_list {
+            if window_list.enable && !komorebi_notification_state.visible_windows.is_empty() {
+                let format = window_list.display.unwrap_or(DisplayFormat::Icon);
+                let mouse_follows_focus = komorebi_notification_state.mouse_follows_focus;
+
+                config.apply_on_widget(false, ui, |ui| {
+                    for (is_focused, container) in &komorebi_notification_state.visible_windows {
+                        let iter = container
+                            .titles
+                            .iter()
+                            .zip(container.exes.iter())
+                            .zip(container.icons.iter())
+                            .zip(container.hwnds.iter());
+
+                        for (i, (((title, exe), icon), &hwnd)) in iter.enumerate() {
+                            let title = self.focused_window_title(exe, title, window_list.max_length);
+                            let selected = *is_focused && i == container.focused_window_idx;
+                            let text_color = if selected { ctx.style().visuals.selection.stroke.color } else { ui.style().visuals.text_color() };
+
+                            let show_icon = format == DisplayFormat::Icon
+                                || format == DisplayFormat::IconAndText
+                                || format == DisplayFormat::IconAndTextOnSelected
+                                || (format == DisplayFormat::TextAndIconOnSelected && selected);
+
+                            // windows without an icon always show their title
+                            let show_text = format == DisplayFormat::Text
+                                || format == DisplayFormat::IconAndText
+                                || format == DisplayFormat::TextAndIconOnSelected
+                                || (format == DisplayFormat::IconAndTextOnSelected && selected)
+                                || icon.is_none();
+
+                            let response = SelectableFrame::new(selected)
+                                .show(ui, |ui| {
+                                    if let (true, Some(img)) = (show_icon, icon) {
+                                        Frame::NONE
+                                            .inner_margin(Margin::same(
+                                                ui.style().spacing.button_padding.y as i8,
+                                            ))
+                                            .show(ui, |ui| {
+                                                ui.add(
+                                                    Image::from(&img_to_texture(ctx, img))
+                                                        .maintain_aspect_ratio(true)
+                                                        .fit_to_exact_size(icon_size),
+                                                );
+                                            });
+                                    }
+
+                                    if show_text {
+                                        ui.add(Label::new(RichText::new(&title).color(text_color)).selectable(false));
+                                    }
+                                })
+                                .on_hover_text(&title);
+
+                            if response.clicked() {
+                                if mouse_follows_focus {
+                                    if komorebi_client::send_batch([
+                                        SocketMessage::MouseFollowsFocus(false),
+                                        SocketMessage::FocusWindowHwnd(hwnd),
+                                        SocketMessage::MouseFollowsFocus(true),
+                                    ]).is_err() {
+                                        tracing::error!(
+                                            "could not send the following batch of messages to komorebi:\n
+                                            MouseFollowsFocus(false)\n
+                                            FocusWindowHwnd({})\n
+                                            MouseFollowsFocus(true)\n",
+                                            hwnd,
+                                        );
+                                    }
+                                } else if komorebi_client::send_message(
+                                    &SocketMessage::FocusWindowHwnd(hwnd)
+                                ).is_err() {
+                                    tracing::error!(
+                                        "could not send message to komorebi: FocusWindowHwnd"
+                                    );
+                                }
+                            } else if response.secondary_clicked()
+                                && komorebi_client::send_message(&SocketMessage::CloseWindowHwnd(hwnd))
+                                    .is_err()
+                            {
+                                tracing::error!("could not send message to komorebi: CloseWindowHwnd");
+                            }
+                        }
+                    }
+                });
+            }
+        }
+
+        if let Some(status) = self.status {
+            if status.enable {
+                let display_format = status.display.unwrap_or(DisplayFormat::IconAndText);
+
+                let (icon, state) = if komorebi_notification_state.is_paused {
+                    (egui_phosphor::regular::PAUSE, "Paused")
+                } else {
+                    (egui_phosphor::regular::PLAY, "Running")
+                };
+
+                let mut details = vec![state.to_string()];
+
+                if status.show_keybinding_mode.unwrap_or(true) {
+                    if let Some(mode) = &komorebi_notification_state.keybinding_mode {
+                        details.push(format!("Mode: {mode}"));
+                    }
+                }
+
+                if status.show_focus_follows_mouse.unwrap_or(true) {
+                    if let Some(implementation) = komorebi_notification_state.focus_follows_mouse {
+                        details.push(format!("FFM: {implementation}"));
+                    }
+                }
+
+                if status.show_window_count.unwrap_or(true) {
+                    details.push(komorebi_notification_state.managed_windows.to_string());
+                }
+
+                let mut layout_job = LayoutJob::simple(
+                    match display_format {
+                        DisplayFormat::Text => String::new(),
+                        _ => icon.to_string(),
+                    },
+                    config.icon_font_id.clone(),
+                    ctx.style().visuals.selection.stroke.color,
+                    100.0,
+                );
+
+                if display_format != DisplayFormat::Icon {
+                    layout_job.append(
+                        &details.join(" | "),
+                        10.0,
+                        TextFormat {
+                            font_id: config.text_font_id.clone(),
+                            color: ctx.style().visuals.text_color(),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                let hover_text = format!(
+                    "komorebi is {}, managing {} windows",
+                    state.to_lowercase(),
+                    komorebi_notification_state.managed_windows
+                );
+
+                config.apply_on_widget(false, ui, |ui| {
+                    let response = SelectableFrame::new(komorebi_notification_state.is_paused)
+                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .on_hover_text(hover_text);
+
+                    if response.clicked() {
+                        if komorebi_client::send_message(&SocketMessage::TogglePause).is_err() {
+                            tracing::error!("could not send message to komorebi: TogglePause");
+                        }
+                    } else if response.secondary_clicked()
+                        && komorebi_client::send_message(&SocketMessage::ReloadConfiguration)
+                            .is_err()
+                    {
+                        tracing::error!("could not send message to komorebi: ReloadConfiguration");
+                    }
+                });
+            }
+        }
     }
 }
 
-fn img_to_texture(ctx: &Context, rgba_image: &RgbaImage) -> TextureHandle {
+pub fn img_to_texture(ctx: &Context, rgba_image: &RgbaImage) -> TextureHandle {
     let size = [rgba_image.width() as usize, rgba_image.height() as usize];
     let pixels = rgba_image.as_flat_samples();
     let color_image = ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
@@ -627,9 +1002,12 @@ pub struct KomorebiNotificationState {
         String,
         Vec<(bool, KomorebiNotificationStateContainerInformation)>,
         WorkspaceLayer,
+        usize,
     )>,
     pub selected_workspace: String,
     pub focused_container_information: KomorebiNotificationStateContainerInformation,
+    /// The containers and floating windows on the visible workspace of the monitor
+    pub visible_windows: Vec<(bool, KomorebiNotificationStateContainerInformation)>,
     pub layout: KomorebiLayout,
     pub hide_empty_workspaces: bool,
     pub mouse_follows_focus: bool,
@@ -637,6 +1015,10 @@ pub struct KomorebiNotificationState {
     pub stack_accent: Option<Color32>,
     pub monitor_index: usize,
     pub monitor_usr_idx_map: HashMap<usize, usize>,
+    pub is_paused: bool,
+    pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
+    pub managed_windows: usize,
+    pub keybinding_mode: Option<String>,
 }
 
 impl KomorebiNotificationState {
@@ -644,12 +1026,26 @@ impl KomorebiNotificationState {
         self.hide_empty_workspaces = config.hide_empty_workspaces;
     }
 
+    /// The index komorebi knows this bar's monitor by in its configuration and commands
+    pub fn usr_monitor_index(&self) -> usize {
+        self.usr_monitor_index_of(self.monitor_index)
+    }
+
+    /// Maps the current index of a monitor back to the index used for it in configuration and
+    /// commands, which komorebi translates incoming monitor indices from
+    pub fn usr_monitor_index_of(&self, monitor_index: usize) -> usize {
+        self.monitor_usr_idx_map
+            .iter()
+            .find_map(|(usr_idx, m_idx)| (*m_idx == monitor_index).then_some(*usr_idx))
+            .unwrap_or(monitor_index)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn handle_notification(
         &mut self,
         ctx: &Context,
         monitor_index: Option<usize>,
-        notification: komorebi_client::Notification,
+        notification: &komorebi_client::Notification,
         bg_color: Rc<RefCell<Color32>>,
         bg_color_with_alpha: Rc<RefCell<Color32>>,
         transparency_alpha: Option<u8>,
@@ -659,12 +1055,13 @@ impl KomorebiNotificationState {
     ) {
         let show_all_icons = render_config.borrow().show_all_icons;
 
-        match notification.event {
+        match &notification.event {
             NotificationEvent::WindowManager(_) => {}
             NotificationEvent::Monitor(_) => {}
+            NotificationEvent::Focus(_) => {}
             NotificationEvent::Socket(message) => match message {
                 SocketMessage::ReloadStaticConfiguration(path) => {
-                    if let Ok(config) = komorebi_client::StaticConfig::read(&path) {
+                    if let Ok(config) = komorebi_client::StaticConfig::read(path) {
                         if let Some(theme) = config.theme {
                             apply_theme(
                                 ctx,
@@ -695,7 +1092,7 @@ impl KomorebiNotificationState {
                 SocketMessage::Theme(theme) => {
                     apply_theme(
                         ctx,
-                        KomobarTheme::from(theme),
+                        KomobarTheme::from(*theme),
                         bg_color,
                         bg_color_with_alpha.clone(),
                         transparency_alpha,
@@ -709,6 +1106,26 @@ impl KomorebiNotificationState {
         }
 
         self.monitor_usr_idx_map = notification.state.monitor_usr_idx_map.clone();
+        self.is_paused = notification.state.is_paused;
+        self.focus_follows_mouse = notification.state.focus_follows_mouse;
+        self.keybinding_mode = notification.state.keybinding_mode.clone();
+        self.managed_windows = notification
+            .state
+            .monitors
+            .elements()
+            .iter()
+            .flat_map(|monitor| monitor.workspaces())
+            .map(|workspace| {
+                workspace
+                    .containers()
+                    .iter()
+                    .chain(workspace.monocle_container())
+                    .map(|container| container.windows().len())
+                    .sum::<usize>()
+                    + workspace.floating_windows().len()
+                    + usize::from(workspace.maximized_window().is_some())
+            })
+            .sum();
 
         if monitor_index.is_none()
             || monitor_index.is_some_and(|idx| idx >= notification.state.monitors.elements().len())
@@ -730,10 +1147,10 @@ impl KomorebiNotificationState {
 
         let mut workspaces = vec![];
 
-        self.selected_workspace = monitor.workspaces()[focused_workspace_idx]
-            .name()
-            .to_owned()
-            .unwrap_or_else(|| format!("{}", focused_workspace_idx + 1));
+        self.selected_workspace = workspace_label(
+            &monitor.workspaces()[focused_workspace_idx],
+            focused_workspace_idx,
+        );
 
         for (i, ws) in monitor.workspaces().iter().enumerate() {
             let should_show = if self.hide_empty_workspaces {
@@ -744,38 +1161,14 @@ impl KomorebiNotificationState {
 
             if should_show {
                 workspaces.push((
-                    ws.name().to_owned().unwrap_or_else(|| format!("{}", i + 1)),
+                    workspace_label(ws, i),
                     if show_all_icons {
-                        let mut containers = vec![];
-                        let mut has_monocle = false;
-
-                        // add monocle container
-                        if let Some(container) = ws.monocle_container() {
-                            containers.push((true, container.into()));
-                            has_monocle = true;
-                        }
-
-                        // add all tiled windows
-                        for (i, container) in ws.containers().iter().enumerate() {
-                            containers.push((
-                                !has_monocle && i == ws.focused_container_idx(),
-                                container.into(),
-                            ));
-                        }
-
-                        // add all floating windows
-                        for floating_window in ws.floating_windows() {
-                            containers.push((
-                                !has_monocle && floating_window.is_focused(),
-                                floating_window.into(),
-                            ));
-                        }
-
-                        containers
+                        workspace_containers(ws)
                     } else {
                         vec![(true, ws.into())]
                     },
                     ws.layer().to_owned(),
+                    i,
                 ));
             }
         }
@@ -799,13 +1192,58 @@ impl KomorebiNotificationState {
         }
 
         self.focused_container_information = (&monitor.workspaces()[focused_workspace_idx]).into();
+        self.visible_windows = workspace_containers(&monitor.workspaces()[focused_workspace_idx]);
+    }
+}
+
+/// The name of the workspace, or its number followed by the name komorebi gave it after the
+/// application with the most windows on it, which keeps the labels of unnamed workspaces unique
+fn workspace_label(workspace: &Workspace, idx: usize) -> String {
+    match (workspace.name(), workspace.auto_name()) {
+        (Some(name), _) => name.clone(),
+        (None, Some(auto_name)) => format!("{}: {auto_name}", idx + 1),
+        (None, None) => format!("{}", idx + 1),
+    }
+}
+
+/// The containers and floating windows of a workspace, and whether each of them is focused
+fn workspace_containers(
+    ws: &Workspace,
+) -> Vec<(bool, KomorebiNotificationStateContainerInformation)> {
+    let mut containers = vec![];
+    let mut has_monocle = false;
+
+    // add monocle container
+    if let Some(container) = ws.monocle_container() {
+        containers.push((true, container.into()));
+        has_monocle = true;
+    }
+
+    // add all tiled windows
+    for (i, container) in ws.containers().iter().enumerate() {
+        containers.push((
+            !has_monocle && i == ws.focused_container_idx(),
+            container.into(),
+        ));
+    }
+
+    // add all floating windows
+    for floating_window in ws.floating_windows() {
+        containers.push((
+            !has_monocle && floating_window.is_focused(),
+            floating_window.into(),
+        ));
     }
+
+    containers
 }
 
 #[derive(Clone, Debug)]
 pub struct KomorebiNotificationStateContainerInformation {
     pub titles: Vec<String>,
+    pub exes: Vec<String>,
     pub icons: Vec<Option<RgbaImage>>,
+    pub hwnds: Vec<isize>,
     pub focused_window_idx: usize,
 }
 
@@ -867,7 +1305,13 @@ impl From<&Container> for KomorebiNotificationStateContainerInformation {
                 .iter()
                 .map(|w| w.title().unwrap_or_default())
                 .collect::<Vec<_>>(),
+            exes: value
+                .windows()
+                .iter()
+                .map(|w| w.exe().unwrap_or_default())
+                .collect::<Vec<_>>(),
             icons,
+            hwnds: value.windows().iter().map(|w| w.hwnd).collect::<Vec<_>>(),
             focused_window_idx: value.focused_window_idx(),
         }
     }
@@ -903,7 +1347,9 @@ impl From<&Window> for KomorebiNotificationStateContainerInformation {
 
         Self {
             titles: vec![value.title().unwrap_or_default()],
+            exes: vec![value.exe().unwrap_or_default()],
             icons,
+            hwnds: vec![value.hwnd],
             focused_window_idx: 0,
         }
     }
@@ -912,7 +1358,9 @@ impl From<&Window> for KomorebiNotificationStateContainerInformation {
 impl KomorebiNotificationStateContainerInformation {
     pub const EMPTY: Self = Self {
         titles: vec![],
+        exes: vec![],
         icons: vec![],
+        hwnds: vec![],
         focused_window_idx: 0,
     };
 }
@@ -0,0 +1,195 @@
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::komorebi::img_to_texture;
+use crate::widgets::systray::icon_to_image;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Image;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use image::RgbaImage;
+use serde::Deserialize;
+use serde::Serialize;
+use windows::core::HSTRING;
+use windows::core::PCWSTR;
+use windows::Win32::UI::Shell::ExtractIconExW;
+use windows::Win32::UI::Shell::ShellExecuteW;
+use windows::Win32::UI::WindowsAndMessaging::DestroyIcon;
+use windows::Win32::UI::WindowsAndMessaging::HICON;
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LauncherConfig {
+    /// Enable the Launcher widget
+    pub enable: bool,
+    /// Applications to show, in order
+    pub apps: Vec<LauncherApp>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct LauncherApp {
+    /// Program, document or URI to open, e.g. "C:\\Program Files\\Mozilla Firefox\\firefox.exe"
+    pub command: String,
+    /// Arguments passed to the program
+    pub arguments: Option<String>,
+    /// Label shown next to the icon
+    pub label: Option<String>,
+    /// Text or icon font glyph to show instead of the icon of the program (default: the icon of the program)
+    pub icon: Option<String>,
+    /// Run the program as an administrator (default: false)
+    pub elevated: Option<bool>,
+}
+
+impl From<LauncherConfig> for Launcher {
+    fn from(value: LauncherConfig) -> Self {
+        Self {
+            enable: value.enable,
+            apps: value
+                .apps
+                .into_iter()
+                .map(|app| {
+                    // only look up the program icon when there is no icon configured
+                    let image = match app.icon {
+                        Some(_) => None,
+                        None => program_icon(&app.command),
+                    };
+
+                    LaunchableApp { image, config: app }
+                })
+                .collect(),
+        }
+    }
+}
+
+pub struct Launcher {
+    pub enable: bool,
+    apps: Vec<LaunchableApp>,
+}
+
+struct LaunchableApp {
+    config: LauncherApp,
+    image: Option<RgbaImage>,
+}
+
+impl LaunchableApp {
+    fn launch(&self) {
+        let verb = if self.config.elevated.unwrap_or_default() {
+            HSTRING::from("runas")
+        } else {
+            HSTRING::from("open")
+        };
+        let command = HSTRING::from(&self.config.command);
+        let arguments = self.config.arguments.as_deref().map(HSTRING::from);
+
+        let instance = unsafe {
+            ShellExecuteW(
+                None,
+                &verb,
+                &command,
+                arguments
+                    .as_ref()
+                    .map_or(PCWSTR::null(), |arguments| PCWSTR(arguments.as_ptr())),
+                PCWSTR::null(),
+                SW_SHOWNORMAL,
+            )
+        };
+
+        // anything at or below 32 is an error code rather than an instance handle
+        if instance.0 as isize <= 32 {
+            tracing::error!(
+                "could not launch '{}': error code {}",
+                self.config.command,
+                instance.0 as isize
+            );
+        }
+    }
+}
+
+/// The large icon embedded in the program, if the command is a path to one
+fn program_icon(command: &str) -> Option<RgbaImage> {
+    let mut icon = HICON::default();
+    let extracted = unsafe { ExtractIconExW(&HSTRING::from(command), 0, Some(&mut icon), None, 1) };
+
+    if extracted == 0 || icon.is_invalid() {
+        return None;
+    }
+
+    let image = icon_to_image(icon);
+
+    unsafe {
+        let _ = DestroyIcon(icon);
+    }
+
+    image
+}
+
+impl BarWidget for Launcher {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable || self.apps.is_empty() {
+            return;
+        }
+
+        let icon_size = Vec2::splat(config.icon_font_id.size);
+        let icon_font_id = config.icon_font_id.clone();
+        let text_font_id = config.text_font_id.clone();
+
+        config.apply_on_widget(false, ui, |ui| {
+            for app in &self.apps {
+                let mut layout_job = LayoutJob::simple(
+                    match (&app.config.icon, &app.image) {
+                        (Some(icon), _) => icon.clone(),
+                        (None, Some(_)) => String::new(),
+                        (None, None) => egui_phosphor::regular::APP_WINDOW.to_string(),
+                    },
+                    icon_font_id.clone(),
+                    ctx.style().visuals.selection.stroke.color,
+                    100.0,
+                );
+
+                if let Some(label) = &app.config.label {
+                    layout_job.append(
+                        label,
+                        10.0,
+                        TextFormat {
+                            font_id: text_font_id.clone(),
+                            color: ctx.style().visuals.text_color(),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                let response = SelectableFrame::new(false)
+                    .show(ui, |ui| {
+                        if let Some(image) = &app.image {
+                            ui.add(
+                                Image::from(&img_to_texture(ctx, image))
+                                    .maintain_aspect_ratio(true)
+                                    .fit_to_exact_size(icon_size),
+                            );
+                        }
+
+                        if !layout_job.text.is_empty() {
+                            ui.add(Label::new(layout_job).selectable(false));
+                        }
+                    })
+                    .on_hover_text(
+                        app.config
+                            .label
+                            .clone()
+                            .unwrap_or_else(|| app.config.command.clone()),
+                    );
+
+                if response.clicked() {
+                    app.launch();
+                }
+            }
+        });
+    }
+}
@@ -20,24 +20,28 @@ use windows::Media::Control::GlobalSystemMediaTransportControlsSessionManager;
 pub struct MediaConfig {
     /// Enable the Media widget
     pub enable: bool,
+    /// Max label width before text truncation (default: the bar's max_label_width)
+    pub max_label_width: Option<f32>,
 }
 
 impl From<MediaConfig> for Media {
     fn from(value: MediaConfig) -> Self {
-        Self::new(value.enable)
+        Self::new(value.enable, value.max_label_width)
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Media {
     pub enable: bool,
+    pub max_label_width: Option<f32>,
     pub session_manager: GlobalSystemMediaTransportControlsSessionManager,
 }
 
 impl Media {
-    pub fn new(enable: bool) -> Self {
+    pub fn new(enable: bool, max_label_width: Option<f32>) -> Self {
         Self {
             enable,
+            max_label_width,
             session_manager: GlobalSystemMediaTransportControlsSessionManager::RequestAsync()
                 .unwrap()
                 .get()
@@ -53,6 +57,22 @@ impl Media {
         }
     }
 
+    pub fn next(&self) {
+        if let Ok(session) = self.session_manager.GetCurrentSession() {
+            if let Ok(op) = session.TrySkipNextAsync() {
+                op.get().unwrap_or_default();
+            }
+        }
+    }
+
+    pub fn previous(&self) {
+        if let Ok(session) = self.session_manager.GetCurrentSession() {
+            if let Ok(op) = session.TrySkipPreviousAsync() {
+                op.get().unwrap_or_default();
+            }
+        }
+    }
+
     fn output(&mut self) -> String {
         if let Ok(session) = self.session_manager.GetCurrentSession() {
             if let Ok(operation) = session.TryGetMediaPropertiesAsync() {
@@ -99,24 +119,34 @@ impl BarWidget for Media {
                     },
                 );
 
+                let max_label_width = self
+                    .max_label_width
+                    .unwrap_or(MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32);
+
                 config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new(false)
-                        .show(ui, |ui| {
-                            let available_height = ui.available_height();
-                            let mut custom_ui = CustomUi(ui);
-
-                            custom_ui.add_sized_left_to_right(
-                                Vec2::new(
-                                    MAX_LABEL_WIDTH.load(Ordering::SeqCst) as f32,
-                                    available_height,
-                                ),
-                                Label::new(layout_job).selectable(false).truncate(),
-                            )
-                        })
-                        .clicked()
-                    {
+                    let response = SelectableFrame::new(false).show(ui, |ui| {
+                        let available_height = ui.available_height();
+                        let mut custom_ui = CustomUi(ui);
+
+                        custom_ui.add_sized_left_to_right(
+                            Vec2::new(max_label_width, available_height),
+                            Label::new(layout_job).selectable(false).truncate(),
+                        )
+                    });
+
+                    if response.clicked() {
                         self.toggle();
                     }
+
+                    // scrolling up goes back a track, scrolling down skips ahead
+                    if response.hovered() {
+                        let scroll_delta = ui.input(|input| input.raw_scroll_delta.y);
+                        if scroll_delta > 0.0 {
+                            self.previous();
+                        } else if scroll_delta < 0.0 {
+                            self.next();
+                        }
+                    }
                 });
             }
         }
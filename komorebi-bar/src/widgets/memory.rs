@@ -1,6 +1,10 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
+use crate::widgets::resource_usage::to_gib;
+use crate::widgets::resource_usage::ResourceDisplayFormat;
+use crate::widgets::resource_usage::UsageHistory;
+use crate::widgets::resource_usage::UsageThresholds;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -25,6 +29,12 @@ pub struct MemoryConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// Display format of the current usage (default: Text)
+    pub display_format: Option<ResourceDisplayFormat>,
+    /// Show used and total memory instead of a percentage (default: false)
+    pub show_used_total: Option<bool>,
+    /// Usage thresholds at which the widget changes colour
+    pub thresholds: Option<UsageThresholds>,
 }
 
 impl From<MemoryConfig> for Memory {
@@ -38,6 +48,10 @@ impl From<MemoryConfig> for Memory {
             ),
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            display_format: value.display_format.unwrap_or_default(),
+            show_used_total: value.show_used_total.unwrap_or(false),
+            thresholds: value.thresholds,
+            history: UsageHistory::default(),
             last_updated: Instant::now()
                 .checked_sub(Duration::from_secs(data_refresh_interval))
                 .unwrap(),
@@ -50,6 +64,10 @@ pub struct Memory {
     system: System,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
+    display_format: ResourceDisplayFormat,
+    show_used_total: bool,
+    thresholds: Option<UsageThresholds>,
+    history: UsageHistory,
     last_updated: Instant,
 }
 
@@ -58,61 +76,89 @@ impl Memory {
         let now = Instant::now();
         if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
             self.system.refresh_memory();
+            self.history.push(self.usage());
             self.last_updated = now;
         }
 
+        if !self.display_format.shows_text() {
+            return match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => String::from("RAM:"),
+                LabelPrefix::None | LabelPrefix::Icon => String::new(),
+            };
+        }
+
         let used = self.system.used_memory();
         let total = self.system.total_memory();
+        let reading = if self.show_used_total {
+            format!("{:.1}/{:.1} GiB", to_gib(used), to_gib(total))
+        } else {
+            format!("{}%", (used * 100) / total)
+        };
+
         match self.label_prefix {
-            LabelPrefix::Text | LabelPrefix::IconAndText => {
-                format!("RAM: {}%", (used * 100) / total)
-            }
-            LabelPrefix::None | LabelPrefix::Icon => format!("{}%", (used * 100) / total),
+            LabelPrefix::Text | LabelPrefix::IconAndText => format!("RAM: {reading}"),
+            LabelPrefix::None | LabelPrefix::Icon => reading,
         }
     }
+
+    fn usage(&self) -> f32 {
+        (self.system.used_memory() * 100) as f32 / self.system.total_memory() as f32
+    }
 }
 
 impl BarWidget for Memory {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
             let output = self.output();
-            if !output.is_empty() {
-                let mut layout_job = LayoutJob::simple(
-                    match self.label_prefix {
-                        LabelPrefix::Icon | LabelPrefix::IconAndText => {
-                            egui_phosphor::regular::MEMORY.to_string()
-                        }
-                        LabelPrefix::None | LabelPrefix::Text => String::new(),
-                    },
-                    config.icon_font_id.clone(),
-                    ctx.style().visuals.selection.stroke.color,
-                    100.0,
-                );
+            let warning_colour = self
+                .thresholds
+                .and_then(|thresholds| thresholds.colour(ctx, self.usage()));
 
-                layout_job.append(
-                    &output,
-                    10.0,
-                    TextFormat {
-                        font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
-                        valign: Align::Center,
-                        ..Default::default()
-                    },
-                );
+            let mut layout_job = LayoutJob::simple(
+                match self.label_prefix {
+                    LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                        egui_phosphor::regular::MEMORY.to_string()
+                    }
+                    LabelPrefix::None | LabelPrefix::Text => String::new(),
+                },
+                config.icon_font_id.clone(),
+                warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
+                100.0,
+            );
 
-                config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new(false)
-                        .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
-                        .clicked()
-                    {
-                        if let Err(error) =
-                            Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
-                        {
-                            eprintln!("{}", error)
+            layout_job.append(
+                &output,
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: warning_colour.unwrap_or(ctx.style().visuals.text_color()),
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+
+            let graph_height = config.text_font_id.size;
+            let graph_colour = warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color);
+
+            config.apply_on_widget(false, ui, |ui| {
+                if SelectableFrame::new(false)
+                    .show(ui, |ui| {
+                        if !layout_job.text.is_empty() {
+                            ui.add(Label::new(layout_job).selectable(false));
                         }
+
+                        if self.display_format.shows_graph() {
+                            self.history.paint(ui, graph_height, graph_colour);
+                        }
+                    })
+                    .clicked()
+                {
+                    if let Err(error) = Command::new("cmd.exe").args(["/C", "taskmgr.exe"]).spawn()
+                    {
+                        eprintln!("{}", error)
                     }
-                });
-            }
+                }
+            });
         }
     }
 }
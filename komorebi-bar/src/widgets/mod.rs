@@ -1,13 +1,25 @@
 pub mod battery;
 pub mod cpu;
 pub mod date;
+pub mod exec;
+pub mod gpu;
+pub mod group;
 pub mod keyboard;
 pub mod komorebi;
 mod komorebi_layout;
+pub mod launcher;
 pub mod media;
 pub mod memory;
 pub mod network;
+pub mod notifications;
+pub mod pomodoro;
+pub mod resource_usage;
+pub mod start_menu;
 pub mod storage;
+pub mod systray;
 pub mod time;
 pub mod update;
+pub mod volume;
+pub mod vpn;
+pub mod weather;
 pub mod widget;
@@ -8,14 +8,26 @@ use eframe::egui::Context;
 use eframe::egui::Label;
 use eframe::egui::TextFormat;
 use eframe::egui::Ui;
+use netdev::interface::InterfaceType;
 use num_derive::FromPrimitive;
 use serde::Deserialize;
 use serde::Serialize;
+use std::ffi::c_void;
 use std::fmt;
 use std::process::Command;
 use std::time::Duration;
 use std::time::Instant;
 use sysinfo::Networks;
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::NetworkManagement::WiFi::wlan_intf_opcode_current_connection;
+use windows::Win32::NetworkManagement::WiFi::WlanCloseHandle;
+use windows::Win32::NetworkManagement::WiFi::WlanEnumInterfaces;
+use windows::Win32::NetworkManagement::WiFi::WlanFreeMemory;
+use windows::Win32::NetworkManagement::WiFi::WlanOpenHandle;
+use windows::Win32::NetworkManagement::WiFi::WlanQueryInterface;
+use windows::Win32::NetworkManagement::WiFi::WLAN_CONNECTION_ATTRIBUTES;
+use windows::Win32::NetworkManagement::WiFi::WLAN_INTERFACE_INFO_LIST;
 
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -28,6 +40,10 @@ pub struct NetworkConfig {
     pub show_network_activity: bool,
     /// Show default interface
     pub show_default_interface: Option<bool>,
+    /// Show the SSID instead of the adapter name when the default interface is Wi-Fi (default: true)
+    pub show_ssid: Option<bool>,
+    /// Units used to display network activity (default: Bytes)
+    pub activity_units: Option<NetworkActivityUnits>,
     /// Characters to reserve for network activity data
     pub network_activity_fill_characters: Option<usize>,
     /// Data refresh interval (default: 10 seconds)
@@ -45,8 +61,11 @@ impl From<NetworkConfig> for Network {
             show_total_activity: value.show_total_data_transmitted,
             show_activity: value.show_network_activity,
             show_default_interface: value.show_default_interface.unwrap_or(true),
+            show_ssid: value.show_ssid.unwrap_or(true),
+            activity_units: value.activity_units.unwrap_or_default(),
             networks_network_activity: Networks::new_with_refreshed_list(),
             default_interface: String::new(),
+            link_status: LinkStatus::Disconnected,
             data_refresh_interval,
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             network_activity_fill_characters: value
@@ -57,6 +76,9 @@ impl From<NetworkConfig> for Network {
             last_updated_network_activity: Instant::now()
                 .checked_sub(Duration::from_secs(data_refresh_interval))
                 .unwrap(),
+            last_updated_default_interface: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
         }
     }
 }
@@ -66,22 +88,65 @@ pub struct Network {
     pub show_total_activity: bool,
     pub show_activity: bool,
     pub show_default_interface: bool,
+    show_ssid: bool,
+    activity_units: NetworkActivityUnits,
     networks_network_activity: Networks,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
     default_interface: String,
+    link_status: LinkStatus,
     last_state_total_activity: Vec<NetworkReading>,
     last_state_activity: Vec<NetworkReading>,
     last_updated_network_activity: Instant,
+    last_updated_default_interface: Instant,
     network_activity_fill_characters: usize,
 }
 
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum NetworkActivityUnits {
+    /// Binary multiples of bytes (KiB/s, MiB/s, ...)
+    #[default]
+    Bytes,
+    /// Decimal multiples of bits (Kb/s, Mb/s, ...)
+    Bits,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LinkStatus {
+    Wifi,
+    Ethernet,
+    Disconnected,
+}
+
 impl Network {
     fn default_interface(&mut self) {
+        let now = Instant::now();
+        if now.duration_since(self.last_updated_default_interface)
+            <= Duration::from_secs(self.data_refresh_interval)
+        {
+            return;
+        }
+
+        self.last_updated_default_interface = now;
+        self.default_interface.clear();
+        self.link_status = LinkStatus::Disconnected;
+
         if let Ok(interface) = netdev::get_default_interface() {
             if let Some(friendly_name) = &interface.friendly_name {
                 self.default_interface.clone_from(friendly_name);
             }
+
+            self.link_status = match interface.if_type {
+                InterfaceType::Wireless80211 => LinkStatus::Wifi,
+                _ => LinkStatus::Ethernet,
+            };
+
+            if self.show_ssid && self.link_status == LinkStatus::Wifi {
+                if let Some(ssid) = wifi_ssid(&interface.name) {
+                    self.default_interface = ssid;
+                }
+            }
         }
     }
 
@@ -107,11 +172,11 @@ impl Network {
                             if self.show_activity {
                                 activity.push(NetworkReading::new(
                                     NetworkReadingFormat::Speed,
-                                    Self::to_pretty_bytes(
+                                    self.to_pretty_rate(
                                         data.received(),
                                         self.data_refresh_interval,
                                     ),
-                                    Self::to_pretty_bytes(
+                                    self.to_pretty_rate(
                                         data.transmitted(),
                                         self.data_refresh_interval,
                                     ),
@@ -236,6 +301,32 @@ impl Network {
         Label::new(layout_job).selectable(false)
     }
 
+    fn to_pretty_rate(&self, input_in_bytes: u64, timespan_in_s: u64) -> String {
+        match self.activity_units {
+            NetworkActivityUnits::Bytes => Self::to_pretty_bytes(input_in_bytes, timespan_in_s),
+            NetworkActivityUnits::Bits => Self::to_pretty_bits(input_in_bytes, timespan_in_s),
+        }
+    }
+
+    fn to_pretty_bits(input_in_bytes: u64, timespan_in_s: u64) -> String {
+        let input = (input_in_bytes * 8) as f32 / timespan_in_s as f32;
+        let mut magnitude = input.log(1000f32) as u32;
+
+        // let the base unit be Kb
+        if magnitude < 1 {
+            magnitude = 1;
+        }
+
+        let base: Option<DataUnit> = num::FromPrimitive::from_u32(magnitude);
+        let result = input / 1000f32.powi(magnitude as i32);
+
+        match base {
+            Some(DataUnit::B) => format!("{result:.1} b"),
+            Some(unit) => format!("{result:.1} {unit}b"),
+            None => String::from("Unknown data unit"),
+        }
+    }
+
     fn to_pretty_bytes(input_in_bytes: u64, timespan_in_s: u64) -> String {
         let input = input_in_bytes as f32 / timespan_in_s as f32;
         let mut magnitude = input.log(1024f32) as u32;
@@ -285,12 +376,21 @@ impl BarWidget for Network {
             if self.show_default_interface {
                 self.default_interface();
 
-                if !self.default_interface.is_empty() {
+                let interface = match self.link_status {
+                    LinkStatus::Disconnected => String::from("Disconnected"),
+                    LinkStatus::Wifi | LinkStatus::Ethernet => self.default_interface.clone(),
+                };
+
+                if !interface.is_empty() {
+                    let icon = match self.link_status {
+                        LinkStatus::Wifi => egui_phosphor::regular::WIFI_HIGH,
+                        LinkStatus::Ethernet => egui_phosphor::regular::NETWORK,
+                        LinkStatus::Disconnected => egui_phosphor::regular::WIFI_SLASH,
+                    };
+
                     let mut layout_job = LayoutJob::simple(
                         match self.label_prefix {
-                            LabelPrefix::Icon | LabelPrefix::IconAndText => {
-                                egui_phosphor::regular::WIFI_HIGH.to_string()
-                            }
+                            LabelPrefix::Icon | LabelPrefix::IconAndText => icon.to_string(),
                             LabelPrefix::None | LabelPrefix::Text => String::new(),
                         },
                         config.icon_font_id.clone(),
@@ -298,12 +398,13 @@ impl BarWidget for Network {
                         100.0,
                     );
 
-                    if let LabelPrefix::Text | LabelPrefix::IconAndText = self.label_prefix {
-                        self.default_interface.insert_str(0, "NET: ");
-                    }
+                    let label = match self.label_prefix {
+                        LabelPrefix::Text | LabelPrefix::IconAndText => format!("NET: {interface}"),
+                        LabelPrefix::None | LabelPrefix::Icon => interface,
+                    };
 
                     layout_job.append(
-                        &self.default_interface,
+                        &label,
                         10.0,
                         TextFormat {
                             font_id: config.text_font_id.clone(),
@@ -318,7 +419,9 @@ impl BarWidget for Network {
                             .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
                             .clicked()
                         {
-                            if let Err(error) = Command::new("cmd.exe").args(["/C", "ncpa"]).spawn()
+                            if let Err(error) = Command::new("cmd.exe")
+                                .args(["/C", "start", "ms-settings:network-status"])
+                                .spawn()
                             {
                                 eprintln!("{}", error)
                             }
@@ -374,3 +477,62 @@ impl fmt::Display for DataUnit {
         write!(f, "{:?}", self)
     }
 }
+
+/// Looks up the SSID of the Wi-Fi network that the adapter with the given GUID name is connected to
+fn wifi_ssid(adapter_name: &str) -> Option<String> {
+    let adapter_guid = adapter_name.trim_matches(|c| c == '{' || c == '}');
+
+    let mut negotiated_version = 0;
+    let mut handle = HANDLE::default();
+    if unsafe { WlanOpenHandle(2, None, &mut negotiated_version, &mut handle) } != ERROR_SUCCESS.0 {
+        return None;
+    }
+
+    let mut ssid = None;
+    let mut interfaces: *mut WLAN_INTERFACE_INFO_LIST = std::ptr::null_mut();
+
+    if unsafe { WlanEnumInterfaces(handle, None, &mut interfaces) } == ERROR_SUCCESS.0
+        && !interfaces.is_null()
+    {
+        let list = unsafe { &*interfaces };
+        let infos = unsafe {
+            std::slice::from_raw_parts(list.InterfaceInfo.as_ptr(), list.dwNumberOfItems as usize)
+        };
+
+        for info in infos {
+            if !adapter_guid.eq_ignore_ascii_case(&format!("{:?}", info.InterfaceGuid)) {
+                continue;
+            }
+
+            let mut size = 0;
+            let mut data: *mut c_void = std::ptr::null_mut();
+
+            if unsafe {
+                WlanQueryInterface(
+                    handle,
+                    &info.InterfaceGuid,
+                    wlan_intf_opcode_current_connection,
+                    None,
+                    &mut size,
+                    &mut data,
+                    None,
+                )
+            } == ERROR_SUCCESS.0
+                && !data.is_null()
+            {
+                let connection = unsafe { &*(data as *const WLAN_CONNECTION_ATTRIBUTES) };
+                let dot11_ssid = connection.wlanAssociationAttributes.dot11Ssid;
+                let len = (dot11_ssid.uSSIDLength as usize).min(dot11_ssid.ucSSID.len());
+                ssid = Some(String::from_utf8_lossy(&dot11_ssid.ucSSID[..len]).to_string());
+
+                unsafe { WlanFreeMemory(data) };
+            }
+        }
+
+        unsafe { WlanFreeMemory(interfaces as *const c_void) };
+    }
+
+    unsafe { WlanCloseHandle(handle, None) };
+
+    ssid
+}
@@ -0,0 +1,300 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::widget::BarWidget;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use quiet_hours::IQuietHoursSettings;
+use serde::Deserialize;
+use serde::Serialize;
+use std::ffi::c_void;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use windows::core::GUID;
+use windows::core::HSTRING;
+use windows::core::PCWSTR;
+use windows::core::PWSTR;
+use windows::Win32::Foundation::E_ACCESSDENIED;
+use windows::Win32::System::Com::CoCreateInstance;
+use windows::Win32::System::Com::CoInitializeEx;
+use windows::Win32::System::Com::CoTaskMemFree;
+use windows::Win32::System::Com::CLSCTX_LOCAL_SERVER;
+use windows::Win32::System::Com::COINIT_APARTMENTTHREADED;
+use windows::Win32::System::Com::COINIT_MULTITHREADED;
+use windows::UI::Notifications::Management::UserNotificationListener;
+use windows::UI::Notifications::Management::UserNotificationListenerAccessStatus;
+use windows::UI::Notifications::NotificationKinds;
+
+/// The unread count is read with the notification listener API, which Windows only makes available
+/// to packaged applications, so without package identity the widget only shows focus assist
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct NotificationsConfig {
+    /// Enable the Notifications widget
+    pub enable: bool,
+    /// Data refresh interval (default: 5 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Focus assist profile which is turned on when the widget is clicked (default: PriorityOnly)
+    pub focus_assist_profile: Option<FocusAssistProfile>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FocusAssistProfile {
+    /// Only show notifications from apps on the priority list
+    #[default]
+    PriorityOnly,
+    /// Only show alarms
+    AlarmsOnly,
+}
+
+impl FocusAssistProfile {
+    fn id(self) -> &'static str {
+        match self {
+            FocusAssistProfile::PriorityOnly => "Microsoft.QuietHoursProfile.PriorityOnly",
+            FocusAssistProfile::AlarmsOnly => "Microsoft.QuietHoursProfile.AlarmsOnly",
+        }
+    }
+}
+
+const UNRESTRICTED_PROFILE: &str = "Microsoft.QuietHoursProfile.Unrestricted";
+
+impl From<NotificationsConfig> for Notifications {
+    fn from(value: NotificationsConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(5);
+
+        // the quiet hours settings are exposed over COM; if it has already been initialized on
+        // the UI thread, this is a no-op
+        let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+
+        Self {
+            enable: value.enable,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            focus_assist_profile: value.focus_assist_profile.unwrap_or_default(),
+            state: NotificationsState::default(),
+            unread: crossbeam_channel::unbounded(),
+            running: Arc::new(AtomicBool::new(false)),
+            last_updated: None,
+        }
+    }
+}
+
+pub struct Notifications {
+    pub enable: bool,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    focus_assist_profile: FocusAssistProfile,
+    state: NotificationsState,
+    /// Unread counts are sent back by a background thread, as the listener API only answers
+    /// asynchronously and may be waiting on the user to grant access
+    unread: (Sender<Option<u32>>, Receiver<Option<u32>>),
+    running: Arc<AtomicBool>,
+    /// None until the first refresh, which happens as soon as the widget is rendered
+    last_updated: Option<Instant>,
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+struct NotificationsState {
+    /// `None` when access to the notifications has not been granted
+    unread: Option<u32>,
+    focus_assist: bool,
+}
+
+impl Notifications {
+    fn output(&mut self, ctx: &Context) -> NotificationsState {
+        let interval = Duration::from_secs(self.data_refresh_interval);
+        if self
+            .last_updated
+            .is_none_or(|last_updated| last_updated.elapsed() > interval)
+        {
+            self.refresh(ctx);
+        }
+
+        while let Ok(unread) = self.unread.1.try_recv() {
+            self.state.unread = unread;
+        }
+
+        self.state
+    }
+
+    fn refresh(&mut self, ctx: &Context) {
+        self.state.focus_assist =
+            selected_focus_assist_profile().is_ok_and(|profile| profile != UNRESTRICTED_PROFILE);
+        self.last_updated = Some(Instant::now());
+
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let sender = self.unread.0.clone();
+        let running = self.running.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            // blocking on the async operations is not allowed on a single-threaded apartment
+            let _ = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+
+            let _ = sender.send(unread_notifications().ok());
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+        });
+    }
+
+    fn toggle_focus_assist(&mut self, ctx: &Context) {
+        let profile = if self.state.focus_assist {
+            UNRESTRICTED_PROFILE
+        } else {
+            self.focus_assist_profile.id()
+        };
+
+        if let Err(error) = select_focus_assist_profile(profile) {
+            tracing::error!("could not change the focus assist profile: {error}");
+        }
+
+        self.refresh(ctx);
+    }
+}
+
+impl BarWidget for Notifications {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        let state = self.output(ctx);
+
+        let icon = match (state.focus_assist, state.unread) {
+            (true, _) => egui_phosphor::regular::BELL_SLASH,
+            (false, Some(unread)) if unread > 0 => egui_phosphor::regular::BELL_RINGING,
+            (false, _) => egui_phosphor::regular::BELL,
+        };
+
+        let mut layout_job = LayoutJob::simple(
+            match self.label_prefix {
+                LabelPrefix::Icon | LabelPrefix::IconAndText => icon.to_string(),
+                LabelPrefix::None | LabelPrefix::Text => String::new(),
+            },
+            config.icon_font_id.clone(),
+            ctx.style().visuals.selection.stroke.color,
+            100.0,
+        );
+
+        if let Some(unread) = state.unread {
+            layout_job.append(
+                &match self.label_prefix {
+                    LabelPrefix::Text | LabelPrefix::IconAndText => format!("NOTIF: {unread}"),
+                    LabelPrefix::None | LabelPrefix::Icon => unread.to_string(),
+                },
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: ctx.style().visuals.text_color(),
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+        }
+
+        config.apply_on_widget(false, ui, |ui| {
+            let response = SelectableFrame::new(state.focus_assist)
+                .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                .on_hover_text(if state.focus_assist {
+                    "Focus assist is on"
+                } else {
+                    "Focus assist is off"
+                });
+
+            if response.clicked() {
+                self.toggle_focus_assist(ctx);
+            } else if response.secondary_clicked() {
+                if let Err(error) = Command::new("cmd.exe")
+                    .args(["/C", "start", "ms-actioncenter:"])
+                    .spawn()
+                {
+                    eprintln!("{}", error)
+                }
+            }
+        });
+    }
+}
+
+/// Fails unless the bar has package identity, which the notification listener API requires
+fn unread_notifications() -> windows::core::Result<u32> {
+    let listener = UserNotificationListener::Current()?;
+
+    // the user is only asked for permission once, after that the answer is remembered
+    let access = match listener.GetAccessStatus()? {
+        UserNotificationListenerAccessStatus::Unspecified => {
+            listener.RequestAccessAsync()?.get()?
+        }
+        access => access,
+    };
+
+    if access != UserNotificationListenerAccessStatus::Allowed {
+        return Err(windows::core::Error::from_hresult(E_ACCESSDENIED));
+    }
+
+    listener
+        .GetNotificationsAsync(NotificationKinds::Toast)?
+        .get()?
+        .Size()
+}
+
+fn quiet_hours_settings() -> windows::core::Result<IQuietHoursSettings> {
+    unsafe { CoCreateInstance(&CLSID_QUIET_HOURS_SETTINGS, None, CLSCTX_LOCAL_SERVER) }
+}
+
+fn selected_focus_assist_profile() -> windows::core::Result<String> {
+    unsafe {
+        let mut profile = PWSTR::null();
+        quiet_hours_settings()?
+            .get_user_selected_profile(&mut profile)
+            .ok()?;
+
+        let result = profile.to_string().unwrap_or_default();
+        CoTaskMemFree(Some(profile.0 as *const c_void));
+
+        Ok(result)
+    }
+}
+
+fn select_focus_assist_profile(profile: &str) -> windows::core::Result<()> {
+    let profile = HSTRING::from(profile);
+    unsafe {
+        quiet_hours_settings()?
+            .put_user_selected_profile(PCWSTR(profile.as_ptr()))
+            .ok()
+    }
+}
+
+// Focus assist (quiet hours) has no public API; this undocumented COM server lives in the shell
+// and backs the toggle in the action center
+const CLSID_QUIET_HOURS_SETTINGS: GUID = GUID::from_u128(0xf53321fa_34f8_4b7f_b9a3_361877cb94cf);
+
+// Only the first two methods of the vtable are declared, since nothing after them is called
+mod quiet_hours {
+    use windows::core::IUnknown;
+    use windows::core::IUnknown_Vtbl;
+    use windows::core::HRESULT;
+    use windows::core::PCWSTR;
+    use windows::core::PWSTR;
+
+    #[windows::core::interface("6bff4732-81ec-4ffb-ae67-b6c1bc29631f")]
+    pub unsafe trait IQuietHoursSettings: IUnknown {
+        pub unsafe fn get_user_selected_profile(&self, profile_id: *mut PWSTR) -> HRESULT;
+        pub unsafe fn put_user_selected_profile(&self, profile_id: PCWSTR) -> HRESULT;
+    }
+}
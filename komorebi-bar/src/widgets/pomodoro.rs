@@ -0,0 +1,264 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::exec::shell_command;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::pos2;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::Sense;
+use eframe::egui::Shape;
+use eframe::egui::Stroke;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+use std::f32::consts::TAU;
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PomodoroConfig {
+    /// Enable the Pomodoro widget
+    pub enable: bool,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Length of a work phase in minutes (default: 25)
+    pub work_duration: Option<u64>,
+    /// Length of a short break in minutes (default: 5)
+    pub short_break_duration: Option<u64>,
+    /// Length of a long break in minutes (default: 15)
+    pub long_break_duration: Option<u64>,
+    /// Number of work phases before a long break is taken (default: 4)
+    pub long_break_interval: Option<u32>,
+    /// Start the next phase straight away when a phase ends (default: false)
+    pub auto_start: Option<bool>,
+    /// Command to run when a phase ends, with the name of the phase that ended in the KOMOREBI_BAR_POMODORO_PHASE environment variable
+    pub on_phase_end: Option<String>,
+}
+
+impl From<PomodoroConfig> for Pomodoro {
+    fn from(value: PomodoroConfig) -> Self {
+        let work_duration = Duration::from_secs(value.work_duration.unwrap_or(25) * 60);
+
+        Self {
+            enable: value.enable,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            work_duration,
+            short_break_duration: Duration::from_secs(value.short_break_duration.unwrap_or(5) * 60),
+            long_break_duration: Duration::from_secs(value.long_break_duration.unwrap_or(15) * 60),
+            long_break_interval: value.long_break_interval.unwrap_or(4).max(1),
+            auto_start: value.auto_start.unwrap_or_default(),
+            on_phase_end: value.on_phase_end,
+            phase: PomodoroPhase::Work,
+            completed_work_phases: 0,
+            remaining: work_duration,
+            running_since: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum PomodoroPhase {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroPhase {
+    fn name(self) -> &'static str {
+        match self {
+            PomodoroPhase::Work => "Work",
+            PomodoroPhase::ShortBreak => "Short break",
+            PomodoroPhase::LongBreak => "Long break",
+        }
+    }
+}
+
+pub struct Pomodoro {
+    pub enable: bool,
+    label_prefix: LabelPrefix,
+    work_duration: Duration,
+    short_break_duration: Duration,
+    long_break_duration: Duration,
+    long_break_interval: u32,
+    auto_start: bool,
+    on_phase_end: Option<String>,
+    phase: PomodoroPhase,
+    completed_work_phases: u32,
+    /// Time left in the current phase as of `running_since`, or as of pausing
+    remaining: Duration,
+    running_since: Option<Instant>,
+}
+
+impl Pomodoro {
+    fn phase_duration(&self) -> Duration {
+        match self.phase {
+            PomodoroPhase::Work => self.work_duration,
+            PomodoroPhase::ShortBreak => self.short_break_duration,
+            PomodoroPhase::LongBreak => self.long_break_duration,
+        }
+    }
+
+    fn time_left(&self) -> Duration {
+        match self.running_since {
+            Some(running_since) => self.remaining.saturating_sub(running_since.elapsed()),
+            None => self.remaining,
+        }
+    }
+
+    fn toggle(&mut self) {
+        self.remaining = self.time_left();
+        self.running_since = match self.running_since {
+            Some(_) => None,
+            None => Some(Instant::now()),
+        };
+    }
+
+    fn reset(&mut self) {
+        self.phase = PomodoroPhase::Work;
+        self.completed_work_phases = 0;
+        self.remaining = self.work_duration;
+        self.running_since = None;
+    }
+
+    /// Moves on to the next phase once the current one has run out
+    fn advance(&mut self) {
+        if self.running_since.is_none() || !self.time_left().is_zero() {
+            return;
+        }
+
+        let ended = self.phase;
+
+        self.phase = match ended {
+            PomodoroPhase::Work => {
+                self.completed_work_phases += 1;
+                if self.completed_work_phases % self.long_break_interval == 0 {
+                    PomodoroPhase::LongBreak
+                } else {
+                    PomodoroPhase::ShortBreak
+                }
+            }
+            PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak => PomodoroPhase::Work,
+        };
+
+        self.remaining = self.phase_duration();
+        self.running_since = self.auto_start.then(Instant::now);
+
+        if let Some(command) = &self.on_phase_end {
+            if let Err(error) = shell_command(command)
+                .env("KOMOREBI_BAR_POMODORO_PHASE", ended.name())
+                .spawn()
+            {
+                tracing::error!("could not run '{command}': {error}");
+            }
+        }
+    }
+
+    /// Draws a ring which empties as the current phase runs out
+    fn paint_progress(&self, ctx: &Context, ui: &mut Ui, size: f32) {
+        let (response, painter) = ui.allocate_painter(Vec2::splat(size), Sense::hover());
+        let centre = response.rect.center();
+        let radius = size / 2.0 - 2.0;
+
+        let fraction =
+            self.time_left().as_secs_f32() / self.phase_duration().as_secs_f32().max(1.0);
+
+        painter.circle_stroke(
+            centre,
+            radius,
+            Stroke::new(1.0, ctx.style().visuals.weak_text_color()),
+        );
+
+        let segments = 32;
+        let points = (0..=segments)
+            .map(|i| {
+                // start at 12 o'clock and go clockwise
+                let angle = TAU * fraction * i as f32 / segments as f32 - TAU / 4.0;
+                pos2(
+                    centre.x + radius * angle.cos(),
+                    centre.y + radius * angle.sin(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        painter.add(Shape::line(
+            points,
+            Stroke::new(2.0, ctx.style().visuals.selection.stroke.color),
+        ));
+    }
+}
+
+impl BarWidget for Pomodoro {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        self.advance();
+
+        if self.running_since.is_some() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+
+        let time_left = self.time_left().as_secs();
+        let mut output = format!("{:02}:{:02}", time_left / 60, time_left % 60);
+        if let LabelPrefix::Text | LabelPrefix::IconAndText = self.label_prefix {
+            output.insert_str(0, &format!("{}: ", self.phase.name().to_uppercase()));
+        }
+
+        let icon = match (self.running_since, self.phase) {
+            (None, _) => egui_phosphor::regular::PAUSE,
+            (Some(_), PomodoroPhase::Work) => egui_phosphor::regular::TIMER,
+            (Some(_), PomodoroPhase::ShortBreak | PomodoroPhase::LongBreak) => {
+                egui_phosphor::regular::COFFEE
+            }
+        };
+
+        let mut layout_job = LayoutJob::simple(
+            match self.label_prefix {
+                LabelPrefix::Icon | LabelPrefix::IconAndText => icon.to_string(),
+                LabelPrefix::None | LabelPrefix::Text => String::new(),
+            },
+            config.icon_font_id.clone(),
+            ctx.style().visuals.selection.stroke.color,
+            100.0,
+        );
+
+        layout_job.append(
+            &output,
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: ctx.style().visuals.text_color(),
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        let size = config.icon_font_id.size;
+
+        config.apply_on_widget(false, ui, |ui| {
+            let response = SelectableFrame::new(false)
+                .show(ui, |ui| {
+                    ui.add(Label::new(layout_job).selectable(false));
+                    self.paint_progress(ctx, ui, size);
+                })
+                .on_hover_text(format!(
+                    "{} ({} completed)",
+                    self.phase.name(),
+                    self.completed_work_phases
+                ));
+
+            if response.clicked() {
+                self.toggle();
+            } else if response.secondary_clicked() {
+                self.reset();
+            }
+        });
+    }
+}
@@ -0,0 +1,147 @@
+use eframe::egui::pos2;
+use eframe::egui::vec2;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::CornerRadius;
+use eframe::egui::Pos2;
+use eframe::egui::Rect;
+use eframe::egui::Response;
+use eframe::egui::Sense;
+use eframe::egui::Shape;
+use eframe::egui::Stroke;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// Number of readings kept for the usage graph
+const HISTORY_LENGTH: usize = 30;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum ResourceDisplayFormat {
+    /// Show the current usage as text
+    #[default]
+    Text,
+    /// Show a graph of recent usage
+    Graph,
+    /// Show the current usage as text followed by a graph of recent usage
+    TextAndGraph,
+}
+
+impl ResourceDisplayFormat {
+    pub fn shows_text(self) -> bool {
+        matches!(self, Self::Text | Self::TextAndGraph)
+    }
+
+    pub fn shows_graph(self) -> bool {
+        matches!(self, Self::Graph | Self::TextAndGraph)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct UsageThresholds {
+    /// Usage percentage at or above which the widget is shown in the warning colour (default: 70)
+    pub warning: Option<u8>,
+    /// Usage percentage at or above which the widget is shown in the critical colour (default: 90)
+    pub critical: Option<u8>,
+    /// Colour used when usage is at or above the warning threshold (default: theme warning colour)
+    pub warning_colour: Option<Colour>,
+    /// Colour used when usage is at or above the critical threshold (default: theme error colour)
+    pub critical_colour: Option<Colour>,
+}
+
+impl UsageThresholds {
+    /// The colour to draw the widget in for the given usage, or `None` if the regular theme
+    /// colours should be used
+    pub fn colour(&self, ctx: &Context, percentage: f32) -> Option<Color32> {
+        if percentage >= f32::from(self.critical.unwrap_or(90)) {
+            Some(
+                self.critical_colour
+                    .map_or(ctx.style().visuals.error_fg_color, Color32::from),
+            )
+        } else if percentage >= f32::from(self.warning.unwrap_or(70)) {
+            Some(
+                self.warning_colour
+                    .map_or(ctx.style().visuals.warn_fg_color, Color32::from),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+pub fn to_gib(bytes: u64) -> f32 {
+    bytes as f32 / (1u64 << 30) as f32
+}
+
+/// A rolling window of usage percentages which can be drawn as a sparkline
+pub struct UsageHistory(VecDeque<f32>);
+
+impl Default for UsageHistory {
+    fn default() -> Self {
+        Self(VecDeque::with_capacity(HISTORY_LENGTH))
+    }
+}
+
+impl UsageHistory {
+    pub fn push(&mut self, percentage: f32) {
+        if self.0.len() == HISTORY_LENGTH {
+            self.0.pop_front();
+        }
+
+        self.0.push_back(percentage.clamp(0.0, 100.0));
+    }
+
+    pub fn paint(&self, ui: &mut Ui, height: f32, colour: Color32) -> Response {
+        let (response, painter) = ui.allocate_painter(vec2(height * 3.0, height), Sense::hover());
+        let rect = response.rect.shrink(1.0);
+
+        // readings are right-aligned so that the graph fills up from the right as it starts
+        let step = rect.width() / (HISTORY_LENGTH - 1) as f32;
+        let offset = HISTORY_LENGTH - self.0.len();
+
+        let points: Vec<Pos2> = self
+            .0
+            .iter()
+            .enumerate()
+            .map(|(i, percentage)| {
+                pos2(
+                    rect.left() + (offset + i) as f32 * step,
+                    rect.bottom() - rect.height() * percentage / 100.0,
+                )
+            })
+            .collect();
+
+        painter.add(Shape::line(points, Stroke::new(1.5, colour)));
+
+        response
+    }
+}
+
+/// Draws one vertical bar per reading, e.g. for per-core CPU usage
+pub fn paint_bars(ui: &mut Ui, height: f32, percentages: &[f32], colour: Color32) -> Response {
+    let bar_width = 3.0;
+    let bar_spacing = 1.0;
+
+    let (response, painter) = ui.allocate_painter(
+        vec2(percentages.len() as f32 * (bar_width + bar_spacing), height),
+        Sense::hover(),
+    );
+    let rect = response.rect;
+
+    for (i, percentage) in percentages.iter().enumerate() {
+        let left = rect.left() + i as f32 * (bar_width + bar_spacing);
+        let top = rect.bottom() - rect.height() * percentage.clamp(0.0, 100.0) / 100.0;
+
+        painter.rect_filled(
+            Rect::from_min_max(pos2(left, top), pos2(left + bar_width, rect.bottom())),
+            CornerRadius::same(0),
+            colour,
+        );
+    }
+
+    response
+}
@@ -0,0 +1,123 @@
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::exec::shell_command;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::Ui;
+use komorebi_client::SocketMessage;
+use serde::Deserialize;
+use serde::Serialize;
+use windows::Win32::UI::Input::KeyboardAndMouse::SendInput;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_KEYBOARD;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBDINPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBD_EVENT_FLAGS;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYEVENTF_KEYUP;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LWIN;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StartMenuConfig {
+    /// Enable the Start Menu widget
+    pub enable: bool,
+    /// Text or icon font glyph to show instead of the Windows logo
+    pub icon: Option<String>,
+    /// What happens when the widget is clicked (default: StartMenu)
+    pub on_click: Option<StartMenuAction>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum StartMenuAction {
+    /// Open the Start menu, as if the Windows key was pressed
+    StartMenu,
+    /// Run a command with cmd.exe, e.g. "flow-launcher"
+    Command(String),
+    /// Send a message to komorebi, e.g. { "type": "TogglePause" }
+    SocketMessage(SocketMessage),
+}
+
+impl From<StartMenuConfig> for StartMenu {
+    fn from(value: StartMenuConfig) -> Self {
+        Self {
+            enable: value.enable,
+            icon: value
+                .icon
+                .unwrap_or_else(|| egui_phosphor::regular::WINDOWS_LOGO.to_string()),
+            on_click: value.on_click.unwrap_or(StartMenuAction::StartMenu),
+        }
+    }
+}
+
+pub struct StartMenu {
+    pub enable: bool,
+    icon: String,
+    on_click: StartMenuAction,
+}
+
+impl StartMenuAction {
+    fn run(&self) {
+        match self {
+            StartMenuAction::StartMenu => press_windows_key(),
+            StartMenuAction::Command(command) => {
+                if let Err(error) = shell_command(command).spawn() {
+                    tracing::error!("could not run '{command}': {error}");
+                }
+            }
+            StartMenuAction::SocketMessage(message) => {
+                if komorebi_client::send_message(message).is_err() {
+                    tracing::error!("could not send message to komorebi: {message}");
+                }
+            }
+        }
+    }
+}
+
+/// Presses and releases the left Windows key, which opens the Start menu
+fn press_windows_key() {
+    let key = |flags| INPUT {
+        r#type: INPUT_KEYBOARD,
+        Anonymous: INPUT_0 {
+            ki: KEYBDINPUT {
+                wVk: VK_LWIN,
+                dwFlags: flags,
+                ..Default::default()
+            },
+        },
+    };
+
+    let inputs = [key(KEYBD_EVENT_FLAGS(0)), key(KEYEVENTF_KEYUP)];
+
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+
+    if sent as usize != inputs.len() {
+        tracing::error!("could not simulate a press of the Windows key");
+    }
+}
+
+impl BarWidget for StartMenu {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        let layout_job = LayoutJob::simple(
+            self.icon.clone(),
+            config.icon_font_id.clone(),
+            ctx.style().visuals.selection.stroke.color,
+            100.0,
+        );
+
+        config.apply_on_widget(false, ui, |ui| {
+            if SelectableFrame::new(false)
+                .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                .clicked()
+            {
+                self.on_click.run();
+            }
+        });
+    }
+}
@@ -1,6 +1,8 @@
 use crate::config::LabelPrefix;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
+use crate::widgets::resource_usage::to_gib;
+use crate::widgets::resource_usage::UsageThresholds;
 use crate::widgets::widget::BarWidget;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
@@ -15,7 +17,7 @@ use std::time::Duration;
 use std::time::Instant;
 use sysinfo::Disks;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StorageConfig {
     /// Enable the Storage widget
@@ -24,6 +26,50 @@ pub struct StorageConfig {
     pub data_refresh_interval: Option<u64>,
     /// Display label prefix
     pub label_prefix: Option<LabelPrefix>,
+    /// What to show for each drive (default: UsedPercentage)
+    pub display: Option<StorageDisplay>,
+    /// Usage thresholds at which a drive changes colour, unless overridden for that drive
+    pub thresholds: Option<UsageThresholds>,
+    /// Drives to show, in order (default: all drives)
+    pub drives: Option<Vec<StorageDrive>>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum StorageDisplay {
+    /// Percentage of the drive which is used
+    #[default]
+    UsedPercentage,
+    /// Percentage of the drive which is free
+    FreePercentage,
+    /// Used and total space of the drive
+    UsedAndTotal,
+    /// Free space left on the drive
+    Free,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct StorageDrive {
+    /// Drive letter or mount point of the drive (e.g. "C:")
+    pub mount: String,
+    /// Label shown instead of the mount point
+    pub label: Option<String>,
+    /// Icon shown for the drive (default: hard drives icon)
+    pub icon: Option<String>,
+    /// Usage thresholds at which the drive changes colour
+    pub thresholds: Option<UsageThresholds>,
+}
+
+impl StorageDrive {
+    fn matches(&self, mount: &str) -> bool {
+        normalise_mount(&self.mount) == normalise_mount(mount)
+    }
+}
+
+/// Lets `C`, `C:` and `C:\` all refer to the same drive
+fn normalise_mount(mount: &str) -> String {
+    mount.trim_end_matches(['\\', '/', ':']).to_uppercase()
 }
 
 impl From<StorageConfig> for Storage {
@@ -33,6 +79,9 @@ impl From<StorageConfig> for Storage {
             disks: Disks::new_with_refreshed_list(),
             data_refresh_interval: value.data_refresh_interval.unwrap_or(10),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
+            display: value.display.unwrap_or_default(),
+            thresholds: value.thresholds,
+            drives: value.drives,
             last_updated: Instant::now(),
         }
     }
@@ -43,11 +92,22 @@ pub struct Storage {
     disks: Disks,
     data_refresh_interval: u64,
     label_prefix: LabelPrefix,
+    display: StorageDisplay,
+    thresholds: Option<UsageThresholds>,
+    drives: Option<Vec<StorageDrive>>,
     last_updated: Instant,
 }
 
+struct DriveOutput {
+    mount: String,
+    icon: String,
+    text: String,
+    usage: f32,
+    thresholds: Option<UsageThresholds>,
+}
+
 impl Storage {
-    fn output(&mut self) -> Vec<String> {
+    fn output(&mut self) -> Vec<DriveOutput> {
         let now = Instant::now();
         if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
             self.disks.refresh(true);
@@ -57,21 +117,59 @@ impl Storage {
         let mut disks = vec![];
 
         for disk in &self.disks {
-            let mount = disk.mount_point();
+            let mount = disk.mount_point().to_string_lossy().to_string();
             let total = disk.total_space();
+            if total == 0 {
+                continue;
+            }
+
+            let drive = match &self.drives {
+                Some(drives) => match drives.iter().find(|drive| drive.matches(&mount)) {
+                    Some(drive) => Some(drive),
+                    None => continue,
+                },
+                None => None,
+            };
+
             let available = disk.available_space();
             let used = total - available;
 
-            disks.push(match self.label_prefix {
-                LabelPrefix::Text | LabelPrefix::IconAndText => {
-                    format!("{} {}%", mount.to_string_lossy(), (used * 100) / total)
+            let reading = match self.display {
+                StorageDisplay::UsedPercentage => format!("{}%", (used * 100) / total),
+                StorageDisplay::FreePercentage => format!("{}% free", (available * 100) / total),
+                StorageDisplay::UsedAndTotal => {
+                    format!("{:.1}/{:.1} GiB", to_gib(used), to_gib(total))
                 }
-                LabelPrefix::None | LabelPrefix::Icon => format!("{}%", (used * 100) / total),
+                StorageDisplay::Free => format!("{:.1} GiB free", to_gib(available)),
+            };
+
+            let name = drive
+                .and_then(|drive| drive.label.clone())
+                .unwrap_or_else(|| mount.clone());
+
+            disks.push(DriveOutput {
+                text: match self.label_prefix {
+                    LabelPrefix::Text | LabelPrefix::IconAndText => format!("{name} {reading}"),
+                    LabelPrefix::None | LabelPrefix::Icon => reading,
+                },
+                icon: drive
+                    .and_then(|drive| drive.icon.clone())
+                    .unwrap_or_else(|| egui_phosphor::regular::HARD_DRIVES.to_string()),
+                usage: (used * 100) as f32 / total as f32,
+                thresholds: drive.and_then(|drive| drive.thresholds).or(self.thresholds),
+                mount,
             })
         }
 
-        disks.sort();
-        disks.reverse();
+        match &self.drives {
+            Some(drives) => disks.sort_by_key(|disk| {
+                drives
+                    .iter()
+                    .position(|drive| drive.matches(&disk.mount))
+                    .unwrap_or(usize::MAX)
+            }),
+            None => disks.sort_by(|a, b| b.mount.cmp(&a.mount)),
+        }
 
         disks
     }
@@ -81,24 +179,26 @@ impl BarWidget for Storage {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
             for output in self.output() {
+                let warning_colour = output
+                    .thresholds
+                    .and_then(|thresholds| thresholds.colour(ctx, output.usage));
+
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
-                        LabelPrefix::Icon | LabelPrefix::IconAndText => {
-                            egui_phosphor::regular::HARD_DRIVES.to_string()
-                        }
+                        LabelPrefix::Icon | LabelPrefix::IconAndText => output.icon,
                         LabelPrefix::None | LabelPrefix::Text => String::new(),
                     },
                     config.icon_font_id.clone(),
-                    ctx.style().visuals.selection.stroke.color,
+                    warning_colour.unwrap_or(ctx.style().visuals.selection.stroke.color),
                     100.0,
                 );
 
                 layout_job.append(
-                    &output,
+                    &output.text,
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
-                        color: ctx.style().visuals.text_color(),
+                        color: warning_colour.unwrap_or(ctx.style().visuals.text_color()),
                         valign: Align::Center,
                         ..Default::default()
                     },
@@ -110,11 +210,7 @@ impl BarWidget for Storage {
                         .clicked()
                     {
                         if let Err(error) = Command::new("cmd.exe")
-                            .args([
-                                "/C",
-                                "explorer.exe",
-                                output.split(' ').collect::<Vec<&str>>()[0],
-                            ])
+                            .args(["/C", "explorer.exe", &output.mount])
                             .spawn()
                         {
                             eprintln!("{}", error)
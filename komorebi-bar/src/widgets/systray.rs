@@ -0,0 +1,576 @@
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::komorebi::img_to_texture;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::Context;
+use eframe::egui::Image;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use image::RgbaImage;
+use komorebi_client::Window;
+use serde::Deserialize;
+use serde::Serialize;
+use std::ffi::c_void;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::Once;
+use std::sync::OnceLock;
+use windows::core::w;
+use windows::core::GUID;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::POINT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::Graphics::Gdi::CreateCompatibleDC;
+use windows::Win32::Graphics::Gdi::DeleteDC;
+use windows::Win32::Graphics::Gdi::DeleteObject;
+use windows::Win32::Graphics::Gdi::GetDIBits;
+use windows::Win32::Graphics::Gdi::GetObjectW;
+use windows::Win32::Graphics::Gdi::BITMAP;
+use windows::Win32::Graphics::Gdi::BITMAPINFO;
+use windows::Win32::Graphics::Gdi::BITMAPINFOHEADER;
+use windows::Win32::Graphics::Gdi::BI_RGB;
+use windows::Win32::Graphics::Gdi::DIB_RGB_COLORS;
+use windows::Win32::Graphics::Gdi::HBITMAP;
+use windows::Win32::System::DataExchange::COPYDATASTRUCT;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Shell::NIF_ICON;
+use windows::Win32::UI::Shell::NIF_MESSAGE;
+use windows::Win32::UI::Shell::NIF_STATE;
+use windows::Win32::UI::Shell::NIF_TIP;
+use windows::Win32::UI::Shell::NIM_ADD;
+use windows::Win32::UI::Shell::NIM_DELETE;
+use windows::Win32::UI::Shell::NIM_MODIFY;
+use windows::Win32::UI::Shell::NIM_SETVERSION;
+use windows::Win32::UI::Shell::NIN_SELECT;
+use windows::Win32::UI::Shell::NIS_HIDDEN;
+use windows::Win32::UI::Shell::NOTIFYICON_VERSION_4;
+use windows::Win32::UI::Shell::NOTIFY_ICON_DATA_FLAGS;
+use windows::Win32::UI::Shell::NOTIFY_ICON_MESSAGE;
+use windows::Win32::UI::WindowsAndMessaging::AllowSetForegroundWindow;
+use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::FindWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+use windows::Win32::UI::WindowsAndMessaging::GetIconInfo;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+use windows::Win32::UI::WindowsAndMessaging::RegisterClassW;
+use windows::Win32::UI::WindowsAndMessaging::RegisterWindowMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SendMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SendNotifyMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SetTimer;
+use windows::Win32::UI::WindowsAndMessaging::SetWindowPos;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::HICON;
+use windows::Win32::UI::WindowsAndMessaging::HWND_BROADCAST;
+use windows::Win32::UI::WindowsAndMessaging::HWND_TOPMOST;
+use windows::Win32::UI::WindowsAndMessaging::ICONINFO;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOMOVE;
+use windows::Win32::UI::WindowsAndMessaging::SWP_NOSIZE;
+use windows::Win32::UI::WindowsAndMessaging::WM_CONTEXTMENU;
+use windows::Win32::UI::WindowsAndMessaging::WM_COPYDATA;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_TIMER;
+use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOPMOST;
+use windows::Win32::UI::WindowsAndMessaging::WS_POPUP;
+
+/// The window class which `Shell_NotifyIcon` looks for when registering tray icons
+const TRAY_WINDOW_CLASS: PCWSTR = w!("Shell_TrayWnd");
+/// The `dwData` of a `WM_COPYDATA` message sent by `Shell_NotifyIcon`
+const TRAY_COPY_DATA: usize = 1;
+/// The magic number at the start of every tray message payload
+const TRAY_DATA_MAGIC: u32 = 0x34753423;
+const TOPMOST_TIMER_ID: usize = 1;
+
+/// Icons which have been registered with the tray host, in the order they were added
+static TRAY_ICONS: LazyLock<Mutex<Vec<TrayIcon>>> = LazyLock::new(|| Mutex::new(vec![]));
+static TRAY_HOST: Once = Once::new();
+static REPAINT_CONTEXT: OnceLock<Context> = OnceLock::new();
+
+/// Tray icons can only be hosted when the Windows taskbar is not running, as the bar would
+/// otherwise have to take over the window class which other applications look the taskbar up by
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SystrayConfig {
+    /// Enable the Systray widget
+    pub enable: bool,
+    /// Executable names (e.g. "Steam.exe") of applications whose tray icons should not be shown
+    pub hidden_icons: Option<Vec<String>>,
+}
+
+impl From<SystrayConfig> for Systray {
+    fn from(value: SystrayConfig) -> Self {
+        if value.enable {
+            TRAY_HOST.call_once(|| {
+                std::thread::spawn(|| {
+                    if let Err(error) = run_tray_host() {
+                        tracing::error!("tray host stopped: {error}");
+                    }
+                });
+            });
+        }
+
+        Self {
+            enable: value.enable,
+            hidden_icons: value
+                .hidden_icons
+                .unwrap_or_default()
+                .iter()
+                .map(|exe| exe.to_lowercase())
+                .collect(),
+        }
+    }
+}
+
+pub struct Systray {
+    pub enable: bool,
+    hidden_icons: Vec<String>,
+}
+
+impl BarWidget for Systray {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        // the tray host runs on its own thread and needs to be able to wake up the bar
+        REPAINT_CONTEXT.get_or_init(|| ctx.clone());
+
+        let icons = TRAY_ICONS.lock().unwrap();
+        let visible_icons = icons
+            .iter()
+            .filter(|icon| !icon.hidden && !self.hidden_icons.contains(&icon.exe))
+            .filter_map(|icon| icon.image.as_ref().map(|image| (icon, image)))
+            .collect::<Vec<_>>();
+
+        if visible_icons.is_empty() {
+            return;
+        }
+
+        let icon_size = Vec2::splat(config.icon_font_id.size);
+
+        config.apply_on_widget(false, ui, |ui| {
+            for (icon, image) in visible_icons {
+                let mut response = SelectableFrame::new(false).show(ui, |ui| {
+                    ui.add(
+                        Image::from(&img_to_texture(ctx, image))
+                            .maintain_aspect_ratio(true)
+                            .fit_to_exact_size(icon_size),
+                    )
+                });
+
+                if !icon.tooltip.is_empty() {
+                    response = response.on_hover_text(&icon.tooltip);
+                }
+
+                if response.clicked() {
+                    icon.click(&[WM_LBUTTONDOWN, WM_LBUTTONUP], NIN_SELECT);
+                } else if response.secondary_clicked() {
+                    icon.click(&[WM_RBUTTONDOWN, WM_RBUTTONUP], WM_CONTEXTMENU);
+                }
+            }
+        });
+    }
+}
+
+/// The payload of the `WM_COPYDATA` message which `Shell_NotifyIcon` sends to the taskbar
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct ShellTrayData {
+    magic: u32,
+    message: u32,
+    data: NotifyIconData,
+}
+
+/// `NOTIFYICONDATAW` as it is sent to the taskbar, where handles are always 32 bits wide, even
+/// when they come from a 64-bit process
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(dead_code)]
+struct NotifyIconData {
+    size: u32,
+    hwnd: u32,
+    uid: u32,
+    flags: u32,
+    callback_message: u32,
+    icon: u32,
+    tooltip: [u16; 128],
+    state: u32,
+    state_mask: u32,
+    info: [u16; 256],
+    version: u32,
+    info_title: [u16; 64],
+    info_flags: u32,
+    guid: GUID,
+    balloon_icon: u32,
+}
+
+#[derive(Clone, Debug)]
+struct TrayIcon {
+    hwnd: isize,
+    uid: u32,
+    process_id: u32,
+    exe: String,
+    callback_message: u32,
+    version: u32,
+    tooltip: String,
+    hidden: bool,
+    image: Option<RgbaImage>,
+}
+
+impl TrayIcon {
+    fn new(data: &NotifyIconData) -> Self {
+        // handles are sign-extended when they are widened to 64 bits
+        let window = Window::from(data.hwnd as i32 as isize);
+
+        Self {
+            hwnd: window.hwnd,
+            uid: data.uid,
+            process_id: window.process_id(),
+            exe: window.exe().unwrap_or_default().to_lowercase(),
+            callback_message: 0,
+            version: 0,
+            tooltip: String::new(),
+            hidden: false,
+            image: None,
+        }
+    }
+
+    fn matches(&self, data: &NotifyIconData) -> bool {
+        self.hwnd == data.hwnd as i32 as isize && self.uid == data.uid
+    }
+
+    fn update(&mut self, data: &NotifyIconData, image: Option<RgbaImage>) {
+        let flags = NOTIFY_ICON_DATA_FLAGS(data.flags);
+
+        if flags.contains(NIF_MESSAGE) {
+            self.callback_message = data.callback_message;
+        }
+
+        if flags.contains(NIF_TIP) {
+            let len = data
+                .tooltip
+                .iter()
+                .position(|c| *c == 0)
+                .unwrap_or(data.tooltip.len());
+            self.tooltip = String::from_utf16_lossy(&data.tooltip[..len]);
+        }
+
+        if flags.contains(NIF_STATE) && data.state_mask & NIS_HIDDEN.0 != 0 {
+            self.hidden = data.state & NIS_HIDDEN.0 != 0;
+        }
+
+        if flags.contains(NIF_ICON) {
+            self.image = image;
+        }
+    }
+
+    /// Sends the given mouse messages to the application which owns the icon, the same way the
+    /// taskbar does when the icon is clicked there
+    fn click(&self, messages: &[u32], version_4_message: u32) {
+        if self.callback_message == 0 {
+            return;
+        }
+
+        let mut cursor = POINT::default();
+        unsafe {
+            let _ = GetCursorPos(&mut cursor);
+            // let the application bring its own windows and menus to the foreground
+            let _ = AllowSetForegroundWindow(self.process_id);
+        }
+
+        let mut messages = messages.to_vec();
+        if self.version >= NOTIFYICON_VERSION_4 {
+            messages.push(version_4_message);
+        }
+
+        for message in messages {
+            let (wparam, lparam) = if self.version >= NOTIFYICON_VERSION_4 {
+                (
+                    WPARAM(((cursor.y as u16 as usize) << 16) | cursor.x as u16 as usize),
+                    LPARAM((((self.uid & 0xffff) << 16) | (message & 0xffff)) as isize),
+                )
+            } else {
+                (WPARAM(self.uid as usize), LPARAM(message as isize))
+            };
+
+            if let Err(error) = unsafe {
+                PostMessageW(
+                    Some(HWND(self.hwnd as *mut c_void)),
+                    self.callback_message,
+                    wparam,
+                    lparam,
+                )
+            } {
+                eprintln!("{}", error)
+            }
+        }
+    }
+}
+
+/// Creates a hidden window with the `Shell_TrayWnd` class name, so that the notifications sent by
+/// `Shell_NotifyIcon` reach the bar, as long as there is no taskbar which already has that class
+/// name; applications also look the taskbar up by it, so it cannot be shadowed while it is running
+fn run_tray_host() -> color_eyre::Result<()> {
+    if taskbar(HWND::default()).is_some() {
+        color_eyre::eyre::bail!(
+            "the windows taskbar is running, so tray icons cannot be shown in the bar"
+        );
+    }
+
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+
+        let window_class = WNDCLASSW {
+            hInstance: instance.into(),
+            lpszClassName: TRAY_WINDOW_CLASS,
+            lpfnWndProc: Some(tray_host_callback),
+            ..Default::default()
+        };
+
+        let _ = RegisterClassW(&window_class);
+
+        let hwnd = CreateWindowExW(
+            WS_EX_TOOLWINDOW | WS_EX_TOPMOST,
+            TRAY_WINDOW_CLASS,
+            PCWSTR::null(),
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            Some(instance.into()),
+            None,
+        )?;
+
+        // Shell_NotifyIcon sends its messages to the first Shell_TrayWnd in the z-order, so this
+        // window has to stay above the one owned by the taskbar
+        SetTimer(Some(hwnd), TOPMOST_TIMER_ID, 100, None);
+
+        // applications register their icons again when they receive this message, which is
+        // how the bar learns about icons that were added before it started
+        let taskbar_created = RegisterWindowMessageW(w!("TaskbarCreated"));
+        SendNotifyMessageW(HWND_BROADCAST, taskbar_created, WPARAM(0), LPARAM(0))?;
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    Ok(())
+}
+
+unsafe extern "system" fn tray_host_callback(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match message {
+        WM_TIMER => {
+            let _ = SetWindowPos(
+                hwnd,
+                Some(HWND_TOPMOST),
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            );
+
+            LRESULT(0)
+        }
+        WM_COPYDATA => {
+            let copy_data = &*(lparam.0 as *const COPYDATASTRUCT);
+            let is_tray_data = copy_data.dwData == TRAY_COPY_DATA;
+
+            if is_tray_data {
+                handle_tray_data(copy_data);
+            }
+
+            // everything is still passed on so that a taskbar which was started after the bar
+            // keeps working
+            let result = match taskbar(hwnd) {
+                Some(taskbar) => SendMessageW(taskbar, message, Some(wparam), Some(lparam)),
+                None => LRESULT(0),
+            };
+
+            if is_tray_data {
+                LRESULT(1)
+            } else {
+                result
+            }
+        }
+        _ => DefWindowProcW(hwnd, message, wparam, lparam),
+    }
+}
+
+/// The `Shell_TrayWnd` owned by the taskbar, if it is running
+fn taskbar(host: HWND) -> Option<HWND> {
+    let mut after = None;
+
+    loop {
+        let hwnd = unsafe { FindWindowExW(None, after, TRAY_WINDOW_CLASS, PCWSTR::null()) }.ok()?;
+
+        if hwnd != host {
+            return Some(hwnd);
+        }
+
+        after = Some(hwnd);
+    }
+}
+
+fn handle_tray_data(copy_data: &COPYDATASTRUCT) {
+    // older applications send a smaller NOTIFYICONDATAW, so anything they leave out is zeroed
+    let mut tray_data: ShellTrayData = unsafe { std::mem::zeroed() };
+    let len = (copy_data.cbData as usize).min(std::mem::size_of::<ShellTrayData>());
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            copy_data.lpData as *const u8,
+            &mut tray_data as *mut ShellTrayData as *mut u8,
+            len,
+        );
+    }
+
+    if tray_data.magic != TRAY_DATA_MAGIC {
+        return;
+    }
+
+    let data = tray_data.data;
+
+    // the icon handle belongs to the application and may be destroyed at any time after this
+    // message has been handled, so it has to be converted straight away
+    let image = if NOTIFY_ICON_DATA_FLAGS(data.flags).contains(NIF_ICON) {
+        icon_to_image(HICON(data.icon as i32 as isize as *mut c_void))
+    } else {
+        None
+    };
+
+    let mut icons = TRAY_ICONS.lock().unwrap();
+    let position = icons.iter().position(|icon| icon.matches(&data));
+
+    match NOTIFY_ICON_MESSAGE(tray_data.message) {
+        NIM_ADD | NIM_MODIFY => {
+            let index = match position {
+                Some(index) => index,
+                None => {
+                    icons.push(TrayIcon::new(&data));
+                    icons.len() - 1
+                }
+            };
+
+            icons[index].update(&data, image);
+        }
+        NIM_DELETE => {
+            if let Some(index) = position {
+                icons.remove(index);
+            }
+        }
+        NIM_SETVERSION => {
+            if let Some(index) = position {
+                icons[index].version = data.version;
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(ctx) = REPAINT_CONTEXT.get() {
+        ctx.request_repaint();
+    }
+}
+
+/// Converts an icon handle into an image which can be turned into a texture; the handle itself is
+/// left for the caller to destroy
+pub fn icon_to_image(icon: HICON) -> Option<RgbaImage> {
+    let mut icon_info = ICONINFO::default();
+    unsafe { GetIconInfo(icon, &mut icon_info) }.ok()?;
+
+    let image = bitmap_to_image(icon_info.hbmColor);
+
+    unsafe {
+        let _ = DeleteObject(icon_info.hbmColor.into());
+        let _ = DeleteObject(icon_info.hbmMask.into());
+    }
+
+    image
+}
+
+fn bitmap_to_image(bitmap: HBITMAP) -> Option<RgbaImage> {
+    let mut bitmap_data = BITMAP::default();
+    if unsafe {
+        GetObjectW(
+            bitmap.into(),
+            std::mem::size_of::<BITMAP>() as i32,
+            Some(&mut bitmap_data as *mut BITMAP as *mut c_void),
+        )
+    } == 0
+    {
+        return None;
+    }
+
+    let width = bitmap_data.bmWidth;
+    let height = bitmap_data.bmHeight;
+
+    let mut bitmap_info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // a negative height gives us the rows from top to bottom
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+    let lines = unsafe {
+        let dc = CreateCompatibleDC(None);
+        let lines = GetDIBits(
+            dc,
+            bitmap,
+            0,
+            height as u32,
+            Some(pixels.as_mut_ptr() as *mut c_void),
+            &mut bitmap_info,
+            DIB_RGB_COLORS,
+        );
+        let _ = DeleteDC(dc);
+        lines
+    };
+
+    if lines == 0 {
+        return None;
+    }
+
+    for pixel in pixels.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+
+    // icons without an alpha channel are meant to be fully opaque
+    if pixels.chunks_exact(4).all(|pixel| pixel[3] == 0) {
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+    }
+
+    RgbaImage::from_raw(width as u32, height as u32, pixels)
+}
@@ -1,16 +1,23 @@
 use crate::bar::Alignment;
 use crate::config::LabelPrefix;
+use crate::popup::Popup;
 use crate::render::RenderConfig;
 use crate::selected_frame::SelectableFrame;
 use crate::widgets::widget::BarWidget;
+use chrono::Datelike;
 use chrono::Local;
+use chrono::Months;
+use chrono::NaiveDate;
 use chrono::NaiveTime;
 use chrono_tz::Tz;
 use eframe::egui::text::LayoutJob;
 use eframe::egui::Align;
 use eframe::egui::Context;
 use eframe::egui::CornerRadius;
+use eframe::egui::Grid;
 use eframe::egui::Label;
+use eframe::egui::Rect;
+use eframe::egui::RichText;
 use eframe::egui::Sense;
 use eframe::egui::Stroke;
 use eframe::egui::TextFormat;
@@ -23,6 +30,8 @@ use serde::Serialize;
 use std::time::Duration;
 use std::time::Instant;
 
+const CALENDAR_SIZE: Vec2 = Vec2::new(240.0, 230.0);
+
 lazy_static! {
     static ref TIME_RANGES: Vec<(&'static str, NaiveTime)> = {
         vec![
@@ -94,6 +103,19 @@ pub struct TimeConfig {
     pub timezone: Option<String>,
     /// Change the icon depending on the time. The default icon is used between 8:30 and 12:00. (default: false)
     pub changing_icon: Option<bool>,
+    /// Additional time zones to display next to the main time, using the same format
+    pub additional_timezones: Option<Vec<AdditionalTimezone>>,
+    /// Open a month calendar when the widget is clicked, the time format is then changed with a right click instead (default: false)
+    pub calendar: Option<bool>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AdditionalTimezone {
+    /// TimeZone (https://docs.rs/chrono-tz/latest/chrono_tz/enum.Tz.html)
+    pub timezone: String,
+    /// Label displayed before the time (default: the city of the time zone, e.g. "Tokyo" for "Asia/Tokyo")
+    pub label: Option<String>,
 }
 
 impl From<TimeConfig> for Time {
@@ -108,6 +130,26 @@ impl From<TimeConfig> for Time {
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
             timezone: value.timezone,
             changing_icon: value.changing_icon.unwrap_or_default(),
+            additional_timezones: value
+                .additional_timezones
+                .unwrap_or_default()
+                .into_iter()
+                .map(|additional| {
+                    let label = additional.label.unwrap_or_else(|| {
+                        additional
+                            .timezone
+                            .rsplit('/')
+                            .next()
+                            .unwrap_or_default()
+                            .replace('_', " ")
+                    });
+
+                    (label, additional.timezone)
+                })
+                .collect(),
+            calendar: value.calendar.unwrap_or_default(),
+            calendar_popup: Popup::new("calendar"),
+            calendar_month: Local::now().date_naive(),
             data_refresh_interval_millis: data_refresh_interval,
             last_state: TimeOutput::new(),
             last_updated: Instant::now()
@@ -160,12 +202,21 @@ impl TimeFormat {
             TimeFormat::Custom(format) => format.to_string(),
         }
     }
+
+    /// The format used for additional time zones, which are always displayed as text
+    fn additional_fmt_string(&self) -> String {
+        match self {
+            TimeFormat::BinaryCircle | TimeFormat::BinaryRectangle => String::from("%T"),
+            _ => self.fmt_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 struct TimeOutput {
     label: String,
     icon: String,
+    additional: Vec<String>,
 }
 
 impl TimeOutput {
@@ -173,6 +224,7 @@ impl TimeOutput {
         Self {
             label: String::new(),
             icon: String::new(),
+            additional: vec![],
         }
     }
 }
@@ -184,6 +236,11 @@ pub struct Time {
     label_prefix: LabelPrefix,
     timezone: Option<String>,
     changing_icon: bool,
+    additional_timezones: Vec<(String, String)>,
+    calendar: bool,
+    calendar_popup: Popup,
+    /// First day of the month shown in the calendar
+    calendar_month: NaiveDate,
     data_refresh_interval_millis: u64,
     last_state: TimeOutput,
     last_updated: Instant,
@@ -227,9 +284,27 @@ impl Time {
                 return TimeOutput {
                     label: formatted,
                     icon: egui_phosphor::regular::WARNING_CIRCLE.to_string(),
+                    additional: vec![],
                 };
             }
 
+            let additional_format = self.format.additional_fmt_string();
+            let additional = self
+                .additional_timezones
+                .iter()
+                .map(|(label, timezone)| match timezone.parse::<Tz>() {
+                    Ok(tz) => format!(
+                        "{label} {}",
+                        Local::now()
+                            .with_timezone(&tz)
+                            .format(&additional_format)
+                            .to_string()
+                            .trim()
+                    ),
+                    Err(_) => format!("Invalid timezone: {:?}", timezone),
+                })
+                .collect();
+
             let current_range = match &self.changing_icon {
                 true => TIME_RANGES
                     .iter()
@@ -243,6 +318,7 @@ impl Time {
             output = TimeOutput {
                 label: formatted,
                 icon: current_range.0.to_string(),
+                additional,
             };
 
             self.last_state.clone_from(&output);
@@ -252,6 +328,34 @@ impl Time {
         output
     }
 
+    /// Today's date in the configured time zone
+    fn today(&self) -> NaiveDate {
+        match self.timezone.as_ref().and_then(|tz| tz.parse::<Tz>().ok()) {
+            Some(tz) => Local::now().with_timezone(&tz).date_naive(),
+            None => Local::now().date_naive(),
+        }
+    }
+
+    fn toggle_calendar(&mut self) {
+        if self.calendar_popup.toggle() {
+            self.calendar_month = self.today().with_day(1).unwrap_or(self.calendar_month);
+        }
+    }
+
+    fn show_calendar(&mut self, ctx: &Context, anchor: Rect, monitor_top: i32) {
+        let month = self.calendar_month;
+        let today = self.today();
+
+        if let Some(Some(month)) =
+            self.calendar_popup
+                .show(ctx, anchor, monitor_top, CALENDAR_SIZE, |ui| {
+                    calendar_ui(ui, month, today)
+                })
+        {
+            self.calendar_month = month;
+        }
+    }
+
     fn paint_binary_circle(
         &mut self,
         size: f32,
@@ -463,14 +567,32 @@ impl BarWidget for Time {
                     );
                 }
 
+                let mut additional_job = LayoutJob::default();
+                for (i, additional) in output.additional.iter().enumerate() {
+                    additional_job.append(
+                        additional,
+                        if i == 0 { 0.0 } else { 10.0 },
+                        TextFormat {
+                            font_id: config.text_font_id.clone(),
+                            color: ctx.style().visuals.text_color(),
+                            valign: Align::Center,
+                            ..Default::default()
+                        },
+                    );
+                }
+
                 let font_id = config.icon_font_id.clone();
                 let is_reversed = matches!(config.alignment, Some(Alignment::Right));
+                let monitor_top = config.monitor_top;
+                let mut calendar_anchor = None;
 
                 config.apply_on_widget(false, ui, |ui| {
-                    if SelectableFrame::new(false)
-                        .show(ui, |ui| {
+                    let response =
+                        SelectableFrame::new(self.calendar_popup.is_open()).show(ui, |ui| {
                             if !is_reversed {
                                 ui.add(Label::new(layout_job.clone()).selectable(false));
+                            } else if !additional_job.text.is_empty() {
+                                ui.add(Label::new(additional_job.clone()).selectable(false));
                             }
 
                             if use_binary_circle || use_binary_rectangle {
@@ -525,14 +647,81 @@ impl BarWidget for Time {
 
                             if is_reversed {
                                 ui.add(Label::new(layout_job.clone()).selectable(false));
+                            } else if !additional_job.text.is_empty() {
+                                ui.add(Label::new(additional_job.clone()).selectable(false));
                             }
-                        })
-                        .clicked()
-                    {
+                        });
+
+                    if self.calendar {
+                        if response.clicked() {
+                            self.toggle_calendar();
+                        } else if response.secondary_clicked() {
+                            self.format.toggle();
+                        }
+
+                        calendar_anchor = Some(response.rect);
+                    } else if response.clicked() {
                         self.format.toggle()
                     }
                 });
+
+                if let Some(anchor) = calendar_anchor {
+                    self.show_calendar(ctx, anchor, monitor_top);
+                }
             }
         }
     }
 }
+
+/// Draws a month grid starting on Monday with today highlighted, returning the month to show
+/// on the next frame
+fn calendar_ui(ui: &mut Ui, month: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+    let mut next_month = Some(month);
+
+    ui.horizontal(|ui| {
+        if ui.button(egui_phosphor::regular::CARET_LEFT).clicked() {
+            next_month = month.checked_sub_months(Months::new(1));
+        }
+
+        ui.label(RichText::new(month.format("%B %Y").to_string()).strong());
+
+        if ui.button(egui_phosphor::regular::CARET_RIGHT).clicked() {
+            next_month = month.checked_add_months(Months::new(1));
+        }
+    });
+
+    Grid::new("calendar")
+        .num_columns(7)
+        .spacing(Vec2::new(8.0, 4.0))
+        .show(ui, |ui| {
+            for weekday in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"] {
+                ui.label(RichText::new(weekday).weak());
+            }
+            ui.end_row();
+
+            let mut column = month.weekday().num_days_from_monday();
+            for _ in 0..column {
+                ui.label("");
+            }
+
+            for day in month
+                .iter_days()
+                .take_while(|day| day.month() == month.month())
+            {
+                let text = RichText::new(day.day().to_string());
+                ui.label(if day == today {
+                    text.color(ui.visuals().selection.stroke.color).strong()
+                } else {
+                    text
+                });
+
+                column += 1;
+                if column == 7 {
+                    ui.end_row();
+                    column = 0;
+                }
+            }
+        });
+
+    next_month
+}
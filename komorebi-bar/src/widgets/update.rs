@@ -11,6 +11,10 @@ use eframe::egui::Ui;
 use serde::Deserialize;
 use serde::Serialize;
 use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -29,36 +33,14 @@ impl From<UpdateConfig> for Update {
     fn from(value: UpdateConfig) -> Self {
         let data_refresh_interval = value.data_refresh_interval.unwrap_or(12);
 
-        let mut latest_version = String::new();
-
-        let client = reqwest::blocking::Client::new();
-        if let Ok(response) = client
-            .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
-            .header("User-Agent", "komorebi-bar-version-checker")
-            .send()
-        {
-            #[derive(Deserialize)]
-            struct Release {
-                tag_name: String,
-            }
-
-            if let Ok(release) =
-                serde_json::from_str::<Release>(&response.text().unwrap_or_default())
-            {
-                let trimmed = release.tag_name.trim_start_matches("v");
-                latest_version = trimmed.to_string();
-            }
-        }
-
         Self {
             enable: value.enable,
             data_refresh_interval,
             installed_version: env!("CARGO_PKG_VERSION").to_string(),
-            latest_version,
+            latest_version: Arc::new(Mutex::new(None)),
+            running: Arc::new(AtomicBool::new(false)),
             label_prefix: value.label_prefix.unwrap_or(LabelPrefix::IconAndText),
-            last_updated: Instant::now()
-                .checked_sub(Duration::from_secs(data_refresh_interval))
-                .unwrap(),
+            last_updated: None,
         }
     }
 }
@@ -67,52 +49,89 @@ pub struct Update {
     pub enable: bool,
     data_refresh_interval: u64,
     installed_version: String,
-    latest_version: String,
+    latest_version: Arc<Mutex<Option<String>>>,
+    running: Arc<AtomicBool>,
     label_prefix: LabelPrefix,
-    last_updated: Instant,
+    /// None until the first check, which happens as soon as the widget is rendered
+    last_updated: Option<Instant>,
+}
+
+fn latest_release() -> color_eyre::Result<String> {
+    #[derive(Deserialize)]
+    struct Release {
+        tag_name: String,
+    }
+
+    let response = reqwest::blocking::Client::new()
+        .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
+        .header("User-Agent", "komorebi-bar-version-checker")
+        .send()?
+        .error_for_status()?
+        .text()?;
+
+    let release: Release = serde_json::from_str(&response)?;
+
+    Ok(release.tag_name.trim_start_matches('v').to_string())
+}
+
+/// Compares versions component by component, so that e.g. 0.1.10 is newer than 0.1.9
+fn is_newer(latest: &str, installed: &str) -> bool {
+    let components = |version: &str| {
+        version
+            .split('.')
+            .map(|component| component.parse::<u64>().unwrap_or_default())
+            .collect::<Vec<_>>()
+    };
+
+    components(latest) > components(installed)
 }
 
 impl Update {
-    fn output(&mut self) -> String {
-        let now = Instant::now();
-        if now.duration_since(self.last_updated)
-            > Duration::from_secs((self.data_refresh_interval * 60) * 60)
-        {
-            let client = reqwest::blocking::Client::new();
-            if let Ok(response) = client
-                .get("https://api.github.com/repos/LGUG2Z/komorebi/releases/latest")
-                .header("User-Agent", "komorebi-bar-version-checker")
-                .send()
-            {
-                #[derive(Deserialize)]
-                struct Release {
-                    tag_name: String,
-                }
-
-                if let Ok(release) =
-                    serde_json::from_str::<Release>(&response.text().unwrap_or_default())
-                {
-                    let trimmed = release.tag_name.trim_start_matches("v");
-                    self.latest_version = trimmed.to_string();
-                }
+    /// Checks for a new release on a background thread so that a slow connection doesn't block
+    /// the bar
+    fn refresh(&mut self, ctx: &Context) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        self.last_updated = Some(Instant::now());
+
+        let latest_version = self.latest_version.clone();
+        let running = self.running.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            match latest_release() {
+                Ok(latest) => *latest_version.lock().unwrap() = Some(latest),
+                Err(error) => tracing::error!("could not check for komorebi updates: {error}"),
             }
 
-            self.last_updated = now;
-        }
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+        });
+    }
 
-        if self.latest_version > self.installed_version {
-            format!("Update available! v{}", self.latest_version)
-        } else {
-            String::new()
+    fn output(&mut self, ctx: &Context) -> Option<String> {
+        let interval = Duration::from_secs((self.data_refresh_interval * 60) * 60);
+        if self
+            .last_updated
+            .is_none_or(|last_updated| last_updated.elapsed() > interval)
+        {
+            self.refresh(ctx);
         }
+
+        self.latest_version
+            .lock()
+            .unwrap()
+            .clone()
+            .filter(|latest| is_newer(latest, &self.installed_version))
     }
 }
 
 impl BarWidget for Update {
     fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
         if self.enable {
-            let output = self.output();
-            if !output.is_empty() {
+            if let Some(latest_version) = self.output(ctx) {
                 let mut layout_job = LayoutJob::simple(
                     match self.label_prefix {
                         LabelPrefix::Icon | LabelPrefix::IconAndText => {
@@ -126,7 +145,7 @@ impl BarWidget for Update {
                 );
 
                 layout_job.append(
-                    &output,
+                    &format!("Update available! v{latest_version}"),
                     10.0,
                     TextFormat {
                         font_id: config.text_font_id.clone(),
@@ -139,12 +158,15 @@ impl BarWidget for Update {
                 config.apply_on_widget(false, ui, |ui| {
                     if SelectableFrame::new(false)
                         .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                        .on_hover_text(format!(
+                            "Installed: v{}, latest: v{latest_version}",
+                            self.installed_version
+                        ))
                         .clicked()
                     {
                         if let Err(error) = Command::new("explorer.exe")
                             .args([format!(
-                                "https://github.com/LGUG2Z/komorebi/releases/v{}",
-                                self.latest_version
+                                "https://github.com/LGUG2Z/komorebi/releases/v{latest_version}"
                             )])
                             .spawn()
                         {
@@ -0,0 +1,441 @@
+use crate::config::LabelPrefix;
+use crate::popup::Popup;
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::Rect;
+use eframe::egui::Slider;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use eframe::egui::Vec2;
+use policy_config::IPolicyConfig;
+use serde::Deserialize;
+use serde::Serialize;
+use std::ffi::c_void;
+use std::time::Duration;
+use std::time::Instant;
+use windows::core::GUID;
+use windows::core::HSTRING;
+use windows::core::PCWSTR;
+use windows::Win32::Devices::FunctionDiscovery::PKEY_Device_FriendlyName;
+use windows::Win32::Media::Audio::eCommunications;
+use windows::Win32::Media::Audio::eConsole;
+use windows::Win32::Media::Audio::eMultimedia;
+use windows::Win32::Media::Audio::eRender;
+use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+use windows::Win32::Media::Audio::IMMDevice;
+use windows::Win32::Media::Audio::IMMDeviceEnumerator;
+use windows::Win32::Media::Audio::MMDeviceEnumerator;
+use windows::Win32::Media::Audio::DEVICE_STATE_ACTIVE;
+use windows::Win32::System::Com::CoCreateInstance;
+use windows::Win32::System::Com::CoInitializeEx;
+use windows::Win32::System::Com::CoTaskMemFree;
+use windows::Win32::System::Com::CLSCTX_ALL;
+use windows::Win32::System::Com::COINIT_APARTMENTTHREADED;
+use windows::Win32::System::Com::STGM_READ;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VolumeConfig {
+    /// Enable the Volume widget
+    pub enable: bool,
+    /// Data refresh interval (default: 1 second)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Percentage by which the volume changes for each scroll step (default: 5)
+    pub scroll_step: Option<u8>,
+    /// Names of the output devices to cycle between with a right click (default: all active output devices)
+    pub devices: Option<Vec<String>>,
+    /// Open a flyout with a volume slider and a list of the output devices with a right click,
+    /// instead of cycling between them (default: false)
+    pub device_popup: Option<bool>,
+}
+
+impl From<VolumeConfig> for Volume {
+    fn from(value: VolumeConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(1);
+
+        // The core audio APIs need COM to be initialized on the UI thread; if it already has
+        // been, this is a no-op
+        let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+
+        Self {
+            enable: value.enable,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            scroll_step: f32::from(value.scroll_step.unwrap_or(5)) / 100.0,
+            devices: value.devices.unwrap_or_default(),
+            device_popup: value
+                .device_popup
+                .unwrap_or_default()
+                .then(|| Popup::new("audio devices")),
+            popup_devices: vec![],
+            state: None,
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+pub struct Volume {
+    pub enable: bool,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    scroll_step: f32,
+    devices: Vec<String>,
+    device_popup: Option<Popup>,
+    /// Id and name of the output devices listed in the flyout, fetched when it is opened
+    popup_devices: Vec<(String, String)>,
+    state: Option<AudioState>,
+    last_updated: Instant,
+}
+
+#[derive(Clone, Debug)]
+struct AudioState {
+    device: String,
+    volume: f32,
+    muted: bool,
+}
+
+impl Volume {
+    fn output(&mut self) -> Option<AudioState> {
+        let now = Instant::now();
+        if now.duration_since(self.last_updated) > Duration::from_secs(self.data_refresh_interval) {
+            self.refresh();
+        }
+
+        self.state.clone()
+    }
+
+    fn refresh(&mut self) {
+        self.state = audio_state().ok();
+        self.last_updated = Instant::now();
+    }
+
+    fn change_volume(&mut self, delta: f32) {
+        if let Ok(endpoint) = default_endpoint_volume() {
+            unsafe {
+                if let Ok(volume) = endpoint.GetMasterVolumeLevelScalar() {
+                    let volume = (volume + delta).clamp(0.0, 1.0);
+                    if let Err(error) =
+                        endpoint.SetMasterVolumeLevelScalar(volume, std::ptr::null())
+                    {
+                        tracing::error!("could not set volume: {error}");
+                    }
+                }
+            }
+        }
+
+        self.refresh();
+    }
+
+    fn toggle_mute(&mut self) {
+        if let Ok(endpoint) = default_endpoint_volume() {
+            unsafe {
+                if let Ok(muted) = endpoint.GetMute() {
+                    if let Err(error) = endpoint.SetMute(!muted.as_bool(), std::ptr::null()) {
+                        tracing::error!("could not toggle mute: {error}");
+                    }
+                }
+            }
+        }
+
+        self.refresh();
+    }
+
+    /// Makes the next output device (either from the configured list, or from all active
+    /// devices) the default for all roles
+    fn cycle_device(&mut self) {
+        let Ok(devices) = output_devices() else {
+            return;
+        };
+
+        let candidates = devices
+            .iter()
+            .filter(|(_, name)| self.devices.is_empty() || self.devices.contains(name))
+            .collect::<Vec<_>>();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let current = self.state.as_ref().map(|state| &state.device);
+        let next = candidates
+            .iter()
+            .position(|(_, name)| Some(name) == current)
+            .map_or(0, |idx| (idx + 1) % candidates.len());
+
+        let (id, name) = candidates[next];
+        self.select_device(id, name);
+    }
+
+    fn select_device(&mut self, id: &str, name: &str) {
+        match set_default_output_device(id) {
+            Ok(()) => tracing::info!("switched default output device to {name}"),
+            Err(error) => tracing::error!("could not switch default output device: {error}"),
+        }
+
+        self.refresh();
+    }
+
+    fn toggle_device_popup(&mut self) {
+        if let Some(popup) = &mut self.device_popup {
+            if popup.toggle() {
+                self.popup_devices = output_devices().unwrap_or_default();
+            }
+        }
+    }
+
+    fn show_device_popup(&mut self, ctx: &Context, anchor: Rect, monitor_top: i32) {
+        let (Some(popup), Some(state)) = (&mut self.device_popup, &self.state) else {
+            return;
+        };
+
+        let devices = &self.popup_devices;
+        let size = Vec2::new(280.0, 60.0 + 26.0 * devices.len() as f32);
+        let mut volume = state.volume;
+
+        let selected = popup.show(ctx, anchor, monitor_top, size, |ui| {
+            ui.spacing_mut().slider_width = ui.available_width();
+            ui.add(Slider::new(&mut volume, 0.0..=1.0).show_value(false));
+            ui.separator();
+
+            let mut selected = None;
+            for (id, name) in devices {
+                if ui.selectable_label(*name == state.device, name).clicked() {
+                    selected = Some((id.clone(), name.clone()));
+                }
+            }
+
+            selected
+        });
+
+        let delta = volume - state.volume;
+        if delta != 0.0 {
+            self.change_volume(delta);
+        }
+
+        if let Some(Some((id, name))) = selected {
+            self.select_device(&id, &name);
+        }
+    }
+}
+
+impl BarWidget for Volume {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if self.enable {
+            let Some(state) = self.output() else {
+                return;
+            };
+
+            let icon = if state.muted {
+                egui_phosphor::regular::SPEAKER_X
+            } else if state.volume == 0.0 {
+                egui_phosphor::regular::SPEAKER_NONE
+            } else if state.volume < 0.5 {
+                egui_phosphor::regular::SPEAKER_LOW
+            } else {
+                egui_phosphor::regular::SPEAKER_HIGH
+            };
+
+            let volume = (state.volume * 100.0).round();
+            let output = match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => format!("VOL: {volume:.0}%"),
+                LabelPrefix::None | LabelPrefix::Icon => format!("{volume:.0}%"),
+            };
+
+            let mut layout_job = LayoutJob::simple(
+                match self.label_prefix {
+                    LabelPrefix::Icon | LabelPrefix::IconAndText => icon.to_string(),
+                    LabelPrefix::None | LabelPrefix::Text => String::new(),
+                },
+                config.icon_font_id.clone(),
+                ctx.style().visuals.selection.stroke.color,
+                100.0,
+            );
+
+            layout_job.append(
+                &output,
+                10.0,
+                TextFormat {
+                    font_id: config.text_font_id.clone(),
+                    color: ctx.style().visuals.text_color(),
+                    valign: Align::Center,
+                    ..Default::default()
+                },
+            );
+
+            let monitor_top = config.monitor_top;
+            let popup_open = self.device_popup.as_ref().is_some_and(Popup::is_open);
+            let mut popup_anchor = None;
+
+            config.apply_on_widget(false, ui, |ui| {
+                let response = SelectableFrame::new(popup_open)
+                    .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                    .on_hover_text(state.device.as_str());
+
+                if response.clicked() {
+                    self.toggle_mute();
+                }
+
+                if response.secondary_clicked() {
+                    if self.device_popup.is_some() {
+                        self.toggle_device_popup();
+                    } else {
+                        self.cycle_device();
+                    }
+                }
+
+                popup_anchor = Some(response.rect);
+
+                if response.hovered() {
+                    let scroll_delta = ui.input(|input| input.raw_scroll_delta.y);
+                    if scroll_delta > 0.0 {
+                        self.change_volume(self.scroll_step);
+                    } else if scroll_delta < 0.0 {
+                        self.change_volume(-self.scroll_step);
+                    }
+                }
+            });
+
+            if let Some(anchor) = popup_anchor {
+                self.show_device_popup(ctx, anchor, monitor_top);
+            }
+        }
+    }
+}
+
+fn device_enumerator() -> windows::core::Result<IMMDeviceEnumerator> {
+    unsafe { CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL) }
+}
+
+fn default_output_device() -> windows::core::Result<IMMDevice> {
+    unsafe { device_enumerator()?.GetDefaultAudioEndpoint(eRender, eConsole) }
+}
+
+fn default_endpoint_volume() -> windows::core::Result<IAudioEndpointVolume> {
+    unsafe { default_output_device()?.Activate(CLSCTX_ALL, None) }
+}
+
+fn device_id(device: &IMMDevice) -> windows::core::Result<String> {
+    unsafe {
+        let id = device.GetId()?;
+        let result = String::from_utf16_lossy(id.as_wide());
+        CoTaskMemFree(Some(id.0 as *const c_void));
+
+        Ok(result)
+    }
+}
+
+fn device_name(device: &IMMDevice) -> windows::core::Result<String> {
+    unsafe {
+        let store = device.OpenPropertyStore(STGM_READ)?;
+        Ok(store.GetValue(&PKEY_Device_FriendlyName)?.to_string())
+    }
+}
+
+fn audio_state() -> windows::core::Result<AudioState> {
+    let device = default_output_device()?;
+    let endpoint: IAudioEndpointVolume = unsafe { device.Activate(CLSCTX_ALL, None)? };
+
+    unsafe {
+        Ok(AudioState {
+            device: device_name(&device)?,
+            volume: endpoint.GetMasterVolumeLevelScalar()?,
+            muted: endpoint.GetMute()?.as_bool(),
+        })
+    }
+}
+
+/// Returns the id and friendly name of every active output device
+fn output_devices() -> windows::core::Result<Vec<(String, String)>> {
+    let mut devices = vec![];
+
+    unsafe {
+        let collection = device_enumerator()?.EnumAudioEndpoints(eRender, DEVICE_STATE_ACTIVE)?;
+
+        for i in 0..collection.GetCount()? {
+            let device = collection.Item(i)?;
+            devices.push((device_id(&device)?, device_name(&device)?));
+        }
+    }
+
+    Ok(devices)
+}
+
+fn set_default_output_device(id: &str) -> windows::core::Result<()> {
+    let policy_config: IPolicyConfig =
+        unsafe { CoCreateInstance(&CLSID_POLICY_CONFIG_CLIENT, None, CLSCTX_ALL)? };
+
+    let id = HSTRING::from(id);
+    for role in [eConsole, eMultimedia, eCommunications] {
+        unsafe { policy_config.set_default_endpoint(PCWSTR(id.as_ptr()), role) }.ok()?;
+    }
+
+    Ok(())
+}
+
+// There is no documented API for changing the default audio device, but this undocumented
+// interface has been stable since Windows 7 and is what the Sound control panel itself uses
+const CLSID_POLICY_CONFIG_CLIENT: GUID = GUID::from_u128(0x870af99c_171d_4f9e_af0d_e63df40c2bc9);
+
+// Only set_default_endpoint is called, but every method has to be declared to get the vtable
+// layout right
+#[allow(dead_code)]
+mod policy_config {
+    use std::ffi::c_void;
+    use windows::core::IUnknown;
+    use windows::core::IUnknown_Vtbl;
+    use windows::core::HRESULT;
+    use windows::core::PCWSTR;
+    use windows::Win32::Media::Audio::ERole;
+
+    #[windows::core::interface("f8679f50-850a-41cf-9c72-430f290290c8")]
+    pub unsafe trait IPolicyConfig: IUnknown {
+        pub unsafe fn get_mix_format(&self, device_id: PCWSTR, format: *mut *mut c_void)
+            -> HRESULT;
+        pub unsafe fn get_device_format(
+            &self,
+            device_id: PCWSTR,
+            default: i32,
+            format: *mut *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn reset_device_format(&self, device_id: PCWSTR) -> HRESULT;
+        pub unsafe fn set_device_format(
+            &self,
+            device_id: PCWSTR,
+            endpoint_format: *mut c_void,
+            mix_format: *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn get_processing_period(
+            &self,
+            device_id: PCWSTR,
+            default: i32,
+            default_period: *mut i64,
+            minimum_period: *mut i64,
+        ) -> HRESULT;
+        pub unsafe fn set_processing_period(&self, device_id: PCWSTR, period: *mut i64) -> HRESULT;
+        pub unsafe fn get_share_mode(&self, device_id: PCWSTR, mode: *mut c_void) -> HRESULT;
+        pub unsafe fn set_share_mode(&self, device_id: PCWSTR, mode: *mut c_void) -> HRESULT;
+        pub unsafe fn get_property_value(
+            &self,
+            device_id: PCWSTR,
+            key: *const c_void,
+            value: *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn set_property_value(
+            &self,
+            device_id: PCWSTR,
+            key: *const c_void,
+            value: *mut c_void,
+        ) -> HRESULT;
+        pub unsafe fn set_default_endpoint(&self, device_id: PCWSTR, role: ERole) -> HRESULT;
+        pub unsafe fn set_endpoint_visibility(&self, device_id: PCWSTR, visible: i32) -> HRESULT;
+    }
+}
@@ -0,0 +1,241 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::selected_frame::SelectableFrame;
+use crate::widgets::exec::shell_command;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Color32;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use komorebi_client::Colour;
+use serde::Deserialize;
+use serde::Serialize;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How long to wait for a connect or disconnect command to take effect before giving up
+const PENDING_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct VpnConfig {
+    /// Enable the Vpn widget
+    pub enable: bool,
+    /// Name or description of the VPN adapter, e.g. "wg0" or "OpenVPN" (case-insensitive)
+    pub adapter: String,
+    /// Label shown instead of the adapter name
+    pub label: Option<String>,
+    /// Data refresh interval (default: 5 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Command to run with cmd.exe when the widget is clicked while disconnected
+    pub connect_command: Option<String>,
+    /// Command to run with cmd.exe when the widget is clicked while connected
+    pub disconnect_command: Option<String>,
+    /// Colour used while connected (default: theme accent colour)
+    pub connected_colour: Option<Colour>,
+    /// Colour used while connecting or disconnecting (default: theme warning colour)
+    pub pending_colour: Option<Colour>,
+    /// Colour used while disconnected (default: theme error colour)
+    pub disconnected_colour: Option<Colour>,
+}
+
+impl From<VpnConfig> for Vpn {
+    fn from(value: VpnConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(5);
+
+        Self {
+            enable: value.enable,
+            adapter: value.adapter.to_lowercase(),
+            label: value.label,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            connect_command: value.connect_command,
+            disconnect_command: value.disconnect_command,
+            connected_colour: value.connected_colour,
+            pending_colour: value.pending_colour,
+            disconnected_colour: value.disconnected_colour,
+            status: VpnStatus::default(),
+            pending: None,
+            last_updated: Instant::now()
+                .checked_sub(Duration::from_secs(data_refresh_interval))
+                .unwrap(),
+        }
+    }
+}
+
+pub struct Vpn {
+    pub enable: bool,
+    adapter: String,
+    label: Option<String>,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    connect_command: Option<String>,
+    disconnect_command: Option<String>,
+    connected_colour: Option<Colour>,
+    pending_colour: Option<Colour>,
+    disconnected_colour: Option<Colour>,
+    status: VpnStatus,
+    /// Whether the adapter is expected to come up, and since when
+    pending: Option<(bool, Instant)>,
+    last_updated: Instant,
+}
+
+#[derive(Clone, Debug, Default)]
+struct VpnStatus {
+    connected: bool,
+    /// Friendly name of the adapter, if it exists
+    name: Option<String>,
+    address: Option<String>,
+}
+
+impl Vpn {
+    fn output(&mut self) -> VpnStatus {
+        let now = Instant::now();
+
+        // check more often while waiting for a command to take effect
+        let refresh_interval = match self.pending {
+            Some(_) => Duration::from_secs(1),
+            None => Duration::from_secs(self.data_refresh_interval),
+        };
+
+        if now.duration_since(self.last_updated) > refresh_interval {
+            self.status = self.query();
+            self.last_updated = now;
+
+            if let Some((expected, since)) = self.pending {
+                if self.status.connected == expected || since.elapsed() > PENDING_TIMEOUT {
+                    self.pending = None;
+                }
+            }
+        }
+
+        self.status.clone()
+    }
+
+    fn query(&self) -> VpnStatus {
+        // adapters like WireGuard's are removed entirely when the tunnel goes down, so a missing
+        // adapter counts as disconnected
+        let Some(interface) = netdev::get_interfaces().into_iter().find(|interface| {
+            interface.name.to_lowercase() == self.adapter
+                || interface
+                    .friendly_name
+                    .as_ref()
+                    .is_some_and(|name| name.to_lowercase() == self.adapter)
+                || interface
+                    .description
+                    .as_ref()
+                    .is_some_and(|description| description.to_lowercase().contains(&self.adapter))
+        }) else {
+            return VpnStatus::default();
+        };
+
+        VpnStatus {
+            connected: interface.is_up(),
+            address: interface.ipv4.first().map(|ip| ip.addr().to_string()),
+            name: interface.friendly_name,
+        }
+    }
+
+    fn toggle(&mut self) {
+        let (command, expected) = if self.status.connected {
+            (&self.disconnect_command, false)
+        } else {
+            (&self.connect_command, true)
+        };
+
+        let Some(command) = command else {
+            return;
+        };
+
+        match shell_command(command).spawn() {
+            Ok(_) => self.pending = Some((expected, Instant::now())),
+            Err(error) => tracing::error!("could not run '{command}': {error}"),
+        }
+    }
+}
+
+impl BarWidget for Vpn {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        let status = self.output();
+
+        if self.pending.is_some() {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+
+        let (icon, state, colour) = match (self.pending, status.connected) {
+            (Some(_), _) => (
+                egui_phosphor::regular::SHIELD,
+                "Pending",
+                self.pending_colour
+                    .map_or(ctx.style().visuals.warn_fg_color, Color32::from),
+            ),
+            (None, true) => (
+                egui_phosphor::regular::SHIELD_CHECK,
+                "Connected",
+                self.connected_colour
+                    .map_or(ctx.style().visuals.selection.stroke.color, Color32::from),
+            ),
+            (None, false) => (
+                egui_phosphor::regular::SHIELD_SLASH,
+                "Disconnected",
+                self.disconnected_colour
+                    .map_or(ctx.style().visuals.error_fg_color, Color32::from),
+            ),
+        };
+
+        let name = self
+            .label
+            .clone()
+            .or(status.name.clone())
+            .unwrap_or_else(|| self.adapter.clone());
+
+        let mut layout_job = LayoutJob::simple(
+            match self.label_prefix {
+                LabelPrefix::Icon | LabelPrefix::IconAndText => icon.to_string(),
+                LabelPrefix::None | LabelPrefix::Text => String::new(),
+            },
+            config.icon_font_id.clone(),
+            colour,
+            100.0,
+        );
+
+        layout_job.append(
+            &match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => format!("VPN: {name}"),
+                LabelPrefix::None | LabelPrefix::Icon => name.clone(),
+            },
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: colour,
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        let hover_text = match &status.address {
+            Some(address) if status.connected => format!("{name}: {state} ({address})"),
+            _ => format!("{name}: {state}"),
+        };
+
+        config.apply_on_widget(false, ui, |ui| {
+            if SelectableFrame::new(false)
+                .show(ui, |ui| ui.add(Label::new(layout_job).selectable(false)))
+                .on_hover_text(hover_text)
+                .clicked()
+                && self.pending.is_none()
+            {
+                self.toggle();
+            }
+        });
+    }
+}
@@ -0,0 +1,394 @@
+use crate::config::LabelPrefix;
+use crate::render::RenderConfig;
+use crate::widgets::widget::BarWidget;
+use eframe::egui::text::LayoutJob;
+use eframe::egui::Align;
+use eframe::egui::Context;
+use eframe::egui::Label;
+use eframe::egui::TextFormat;
+use eframe::egui::Ui;
+use eframe::egui::WidgetText;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WeatherConfig {
+    /// Enable the Weather widget
+    pub enable: bool,
+    /// Data refresh interval (default: 900 seconds)
+    pub data_refresh_interval: Option<u64>,
+    /// Display label prefix
+    pub label_prefix: Option<LabelPrefix>,
+    /// Weather data provider (default: OpenMeteo)
+    pub provider: Option<WeatherProvider>,
+    /// Location to show the weather for (default: approximate location based on the public IP address)
+    pub location: Option<WeatherLocation>,
+    /// Units of the temperature (default: Metric)
+    pub units: Option<WeatherUnits>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WeatherProvider {
+    /// Open-Meteo (https://open-meteo.com), which does not require an API key
+    #[default]
+    OpenMeteo,
+    /// OpenWeather (https://openweathermap.org), which requires an API key
+    OpenWeather {
+        /// OpenWeather API key
+        api_key: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WeatherLocation {
+    /// Latitude in decimal degrees
+    pub latitude: f64,
+    /// Longitude in decimal degrees
+    pub longitude: f64,
+    /// Name shown when hovering over the widget
+    pub name: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WeatherUnits {
+    /// Degrees Celsius
+    #[default]
+    Metric,
+    /// Degrees Fahrenheit
+    Imperial,
+}
+
+impl From<WeatherConfig> for Weather {
+    fn from(value: WeatherConfig) -> Self {
+        let data_refresh_interval = value.data_refresh_interval.unwrap_or(900);
+
+        Self {
+            enable: value.enable,
+            data_refresh_interval,
+            label_prefix: value.label_prefix.unwrap_or(LabelPrefix::Icon),
+            provider: value.provider.unwrap_or_default(),
+            units: value.units.unwrap_or_default(),
+            location: Arc::new(Mutex::new(value.location)),
+            reading: Arc::new(Mutex::new(None)),
+            running: Arc::new(AtomicBool::new(false)),
+            last_updated: None,
+        }
+    }
+}
+
+pub struct Weather {
+    pub enable: bool,
+    data_refresh_interval: u64,
+    label_prefix: LabelPrefix,
+    provider: WeatherProvider,
+    units: WeatherUnits,
+    location: Arc<Mutex<Option<WeatherLocation>>>,
+    reading: Arc<Mutex<Option<WeatherReading>>>,
+    running: Arc<AtomicBool>,
+    /// None until the first fetch, which happens as soon as the widget is rendered
+    last_updated: Option<Instant>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum WeatherCondition {
+    Clear,
+    PartlyCloudy,
+    Cloudy,
+    Fog,
+    Drizzle,
+    Rain,
+    Snow,
+    Thunderstorm,
+}
+
+impl WeatherCondition {
+    /// Maps a WMO weather interpretation code, as returned by Open-Meteo
+    fn from_wmo_code(code: u32) -> Self {
+        match code {
+            0 | 1 => Self::Clear,
+            2 => Self::PartlyCloudy,
+            3 => Self::Cloudy,
+            45 | 48 => Self::Fog,
+            51..=57 => Self::Drizzle,
+            71..=77 | 85 | 86 => Self::Snow,
+            95..=99 => Self::Thunderstorm,
+            _ => Self::Rain,
+        }
+    }
+
+    /// Maps an OpenWeather condition id
+    fn from_openweather_id(id: u32) -> Self {
+        match id {
+            200..=299 => Self::Thunderstorm,
+            300..=399 => Self::Drizzle,
+            600..=699 => Self::Snow,
+            700..=799 => Self::Fog,
+            800 => Self::Clear,
+            801 | 802 => Self::PartlyCloudy,
+            803..=899 => Self::Cloudy,
+            _ => Self::Rain,
+        }
+    }
+
+    fn icon(self, is_day: bool) -> &'static str {
+        match self {
+            Self::Clear if is_day => egui_phosphor::regular::SUN,
+            Self::Clear => egui_phosphor::regular::MOON,
+            Self::PartlyCloudy if is_day => egui_phosphor::regular::CLOUD_SUN,
+            Self::PartlyCloudy => egui_phosphor::regular::CLOUD_MOON,
+            Self::Cloudy => egui_phosphor::regular::CLOUD,
+            Self::Fog => egui_phosphor::regular::CLOUD_FOG,
+            Self::Drizzle | Self::Rain => egui_phosphor::regular::CLOUD_RAIN,
+            Self::Snow => egui_phosphor::regular::CLOUD_SNOW,
+            Self::Thunderstorm => egui_phosphor::regular::CLOUD_LIGHTNING,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Clear => "Clear",
+            Self::PartlyCloudy => "Partly cloudy",
+            Self::Cloudy => "Cloudy",
+            Self::Fog => "Fog",
+            Self::Drizzle => "Drizzle",
+            Self::Rain => "Rain",
+            Self::Snow => "Snow",
+            Self::Thunderstorm => "Thunderstorm",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct WeatherReading {
+    temperature: f64,
+    condition: WeatherCondition,
+    is_day: bool,
+    location: Option<String>,
+}
+
+impl WeatherProvider {
+    fn fetch(
+        &self,
+        location: &WeatherLocation,
+        units: WeatherUnits,
+    ) -> color_eyre::Result<WeatherReading> {
+        match self {
+            WeatherProvider::OpenMeteo => {
+                #[derive(Deserialize)]
+                struct Response {
+                    current: Current,
+                }
+
+                #[derive(Deserialize)]
+                struct Current {
+                    temperature_2m: f64,
+                    weather_code: u32,
+                    is_day: u8,
+                }
+
+                let url = format!(
+                    "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=temperature_2m,weather_code,is_day&temperature_unit={}",
+                    location.latitude,
+                    location.longitude,
+                    match units {
+                        WeatherUnits::Metric => "celsius",
+                        WeatherUnits::Imperial => "fahrenheit",
+                    }
+                );
+
+                let response: Response = serde_json::from_str(&get(&url)?)?;
+
+                Ok(WeatherReading {
+                    temperature: response.current.temperature_2m,
+                    condition: WeatherCondition::from_wmo_code(response.current.weather_code),
+                    is_day: response.current.is_day == 1,
+                    location: location.name.clone(),
+                })
+            }
+            WeatherProvider::OpenWeather { api_key } => {
+                #[derive(Deserialize)]
+                struct Response {
+                    weather: Vec<Condition>,
+                    main: Main,
+                    name: Option<String>,
+                }
+
+                #[derive(Deserialize)]
+                struct Condition {
+                    id: u32,
+                    icon: String,
+                }
+
+                #[derive(Deserialize)]
+                struct Main {
+                    temp: f64,
+                }
+
+                let url = format!(
+                    "https://api.openweathermap.org/data/2.5/weather?lat={}&lon={}&units={}&appid={}",
+                    location.latitude,
+                    location.longitude,
+                    match units {
+                        WeatherUnits::Metric => "metric",
+                        WeatherUnits::Imperial => "imperial",
+                    },
+                    api_key
+                );
+
+                let response: Response = serde_json::from_str(&get(&url)?)?;
+                let condition = response
+                    .weather
+                    .first()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("no weather conditions returned"))?;
+
+                Ok(WeatherReading {
+                    temperature: response.main.temp,
+                    condition: WeatherCondition::from_openweather_id(condition.id),
+                    // OpenWeather icon names end with "d" during the day and "n" at night
+                    is_day: !condition.icon.ends_with('n'),
+                    location: location.name.clone().or(response.name),
+                })
+            }
+        }
+    }
+}
+
+fn get(url: &str) -> color_eyre::Result<String> {
+    Ok(reqwest::blocking::Client::new()
+        .get(url)
+        .header("User-Agent", "komorebi-bar")
+        .send()?
+        .error_for_status()?
+        .text()?)
+}
+
+/// Looks up the approximate location of the public IP address
+fn geolocate() -> color_eyre::Result<WeatherLocation> {
+    #[derive(Deserialize)]
+    struct Response {
+        latitude: f64,
+        longitude: f64,
+        city: Option<String>,
+    }
+
+    let response: Response = serde_json::from_str(&get("https://ipapi.co/json/")?)?;
+
+    Ok(WeatherLocation {
+        latitude: response.latitude,
+        longitude: response.longitude,
+        name: response.city,
+    })
+}
+
+impl Weather {
+    /// Fetches the weather on a background thread so that slow responses don't block the bar
+    fn refresh(&mut self, ctx: &Context) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        self.last_updated = Some(Instant::now());
+
+        let provider = self.provider.clone();
+        let units = self.units;
+        let location = self.location.clone();
+        let reading = self.reading.clone();
+        let running = self.running.clone();
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let configured_location = location.lock().unwrap().clone();
+
+            // the IP-based location is only looked up once and then reused
+            let current_location = match configured_location {
+                Some(location) => Ok(location),
+                None => geolocate().inspect(|geolocated| {
+                    *location.lock().unwrap() = Some(geolocated.clone());
+                }),
+            };
+
+            match current_location.and_then(|location| provider.fetch(&location, units)) {
+                Ok(latest) => *reading.lock().unwrap() = Some(latest),
+                Err(error) => tracing::error!("could not fetch the weather: {error}"),
+            }
+
+            running.store(false, Ordering::SeqCst);
+            ctx.request_repaint();
+        });
+    }
+}
+
+impl BarWidget for Weather {
+    fn render(&mut self, ctx: &Context, ui: &mut Ui, config: &mut RenderConfig) {
+        if !self.enable {
+            return;
+        }
+
+        let interval = Duration::from_secs(self.data_refresh_interval);
+        if self
+            .last_updated
+            .is_none_or(|last_updated| last_updated.elapsed() > interval)
+        {
+            self.refresh(ctx);
+        }
+
+        let Some(reading) = self.reading.lock().unwrap().clone() else {
+            return;
+        };
+
+        let mut layout_job = LayoutJob::simple(
+            match self.label_prefix {
+                LabelPrefix::Icon | LabelPrefix::IconAndText => {
+                    reading.condition.icon(reading.is_day).to_string()
+                }
+                LabelPrefix::None | LabelPrefix::Text => String::new(),
+            },
+            config.icon_font_id.clone(),
+            ctx.style().visuals.selection.stroke.color,
+            100.0,
+        );
+
+        let temperature = format!(
+            "{:.0}{}",
+            reading.temperature,
+            match self.units {
+                WeatherUnits::Metric => "°C",
+                WeatherUnits::Imperial => "°F",
+            }
+        );
+
+        layout_job.append(
+            &match self.label_prefix {
+                LabelPrefix::Text | LabelPrefix::IconAndText => format!("WEATHER: {temperature}"),
+                LabelPrefix::None | LabelPrefix::Icon => temperature,
+            },
+            10.0,
+            TextFormat {
+                font_id: config.text_font_id.clone(),
+                color: ctx.style().visuals.text_color(),
+                valign: Align::Center,
+                ..Default::default()
+            },
+        );
+
+        let hover_text = match &reading.location {
+            Some(location) => format!("{} in {location}", reading.condition.description()),
+            None => reading.condition.description().to_string(),
+        };
+
+        config.apply_on_widget(true, ui, |ui| {
+            ui.add(Label::new(WidgetText::LayoutJob(layout_job)).selectable(false))
+                .on_hover_text(hover_text)
+        });
+    }
+}
@@ -5,22 +5,44 @@ use crate::widgets::cpu::Cpu;
 use crate::widgets::cpu::CpuConfig;
 use crate::widgets::date::Date;
 use crate::widgets::date::DateConfig;
+use crate::widgets::exec::Exec;
+use crate::widgets::exec::ExecConfig;
+use crate::widgets::gpu::Gpu;
+use crate::widgets::gpu::GpuConfig;
+use crate::widgets::group::Group;
+use crate::widgets::group::GroupConfig;
 use crate::widgets::keyboard::Keyboard;
 use crate::widgets::keyboard::KeyboardConfig;
 use crate::widgets::komorebi::Komorebi;
 use crate::widgets::komorebi::KomorebiConfig;
+use crate::widgets::launcher::Launcher;
+use crate::widgets::launcher::LauncherConfig;
 use crate::widgets::media::Media;
 use crate::widgets::media::MediaConfig;
 use crate::widgets::memory::Memory;
 use crate::widgets::memory::MemoryConfig;
 use crate::widgets::network::Network;
 use crate::widgets::network::NetworkConfig;
+use crate::widgets::notifications::Notifications;
+use crate::widgets::notifications::NotificationsConfig;
+use crate::widgets::pomodoro::Pomodoro;
+use crate::widgets::pomodoro::PomodoroConfig;
+use crate::widgets::start_menu::StartMenu;
+use crate::widgets::start_menu::StartMenuConfig;
 use crate::widgets::storage::Storage;
 use crate::widgets::storage::StorageConfig;
+use crate::widgets::systray::Systray;
+use crate::widgets::systray::SystrayConfig;
 use crate::widgets::time::Time;
 use crate::widgets::time::TimeConfig;
 use crate::widgets::update::Update;
 use crate::widgets::update::UpdateConfig;
+use crate::widgets::volume::Volume;
+use crate::widgets::volume::VolumeConfig;
+use crate::widgets::vpn::Vpn;
+use crate::widgets::vpn::VpnConfig;
+use crate::widgets::weather::Weather;
+use crate::widgets::weather::WeatherConfig;
 use eframe::egui::Context;
 use eframe::egui::Ui;
 use serde::Deserialize;
@@ -36,14 +58,25 @@ pub enum WidgetConfig {
     Battery(BatteryConfig),
     Cpu(CpuConfig),
     Date(DateConfig),
+    Exec(ExecConfig),
+    Gpu(GpuConfig),
+    Group(GroupConfig),
     Keyboard(KeyboardConfig),
     Komorebi(KomorebiConfig),
+    Launcher(LauncherConfig),
     Media(MediaConfig),
     Memory(MemoryConfig),
     Network(NetworkConfig),
+    Notifications(NotificationsConfig),
+    Pomodoro(PomodoroConfig),
+    StartMenu(StartMenuConfig),
     Storage(StorageConfig),
+    Systray(SystrayConfig),
     Time(TimeConfig),
     Update(UpdateConfig),
+    Volume(VolumeConfig),
+    Vpn(VpnConfig),
+    Weather(WeatherConfig),
 }
 
 impl WidgetConfig {
@@ -52,38 +85,99 @@ impl WidgetConfig {
             WidgetConfig::Battery(config) => Box::new(Battery::from(*config)),
             WidgetConfig::Cpu(config) => Box::new(Cpu::from(*config)),
             WidgetConfig::Date(config) => Box::new(Date::from(config.clone())),
+            WidgetConfig::Exec(config) => Box::new(Exec::from(config.clone())),
+            WidgetConfig::Gpu(config) => Box::new(Gpu::from(config.clone())),
+            WidgetConfig::Group(config) => Box::new(Group::from(config.clone())),
             WidgetConfig::Keyboard(config) => Box::new(Keyboard::from(*config)),
             WidgetConfig::Komorebi(config) => Box::new(Komorebi::from(config)),
+            WidgetConfig::Launcher(config) => Box::new(Launcher::from(config.clone())),
             WidgetConfig::Media(config) => Box::new(Media::from(*config)),
             WidgetConfig::Memory(config) => Box::new(Memory::from(*config)),
             WidgetConfig::Network(config) => Box::new(Network::from(*config)),
-            WidgetConfig::Storage(config) => Box::new(Storage::from(*config)),
+            WidgetConfig::Notifications(config) => Box::new(Notifications::from(*config)),
+            WidgetConfig::Pomodoro(config) => Box::new(Pomodoro::from(config.clone())),
+            WidgetConfig::StartMenu(config) => Box::new(StartMenu::from(config.clone())),
+            WidgetConfig::Storage(config) => Box::new(Storage::from(config.clone())),
+            WidgetConfig::Systray(config) => Box::new(Systray::from(config.clone())),
             WidgetConfig::Time(config) => Box::new(Time::from(config.clone())),
             WidgetConfig::Update(config) => Box::new(Update::from(*config)),
+            WidgetConfig::Volume(config) => Box::new(Volume::from(config.clone())),
+            WidgetConfig::Vpn(config) => Box::new(Vpn::from(config.clone())),
+            WidgetConfig::Weather(config) => Box::new(Weather::from(config.clone())),
         }
     }
 
+    /// Fills in the data refresh interval of system widgets which don't set their own; the Update
+    /// and Weather widgets are left alone since they call remote APIs
+    pub fn set_default_data_refresh_interval(&mut self, interval: u64) {
+        let data_refresh_interval = match self {
+            WidgetConfig::Battery(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Cpu(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Exec(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Gpu(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Group(config) => {
+                config
+                    .widgets
+                    .iter_mut()
+                    .for_each(|widget| widget.set_default_data_refresh_interval(interval));
+                return;
+            }
+            WidgetConfig::Keyboard(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Memory(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Network(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Notifications(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Storage(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Volume(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Vpn(config) => &mut config.data_refresh_interval,
+            WidgetConfig::Date(_)
+            | WidgetConfig::Komorebi(_)
+            | WidgetConfig::Launcher(_)
+            | WidgetConfig::Media(_)
+            | WidgetConfig::Pomodoro(_)
+            | WidgetConfig::StartMenu(_)
+            | WidgetConfig::Systray(_)
+            | WidgetConfig::Time(_)
+            | WidgetConfig::Update(_)
+            | WidgetConfig::Weather(_) => return,
+        };
+
+        data_refresh_interval.get_or_insert(interval);
+    }
+
     pub fn enabled(&self) -> bool {
         match self {
             WidgetConfig::Battery(config) => config.enable,
             WidgetConfig::Cpu(config) => config.enable,
             WidgetConfig::Date(config) => config.enable,
+            WidgetConfig::Exec(config) => config.enable,
+            WidgetConfig::Gpu(config) => config.enable,
+            WidgetConfig::Group(config) => config.enable,
             WidgetConfig::Keyboard(config) => config.enable,
             WidgetConfig::Komorebi(config) => {
                 config.workspaces.as_ref().is_some_and(|w| w.enable)
                     || config.layout.as_ref().is_some_and(|w| w.enable)
                     || config.focused_window.as_ref().is_some_and(|w| w.enable)
+                    || config.window_list.as_ref().is_some_and(|w| w.enable)
                     || config
                         .configuration_switcher
                         .as_ref()
                         .is_some_and(|w| w.enable)
+                    || config.status.as_ref().is_some_and(|w| w.enable)
             }
+            WidgetConfig::Launcher(config) => config.enable,
             WidgetConfig::Media(config) => config.enable,
             WidgetConfig::Memory(config) => config.enable,
             WidgetConfig::Network(config) => config.enable,
+            WidgetConfig::Notifications(config) => config.enable,
+            WidgetConfig::Pomodoro(config) => config.enable,
+            WidgetConfig::StartMenu(config) => config.enable,
             WidgetConfig::Storage(config) => config.enable,
+            WidgetConfig::Systray(config) => config.enable,
             WidgetConfig::Time(config) => config.enable,
             WidgetConfig::Update(config) => config.enable,
+            WidgetConfig::Volume(config) => config.enable,
+            WidgetConfig::Vpn(config) => config.enable,
+            WidgetConfig::Weather(config) => config.enable,
         }
     }
 }
@@ -8,6 +8,7 @@ pub use komorebi::border_manager::BorderInfo;
 pub use komorebi::colour::Colour;
 pub use komorebi::colour::Rgb;
 pub use komorebi::config_generation::ApplicationConfiguration;
+pub use komorebi::config_generation::BorderOverflowOffset;
 pub use komorebi::config_generation::IdWithIdentifier;
 pub use komorebi::config_generation::IdWithIdentifierAndComment;
 pub use komorebi::config_generation::MatchingRule;
@@ -17,6 +18,8 @@ pub use komorebi::core::config_generation::ApplicationConfigurationGenerator;
 pub use komorebi::core::resolve_home_path;
 pub use komorebi::core::AnimationStyle;
 pub use komorebi::core::ApplicationIdentifier;
+pub use komorebi::core::Area;
+pub use komorebi::core::AreaSplit;
 pub use komorebi::core::Arrangement;
 pub use komorebi::core::Axis;
 pub use komorebi::core::BorderImplementation;
@@ -29,9 +32,14 @@ pub use komorebi::core::CustomLayout;
 pub use komorebi::core::CycleDirection;
 pub use komorebi::core::DefaultLayout;
 pub use komorebi::core::Direction;
+pub use komorebi::core::DirectionalFocusBehaviour;
+pub use komorebi::core::FloatingZOrder;
 pub use komorebi::core::FocusFollowsMouseImplementation;
 pub use komorebi::core::HidingBehaviour;
 pub use komorebi::core::Layout;
+pub use komorebi::core::LogLevel;
+pub use komorebi::core::LogRotation;
+pub use komorebi::core::MonocleWithNewWindowBehaviour;
 pub use komorebi::core::MoveBehaviour;
 pub use komorebi::core::OperationBehaviour;
 pub use komorebi::core::OperationDirection;
@@ -39,43 +47,70 @@ pub use komorebi::core::PathExt;
 pub use komorebi::core::Rect;
 pub use komorebi::core::Sizing;
 pub use komorebi::core::SocketMessage;
+pub use komorebi::core::SocketResponse;
+pub use komorebi::core::SplitDirection;
 pub use komorebi::core::StackbarLabel;
 pub use komorebi::core::StackbarMode;
 pub use komorebi::core::StateQuery;
 pub use komorebi::core::WindowKind;
+pub use komorebi::core::WorkspaceSaveOption;
+pub use komorebi::event_hooks::EventHook;
+pub use komorebi::event_hooks::HookEvent;
+pub use komorebi::metrics::Metrics;
 pub use komorebi::monitor::Monitor;
 pub use komorebi::monitor_reconciliator::MonitorNotification;
+pub use komorebi::paths::hwnd_json_path;
+pub use komorebi::paths::log_dir;
+pub use komorebi::paths::set_socket_name;
+pub use komorebi::paths::socket_path;
+pub use komorebi::paths::Paths;
 pub use komorebi::ring::Ring;
+#[cfg(feature = "schemars")]
+pub use komorebi::socket_protocol_schema;
 pub use komorebi::win32_display_data;
 pub use komorebi::window::Window;
 pub use komorebi::window_manager_event::WindowManagerEvent;
+pub use komorebi::workspace::SavedWorkspace;
 pub use komorebi::workspace::Workspace;
 pub use komorebi::workspace::WorkspaceGlobals;
 pub use komorebi::workspace::WorkspaceLayer;
+pub use komorebi::workspace::WorkspaceSnapshot;
+pub use komorebi::workspace_preview::PreviewWindow;
+pub use komorebi::workspace_preview::Thumbnail;
+pub use komorebi::workspace_preview::WorkspacePreview;
 pub use komorebi::AnimationsConfig;
 pub use komorebi::AppSpecificConfigurationPath;
 pub use komorebi::AspectRatio;
 pub use komorebi::BorderColours;
 pub use komorebi::CrossBoundaryBehaviour;
+pub use komorebi::FocusNotification;
 pub use komorebi::GlobalState;
 pub use komorebi::KomorebiTheme;
 pub use komorebi::MonitorConfig;
 pub use komorebi::Notification;
 pub use komorebi::NotificationEvent;
+pub use komorebi::PingResponse;
 pub use komorebi::PredefinedAspectRatio;
 pub use komorebi::RuleDebug;
+#[cfg(feature = "schemars")]
+pub use komorebi::SocketProtocolSchema;
+pub use komorebi::SocketSubscriber;
 pub use komorebi::StackbarConfig;
 pub use komorebi::State;
 pub use komorebi::StaticConfig;
 pub use komorebi::SubscribeOptions;
+pub use komorebi::Subscribers;
 pub use komorebi::TabsConfig;
 pub use komorebi::WindowContainerBehaviour;
 pub use komorebi::WindowsApi;
 pub use komorebi::WorkspaceConfig;
+pub use komorebi::SOCKET_PROTOCOL_VERSION;
 
+use komorebi::paths;
 use komorebi::DATA_DIR;
 
 use std::io::BufReader;
+use std::io::ErrorKind;
 use std::io::Read;
 use std::io::Write;
 use std::net::Shutdown;
@@ -83,17 +118,63 @@ use std::time::Duration;
 pub use uds_windows::UnixListener;
 use uds_windows::UnixStream;
 
-const KOMOREBI: &str = "komorebi.sock";
-
 pub fn send_message(message: &SocketMessage) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    let socket = paths::socket_path();
     let mut stream = UnixStream::connect(socket)?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     stream.write_all(serde_json::to_string(message)?.as_bytes())
 }
 
+/// How long [`send_message_and_wait`] waits for komorebi to finish processing a message
+pub const MESSAGE_PROCESSING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Send a message and wait for komorebi to finish processing it
+///
+/// If komorebi was unable to process the message, the [`SocketResponse::Error`] message written
+/// back on the command stream is returned as an error
+pub fn send_message_and_wait(message: &SocketMessage) -> std::io::Result<()> {
+    send_message_and_wait_with_timeout(message, MESSAGE_PROCESSING_TIMEOUT)
+}
+
+/// Send a message and wait up to `timeout` for komorebi to finish processing it, for messages
+/// which can take longer than [`MESSAGE_PROCESSING_TIMEOUT`], such as
+/// [`SocketMessage::WaitForWindow`]
+pub fn send_message_and_wait_with_timeout(
+    message: &SocketMessage,
+    timeout: Duration,
+) -> std::io::Result<()> {
+    let socket = paths::socket_path();
+
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(Duration::from_secs(1)))?;
+    stream.write_all(serde_json::to_string(message)?.as_bytes())?;
+    stream.shutdown(Shutdown::Write)?;
+
+    let mut reader = BufReader::new(stream);
+    let mut response = String::new();
+
+    // Some messages (eg. Stop) terminate komorebi before the connection is closed cleanly, so
+    // failing to read a response is not treated as a failure to process the message, unless
+    // komorebi never got back to us at all
+    if let Err(error) = reader.read_to_string(&mut response) {
+        return match error.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => Err(std::io::Error::new(
+                ErrorKind::TimedOut,
+                format!("komorebi did not finish processing the message within {timeout:?}"),
+            )),
+            _ => Ok(()),
+        };
+    }
+
+    match serde_json::from_str::<SocketResponse>(&response) {
+        Ok(SocketResponse::Error { message }) => Err(std::io::Error::other(message)),
+        Err(_) => Ok(()),
+    }
+}
+
 pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io::Result<()> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    let socket = paths::socket_path();
     let mut stream = UnixStream::connect(socket)?;
     stream.set_write_timeout(Some(Duration::from_secs(1)))?;
     let msgs = messages.into_iter().fold(String::new(), |mut s, m| {
@@ -107,7 +188,7 @@ pub fn send_batch(messages: impl IntoIterator<Item = SocketMessage>) -> std::io:
 }
 
 pub fn send_query(message: &SocketMessage) -> std::io::Result<String> {
-    let socket = DATA_DIR.join(KOMOREBI);
+    let socket = paths::socket_path();
 
     let mut stream = UnixStream::connect(socket)?;
     stream.set_read_timeout(Some(Duration::from_secs(1)))?;
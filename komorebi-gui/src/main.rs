@@ -119,11 +119,21 @@ struct KomorebiGui {
     mouse_follows_focus: bool,
     monitors: Vec<MonitorConfig>,
     workspace_names: HashMap<usize, Vec<String>>,
+    monitor_usr_idx_map: HashMap<usize, usize>,
     debug_hwnd: isize,
     debug_windows: Vec<Window>,
     debug_rule: Option<RuleDebug>,
 }
 
+/// Komorebi translates the monitor indices it receives from the ones used in its configuration,
+/// so monitors are shown and addressed by those
+fn usr_monitor_idx(monitor_usr_idx_map: &HashMap<usize, usize>, monitor_idx: usize) -> usize {
+    monitor_usr_idx_map
+        .iter()
+        .find_map(|(usr_idx, m_idx)| (*m_idx == monitor_idx).then_some(*usr_idx))
+        .unwrap_or(monitor_idx)
+}
+
 fn colour32(colour: Option<Colour>) -> Color32 {
     match colour {
         Some(Colour::Rgb(rgb)) => Color32::from_rgb(rgb.r as u8, rgb.g as u8, rgb.b as u8),
@@ -174,7 +184,9 @@ impl KomorebiGui {
 
         for (monitor_idx, m) in monitors.iter().enumerate() {
             for ws in &m.workspaces {
-                let names = workspace_names.entry(monitor_idx).or_insert_with(Vec::new);
+                let names = workspace_names
+                    .entry(usr_monitor_idx(&state.monitor_usr_idx_map, monitor_idx))
+                    .or_insert_with(Vec::new);
                 names.push(ws.name.clone());
             }
         }
@@ -204,6 +216,7 @@ impl KomorebiGui {
             mouse_follows_focus: state.mouse_follows_focus,
             monitors,
             workspace_names,
+            monitor_usr_idx_map: state.monitor_usr_idx_map,
             debug_hwnd: 0,
             debug_windows,
             stackbar_config,
@@ -575,6 +588,7 @@ impl eframe::App for KomorebiGui {
                 });
 
                 for (monitor_idx, monitor) in self.monitors.iter_mut().enumerate() {
+                    let monitor_idx = usr_monitor_idx(&self.monitor_usr_idx_map, monitor_idx);
                     ui.collapsing(
                         format!(
                             "Monitor {monitor_idx} ({}x{})",
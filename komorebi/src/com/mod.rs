@@ -10,12 +10,18 @@ use interfaces::IServiceProvider;
 
 use std::ffi::c_void;
 
+use color_eyre::eyre::bail;
+use color_eyre::Result;
+use windows::core::GUID;
+use windows::Win32::Foundation::E_ACCESSDENIED;
 use windows::Win32::Foundation::HWND;
 use windows::Win32::System::Com::CoCreateInstance;
 use windows::Win32::System::Com::CoInitializeEx;
 use windows::Win32::System::Com::CoUninitialize;
 use windows::Win32::System::Com::CLSCTX_ALL;
 use windows::Win32::System::Com::COINIT_MULTITHREADED;
+use windows::Win32::UI::Shell::IVirtualDesktopManager;
+use windows::Win32::UI::Shell::VirtualDesktopManager;
 use windows_core::Interface;
 
 struct ComInit();
@@ -66,13 +72,21 @@ fn get_iapplication_view_collection(provider: &IServiceProvider) -> IApplication
 
 #[no_mangle]
 pub extern "C" fn SetCloak(hwnd: HWND, cloak_type: u32, flags: i32) {
+    if let Err(error) = set_cloak(hwnd, cloak_type, flags) {
+        tracing::error!("{error}");
+    }
+}
+
+/// Changes the cloaking status of a window through its application view, which unlike
+/// DwmSetWindowAttribute also works for windows of other processes
+pub fn set_cloak(hwnd: HWND, cloak_type: u32, flags: i32) -> Result<()> {
     COM_INIT.with(|_| {
         let provider = get_iservice_provider();
         let view_collection = get_iapplication_view_collection(&provider);
         let mut view = None;
         unsafe {
             if view_collection.get_view_for_hwnd(hwnd, &mut view).is_err() {
-                tracing::error!(
+                bail!(
                     "could not get view for hwnd {} due to os error: {}",
                     hwnd.0 as isize,
                     std::io::Error::last_os_error()
@@ -80,21 +94,78 @@ pub extern "C" fn SetCloak(hwnd: HWND, cloak_type: u32, flags: i32) {
             }
         };
 
-        view.map_or_else(
-            || {
-                tracing::error!("no view was found for {}", hwnd.0 as isize);
-            },
-            |view| {
-                unsafe {
-                    if view.set_cloak(cloak_type, flags).is_err() {
-                        tracing::error!(
-                            "could not change the cloaking status for hwnd {} due to os error: {}",
-                            hwnd.0 as isize,
-                            std::io::Error::last_os_error()
-                        );
-                    }
-                };
-            },
-        );
-    });
+        let Some(view) = view else {
+            bail!("no view was found for {}", hwnd.0 as isize);
+        };
+
+        unsafe {
+            if view.set_cloak(cloak_type, flags).is_err() {
+                bail!(
+                    "could not change the cloaking status for hwnd {} due to os error: {}",
+                    hwnd.0 as isize,
+                    std::io::Error::last_os_error()
+                );
+            }
+        };
+
+        Ok(())
+    })
+}
+
+/// Moves a window to the virtual desktop with the given id, as stored in the registry, returning
+/// false if it could not be moved because it belongs to another process, which Windows does not
+/// allow through the public virtual desktop API
+pub fn move_window_to_virtual_desktop(hwnd: isize, desktop_id: &[u8]) -> Result<bool> {
+    let Ok(bytes) = <[u8; 16]>::try_from(desktop_id) else {
+        bail!("{:?} is not a valid virtual desktop id", desktop_id);
+    };
+
+    let guid = GUID::from_values(
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        [
+            bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15],
+        ],
+    );
+
+    COM_INIT.with(|_| unsafe {
+        let manager: IVirtualDesktopManager =
+            CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL)?;
+        match manager.MoveWindowToDesktop(HWND(hwnd as *mut c_void), &guid) {
+            Ok(()) => Ok(true),
+            Err(error) if error.code() == E_ACCESSDENIED => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    })
+}
+
+/// Whether a window is on the virtual desktop currently being displayed, which is also true for
+/// windows which have been pinned to every virtual desktop
+pub fn is_window_on_current_virtual_desktop(hwnd: isize) -> Result<bool> {
+    COM_INIT.with(|_| unsafe {
+        let manager: IVirtualDesktopManager =
+            CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL)?;
+        Ok(manager
+            .IsWindowOnCurrentVirtualDesktop(HWND(hwnd as *mut c_void))?
+            .as_bool())
+    })
+}
+
+/// The id of the virtual desktop which a window is on, in the same format as it is stored in the
+/// registry
+pub fn window_desktop_id(hwnd: isize) -> Result<Vec<u8>> {
+    let guid = COM_INIT.with(|_| unsafe {
+        let manager: IVirtualDesktopManager =
+            CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL)?;
+        manager.GetWindowDesktopId(HWND(hwnd as *mut c_void))
+    })?;
+
+    let mut bytes = Vec::with_capacity(16);
+    bytes.extend_from_slice(&guid.data1.to_le_bytes());
+    bytes.extend_from_slice(&guid.data2.to_le_bytes());
+    bytes.extend_from_slice(&guid.data3.to_le_bytes());
+    bytes.extend_from_slice(&guid.data4);
+
+    Ok(bytes)
 }
@@ -1,19 +1,28 @@
 use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
 
+use getset::CopyGetters;
 use getset::Getters;
+use getset::Setters;
 use nanoid::nanoid;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::core::SplitDirection;
 use crate::ring::Ring;
 use crate::window::Window;
+use crate::MAX_CONTAINER_WINDOWS;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters, CopyGetters, Setters)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Container {
     #[getset(get = "pub")]
     id: String,
     windows: Ring<Window>,
+    /// Overrides the direction in which the BSP layout splits this container's zone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub", set = "pub")]
+    split_direction: Option<SplitDirection>,
 }
 
 impl_ring_elements!(Container, Window);
@@ -23,6 +32,7 @@ impl Default for Container {
         Self {
             id: nanoid!(),
             windows: Ring::default(),
+            split_direction: None,
         }
     }
 }
@@ -91,6 +101,12 @@ impl Container {
         None
     }
 
+    /// Whether the container already holds as many windows as `max_container_windows` allows
+    pub fn is_full(&self) -> bool {
+        let max_windows = MAX_CONTAINER_WINDOWS.load(Ordering::SeqCst);
+        max_windows > 0 && self.windows().len() >= max_windows
+    }
+
     pub fn contains_window(&self, hwnd: isize) -> bool {
         for window in self.windows() {
             if window.hwnd == hwnd {
@@ -21,6 +21,7 @@ pub trait Arrangement {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        split_directions: &[Option<SplitDirection>],
     ) -> Vec<Rect>;
 }
 
@@ -33,6 +34,7 @@ impl Arrangement for DefaultLayout {
         container_padding: Option<i32>,
         layout_flip: Option<Axis>,
         resize_dimensions: &[Option<Rect>],
+        split_directions: &[Option<SplitDirection>],
     ) -> Vec<Rect> {
         let len = usize::from(len);
         let mut dimensions = match self {
@@ -42,6 +44,7 @@ impl Arrangement for DefaultLayout {
                 area,
                 layout_flip,
                 calculate_resize_adjustments(resize_dimensions),
+                split_directions,
             ),
             Self::Columns => {
                 let mut layouts = columns(area, len);
@@ -487,11 +490,14 @@ impl Arrangement for CustomLayout {
         container_padding: Option<i32>,
         _layout_flip: Option<Axis>,
         _resize_dimensions: &[Option<Rect>],
+        _split_directions: &[Option<SplitDirection>],
     ) -> Vec<Rect> {
         let mut dimensions = vec![];
         let container_count = len.get();
 
-        if container_count < self.len() {
+        if self.iter().any(|column| matches!(column, Column::Areas(_))) {
+            dimensions = calculate_with_areas(self, area, container_count);
+        } else if container_count < self.len() {
             let mut layouts = columns(area, container_count);
             dimensions.append(&mut layouts);
         } else {
@@ -557,6 +563,10 @@ impl Arrangement for CustomLayout {
                         Column::Primary(None) | Column::Secondary(None) => {
                             dimensions.push(column_area);
                         }
+                        Column::Areas(areas) => {
+                            let mut rects = areas.rects(&column_area, areas.capacity());
+                            dimensions.append(&mut rects);
+                        }
                         Column::Secondary(Some(split)) => match split {
                             ColumnSplitWithCapacity::Horizontal(capacity) => {
                                 let mut rows = rows(&column_area, *capacity);
@@ -602,6 +612,85 @@ impl Arrangement for CustomLayout {
     }
 }
 
+/// Lays out a custom layout with `Areas` columns, where every column with a width percentage takes
+/// up that share of the work area and the remaining space is shared equally by the other columns.
+/// Columns are filled with containers in order and those left without any are not laid out.
+fn calculate_with_areas(layout: &CustomLayout, area: &Rect, container_count: usize) -> Vec<Rect> {
+    let count_map = layout.column_container_counts();
+
+    let mut remaining = container_count;
+    let mut active = vec![];
+    for (idx, column) in layout.iter().enumerate() {
+        let count = match column {
+            Column::Tertiary(_) => remaining,
+            _ => remaining.min(count_map.get(&idx).copied().unwrap_or_default()),
+        };
+
+        if count > 0 {
+            remaining -= count;
+            active.push((column, count));
+        }
+    }
+
+    let fixed: f32 = active
+        .iter()
+        .filter_map(|(column, _)| column.width_percentage())
+        .sum();
+    let flexible = active
+        .iter()
+        .filter(|(column, _)| column.width_percentage().is_none())
+        .count();
+
+    // if the fixed columns leave no space, the others get the share of an equal split instead
+    #[allow(clippy::cast_precision_loss)]
+    let flexible_percentage = if fixed < 100.0 && flexible > 0 {
+        (100.0 - fixed) / flexible as f32
+    } else {
+        100.0 / active.len() as f32
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let total = fixed + flexible_percentage * flexible as f32;
+
+    let mut dimensions = vec![];
+    let mut left = area.left;
+
+    for (i, (column, count)) in active.iter().enumerate() {
+        let percentage = column.width_percentage().unwrap_or(flexible_percentage);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+        let right = if i == active.len() - 1 {
+            area.left + area.right - left
+        } else {
+            (area.right as f32 * percentage / total).round() as i32
+        };
+
+        let column_area = Rect {
+            left,
+            top: area.top,
+            right,
+            bottom: area.bottom,
+        };
+
+        left += right;
+
+        match column {
+            Column::Primary(_) | Column::Secondary(None) => dimensions.push(column_area),
+            Column::Secondary(Some(ColumnSplitWithCapacity::Horizontal(_)))
+            | Column::Tertiary(ColumnSplit::Horizontal) => {
+                dimensions.append(&mut rows(&column_area, *count));
+            }
+            Column::Secondary(Some(ColumnSplitWithCapacity::Vertical(_)))
+            | Column::Tertiary(ColumnSplit::Vertical) => {
+                dimensions.append(&mut columns(&column_area, *count));
+            }
+            Column::Areas(areas) => dimensions.append(&mut areas.rects(&column_area, *count)),
+        }
+    }
+
+    dimensions
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Axis {
@@ -610,6 +699,48 @@ pub enum Axis {
     HorizontalAndVertical,
 }
 
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq, Eq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum SplitDirection {
+    /// Split the container's zone so that the next window is placed beside it
+    Horizontal,
+    /// Split the container's zone so that the next window is placed below it
+    Vertical,
+}
+
+impl SplitDirection {
+    /// The direction that the BSP layout splits the zone of the container at `idx` in by default
+    #[must_use]
+    pub const fn default_for_idx(idx: usize) -> Self {
+        if idx % 2 == 0 {
+            Self::Horizontal
+        } else {
+            Self::Vertical
+        }
+    }
+
+    /// The direction that the BSP layout splits the zone of the container at `idx` in, taking
+    /// any per-container overrides into account
+    #[must_use]
+    pub fn for_idx(idx: usize, split_directions: &[Option<Self>]) -> Self {
+        split_directions
+            .get(idx)
+            .copied()
+            .flatten()
+            .unwrap_or_else(|| Self::default_for_idx(idx))
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Horizontal => Self::Vertical,
+            Self::Vertical => Self::Horizontal,
+        }
+    }
+}
+
 #[must_use]
 fn columns(area: &Rect, len: usize) -> Vec<Rect> {
     #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
@@ -758,6 +889,7 @@ fn recursive_fibonacci(
     area: &Rect,
     layout_flip: Option<Axis>,
     resize_adjustments: Vec<Option<Rect>>,
+    split_directions: &[Option<SplitDirection>],
 ) -> Vec<Rect> {
     let mut a = *area;
 
@@ -818,7 +950,7 @@ fn recursive_fibonacci(
             right: resized.right,
             bottom: resized.bottom,
         }]
-    } else if idx % 2 != 0 {
+    } else if SplitDirection::for_idx(idx, split_directions) == SplitDirection::Vertical {
         let mut res = vec![Rect {
             left: resized.left,
             top: main_y,
@@ -836,6 +968,7 @@ fn recursive_fibonacci(
             },
             layout_flip,
             resize_adjustments,
+            split_directions,
         ));
         res
     } else {
@@ -856,6 +989,7 @@ fn recursive_fibonacci(
             },
             layout_flip,
             resize_adjustments,
+            split_directions,
         ));
         res
     }
@@ -74,6 +74,10 @@ pub struct AscApplicationRules {
     /// Rules to identify applications which are slow to send initial event notifications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slow_application: Option<Vec<MatchingRule>>,
+    /// Rules to identify border overflow applications, whose invisible borders are measured every
+    /// time they are positioned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_overflow: Option<Vec<MatchingRule>>,
 }
 
 impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
@@ -91,6 +95,7 @@ impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
                 layered: None,
                 object_name_change: None,
                 slow_application: None,
+                border_overflow: None,
             };
 
             rules.ignore = entry.ignore_identifiers.clone();
@@ -114,7 +119,10 @@ impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
                             rules.manage =
                                 Some(vec![MatchingRule::Simple(entry.identifier.clone())]);
                         }
-                        ApplicationOptions::BorderOverflow => {}
+                        ApplicationOptions::BorderOverflow => {
+                            rules.border_overflow =
+                                Some(vec![MatchingRule::Simple(entry.identifier.clone())]);
+                        }
                     }
                 }
             }
@@ -127,6 +135,7 @@ impl From<Vec<ApplicationConfiguration>> for ApplicationSpecificConfiguration {
                 || rules.layered.is_some()
                 || rules.object_name_change.is_some()
                 || rules.slow_application.is_some()
+                || rules.border_overflow.is_some()
             {
                 map.insert(key, AscApplicationRulesOrSchema::AscApplicationRules(rules));
             }
@@ -6,6 +6,7 @@ use strum::Display;
 use strum::EnumString;
 
 use super::ApplicationIdentifier;
+use super::Rect;
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -36,7 +37,7 @@ impl ApplicationOptions {
                 format!("komorebic.exe manage-rule {kind} \"{id}\"")
             }
             ApplicationOptions::BorderOverflow => {
-                unreachable!("deprecated");
+                format!("komorebic.exe identify-border-overflow-application {kind} \"{id}\"")
             }
         }
     }
@@ -67,6 +68,17 @@ pub struct WorkspaceMatchingRule {
     pub initial_only: bool,
 }
 
+/// An explicit correction for the invisible borders of an application, used in place of the one
+/// which is otherwise detected automatically
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BorderOverflowOffset {
+    pub matching_rule: MatchingRule,
+    /// The size of the invisible border on each edge of the window, which it is grown by when it
+    /// is positioned
+    pub offset: Rect,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct IdWithIdentifier {
@@ -76,7 +88,7 @@ pub struct IdWithIdentifier {
     pub matching_strategy: Option<MatchingStrategy>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Display)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Display, ValueEnum)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum MatchingStrategy {
     Legacy,
@@ -36,7 +36,7 @@ impl CustomLayout {
         let path = path.as_ref();
         let layout: Self = match path.extension() {
             Some(extension) if extension == "yaml" || extension == "yml" => {
-                serde_json::from_reader(BufReader::new(File::open(path)?))?
+                serde_yaml::from_reader(BufReader::new(File::open(path)?))?
             }
             Some(extension) if extension == "json" => {
                 serde_json::from_reader(BufReader::new(File::open(path)?))?
@@ -51,6 +51,48 @@ impl CustomLayout {
         Ok(layout)
     }
 
+    /// Writes the layout to a json or yaml file, depending on the extension of `path`
+    pub fn to_path<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let contents = match path.extension() {
+            Some(extension) if extension == "yaml" || extension == "yml" => {
+                serde_yaml::to_string(self)?
+            }
+            Some(extension) if extension == "json" => serde_json::to_string_pretty(self)?,
+            _ => return Err(anyhow!("custom layouts must be json or yaml files")),
+        };
+
+        std::fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Builds a layout of `Areas` columns which reproduces `rects`, the zones of the containers of
+    /// a workspace within `area`, followed by a tertiary column for any further containers.
+    /// Containers fill the areas column by column, from top to bottom and left to right.
+    pub fn from_rects(area: &Rect, rects: &[Rect]) -> Result<Self> {
+        if rects.is_empty() {
+            bail!("there are no containers to build a layout from");
+        }
+
+        let root = Area::from_rects(rects, area, 100.0)?;
+
+        let mut columns = match root.split {
+            Some(AreaSplit {
+                direction: ColumnSplit::Vertical,
+                areas,
+            }) => areas.into_iter().map(Column::Areas).collect(),
+            split => vec![Column::Areas(Area {
+                percentage: root.percentage,
+                split,
+            })],
+        };
+
+        columns.push(Column::Tertiary(ColumnSplit::Horizontal));
+
+        Ok(Self(columns))
+    }
+
     #[must_use]
     pub fn column_with_idx(&self, idx: usize) -> (usize, Option<&Column>) {
         let column_idx = self.column_for_container_idx(idx);
@@ -58,6 +100,7 @@ impl CustomLayout {
         (column_idx, column)
     }
 
+    /// The index of the primary column, or of the first `Areas` column if there isn't one
     #[must_use]
     pub fn primary_idx(&self) -> Option<usize> {
         for (i, column) in self.iter().enumerate() {
@@ -66,7 +109,8 @@ impl CustomLayout {
             }
         }
 
-        None
+        self.iter()
+            .position(|column| matches!(column, Column::Areas(_)))
     }
 
     #[must_use]
@@ -113,17 +157,26 @@ impl CustomLayout {
 
         let mut primaries = 0;
         let mut tertiaries = 0;
+        let mut areas = 0;
 
         for column in self.iter() {
             match column {
                 Column::Primary(_) => primaries += 1,
                 Column::Tertiary(_) => tertiaries += 1,
                 Column::Secondary(_) => {}
+                Column::Areas(area) => {
+                    if !area.is_valid() {
+                        return false;
+                    }
+
+                    areas += 1;
+                }
             }
         }
 
-        // There must only be one primary and one tertiary column
-        matches!(primaries, 1) && matches!(tertiaries, 1)
+        // There must only be one primary and one tertiary column, though layouts made up of
+        // areas don't need a primary column
+        (matches!(primaries, 1) || (primaries == 0 && areas > 0)) && matches!(tertiaries, 1)
     }
 
     pub(crate) fn column_container_counts(&self) -> HashMap<usize, usize> {
@@ -143,6 +196,9 @@ impl CustomLayout {
                         },
                     );
                 }
+                Column::Areas(area) => {
+                    count_map.insert(idx, area.capacity());
+                }
                 Column::Tertiary(_) => {}
             }
         }
@@ -250,13 +306,228 @@ impl CustomLayout {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "column", content = "configuration")]
 pub enum Column {
     Primary(Option<ColumnWidth>),
     Secondary(Option<ColumnSplitWithCapacity>),
     Tertiary(ColumnSplit),
+    /// A column with a width percentage which can be divided into nested areas, each of which
+    /// holds a single container, as produced by layout editors
+    Areas(Area),
+}
+
+impl Column {
+    /// The percentage of the work area which the column takes up, if it has a fixed width
+    #[must_use]
+    pub fn width_percentage(&self) -> Option<f32> {
+        match self {
+            Self::Primary(Some(ColumnWidth::WidthPercentage(percentage))) => Some(*percentage),
+            Self::Areas(area) => Some(area.percentage),
+            _ => None,
+        }
+    }
+}
+
+/// A zone of a custom layout, sized as a percentage of the width of the work area for an `Areas`
+/// column, or else of the area which it was split from
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Area {
+    pub percentage: f32,
+    /// Nested areas which this area is divided into, otherwise the area holds a container
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split: Option<AreaSplit>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct AreaSplit {
+    /// Horizontal splits stack the nested areas from top to bottom as rows, vertical splits
+    /// place them side by side from left to right
+    pub direction: ColumnSplit,
+    pub areas: Vec<Area>,
+}
+
+/// How far apart, in pixels, the edges of neighbouring zones can be while still being treated as
+/// touching when building a custom layout from them
+const AREA_EDGE_TOLERANCE: i32 = 4;
+
+impl Area {
+    /// The number of containers which the area holds
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.split
+            .as_ref()
+            .map_or(1, |split| split.areas.iter().map(Self::capacity).sum())
+    }
+
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.percentage > 0.0
+            && self.split.as_ref().is_none_or(|split| {
+                !split.areas.is_empty() && split.areas.iter().all(Self::is_valid)
+            })
+    }
+
+    /// The rects of the first `limit` containers held by the area when it covers `area`. Nested
+    /// areas which don't get a container are left out and their space is shared by the others.
+    #[must_use]
+    pub fn rects(&self, area: &Rect, limit: usize) -> Vec<Rect> {
+        let Some(split) = &self.split else {
+            return vec![*area];
+        };
+
+        let mut remaining = limit;
+        let mut used = vec![];
+        for nested in &split.areas {
+            if remaining == 0 {
+                break;
+            }
+
+            let count = remaining.min(nested.capacity());
+            remaining -= count;
+            used.push((nested, count));
+        }
+
+        let total: f32 = used.iter().map(|(nested, _)| nested.percentage).sum();
+        let extent = match split.direction {
+            ColumnSplit::Horizontal => area.bottom,
+            ColumnSplit::Vertical => area.right,
+        };
+
+        let mut offset = 0;
+        let mut rects = vec![];
+
+        for (i, (nested, count)) in used.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+            let size = if i == used.len() - 1 {
+                extent - offset
+            } else {
+                (extent as f32 * nested.percentage / total).round() as i32
+            };
+
+            let nested_area = match split.direction {
+                ColumnSplit::Horizontal => Rect {
+                    left: area.left,
+                    top: area.top + offset,
+                    right: area.right,
+                    bottom: size,
+                },
+                ColumnSplit::Vertical => Rect {
+                    left: area.left + offset,
+                    top: area.top,
+                    right: size,
+                    bottom: area.bottom,
+                },
+            };
+
+            offset += size;
+            rects.append(&mut nested.rects(&nested_area, *count));
+        }
+
+        rects
+    }
+
+    /// Builds the area covering `bounds` out of `rects` by repeatedly dividing them into columns
+    /// or rows, which is possible for every default layout
+    fn from_rects(rects: &[Rect], bounds: &Rect, percentage: f32) -> Result<Self> {
+        if rects.len() == 1 {
+            return Ok(Self {
+                percentage,
+                split: None,
+            });
+        }
+
+        for direction in [ColumnSplit::Vertical, ColumnSplit::Horizontal] {
+            let groups = group_rects(rects, direction);
+            if groups.len() < 2 {
+                continue;
+            }
+
+            let (bounds_start, extent) = match direction {
+                ColumnSplit::Horizontal => (bounds.top, bounds.bottom),
+                ColumnSplit::Vertical => (bounds.left, bounds.right),
+            };
+
+            // each group reaches up to the start of the next one, so that rounding errors and
+            // gaps between zones don't leave space unaccounted for
+            let mut starts = groups
+                .iter()
+                .map(|group| match direction {
+                    ColumnSplit::Horizontal => group[0].top,
+                    ColumnSplit::Vertical => group[0].left,
+                })
+                .collect::<Vec<_>>();
+            starts[0] = bounds_start;
+
+            let mut areas = vec![];
+            for (i, group) in groups.iter().enumerate() {
+                let start = starts[i];
+                let end = starts.get(i + 1).copied().unwrap_or(bounds_start + extent);
+
+                let group_bounds = match direction {
+                    ColumnSplit::Horizontal => Rect {
+                        left: bounds.left,
+                        top: start,
+                        right: bounds.right,
+                        bottom: end - start,
+                    },
+                    ColumnSplit::Vertical => Rect {
+                        left: start,
+                        top: bounds.top,
+                        right: end - start,
+                        bottom: bounds.bottom,
+                    },
+                };
+
+                #[allow(clippy::cast_precision_loss)]
+                let percentage = ((end - start) as f32 * 10_000.0 / extent as f32).round() / 100.0;
+
+                areas.push(Self::from_rects(group, &group_bounds, percentage)?);
+            }
+
+            return Ok(Self {
+                percentage,
+                split: Option::from(AreaSplit { direction, areas }),
+            });
+        }
+
+        bail!("the zones of the layout cannot be divided into columns and rows")
+    }
+}
+
+/// Sorts `rects` into the columns (for vertical splits) or rows (for horizontal splits) which
+/// they can be divided into without any rect crossing from one into another
+fn group_rects(rects: &[Rect], direction: ColumnSplit) -> Vec<Vec<Rect>> {
+    let span = |rect: &Rect| match direction {
+        ColumnSplit::Horizontal => (rect.top, rect.top + rect.bottom),
+        ColumnSplit::Vertical => (rect.left, rect.left + rect.right),
+    };
+
+    let mut sorted = rects.to_vec();
+    sorted.sort_by_key(|rect| span(rect).0);
+
+    let mut groups: Vec<Vec<Rect>> = vec![];
+    let mut group_end = i32::MIN;
+
+    for rect in sorted {
+        let (start, end) = span(&rect);
+
+        match groups.last_mut() {
+            Some(group) if start < group_end - AREA_EDGE_TOLERANCE => {
+                group.push(rect);
+                group_end = group_end.max(end);
+            }
+            _ => {
+                groups.push(vec![rect]);
+                group_end = end;
+            }
+        }
+    }
+
+    groups
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
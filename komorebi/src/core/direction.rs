@@ -4,6 +4,7 @@ use super::custom_layout::ColumnSplitWithCapacity;
 use super::custom_layout::CustomLayout;
 use super::DefaultLayout;
 use super::OperationDirection;
+use super::SplitDirection;
 
 pub trait Direction {
     fn index_in_direction(
@@ -11,6 +12,7 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> Option<usize>;
 
     fn is_valid_direction(
@@ -18,30 +20,35 @@ pub trait Direction {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> bool;
     fn up_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize;
     fn down_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize;
     fn left_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize;
     fn right_index(
         &self,
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize;
 }
 
@@ -51,32 +58,53 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> Option<usize> {
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.left_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        split_directions,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.right_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        split_directions,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.up_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        split_directions,
+                    ))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(Some(op_direction), idx, Some(count)))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.down_index(
+                        Some(op_direction),
+                        idx,
+                        Some(count),
+                        split_directions,
+                    ))
                 } else {
                     None
                 }
@@ -89,6 +117,7 @@ impl Direction for DefaultLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> bool {
         if count < 2 {
             return false;
@@ -96,7 +125,9 @@ impl Direction for DefaultLayout {
 
         match op_direction {
             OperationDirection::Up => match self {
-                Self::BSP => idx != 0 && idx != 1,
+                Self::BSP => {
+                    bsp_split_before(idx, SplitDirection::Vertical, split_directions).is_some()
+                }
                 Self::Columns => false,
                 Self::Rows | Self::HorizontalStack => idx != 0,
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != 1,
@@ -104,7 +135,11 @@ impl Direction for DefaultLayout {
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
             },
             OperationDirection::Down => match self {
-                Self::BSP => idx != count - 1 && idx % 2 != 0,
+                Self::BSP => {
+                    idx != count - 1
+                        && SplitDirection::for_idx(idx, split_directions)
+                            == SplitDirection::Vertical
+                }
                 Self::Columns => false,
                 Self::Rows => idx != count - 1,
                 Self::VerticalStack | Self::RightMainVerticalStack => idx != 0 && idx != count - 1,
@@ -113,7 +148,9 @@ impl Direction for DefaultLayout {
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
             },
             OperationDirection::Left => match self {
-                Self::BSP => idx != 0,
+                Self::BSP => {
+                    bsp_split_before(idx, SplitDirection::Horizontal, split_directions).is_some()
+                }
                 Self::Columns | Self::VerticalStack => idx != 0,
                 Self::RightMainVerticalStack => idx == 0,
                 Self::Rows => false,
@@ -122,7 +159,11 @@ impl Direction for DefaultLayout {
                 Self::Grid => !is_grid_edge(op_direction, idx, count),
             },
             OperationDirection::Right => match self {
-                Self::BSP => idx % 2 == 0 && idx != count - 1,
+                Self::BSP => {
+                    idx != count - 1
+                        && SplitDirection::for_idx(idx, split_directions)
+                            == SplitDirection::Horizontal
+                }
                 Self::Columns => idx != count - 1,
                 Self::Rows => false,
                 Self::VerticalStack => idx == 0,
@@ -142,15 +183,11 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize {
         match self {
-            Self::BSP => {
-                if idx % 2 == 0 {
-                    idx - 1
-                } else {
-                    idx - 2
-                }
-            }
+            Self::BSP => bsp_split_before(idx, SplitDirection::Vertical, split_directions)
+                .unwrap_or_else(|| unreachable!()),
             Self::Columns => unreachable!(),
             Self::Rows
             | Self::VerticalStack
@@ -166,6 +203,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         match self {
             Self::BSP
@@ -184,15 +222,11 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        split_directions: &[Option<SplitDirection>],
     ) -> usize {
         match self {
-            Self::BSP => {
-                if idx % 2 == 0 {
-                    idx - 2
-                } else {
-                    idx - 1
-                }
-            }
+            Self::BSP => bsp_split_before(idx, SplitDirection::Horizontal, split_directions)
+                .unwrap_or_else(|| unreachable!()),
             Self::Columns | Self::HorizontalStack => idx - 1,
             Self::Rows => unreachable!(),
             Self::VerticalStack => 0,
@@ -211,6 +245,7 @@ impl Direction for DefaultLayout {
         op_direction: Option<OperationDirection>,
         idx: usize,
         count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         match self {
             Self::BSP | Self::Columns | Self::HorizontalStack => idx + 1,
@@ -227,6 +262,18 @@ impl Direction for DefaultLayout {
     }
 }
 
+/// The closest container before `idx` in the BSP layout whose zone was split in `split_direction`,
+/// which is the neighbour of the container at `idx` on the side that the split put it on
+fn bsp_split_before(
+    idx: usize,
+    split_direction: SplitDirection,
+    split_directions: &[Option<SplitDirection>],
+) -> Option<usize> {
+    (0..idx)
+        .rev()
+        .find(|i| SplitDirection::for_idx(*i, split_directions) == split_direction)
+}
+
 struct GridItem {
     state: GridItemState,
     row: usize,
@@ -348,36 +395,42 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> Option<usize> {
         if count <= self.len() {
-            return DefaultLayout::Columns.index_in_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.index_in_direction(
+                op_direction,
+                idx,
+                count,
+                split_directions,
+            );
         }
 
         match op_direction {
             OperationDirection::Left => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.left_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.left_index(None, idx, None, split_directions))
                 } else {
                     None
                 }
             }
             OperationDirection::Right => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.right_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.right_index(None, idx, None, split_directions))
                 } else {
                     None
                 }
             }
             OperationDirection::Up => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.up_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.up_index(None, idx, None, split_directions))
                 } else {
                     None
                 }
             }
             OperationDirection::Down => {
-                if self.is_valid_direction(op_direction, idx, count) {
-                    Option::from(self.down_index(None, idx, None))
+                if self.is_valid_direction(op_direction, idx, count, split_directions) {
+                    Option::from(self.down_index(None, idx, None, split_directions))
                 } else {
                     None
                 }
@@ -390,9 +443,15 @@ impl Direction for CustomLayout {
         op_direction: OperationDirection,
         idx: usize,
         count: usize,
+        split_directions: &[Option<SplitDirection>],
     ) -> bool {
         if count <= self.len() {
-            return DefaultLayout::Columns.is_valid_direction(op_direction, idx, count);
+            return DefaultLayout::Columns.is_valid_direction(
+                op_direction,
+                idx,
+                count,
+                split_directions,
+            );
         }
 
         match op_direction {
@@ -436,6 +495,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         idx - 1
     }
@@ -445,6 +505,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         idx + 1
     }
@@ -454,6 +515,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
         if column_idx - 1 == 0 {
@@ -468,6 +530,7 @@ impl Direction for CustomLayout {
         _op_direction: Option<OperationDirection>,
         idx: usize,
         _count: Option<usize>,
+        _split_directions: &[Option<SplitDirection>],
     ) -> usize {
         let column_idx = self.column_for_container_idx(idx);
         self.first_container_idx(column_idx + 1)
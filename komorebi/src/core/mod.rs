@@ -14,10 +14,14 @@ use strum::Display;
 use strum::EnumString;
 
 use crate::animation::prefix::AnimationPrefix;
+use crate::core::config_generation::MatchingStrategy;
 use crate::KomorebiTheme;
 pub use animation::AnimationStyle;
 pub use arrangement::Arrangement;
 pub use arrangement::Axis;
+pub use arrangement::SplitDirection;
+pub use custom_layout::Area;
+pub use custom_layout::AreaSplit;
 pub use custom_layout::Column;
 pub use custom_layout::ColumnSplit;
 pub use custom_layout::ColumnSplitWithCapacity;
@@ -65,17 +69,21 @@ pub enum SocketMessage {
     MoveContainerToLastWorkspace,
     SendContainerToLastWorkspace,
     MoveContainerToMonitorNumber(usize),
+    MoveContainerToMonitorAlias(String),
     CycleMoveContainerToMonitor(CycleDirection),
     MoveContainerToWorkspaceNumber(usize),
     MoveContainerToNamedWorkspace(String),
     CycleMoveContainerToWorkspace(CycleDirection),
     SendContainerToMonitorNumber(usize),
+    SendContainerToMonitorAlias(String),
     CycleSendContainerToMonitor(CycleDirection),
     SendContainerToWorkspaceNumber(usize),
     CycleSendContainerToWorkspace(CycleDirection),
     SendContainerToMonitorWorkspaceNumber(usize, usize),
     MoveContainerToMonitorWorkspaceNumber(usize, usize),
     SendContainerToNamedWorkspace(String),
+    MoveContainerToVirtualDesktopNumber(usize),
+    SendContainerToVirtualDesktopNumber(usize),
     CycleMoveWorkspaceToMonitor(CycleDirection),
     MoveWorkspaceToMonitorNumber(usize),
     SwapWorkspacesToMonitorNumber(usize),
@@ -86,27 +94,41 @@ pub enum SocketMessage {
     PromoteFocus,
     PromoteWindow(OperationDirection),
     EagerFocus(String),
+    FocusWindowHwnd(isize),
+    FocusWindowUnderCursor,
+    CloseWindowHwnd(isize),
     LockMonitorWorkspaceContainer(usize, usize, usize),
     UnlockMonitorWorkspaceContainer(usize, usize, usize),
     ToggleLock,
     ToggleFloat,
+    ToggleWorkspaceFloat,
     ToggleMonocle,
     ToggleMaximize,
     ToggleWindowContainerBehaviour,
     ToggleFloatOverride,
     WindowHidingBehaviour(HidingBehaviour),
+    FloatingWindowZOrder(FloatingZOrder),
     ToggleCrossMonitorMoveBehaviour,
     CrossMonitorMoveBehaviour(MoveBehaviour),
+    MonocleWithNewWindowBehaviour(MonocleWithNewWindowBehaviour),
     UnmanagedWindowOperationBehaviour(OperationBehaviour),
+    DirectionalFocusBehaviour(DirectionalFocusBehaviour),
     // Current Workspace Commands
     ManageFocusedWindow,
     UnmanageFocusedWindow,
+    ManageWindowByHwnd(isize),
+    UnmanageWindowByHwnd(isize),
+    MoveWindowToWorkspace(isize, usize, usize),
     AdjustContainerPadding(Sizing, i32),
     AdjustWorkspacePadding(Sizing, i32),
     ChangeLayout(DefaultLayout),
     CycleLayout(CycleDirection),
     ChangeLayoutCustom(PathBuf),
+    SaveCustomLayout(PathBuf),
     FlipLayout(Axis),
+    ToggleSplitDirection,
+    SplitHorizontal,
+    SplitVertical,
     ToggleWorkspaceWindowContainerBehaviour,
     ToggleWorkspaceFloatOverride,
     // Monitor and Workspace Commands
@@ -119,20 +141,27 @@ pub enum SocketMessage {
     Stop,
     StopIgnoreRestore,
     TogglePause,
+    ToggleSoftStop,
+    Overview,
     Retile,
     RetileWithResizeDimensions,
+    BalanceWorkspace,
     QuickSave,
+    QuickSaveWithOptions(Vec<WorkspaceSaveOption>),
     QuickLoad,
     Save(PathBuf),
+    SaveWithOptions(PathBuf, Vec<WorkspaceSaveOption>),
     Load(PathBuf),
     CycleFocusMonitor(CycleDirection),
     CycleFocusWorkspace(CycleDirection),
     CycleFocusEmptyWorkspace(CycleDirection),
     FocusMonitorNumber(usize),
+    FocusMonitorAlias(String),
     FocusMonitorAtCursor,
     FocusLastWorkspace,
     CloseWorkspace,
     FocusWorkspaceNumber(usize),
+    FocusWorkspaceNumberWithMouse(usize),
     FocusWorkspaceNumbers(usize),
     FocusMonitorWorkspaceNumber(usize, usize),
     FocusNamedWorkspace(String),
@@ -142,6 +171,10 @@ pub enum SocketMessage {
     WorkspacePadding(usize, usize, i32),
     NamedWorkspacePadding(String, i32),
     FocusedWorkspacePadding(i32),
+    MonitorContainerPadding(usize, i32),
+    MonitorWorkspacePadding(usize, i32),
+    DefaultContainerPadding(i32),
+    DefaultWorkspacePadding(i32),
     WorkspaceTiling(usize, usize, bool),
     NamedWorkspaceTiling(String, bool),
     WorkspaceName(usize, usize, String),
@@ -159,7 +192,13 @@ pub enum SocketMessage {
     // Configuration
     ReloadConfiguration,
     ReplaceConfiguration(PathBuf),
+    ApplySpecificConfiguration(PathBuf),
+    DumpRules(PathBuf),
+    LoadRules(PathBuf),
     ReloadStaticConfiguration(PathBuf),
+    ReloadBindings,
+    EnterKeybindingMode(String),
+    ExitKeybindingMode,
     WatchConfiguration(bool),
     CompleteConfiguration,
     AltFocusHack(bool),
@@ -202,35 +241,55 @@ pub enum SocketMessage {
     ClearNamedWorkspaceRules(String),
     ClearAllWorkspaceRules,
     EnforceWorkspaceRules,
+    Exec(String, Option<String>, bool),
+    WaitForWindow(ApplicationIdentifier, String, u64),
     #[serde(alias = "FloatRule")]
     IgnoreRule(ApplicationIdentifier, String),
+    IgnoreRuleWithMatchingStrategy(ApplicationIdentifier, String, MatchingStrategy),
     ManageRule(ApplicationIdentifier, String),
     IdentifyObjectNameChangeApplication(ApplicationIdentifier, String),
     IdentifyTrayApplication(ApplicationIdentifier, String),
+    IdentifyStackApplication(ApplicationIdentifier, String),
     IdentifyLayeredApplication(ApplicationIdentifier, String),
     IdentifyBorderOverflowApplication(ApplicationIdentifier, String),
+    IdentifyBorderOverflowWithOffset(ApplicationIdentifier, String, Rect),
     State,
     GlobalState,
+    Ping,
     VisibleWindows,
     MonitorInformation,
+    PreviewLayout(DefaultLayout),
+    PreviewLayoutCustom(PathBuf),
     Query(StateQuery),
     FocusFollowsMouse(FocusFollowsMouseImplementation, bool),
     ToggleFocusFollowsMouse(FocusFollowsMouseImplementation),
+    FocusFollowsMouseHoverDelay(u64),
+    FocusFollowsMouseExclusionZone(Rect),
+    FocusFollowsMouseIgnoreRule(ApplicationIdentifier, String),
+    ClearFocusFollowsMouseExclusions,
     MouseFollowsFocus(bool),
     ToggleMouseFollowsFocus,
+    VirtualDesktopAwareness(bool),
+    StackBySameExe(bool),
+    MaxContainerWindows(usize),
+    LogLevel(LogLevel),
     RemoveTitleBar(ApplicationIdentifier, String),
     ToggleTitleBars,
+    IdentifyPresentationApplication(ApplicationIdentifier, String),
+    TogglePresentationMode,
     AddSubscriberSocket(String),
     AddSubscriberSocketWithOptions(String, SubscribeOptions),
     RemoveSubscriberSocket(String),
     AddSubscriberPipe(String),
     RemoveSubscriberPipe(String),
+    ListSubscribers,
     ApplicationSpecificConfigurationSchema,
     NotificationSchema,
     SocketSchema,
     StaticConfigSchema,
     GenerateStaticConfig,
     DebugWindow(isize),
+    DebugVisibleWindows,
 }
 
 impl SocketMessage {
@@ -247,11 +306,30 @@ impl FromStr for SocketMessage {
     }
 }
 
+/// A structured payload written back on the command stream when a `SocketMessage` could not be
+/// processed successfully
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SocketResponse {
+    Error { message: String },
+}
+
+impl SocketResponse {
+    pub fn as_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_string(self)?.as_bytes().to_vec())
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SubscribeOptions {
     /// Only emit notifications when the window manager state has changed
     pub filter_state_changes: bool,
+    /// Emit at most this many notifications per second, holding back the most recent notification
+    /// until it can be sent and dropping any older ones which were superseded in the meantime
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_notifications_per_second: Option<u32>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Display, Serialize, Deserialize, ValueEnum)]
@@ -329,6 +407,9 @@ pub enum StateQuery {
     FocusedContainerIndex,
     FocusedWindowIndex,
     FocusedWorkspaceName,
+    Metrics,
+    Paths,
+    WorkspacePreviews,
 }
 
 #[derive(
@@ -398,6 +479,43 @@ pub enum CrossBoundaryBehaviour {
     Monitor,
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MonocleWithNewWindowBehaviour {
+    /// Add new windows to the layout hidden behind the monocle container, which keeps focus
+    #[default]
+    Background,
+    /// Make the container of the new window the monocle container, hiding the previous one behind it
+    Focus,
+}
+
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum DirectionalFocusBehaviour {
+    /// Only consider the windows on the active layer of the workspace
+    #[default]
+    Layer,
+    /// Consider both tiled and floating windows, based on where they are on the screen
+    Geometric,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum WorkspaceSaveOption {
+    /// The layout of the workspace, including custom layouts and layout flips
+    Layout,
+    /// Whether the workspace tiles its windows
+    Tiling,
+    /// The workspace and container padding of the workspace
+    Padding,
+    /// The name of the workspace
+    Name,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum HidingBehaviour {
@@ -405,8 +523,64 @@ pub enum HidingBehaviour {
     Hide,
     /// Use the SW_MINIMIZE flag to hide windows when switching workspaces (has issues with frequent workspace switching)
     Minimize,
-    /// Use the undocumented SetCloak Win32 function to hide windows when switching workspaces
+    /// Cloak windows with DWM when switching workspaces, which avoids minimize animations and taskbar
+    /// flicker, falling back to the undocumented SetCloak Win32 function and then to SW_HIDE for
+    /// windows which can't be cloaked
     Cloak,
+    /// Move windows past the edge of the screen when switching workspaces (windows stay on the taskbar)
+    MoveOffscreen,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum FloatingZOrder {
+    /// Keep floating windows above tiled windows, raising them after every retile
+    AboveTiles,
+    /// Leave the z-order of floating windows alone
+    Normal,
+    /// Keep floating windows below tiled windows, lowering them after every retile
+    BelowTiles,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LogLevel {
+    /// Only log errors
+    Error,
+    /// Log warnings and errors
+    Warn,
+    /// Log informational messages, warnings and errors
+    Info,
+    /// Log debugging messages and everything above
+    Debug,
+    /// Log everything
+    Trace,
+}
+
+impl From<LogLevel> for tracing::level_filters::LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => Self::ERROR,
+            LogLevel::Warn => Self::WARN,
+            LogLevel::Info => Self::INFO,
+            LogLevel::Debug => Self::DEBUG,
+            LogLevel::Trace => Self::TRACE,
+        }
+    }
+}
+
+#[derive(
+    Copy, Clone, Debug, Default, Serialize, Deserialize, Display, EnumString, ValueEnum, PartialEq,
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum LogRotation {
+    /// Start a new log file every hour
+    Hourly,
+    /// Start a new log file every day
+    #[default]
+    Daily,
+    /// Always append to the same log file
+    Never,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Display, EnumString, ValueEnum)]
@@ -8,6 +8,7 @@ use strum::EnumString;
 
 use super::direction::Direction;
 use super::Axis;
+use super::SplitDirection;
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, Display, EnumString, ValueEnum)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -57,7 +58,8 @@ impl OperationDirection {
         layout_flip: Option<Axis>,
         idx: usize,
         len: NonZeroUsize,
+        split_directions: &[Option<SplitDirection>],
     ) -> Option<usize> {
-        layout.index_in_direction(self.flip(layout_flip), idx, len.get())
+        layout.index_in_direction(self.flip(layout_flip), idx, len.get(), split_directions)
     }
 }
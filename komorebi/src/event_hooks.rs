@@ -0,0 +1,111 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::Notification;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::process::Stdio;
+use strum::Display;
+use windows::Win32::System::Threading::CREATE_NO_WINDOW;
+
+/// The events which user commands can be hooked to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Display, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum HookEvent {
+    /// A window started being managed
+    WindowManaged,
+    /// A different workspace was focused, either on the same monitor or on another one
+    WorkspaceFocused,
+    /// A monitor was connected
+    MonitorAdded,
+    /// A monitor was disconnected
+    MonitorRemoved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct EventHook {
+    /// The event which runs the command
+    pub event: HookEvent,
+    /// The command to run with cmd.exe, which receives the notification for the event as JSON on
+    /// stdin, in the same format that subscribers receive it in
+    pub command: String,
+}
+
+lazy_static! {
+    static ref HOOKS: Mutex<Vec<EventHook>> = Mutex::new(vec![]);
+}
+
+pub fn set_hooks(hooks: &[EventHook]) {
+    tracing::info!("loaded {} event hooks", hooks.len());
+    *HOOKS.lock() = hooks.to_vec();
+}
+
+pub fn hooks() -> Vec<EventHook> {
+    HOOKS.lock().clone()
+}
+
+/// Runs every command hooked to `event` in the background, so that slow scripts never hold up
+/// the window manager. The notification is only built if there is at least one such command.
+pub fn run(event: HookEvent, notification: impl FnOnce() -> Notification) {
+    let commands = HOOKS
+        .lock()
+        .iter()
+        .filter(|hook| hook.event == event)
+        .map(|hook| hook.command.clone())
+        .collect::<Vec<_>>();
+
+    if commands.is_empty() {
+        return;
+    }
+
+    let notification = match serde_json::to_string(&notification()) {
+        Ok(notification) => notification,
+        Err(error) => {
+            tracing::error!("could not serialize the notification for {event} hooks: {error}");
+            return;
+        }
+    };
+
+    for command in commands {
+        let notification = notification.clone();
+        std::thread::spawn(move || {
+            if let Err(error) = run_command(&command, &notification) {
+                tracing::error!("could not run {event} hook '{command}': {error}");
+            }
+        });
+    }
+}
+
+fn run_command(command: &str, notification: &str) -> std::io::Result<()> {
+    tracing::info!("running hook '{command}'");
+
+    let mut child = Command::new("cmd.exe")
+        .arg("/C")
+        .raw_arg(command)
+        .creation_flags(CREATE_NO_WINDOW.0)
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    // stdin is closed when this is dropped, so that commands which read all of it can finish
+    if let Some(mut stdin) = child.stdin.take() {
+        match stdin.write_all(notification.as_bytes()) {
+            // commands which don't care about the notification are free to exit without reading it
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => {}
+            result => result?,
+        }
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        tracing::warn!("hook '{command}' exited with {status}");
+    }
+
+    Ok(())
+}
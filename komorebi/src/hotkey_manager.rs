@@ -0,0 +1,791 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::mouse_binding_manager;
+use crate::static_config::KeyBinding;
+use crate::OperationDirection;
+use crate::SocketMessage;
+use crate::StaticConfig;
+use crate::WindowManager;
+use crate::WindowsApi;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::OptionExt;
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU16;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+use windows::Win32::UI::Input::KeyboardAndMouse::RegisterHotKey;
+use windows::Win32::UI::Input::KeyboardAndMouse::SendInput;
+use windows::Win32::UI::Input::KeyboardAndMouse::UnregisterHotKey;
+use windows::Win32::UI::Input::KeyboardAndMouse::HOT_KEY_MODIFIERS;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_0;
+use windows::Win32::UI::Input::KeyboardAndMouse::INPUT_KEYBOARD;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBDINPUT;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYBD_EVENT_FLAGS;
+use windows::Win32::UI::Input::KeyboardAndMouse::KEYEVENTF_KEYUP;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOD_ALT;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOD_CONTROL;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOD_NOREPEAT;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOD_SHIFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::MOD_WIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_BACK;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_CONTROL;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_DELETE;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_DOWN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_END;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_ESCAPE;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_F1;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_HOME;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_INSERT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LCONTROL;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LEFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LMENU;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LSHIFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LWIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_MENU;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_NEXT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_1;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_2;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_3;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_4;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_5;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_6;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_7;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_COMMA;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_MINUS;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_PERIOD;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_OEM_PLUS;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_PRIOR;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RCONTROL;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RETURN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RIGHT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RMENU;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RSHIFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RWIN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_SHIFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_SPACE;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_TAB;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_UP;
+use windows::Win32::UI::WindowsAndMessaging::CallNextHookEx;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SetWindowsHookExW;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::UnhookWindowsHookEx;
+use windows::Win32::UI::WindowsAndMessaging::HC_ACTION;
+use windows::Win32::UI::WindowsAndMessaging::KBDLLHOOKSTRUCT;
+use windows::Win32::UI::WindowsAndMessaging::LLKHF_INJECTED;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::WH_KEYBOARD_LL;
+use windows::Win32::UI::WindowsAndMessaging::WM_KEYDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_KEYUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_SYSKEYDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_SYSKEYUP;
+
+/// An unassigned virtual key which is tapped after a chord involving the Windows key has been
+/// handled, so that releasing the Windows key doesn't open the Start menu
+const MASK_KEY: VIRTUAL_KEY = VIRTUAL_KEY(0xE8);
+
+lazy_static! {
+    static ref BINDINGS: RwLock<HashMap<KeyChord, Vec<SocketMessage>>> =
+        RwLock::new(HashMap::new());
+    static ref MODES: RwLock<HashMap<String, HashMap<KeyChord, Vec<SocketMessage>>>> =
+        RwLock::new(HashMap::new());
+    static ref ACTIVE_MODE: RwLock<Option<String>> = RwLock::new(None);
+    static ref CONFIG_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
+/// The key whose key down event was last swallowed, so that its key up event is swallowed too
+static SWALLOWED_KEY: AtomicU16 = AtomicU16::new(0);
+
+/// Whether Win+Arrow on a tiled window moves it within the layout instead of snapping it natively
+pub static SNAP_INTERCEPTION: AtomicBool = AtomicBool::new(false);
+
+static HOOK: OnceLock<std::thread::JoinHandle<()>> = OnceLock::new();
+
+pub enum Notification {
+    Messages(Vec<SocketMessage>),
+    /// A Win+Arrow chord, along with its arrow key so that it can be replayed if the foreground
+    /// window turns out not to be tiled
+    Snap(OperationDirection, u16),
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub win: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+}
+
+impl Modifiers {
+    /// The modifiers which are currently held down
+    pub fn pressed() -> Self {
+        let is_down = |keys: &[VIRTUAL_KEY]| {
+            keys.iter()
+                .any(|key| unsafe { GetAsyncKeyState(i32::from(key.0)) } < 0)
+        };
+
+        Self {
+            win: is_down(&[VK_LWIN, VK_RWIN]),
+            ctrl: is_down(&[VK_CONTROL, VK_LCONTROL, VK_RCONTROL]),
+            alt: is_down(&[VK_MENU, VK_LMENU, VK_RMENU]),
+            shift: is_down(&[VK_SHIFT, VK_LSHIFT, VK_RSHIFT]),
+        }
+    }
+
+    /// Sets the modifier with the given name, returning false if the name isn't a modifier
+    pub fn set(&mut self, name: &str) -> bool {
+        match name {
+            "win" | "super" => self.win = true,
+            "ctrl" | "control" => self.ctrl = true,
+            "alt" => self.alt = true,
+            "shift" => self.shift = true,
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn hot_key_modifiers(self) -> HOT_KEY_MODIFIERS {
+        let mut modifiers = MOD_NOREPEAT;
+
+        for (held, modifier) in [
+            (self.win, MOD_WIN),
+            (self.ctrl, MOD_CONTROL),
+            (self.alt, MOD_ALT),
+            (self.shift, MOD_SHIFT),
+        ] {
+            if held {
+                modifiers |= modifier;
+            }
+        }
+
+        modifiers
+    }
+}
+
+/// A combination of modifiers and a single key, such as "win+shift+h"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    pub key: u16,
+}
+
+impl FromStr for KeyChord {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut key = None;
+
+        for part in s.split('+').map(|part| part.trim().to_lowercase()) {
+            if modifiers.set(&part) {
+                continue;
+            }
+
+            if key.is_some() {
+                return Err(anyhow!("'{s}' has more than one non-modifier key"));
+            }
+
+            key = Some(virtual_key(&part).ok_or_eyre(format!("unknown key '{part}'"))?);
+        }
+
+        Ok(Self {
+            modifiers,
+            key: key.ok_or_eyre(format!("'{s}' has no non-modifier key"))?,
+        })
+    }
+}
+
+impl Display for KeyChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (held, name) in [
+            (self.modifiers.win, "win"),
+            (self.modifiers.ctrl, "ctrl"),
+            (self.modifiers.alt, "alt"),
+            (self.modifiers.shift, "shift"),
+        ] {
+            if held {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        write!(f, "{:#04x}", self.key)
+    }
+}
+
+/// The virtual key code of a key name, which is either a single letter, digit or punctuation
+/// character, or one of the names of the keys which don't have one
+fn virtual_key(name: &str) -> Option<u16> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some(u16::from(c.to_ascii_uppercase() as u8));
+        }
+    }
+
+    if let Some(n) = name.strip_prefix('f').and_then(|n| n.parse::<u16>().ok()) {
+        return (1..=24).contains(&n).then(|| VK_F1.0 + n - 1);
+    }
+
+    let key = match name {
+        "left" => VK_LEFT,
+        "right" => VK_RIGHT,
+        "up" => VK_UP,
+        "down" => VK_DOWN,
+        "enter" | "return" => VK_RETURN,
+        "space" => VK_SPACE,
+        "tab" => VK_TAB,
+        "escape" | "esc" => VK_ESCAPE,
+        "backspace" => VK_BACK,
+        "delete" | "del" => VK_DELETE,
+        "insert" => VK_INSERT,
+        "home" => VK_HOME,
+        "end" => VK_END,
+        "pageup" => VK_PRIOR,
+        "pagedown" => VK_NEXT,
+        "minus" | "-" => VK_OEM_MINUS,
+        "equal" | "=" => VK_OEM_PLUS,
+        "comma" | "," => VK_OEM_COMMA,
+        "period" | "." => VK_OEM_PERIOD,
+        "semicolon" | ";" => VK_OEM_1,
+        "slash" | "/" => VK_OEM_2,
+        "backtick" | "`" => VK_OEM_3,
+        "openbracket" | "[" => VK_OEM_4,
+        "backslash" | "\\" => VK_OEM_5,
+        "closebracket" | "]" => VK_OEM_6,
+        "quote" | "'" => VK_OEM_7,
+        _ => return None,
+    };
+
+    Some(key.0)
+}
+
+fn is_modifier(key: u16) -> bool {
+    [
+        VK_LWIN,
+        VK_RWIN,
+        VK_CONTROL,
+        VK_LCONTROL,
+        VK_RCONTROL,
+        VK_MENU,
+        VK_LMENU,
+        VK_RMENU,
+        VK_SHIFT,
+        VK_LSHIFT,
+        VK_RSHIFT,
+    ]
+    .iter()
+    .any(|modifier| modifier.0 == key)
+}
+
+/// Replaces the active key bindings, keybinding modes and mouse bindings with those from the static configuration
+/// file at `path`, installing the keyboard hook the first time there are any
+pub fn set_bindings(path: &PathBuf, config: &StaticConfig) {
+    *CONFIG_PATH.lock() = Some(path.clone());
+
+    let chords = parse_bindings(config.keybindings.as_deref().unwrap_or_default(), true);
+    tracing::info!("loaded {} key bindings", chords.len());
+
+    mouse_binding_manager::set_bindings(config.mouse_bindings.as_deref().unwrap_or_default());
+
+    let mut modes = HashMap::new();
+    for (name, bindings) in config.keybinding_modes.iter().flatten() {
+        let mode_chords = parse_mode_bindings(name, bindings);
+        tracing::info!(
+            "loaded {} key bindings for mode '{name}'",
+            mode_chords.len()
+        );
+        modes.insert(name.clone(), mode_chords);
+    }
+
+    let install =
+        !chords.is_empty() || !modes.is_empty() || SNAP_INTERCEPTION.load(Ordering::SeqCst);
+
+    // the active mode may have been removed from the configuration
+    let active_mode = ACTIVE_MODE.read().clone();
+    if active_mode.is_some_and(|mode| !modes.contains_key(&mode)) {
+        exit_mode();
+    }
+
+    *BINDINGS.write() = chords;
+    *MODES.write() = modes;
+
+    if install {
+        install_hook();
+    }
+}
+
+/// Parses key bindings into the chords which trigger them, skipping those which can't be parsed or
+/// which are bound more than once
+fn parse_bindings(
+    bindings: &[KeyBinding],
+    check_registered_elsewhere: bool,
+) -> HashMap<KeyChord, Vec<SocketMessage>> {
+    let mut chords = HashMap::new();
+
+    for binding in bindings {
+        let chord = match KeyChord::from_str(&binding.keys) {
+            Ok(chord) => chord,
+            Err(error) => {
+                tracing::error!("ignoring key binding '{}': {error}", binding.keys);
+                continue;
+            }
+        };
+
+        if chords.contains_key(&chord) {
+            tracing::error!(
+                "ignoring key binding '{}': the chord is already bound earlier in the configuration",
+                binding.keys
+            );
+            continue;
+        }
+
+        if check_registered_elsewhere && is_registered_elsewhere(chord) {
+            tracing::warn!(
+                "key binding '{}' is also registered as a hotkey by another application, which will no longer receive it",
+                binding.keys
+            );
+        }
+
+        chords.insert(chord, binding.messages.clone());
+    }
+
+    chords
+}
+
+/// The chords of a keybinding mode, which are only matched while the mode is active and so don't
+/// conflict with hotkeys registered by other applications, except for Escape which always leaves
+/// the mode
+fn parse_mode_bindings(
+    name: &str,
+    bindings: &[KeyBinding],
+) -> HashMap<KeyChord, Vec<SocketMessage>> {
+    let mut chords = parse_bindings(bindings, false);
+
+    chords.retain(|chord, _| {
+        let is_escape = chord.key == VK_ESCAPE.0;
+        if is_escape {
+            tracing::error!(
+                "ignoring key binding '{chord}' in mode '{name}': escape is reserved for leaving modes"
+            );
+        }
+
+        !is_escape
+    });
+
+    chords
+}
+
+/// Re-reads the key bindings from the static configuration file they were last loaded from
+pub fn reload_bindings() -> Result<()> {
+    let path = CONFIG_PATH
+        .lock()
+        .clone()
+        .ok_or_eyre("key bindings can only be reloaded when komorebi was started with a static configuration file")?;
+
+    let config = StaticConfig::read(&path)?;
+    set_bindings(&path, &config);
+
+    Ok(())
+}
+
+/// The name of the keybinding mode which is currently active, if any
+pub fn active_mode() -> Option<String> {
+    ACTIVE_MODE.read().clone()
+}
+
+pub fn enter_mode(name: &str) -> Result<()> {
+    if !MODES.read().contains_key(name) {
+        return Err(anyhow!("there is no keybinding mode named '{name}'"));
+    }
+
+    *ACTIVE_MODE.write() = Some(name.to_string());
+    tracing::info!("entered keybinding mode '{name}'");
+
+    Ok(())
+}
+
+pub fn exit_mode() {
+    if let Some(name) = ACTIVE_MODE.write().take() {
+        tracing::info!("left keybinding mode '{name}'");
+    }
+}
+
+/// Whether another application has already registered the chord as a global hotkey, which is
+/// checked by briefly trying to register it ourselves
+fn is_registered_elsewhere(chord: KeyChord) -> bool {
+    // the id only has to be unique to this thread while the hotkey is registered
+    let id = 0xBFFF;
+
+    unsafe {
+        if RegisterHotKey(
+            None,
+            id,
+            chord.modifiers.hot_key_modifiers(),
+            u32::from(chord.key),
+        )
+        .is_ok()
+        {
+            let _ = UnregisterHotKey(None, id);
+            false
+        } else {
+            true
+        }
+    }
+}
+
+fn install_hook() {
+    HOOK.get_or_init(|| {
+        std::thread::spawn(move || {
+            let hook =
+                match unsafe { SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook), None, 0) } {
+                    Ok(hook) => hook,
+                    Err(error) => {
+                        tracing::error!("could not install keyboard hook: {error}");
+                        return;
+                    }
+                };
+
+            tracing::info!("keyboard hook installed");
+
+            // low-level hooks are called on the thread which installed them while it is waiting
+            // for messages, so it must never sleep
+            let mut msg: MSG = MSG::default();
+
+            loop {
+                unsafe {
+                    if !GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                        tracing::debug!("keyboard hook thread shutdown");
+                        break;
+                    };
+
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+
+            unsafe {
+                let _ = UnhookWindowsHookEx(hook);
+            }
+        })
+    });
+}
+
+/// Swallows key presses which match a binding and hands their messages off to be processed, since
+/// the hook has to return quickly for the rest of the system to keep receiving input
+extern "system" fn keyboard_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        // SAFETY: lparam points to a KBDLLHOOKSTRUCT for keyboard hooks
+        let event = unsafe { &*(lparam.0 as *const KBDLLHOOKSTRUCT) };
+        let key = event.vkCode as u16;
+        let injected = event.flags.0 & LLKHF_INJECTED.0 != 0;
+
+        match wparam.0 as u32 {
+            WM_KEYDOWN | WM_SYSKEYDOWN if !injected && !is_modifier(key) => {
+                let chord = KeyChord {
+                    modifiers: Modifiers::pressed(),
+                    key,
+                };
+
+                if let Some(messages) = bound_messages(chord) {
+                    if event_tx()
+                        .try_send(Notification::Messages(messages))
+                        .is_err()
+                    {
+                        tracing::warn!("channel is full; dropping notification")
+                    }
+
+                    if chord.modifiers.win {
+                        mask_windows_key();
+                    }
+
+                    SWALLOWED_KEY.store(key, Ordering::SeqCst);
+                    return LRESULT(1);
+                }
+
+                // the hook can't wait for the window manager to check whether the foreground
+                // window is tiled, so the chord is replayed later if it isn't
+                if let Some(direction) = snap_direction(chord) {
+                    if event_tx()
+                        .try_send(Notification::Snap(direction, key))
+                        .is_err()
+                    {
+                        tracing::warn!("channel is full; dropping notification")
+                    }
+
+                    SWALLOWED_KEY.store(key, Ordering::SeqCst);
+                    return LRESULT(1);
+                }
+            }
+            WM_KEYUP | WM_SYSKEYUP if !injected && SWALLOWED_KEY.load(Ordering::SeqCst) == key => {
+                SWALLOWED_KEY.store(0, Ordering::SeqCst);
+                return LRESULT(1);
+            }
+            _ => {}
+        }
+    }
+
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+/// The messages bound to a chord, looking in the active keybinding mode before the regular bindings
+fn bound_messages(chord: KeyChord) -> Option<Vec<SocketMessage>> {
+    // the name is cloned so that only one of the locks is held at a time
+    if let Some(mode) = active_mode() {
+        if chord.key == VK_ESCAPE.0 {
+            return Some(vec![SocketMessage::ExitKeybindingMode]);
+        }
+
+        if let Some(messages) = MODES
+            .read()
+            .get(&mode)
+            .and_then(|chords| chords.get(&chord))
+        {
+            return Some(messages.clone());
+        }
+    }
+
+    BINDINGS.read().get(&chord).cloned()
+}
+
+/// The direction to move the foreground window in for a chord which would otherwise make Windows
+/// snap it to an edge of the screen
+fn snap_direction(chord: KeyChord) -> Option<OperationDirection> {
+    let win_only = Modifiers {
+        win: true,
+        ..Default::default()
+    };
+
+    if !SNAP_INTERCEPTION.load(Ordering::SeqCst) || chord.modifiers != win_only {
+        return None;
+    }
+
+    [
+        (VK_LEFT, OperationDirection::Left),
+        (VK_RIGHT, OperationDirection::Right),
+        (VK_UP, OperationDirection::Up),
+        (VK_DOWN, OperationDirection::Down),
+    ]
+    .into_iter()
+    .find_map(|(key, direction)| (key.0 == chord.key).then_some(direction))
+}
+
+/// Whether the foreground window is tiled on the focused workspace, where snapping it natively
+/// would pull it out of the layout
+fn is_foreground_window_tiled(wm: &WindowManager) -> bool {
+    let Ok(hwnd) = WindowsApi::foreground_window() else {
+        return false;
+    };
+
+    wm.focused_workspace()
+        .is_ok_and(|workspace| *workspace.tile() && workspace.container_for_window(hwnd).is_some())
+}
+
+/// Taps an unassigned key so that the Windows key isn't seen as having been pressed on its own
+pub fn mask_windows_key() {
+    tap_key(MASK_KEY);
+}
+
+/// Replays a swallowed Win+Arrow chord so that Windows snaps the window natively, pressing the
+/// Windows key again if it was released while the window manager was checking the foreground window
+fn replay_snap(key: VIRTUAL_KEY) {
+    if Modifiers::pressed().win {
+        tap_key(key);
+    } else {
+        send_keys(&[
+            (VK_LWIN, KEYBD_EVENT_FLAGS(0)),
+            (key, KEYBD_EVENT_FLAGS(0)),
+            (key, KEYEVENTF_KEYUP),
+            (VK_LWIN, KEYEVENTF_KEYUP),
+        ]);
+    }
+}
+
+/// Sends a key down and key up event for a key
+fn tap_key(key: VIRTUAL_KEY) {
+    send_keys(&[(key, KEYBD_EVENT_FLAGS(0)), (key, KEYEVENTF_KEYUP)]);
+}
+
+/// Sends key events as a single batch of input, which the keyboard hook lets through since they
+/// are injected
+fn send_keys(keys: &[(VIRTUAL_KEY, KEYBD_EVENT_FLAGS)]) {
+    let inputs = keys
+        .iter()
+        .map(|(key, flags)| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: *key,
+                    dwFlags: *flags,
+                    ..Default::default()
+                },
+            },
+        })
+        .collect::<Vec<_>>();
+
+    unsafe {
+        SendInput(&inputs, std::mem::size_of::<INPUT>() as i32);
+    }
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    for notification in receiver {
+        let mut wm = wm.lock();
+
+        let messages = match notification {
+            Notification::Messages(messages) => messages,
+            Notification::Snap(direction, key) => {
+                if wm.is_paused || !is_foreground_window_tiled(&wm) {
+                    replay_snap(VIRTUAL_KEY(key));
+                    continue;
+                }
+
+                mask_windows_key();
+                vec![SocketMessage::MoveWindow(direction)]
+            }
+        };
+
+        for message in messages {
+            // the same messages are accepted while paused as on the command socket, as well as
+            // those which change the keybinding mode so that Escape can always leave it
+            if wm.is_paused
+                && !matches!(
+                    message,
+                    SocketMessage::TogglePause
+                        | SocketMessage::ToggleSoftStop
+                        | SocketMessage::State
+                        | SocketMessage::GlobalState
+                        | SocketMessage::Ping
+                        | SocketMessage::Stop
+                        | SocketMessage::EnterKeybindingMode(_)
+                        | SocketMessage::ExitKeybindingMode
+                )
+            {
+                tracing::trace!("ignoring while paused");
+                continue;
+            }
+
+            if let Err(error) = wm.process_command(message.clone(), std::io::sink()) {
+                tracing::error!("could not process bound message {message}: {error}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_chord() {
+        let chord = KeyChord::from_str("Win + Shift + h").unwrap();
+
+        assert_eq!(
+            chord.modifiers,
+            Modifiers {
+                win: true,
+                shift: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(chord.key, u16::from(b'H'));
+
+        assert_eq!(KeyChord::from_str("alt+f12").unwrap().key, VK_F1.0 + 11);
+        assert_eq!(KeyChord::from_str("ctrl+[").unwrap().key, VK_OEM_4.0);
+        assert_eq!(KeyChord::from_str("alt+left").unwrap().key, VK_LEFT.0);
+    }
+
+    #[test]
+    fn test_parse_invalid_key_chord() {
+        assert!(KeyChord::from_str("win+shift").is_err());
+        assert!(KeyChord::from_str("win+h+j").is_err());
+        assert!(KeyChord::from_str("win+f25").is_err());
+        assert!(KeyChord::from_str("win+nope").is_err());
+    }
+
+    #[test]
+    fn test_mode_chords_reserve_escape() {
+        let binding = |keys: &str| KeyBinding {
+            keys: keys.to_string(),
+            messages: vec![SocketMessage::Retile],
+        };
+
+        let chords =
+            parse_mode_bindings("resize", &[binding("h"), binding("escape"), binding("h")]);
+
+        assert_eq!(chords.len(), 1);
+        assert!(chords.contains_key(&KeyChord::from_str("h").unwrap()));
+    }
+
+    #[test]
+    fn test_snap_direction() {
+        SNAP_INTERCEPTION.store(true, Ordering::SeqCst);
+
+        let direction = |keys: &str| snap_direction(KeyChord::from_str(keys).unwrap());
+
+        assert!(matches!(
+            direction("win+left"),
+            Some(OperationDirection::Left)
+        ));
+        assert!(matches!(
+            direction("win+down"),
+            Some(OperationDirection::Down)
+        ));
+        assert!(direction("win+shift+left").is_none());
+        assert!(direction("alt+left").is_none());
+        assert!(direction("win+h").is_none());
+    }
+}
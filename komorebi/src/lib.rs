@@ -8,20 +8,31 @@ pub mod ring;
 pub mod colour;
 pub mod container;
 pub mod core;
+pub mod event_hooks;
 pub mod focus_manager;
+pub mod hotkey_manager;
 pub mod locked_deque;
+pub mod log_appender;
+pub mod metrics;
 pub mod monitor;
 pub mod monitor_reconciliator;
+pub mod mouse_binding_manager;
+pub mod overview;
+pub mod paths;
 pub mod process_command;
 pub mod process_event;
 pub mod process_movement;
 pub mod reaper;
 pub mod set_window_position;
 pub mod stackbar_manager;
+pub mod state_snapshot;
 pub mod static_config;
 pub mod styles;
+pub mod subscription_throttle;
 pub mod theme_manager;
 pub mod transparency_manager;
+pub mod tray_manager;
+pub mod watchdog;
 pub mod window;
 pub mod window_manager;
 pub mod window_manager_event;
@@ -30,6 +41,7 @@ pub mod windows_callbacks;
 pub mod winevent;
 pub mod winevent_listener;
 pub mod workspace;
+pub mod workspace_preview;
 pub mod workspace_reconciliator;
 
 use lazy_static::lazy_static;
@@ -45,8 +57,12 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU32;
 use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
 
 pub use colour::*;
 pub use core::*;
@@ -60,6 +76,7 @@ pub use window_manager_event::*;
 pub use windows_api::WindowsApi;
 pub use windows_api::*;
 
+use crate::core::config_generation::BorderOverflowOffset;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
@@ -71,6 +88,9 @@ use parking_lot::RwLock;
 use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
+use tracing_subscriber::reload;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Registry;
 use uds_windows::UnixStream;
 use which::which;
 use winreg::enums::HKEY_CURRENT_USER;
@@ -78,6 +98,10 @@ use winreg::RegKey;
 
 lazy_static! {
     static ref HIDDEN_HWNDS: Arc<Mutex<Vec<isize>>> = Arc::new(Mutex::new(vec![]));
+    static ref OFFSCREEN_WINDOW_RECTS: Arc<Mutex<HashMap<isize, Rect>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref CLOAKED_HWNDS: Arc<Mutex<HashMap<isize, CloakMethod>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     static ref LAYERED_WHITELIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![
         MatchingRule::Simple(IdWithIdentifier {
             kind: ApplicationIdentifier::Exe,
@@ -132,6 +156,13 @@ lazy_static! {
     ]));
     static ref OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST: Arc<Mutex<Vec<Regex>>> = Arc::new(Mutex::new(Vec::new()));
     static ref TRANSPARENCY_BLACKLIST: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref BORDER_OVERFLOW_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref BORDER_OVERFLOW_OFFSETS: Arc<Mutex<Vec<BorderOverflowOffset>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref FRAME_COMPENSATIONS: Arc<Mutex<HashMap<isize, (u32, FrameCompensation)>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref MINIMUM_SIZES: Arc<Mutex<HashMap<isize, Option<(i32, i32)>>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref RUNTIME_RULES: Arc<Mutex<Rules>> = Arc::new(Mutex::new(Rules::default()));
     static ref MONITOR_INDEX_PREFERENCES: Arc<Mutex<HashMap<usize, Rect>>> =
         Arc::new(Mutex::new(HashMap::new()));
     static ref DISPLAY_INDEX_PREFERENCES: Arc<RwLock<HashMap<usize, String>>> =
@@ -161,6 +192,12 @@ lazy_static! {
         })
     ]));
     static ref FLOATING_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    static ref TRANSIENT_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(Vec::new()));
+    // (exe, class, title) of windows which were destroyed shortly after being shown, and when
+    static ref SHORT_LIVED_WINDOWS: Arc<Mutex<HashMap<(String, String, String), Instant>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    static ref RECENTLY_SHOWN_WINDOWS: Arc<Mutex<HashMap<isize, (Instant, (String, String, String))>>> =
+        Arc::new(Mutex::new(HashMap::new()));
     static ref PERMAIGNORE_CLASSES: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(vec![
         "Chrome_RenderWidgetHostHWND".to_string(),
     ]));
@@ -187,6 +224,8 @@ lazy_static! {
         Arc::new(Mutex::new(HashMap::new()));
     static ref HIDING_BEHAVIOUR: Arc<Mutex<HidingBehaviour>> =
         Arc::new(Mutex::new(HidingBehaviour::Cloak));
+    static ref FLOATING_WINDOW_Z_ORDER: Arc<Mutex<FloatingZOrder>> =
+        Arc::new(Mutex::new(FloatingZOrder::Normal));
     pub static ref HOME_DIR: PathBuf = {
         std::env::var("KOMOREBI_CONFIG_HOME").map_or_else(|_| dirs::home_dir().expect("there is no home directory"), |home_path| {
             let home = PathBuf::from(&home_path);
@@ -200,6 +239,7 @@ lazy_static! {
             }
         })
     };
+    pub static ref STARTED_AT: Instant = Instant::now();
     pub static ref DATA_DIR: PathBuf = dirs::data_local_dir().expect("there is no local data directory").join("komorebi");
     pub static ref AHK_EXE: String = {
         let mut ahk: String = String::from("autohotkey.exe");
@@ -222,6 +262,10 @@ lazy_static! {
     // Use app-specific titlebar removal options where possible
     // eg. Windows Terminal, IntelliJ IDEA, Firefox
     static ref NO_TITLEBAR: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
+    static ref STACK_APPLICATION_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
+    // Windows which are lowered instead of hidden in presentation mode, so that screen sharing
+    // streams of them don't freeze
+    static ref PRESENTATION_APPLICATIONS: Arc<Mutex<Vec<MatchingRule>>> = Arc::new(Mutex::new(vec![]));
 
     static ref WINDOWS_BY_BAR_HWNDS: Arc<Mutex<HashMap<isize, VecDeque<isize>>>> =
         Arc::new(Mutex::new(HashMap::new()));
@@ -238,8 +282,36 @@ pub static SESSION_ID: AtomicU32 = AtomicU32::new(0);
 
 pub static REMOVE_TITLEBARS: AtomicBool = AtomicBool::new(false);
 
+/// Whether workspaces without a name of their own are named after the application with the most
+/// windows on them in the state sent to subscribers
+pub static AUTO_NAME_WORKSPACES: AtomicBool = AtomicBool::new(false);
+
+/// Whether the windows of presentation applications are lowered to the bottom of the Z order
+/// instead of being hidden when their workspace is not focused
+pub static PRESENTATION_MODE: AtomicBool = AtomicBool::new(false);
+
+pub static STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS: AtomicBool = AtomicBool::new(false);
+pub static STACK_BY_SAME_EXE: AtomicBool = AtomicBool::new(false);
+
+/// The maximum number of windows in a container, where 0 means there is no limit
+pub static MAX_CONTAINER_WINDOWS: AtomicUsize = AtomicUsize::new(0);
+
 pub static SLOW_APPLICATION_COMPENSATION_TIME: AtomicU64 = AtomicU64::new(20);
 
+pub static TRANSIENT_WINDOW_LIFETIME: AtomicU64 = AtomicU64::new(0);
+
+/// How long windows like one which was destroyed shortly after being shown are treated as transient
+pub const SHORT_LIVED_WINDOW_EXPIRY: Duration = Duration::from_secs(5 * 60);
+
+pub static VIRTUAL_DESKTOP_AWARENESS: AtomicBool = AtomicBool::new(false);
+
+/// Whether rules added through socket messages are written to the data directory and loaded
+/// again on startup
+pub static PERSIST_RULES: AtomicBool = AtomicBool::new(false);
+
+/// Handle to the log filter installed by komorebi.exe, used to change the log level at runtime
+pub static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
 #[must_use]
 pub fn current_virtual_desktop() -> Option<Vec<u8>> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
@@ -284,6 +356,30 @@ pub fn current_virtual_desktop() -> Option<Vec<u8>> {
     current
 }
 
+/// The ids of all virtual desktops, in the order in which they are shown in the task view
+#[must_use]
+pub fn virtual_desktops() -> Vec<Vec<u8>> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+
+    hkcu.open_subkey(r"SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\VirtualDesktops")
+        .ok()
+        .and_then(|desktops| desktops.get_raw_value("VirtualDesktopIDs").ok())
+        .map(|ids| ids.bytes.chunks_exact(16).map(<[u8]>::to_vec).collect())
+        .unwrap_or_default()
+}
+
+/// Focus changes which were caused by something other than a komorebi command, such as alt-tab
+/// or clicking on a window, sent in addition to the event which caused them
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(tag = "type", content = "content")]
+pub enum FocusNotification {
+    /// The monitor index and workspace index which are now focused
+    MonitorFocusChange(usize, usize),
+    /// The workspace index which is now focused on the (unchanged) focused monitor index
+    WorkspaceFocusChange(usize, usize),
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
@@ -291,6 +387,7 @@ pub enum NotificationEvent {
     WindowManager(WindowManagerEvent),
     Socket(SocketMessage),
     Monitor(MonitorNotification),
+    Focus(FocusNotification),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -300,6 +397,69 @@ pub struct Notification {
     pub state: State,
 }
 
+/// Incremented whenever a breaking change is made to the shape of `SocketMessage`, `Notification`
+/// or `State`, so that external clients can check that they are still compatible
+pub const SOCKET_PROTOCOL_VERSION: u32 = 1;
+
+/// JSON Schemas of everything that can be sent to or received from komorebi over its sockets
+#[cfg(feature = "schemars")]
+#[derive(Debug, Serialize)]
+pub struct SocketProtocolSchema {
+    pub protocol_version: u32,
+    pub komorebi_version: String,
+    pub socket_message: schemars::schema::RootSchema,
+    pub notification: schemars::schema::RootSchema,
+    pub state: schemars::schema::RootSchema,
+}
+
+#[cfg(feature = "schemars")]
+pub fn socket_protocol_schema() -> SocketProtocolSchema {
+    SocketProtocolSchema {
+        protocol_version: SOCKET_PROTOCOL_VERSION,
+        komorebi_version: env!("CARGO_PKG_VERSION").to_string(),
+        socket_message: schemars::schema_for!(SocketMessage),
+        notification: schemars::schema_for!(Notification),
+        state: schemars::schema_for!(State),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SocketSubscriber {
+    pub socket: String,
+    pub options: SubscribeOptions,
+}
+
+/// The subscribers which are currently receiving event notifications
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Subscribers {
+    pub sockets: Vec<SocketSubscriber>,
+    pub pipes: Vec<String>,
+}
+
+impl Subscribers {
+    pub fn current() -> Self {
+        let sockets = SUBSCRIPTION_SOCKETS.lock();
+        let options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
+
+        let mut sockets: Vec<SocketSubscriber> = sockets
+            .keys()
+            .map(|socket| SocketSubscriber {
+                socket: socket.clone(),
+                options: options.get(socket).copied().unwrap_or_default(),
+            })
+            .collect();
+
+        let mut pipes: Vec<String> = SUBSCRIPTION_PIPES.lock().keys().cloned().collect();
+
+        sockets.sort_by(|a, b| a.socket.cmp(&b.socket));
+        pipes.sort();
+
+        Self { sockets, pipes }
+    }
+}
+
 pub fn notify_subscribers(notification: Notification, state_has_been_modified: bool) -> Result<()> {
     let is_override_event = matches!(
         notification.event,
@@ -312,24 +472,41 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
             | NotificationEvent::WindowManager(WindowManagerEvent::Uncloak(_, _))
     );
 
-    let notification = &serde_json::to_string(&notification)?;
+    let serialized = serde_json::to_string(&notification)?;
+
+    if state_has_been_modified {
+        state_snapshot::send_notification(notification.state);
+    }
+
+    let notification = &serialized;
     let mut stale_sockets = vec![];
     let mut sockets = SUBSCRIPTION_SOCKETS.lock();
     let options = SUBSCRIPTION_SOCKET_OPTIONS.lock();
 
     for (socket, path) in &mut *sockets {
-        let apply_state_filter = (*options)
-            .get(socket)
-            .copied()
-            .unwrap_or_default()
-            .filter_state_changes;
+        let socket_options = (*options).get(socket).copied().unwrap_or_default();
+        let apply_state_filter = socket_options.filter_state_changes;
 
         if !apply_state_filter || state_has_been_modified || is_override_event {
-            match UnixStream::connect(path) {
-                Ok(mut stream) => {
-                    tracing::debug!("pushed notification to subscriber: {socket}");
-                    stream.write_all(notification.as_bytes())?;
+            if let Some(max_per_second) = socket_options
+                .max_notifications_per_second
+                .filter(|max| *max > 0)
+            {
+                if !subscription_throttle::send_now(socket, max_per_second, notification) {
+                    continue;
                 }
+            }
+
+            match UnixStream::connect(path) {
+                Ok(mut stream) => match stream.write_all(notification.as_bytes()) {
+                    Ok(()) => {
+                        tracing::debug!("pushed notification to subscriber: {socket}");
+                    }
+                    Err(error) => {
+                        tracing::debug!("could not push notification to {socket}: {error}");
+                        stale_sockets.push(socket.clone());
+                    }
+                },
                 Err(_) => {
                     stale_sockets.push(socket.clone());
                 }
@@ -337,9 +514,13 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
         }
     }
 
+    drop(options);
+
     for socket in stale_sockets {
         tracing::warn!("removing stale subscription: {socket}");
         sockets.remove(&socket);
+        SUBSCRIPTION_SOCKET_OPTIONS.lock().remove(&socket);
+        subscription_throttle::remove(&socket);
         let socket_path = DATA_DIR.join(socket);
         if let Err(error) = std::fs::remove_file(&socket_path) {
             tracing::error!(
@@ -361,13 +542,25 @@ pub fn notify_subscribers(notification: Notification, state_has_been_modified: b
                 // 2 (0x2)
                 // The system cannot find the file specified.
 
+                // ERROR_BROKEN_PIPE
+                // 109 (0x6D)
+                // The pipe has been ended.
+
                 // ERROR_NO_DATA
                 // 232 (0xE8)
                 // The pipe is being closed.
 
+                // ERROR_PIPE_NOT_CONNECTED
+                // 233 (0xE9)
+                // No process is on the other end of the pipe.
+
                 // Remove the subscription; the process will have to subscribe again
-                if let Some(2 | 232) = error.raw_os_error() {
+                if matches!(error.raw_os_error(), Some(2 | 109 | 232 | 233))
+                    || error.kind() == std::io::ErrorKind::BrokenPipe
+                {
                     stale_pipes.push(subscriber.clone());
+                } else {
+                    tracing::debug!("could not push notification to {subscriber}: {error}");
                 }
             }
         }
@@ -0,0 +1,157 @@
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// A log file which is rotated whenever writing to it would take it over a maximum size, rather
+/// than on a schedule like the appenders from `tracing_appender::rolling`
+///
+/// The file being written to always keeps its name, and rotated files are numbered from the most
+/// recent, so `komorebi.log.1` holds the lines which were written just before `komorebi.log`.
+#[derive(Debug)]
+pub struct SizeRotatingAppender {
+    path: PathBuf,
+    max_size: u64,
+    max_files: Option<usize>,
+    // None while the files are being renamed, as open files cannot be renamed on Windows, and
+    // until the next write if renaming them failed
+    file: Option<File>,
+    size: u64,
+}
+
+impl SizeRotatingAppender {
+    /// Appends to `file_name` in `directory`, keeping at most `max_files` files including the one
+    /// being written to, or every file if there is no maximum
+    pub fn new(
+        directory: impl AsRef<Path>,
+        file_name: &str,
+        max_size: u64,
+        max_files: Option<usize>,
+    ) -> std::io::Result<Self> {
+        let path = directory.as_ref().join(file_name);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_size,
+            max_files,
+            file: Some(file),
+            size,
+        })
+    }
+
+    fn rotated_path(&self, idx: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{idx}"));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if let Some(mut file) = self.file.take() {
+            file.flush()?;
+        }
+
+        let mut rotated = 0;
+        while self.rotated_path(rotated + 1).exists() {
+            rotated += 1;
+        }
+
+        // the number of rotated files to keep alongside the one being written to
+        let keep = self
+            .max_files
+            .map_or(rotated + 1, |max_files| max_files.saturating_sub(1));
+
+        for idx in keep.max(1)..=rotated {
+            match std::fs::remove_file(self.rotated_path(idx)) {
+                Err(error) if error.kind() != ErrorKind::NotFound => return Err(error),
+                _ => {}
+            }
+        }
+
+        for idx in (1..keep.min(rotated + 1)).rev() {
+            std::fs::rename(self.rotated_path(idx), self.rotated_path(idx + 1))?;
+        }
+
+        if keep > 0 {
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = Some(
+            OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&self.path)?,
+        );
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for SizeRotatingAppender {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.file.is_none() || (self.size > 0 && self.size + buf.len() as u64 > self.max_size) {
+            self.rotate()?;
+        }
+
+        let written = match &mut self.file {
+            Some(file) => file.write(buf)?,
+            None => return Err(std::io::Error::other("the log file is not open")),
+        };
+
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    #[test]
+    fn test_rotates_by_size_and_keeps_max_files() {
+        let directory =
+            std::env::temp_dir().join(format!("komorebi-log-appender-test-{}", std::process::id()));
+
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let mut appender = SizeRotatingAppender::new(&directory, "test.log", 4, Some(3)).unwrap();
+        for line in ["aaa\n", "bbb\n", "ccc\n", "ddd\n"] {
+            appender.write_all(line.as_bytes()).unwrap();
+        }
+
+        appender.flush().unwrap();
+
+        assert_eq!(
+            contents(&directory.join("test.log")).as_deref(),
+            Some("ddd\n")
+        );
+        assert_eq!(
+            contents(&directory.join("test.log.1")).as_deref(),
+            Some("ccc\n")
+        );
+        assert_eq!(
+            contents(&directory.join("test.log.2")).as_deref(),
+            Some("bbb\n")
+        );
+        assert_eq!(contents(&directory.join("test.log.3")), None);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -7,7 +7,6 @@
     clippy::doc_markdown
 )]
 
-use std::env::temp_dir;
 use std::net::Shutdown;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
@@ -27,37 +26,56 @@ use parking_lot::Mutex;
 use sysinfo::Process;
 use sysinfo::ProcessesToUpdate;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::RollingFileAppender;
+use tracing_appender::rolling::Rotation;
 use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::reload;
 use tracing_subscriber::EnvFilter;
 use uds_windows::UnixStream;
 
 use komorebi::border_manager;
+use komorebi::core::LogRotation;
 use komorebi::focus_manager;
+use komorebi::hotkey_manager;
 use komorebi::load_configuration;
+use komorebi::log_appender::SizeRotatingAppender;
 use komorebi::monitor_reconciliator;
+use komorebi::mouse_binding_manager;
+use komorebi::overview;
+use komorebi::paths;
 use komorebi::process_command::listen_for_commands;
 use komorebi::process_command::listen_for_commands_tcp;
 use komorebi::process_event::listen_for_events;
 use komorebi::process_movement::listen_for_movements;
 use komorebi::reaper;
 use komorebi::stackbar_manager;
+use komorebi::state_snapshot;
+use komorebi::static_config::Rules;
 use komorebi::static_config::StaticConfig;
+use komorebi::subscription_throttle;
 use komorebi::theme_manager;
 use komorebi::transparency_manager;
+use komorebi::tray_manager;
 use komorebi::window_manager::State;
 use komorebi::window_manager::WindowManager;
 use komorebi::windows_api::WindowsApi;
 use komorebi::winevent_listener;
 use komorebi::workspace_reconciliator;
 use komorebi::CUSTOM_FFM;
-use komorebi::DATA_DIR;
 use komorebi::HOME_DIR;
 use komorebi::INITIAL_CONFIGURATION_LOADED;
+use komorebi::LOG_FILTER;
+use komorebi::PERSIST_RULES;
 use komorebi::SESSION_ID;
+use komorebi::STARTED_AT;
 
 shadow_rs::shadow!(build);
 
-fn setup() -> Result<(WorkerGuard, WorkerGuard)> {
+fn setup(
+    log_rotation: LogRotation,
+    log_retention: Option<usize>,
+    log_max_size: Option<u64>,
+) -> Result<(WorkerGuard, WorkerGuard)> {
     if std::env::var("RUST_LIB_BACKTRACE").is_err() {
         std::env::set_var("RUST_LIB_BACKTRACE", "1");
     }
@@ -68,15 +86,54 @@ fn setup() -> Result<(WorkerGuard, WorkerGuard)> {
         std::env::set_var("RUST_LOG", "info");
     }
 
-    let appender = tracing_appender::rolling::daily(std::env::temp_dir(), "komorebi_plaintext.log");
-    let color_appender = tracing_appender::rolling::daily(std::env::temp_dir(), "komorebi.log");
-    let (non_blocking, guard) = tracing_appender::non_blocking(appender);
-    let (color_non_blocking, color_guard) = tracing_appender::non_blocking(color_appender);
+    let ((non_blocking, guard), (color_non_blocking, color_guard)) = match log_max_size {
+        Some(megabytes) => {
+            let max_size = megabytes.saturating_mul(1024 * 1024);
+            (
+                tracing_appender::non_blocking(SizeRotatingAppender::new(
+                    paths::log_dir(),
+                    "komorebi_plaintext.log",
+                    max_size,
+                    log_retention,
+                )?),
+                tracing_appender::non_blocking(SizeRotatingAppender::new(
+                    paths::log_dir(),
+                    "komorebi.log",
+                    max_size,
+                    log_retention,
+                )?),
+            )
+        }
+        None => {
+            let rotation = match log_rotation {
+                LogRotation::Hourly => Rotation::HOURLY,
+                LogRotation::Daily => Rotation::DAILY,
+                LogRotation::Never => Rotation::NEVER,
+            };
+
+            (
+                tracing_appender::non_blocking(log_appender(
+                    "komorebi_plaintext.log",
+                    rotation.clone(),
+                    log_retention,
+                )?),
+                tracing_appender::non_blocking(log_appender(
+                    "komorebi.log",
+                    rotation,
+                    log_retention,
+                )?),
+            )
+        }
+    };
+
+    // The filter is wrapped in a reload layer so that the log level can be
+    // changed at runtime with 'komorebic log-level'
+    let (filter, filter_handle) = reload::Layer::new(EnvFilter::from_default_env());
 
     tracing::subscriber::set_global_default(
-        tracing_subscriber::fmt::Subscriber::builder()
-            .with_env_filter(EnvFilter::from_default_env())
-            .finish()
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(tracing_subscriber::fmt::Layer::default())
             .with(
                 tracing_subscriber::fmt::Layer::default()
                     .with_writer(non_blocking)
@@ -89,6 +146,10 @@ fn setup() -> Result<(WorkerGuard, WorkerGuard)> {
             ),
     )?;
 
+    if LOG_FILTER.set(filter_handle).is_err() {
+        tracing::warn!("log filter handle has already been set");
+    }
+
     // https://github.com/tokio-rs/tracing/blob/master/examples/examples/panic_hook.rs
     // Set a panic hook that records the panic as a `tracing` event at the
     // `ERROR` verbosity level.
@@ -120,6 +181,22 @@ fn setup() -> Result<(WorkerGuard, WorkerGuard)> {
     Ok((guard, color_guard))
 }
 
+fn log_appender(
+    prefix: &str,
+    rotation: Rotation,
+    retention: Option<usize>,
+) -> Result<RollingFileAppender> {
+    let mut builder = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(prefix);
+
+    if let Some(retention) = retention {
+        builder = builder.max_log_files(retention);
+    }
+
+    Ok(builder.build(paths::log_dir())?)
+}
+
 #[cfg(feature = "deadlock_detection")]
 #[tracing::instrument]
 fn detect_deadlocks() {
@@ -158,17 +235,36 @@ struct Opts {
     /// Path to a static configuration JSON file
     #[clap(short, long)]
     config: Option<PathBuf>,
-    /// Do not attempt to auto-apply a dumped state temp file from a previously running instance of komorebi
+    /// Do not attempt to auto-apply the state file from a previously running instance of komorebi
     #[clap(long)]
     clean_state: bool,
+    /// How often to start a new log file
+    #[clap(value_enum, long, default_value_t = LogRotation::Daily)]
+    log_rotation: LogRotation,
+    /// Maximum number of rotated log files to keep (default: keep all)
+    #[clap(long)]
+    log_retention: Option<usize>,
+    /// Start a new log file whenever the current one reaches this size in megabytes, instead of
+    /// on the schedule given by --log-rotation
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "log_rotation")]
+    log_max_size: Option<u64>,
+    /// Name of the socket to listen for commands on, to run another instance alongside the one
+    /// listening on the default komorebi.sock
+    #[clap(long)]
+    socket_name: Option<String>,
 }
 
 #[tracing::instrument]
 #[allow(clippy::cognitive_complexity)]
 fn main() -> Result<()> {
+    lazy_static::initialize(&STARTED_AT);
     let opts: Opts = Opts::parse();
     CUSTOM_FFM.store(opts.focus_follows_mouse, Ordering::SeqCst);
 
+    if let Some(socket_name) = &opts.socket_name {
+        paths::set_socket_name(socket_name);
+    }
+
     let process_id = WindowsApi::current_process_id();
     WindowsApi::allow_set_foreground_window(process_id)?;
     WindowsApi::set_process_dpi_awareness_context()?;
@@ -181,7 +277,17 @@ fn main() -> Result<()> {
 
     let matched_procs: Vec<&Process> = system.processes_by_name("komorebi.exe".as_ref()).collect();
 
-    if matched_procs.len() > 1 {
+    // Instances with their own socket name are expected to run alongside another instance, so
+    // they only need to make sure that nothing is listening on that socket already
+    if opts.socket_name.is_some() {
+        if UnixStream::connect(paths::socket_path()).is_ok() {
+            tracing::error!(
+                "komorebi.exe is already listening on {}, please exit the existing process before starting a new one",
+                paths::socket_path().display()
+            );
+            std::process::exit(1);
+        }
+    } else if matched_procs.len() > 1 {
         let mut len = matched_procs.len();
         for proc in matched_procs {
             if let Some(executable_path) = proc.exe() {
@@ -197,8 +303,10 @@ fn main() -> Result<()> {
         }
     }
 
+    std::fs::create_dir_all(paths::log_dir())?;
+
     // File logging worker guard has to have an assignment in the main fn to work
-    let (_guard, _color_guard) = setup()?;
+    let (_guard, _color_guard) = setup(opts.log_rotation, opts.log_retention, opts.log_max_size)?;
 
     WindowsApi::foreground_lock_timeout()?;
 
@@ -219,7 +327,9 @@ fn main() -> Result<()> {
         Option::from,
     );
 
-    std::fs::create_dir_all(&*DATA_DIR)?;
+    if let Some(config) = &static_config {
+        let _ = paths::STATIC_CONFIG.set(config.clone());
+    }
 
     let wm = if let Some(config) = &static_config {
         tracing::info!(
@@ -239,12 +349,22 @@ fn main() -> Result<()> {
         )?))
     };
 
-    wm.lock().init()?;
+    let adopted = wm.lock().init()?;
 
     if let Some(config) = &static_config {
         StaticConfig::postload(config, &wm)?;
     }
 
+    let persisted_rules = Rules::persisted_path();
+    if PERSIST_RULES.load(Ordering::SeqCst) && persisted_rules.is_file() {
+        if let Err(error) = Rules::load_persisted() {
+            tracing::warn!(
+                "could not load persisted rules from {}: {error}",
+                persisted_rules.display()
+            );
+        }
+    }
+
     if !opts.await_configuration && !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
         INITIAL_CONFIGURATION_LOADED.store(true, Ordering::SeqCst);
     };
@@ -260,11 +380,12 @@ fn main() -> Result<()> {
         }
     }
 
-    let dumped_state = temp_dir().join("komorebi.state.json");
+    let dumped_state = state_snapshot::state_file_path();
+    let mut applied_state = false;
 
     if !opts.clean_state && dumped_state.is_file() {
         if let Ok(state) = serde_json::from_str(&std::fs::read_to_string(&dumped_state)?) {
-            wm.lock().apply_state(state);
+            applied_state = wm.lock().apply_state(state);
         } else {
             tracing::warn!(
                 "cannot apply state from {}; state struct is not up to date",
@@ -275,6 +396,14 @@ fn main() -> Result<()> {
 
     wm.lock().retile_all(false)?;
 
+    // without a previous session to restore, the windows which were already open are tiled in a
+    // way that resembles how they were arranged
+    if !applied_state {
+        if let Err(error) = wm.lock().infer_resize_dimensions(&adopted) {
+            tracing::warn!("could not infer resize dimensions from the existing windows: {error}");
+        }
+    }
+
     border_manager::listen_for_notifications(wm.clone());
     stackbar_manager::listen_for_notifications(wm.clone());
     transparency_manager::listen_for_notifications(wm.clone());
@@ -282,7 +411,13 @@ fn main() -> Result<()> {
     monitor_reconciliator::listen_for_notifications(wm.clone())?;
     reaper::listen_for_notifications(wm.clone(), wm.lock().known_hwnds.clone());
     focus_manager::listen_for_notifications(wm.clone());
+    hotkey_manager::listen_for_notifications(wm.clone());
+    mouse_binding_manager::listen_for_notifications(wm.clone());
+    overview::listen_for_notifications(wm.clone());
+    tray_manager::listen_for_notifications(wm.clone());
     theme_manager::listen_for_notifications();
+    state_snapshot::listen_for_notifications();
+    subscription_throttle::listen_for_notifications();
 
     listen_for_commands(wm.clone());
 
@@ -328,8 +463,7 @@ fn main() -> Result<()> {
         }
     }
 
-    let socket = DATA_DIR.join("komorebi.sock");
-    let _ = std::fs::remove_file(socket);
+    let _ = std::fs::remove_file(paths::socket_path());
 
     std::process::exit(130);
 }
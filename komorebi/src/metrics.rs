@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::core::SocketMessage;
+use crate::STARTED_AT;
+
+pub static EVENTS_PROCESSED: AtomicU64 = AtomicU64::new(0);
+pub static RETILES: AtomicU64 = AtomicU64::new(0);
+static COMMANDS_PROCESSED: AtomicU64 = AtomicU64::new(0);
+static COMMAND_LATENCY_TOTAL_MICROS: AtomicU64 = AtomicU64::new(0);
+static COMMAND_LATENCY_MAX_MICROS: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    static ref COMMAND_ERRORS: Arc<Mutex<HashMap<String, u64>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+}
+
+/// A snapshot of the internal counters of the window manager, returned by `komorebic query metrics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Metrics {
+    /// Number of seconds since komorebi was started
+    pub uptime_seconds: u64,
+    /// Number of window manager events which have been processed
+    pub events_processed: u64,
+    /// Number of socket commands which have been processed
+    pub commands_processed: u64,
+    /// Number of times a workspace layout has been calculated and applied
+    pub retiles: u64,
+    /// Number of windows currently managed on all monitors and workspaces
+    pub windows_managed: usize,
+    /// Mean time taken to process a socket command, in microseconds
+    pub average_command_latency_micros: u64,
+    /// Longest time taken to process a socket command, in microseconds
+    pub max_command_latency_micros: u64,
+    /// Number of failed socket commands, keyed by the name of the command
+    pub command_errors: HashMap<String, u64>,
+}
+
+impl Metrics {
+    pub fn snapshot(windows_managed: usize) -> Self {
+        let commands_processed = COMMANDS_PROCESSED.load(Ordering::SeqCst);
+        let latency_total = COMMAND_LATENCY_TOTAL_MICROS.load(Ordering::SeqCst);
+
+        Self {
+            uptime_seconds: STARTED_AT.elapsed().as_secs(),
+            events_processed: EVENTS_PROCESSED.load(Ordering::SeqCst),
+            commands_processed,
+            retiles: RETILES.load(Ordering::SeqCst),
+            windows_managed,
+            average_command_latency_micros: latency_total
+                .checked_div(commands_processed)
+                .unwrap_or_default(),
+            max_command_latency_micros: COMMAND_LATENCY_MAX_MICROS.load(Ordering::SeqCst),
+            command_errors: COMMAND_ERRORS.lock().clone(),
+        }
+    }
+}
+
+pub fn record_command(message: &SocketMessage, latency: Duration, failed: bool) {
+    let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+
+    COMMANDS_PROCESSED.fetch_add(1, Ordering::SeqCst);
+    COMMAND_LATENCY_TOTAL_MICROS.fetch_add(micros, Ordering::SeqCst);
+    COMMAND_LATENCY_MAX_MICROS.fetch_max(micros, Ordering::SeqCst);
+
+    if failed {
+        *COMMAND_ERRORS
+            .lock()
+            .entry(message.to_string())
+            .or_default() += 1;
+    }
+}
@@ -97,7 +97,9 @@ pub fn new(
     serial_number_id: Option<String>,
 ) -> Monitor {
     let mut workspaces = Ring::default();
-    workspaces.elements_mut().push_back(Workspace::default());
+    workspaces
+        .elements_mut()
+        .push_back(workspace_without_padding());
 
     Monitor {
         id,
@@ -118,6 +120,16 @@ pub fn new(
     }
 }
 
+/// A workspace without padding of its own, so that it falls back to the monitor and global
+/// padding through its globals
+fn workspace_without_padding() -> Workspace {
+    let mut workspace = Workspace::default();
+    workspace.set_container_padding(None);
+    workspace.set_workspace_padding(None);
+
+    workspace
+}
+
 impl Monitor {
     pub fn new(
         id: isize,
@@ -329,7 +341,8 @@ impl Monitor {
         }
 
         if idx == 0 {
-            self.workspaces_mut().push_back(Workspace::default());
+            let workspace = self.new_workspace();
+            self.workspaces_mut().push_back(workspace);
         } else {
             self.focus_workspace(idx.saturating_sub(1)).ok()?;
         };
@@ -339,11 +352,30 @@ impl Monitor {
 
     pub fn ensure_workspace_count(&mut self, ensure_count: usize) {
         if self.workspaces().len() < ensure_count {
-            self.workspaces_mut()
-                .resize(ensure_count, Workspace::default());
+            let workspace = self.new_workspace();
+            self.workspaces_mut().resize(ensure_count, workspace);
         }
     }
 
+    /// Creates a workspace without padding of its own, so that it falls back to the padding defaults
+    /// of this monitor or the global defaults through its `globals`
+    pub fn new_workspace(&self) -> Workspace {
+        workspace_without_padding()
+    }
+
+    /// Creates a copy of this monitor's workspaces without any of their containers or windows
+    pub fn workspaces_without_windows(&self) -> Ring<Workspace> {
+        let mut workspaces = Ring::default();
+        for workspace in self.workspaces() {
+            workspaces
+                .elements_mut()
+                .push_back(workspace.without_windows());
+        }
+
+        workspaces.focus(self.focused_workspace_idx());
+        workspaces
+    }
+
     pub fn remove_workspaces(&mut self) -> VecDeque<Workspace> {
         self.workspaces_mut().drain(..).collect()
     }
@@ -355,6 +387,7 @@ impl Monitor {
         follow: bool,
         direction: Option<OperationDirection>,
     ) -> Result<()> {
+        let new_workspace = self.new_workspace();
         let workspace = self
             .focused_workspace_mut()
             .ok_or_else(|| anyhow!("there is no workspace"))?;
@@ -375,7 +408,7 @@ impl Monitor {
                 #[allow(clippy::option_if_let_else)]
                 let target_workspace = match workspaces.get_mut(target_workspace_idx) {
                     None => {
-                        workspaces.resize(target_workspace_idx + 1, Workspace::default());
+                        workspaces.resize(target_workspace_idx + 1, new_workspace);
                         workspaces.get_mut(target_workspace_idx).unwrap()
                     }
                     Some(workspace) => workspace,
@@ -394,7 +427,7 @@ impl Monitor {
             #[allow(clippy::option_if_let_else)]
             let target_workspace = match workspaces.get_mut(target_workspace_idx) {
                 None => {
-                    workspaces.resize(target_workspace_idx + 1, Workspace::default());
+                    workspaces.resize(target_workspace_idx + 1, new_workspace);
                     workspaces.get_mut(target_workspace_idx).unwrap()
                 }
                 Some(workspace) => workspace,
@@ -425,10 +458,11 @@ impl Monitor {
         tracing::info!("focusing workspace");
 
         {
+            let new_workspace = self.new_workspace();
             let workspaces = self.workspaces_mut();
 
             if workspaces.get(idx).is_none() {
-                workspaces.resize(idx + 1, Workspace::default());
+                workspaces.resize(idx + 1, new_workspace);
             }
 
             self.workspaces.focus(idx);
@@ -452,6 +486,13 @@ impl Monitor {
         self.workspaces().len()
     }
 
+    /// Scales the resize adjustments on every workspace after the work area has changed size
+    pub fn scale_resize_dimensions(&mut self, from: &Rect, to: &Rect) {
+        for workspace in self.workspaces_mut() {
+            workspace.scale_resize_dimensions(from, to);
+        }
+    }
+
     pub fn update_focused_workspace(&mut self, offset: Option<Rect>) -> Result<()> {
         let offset = if self.work_area_offset().is_some() {
             self.work_area_offset()
@@ -603,4 +644,38 @@ mod tests {
         // Should be the last workspace index: 1
         assert_eq!(new_workspace_index, 1);
     }
+
+    #[test]
+    fn test_new_workspaces_inherit_monitor_padding() {
+        let mut m = Monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "TestMonitor".to_string(),
+            "TestDevice".to_string(),
+            "TestDeviceID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        m.ensure_workspace_count(3);
+
+        // A workspace with its own padding should keep it
+        m.workspaces_mut()[1].set_container_padding(Some(1));
+
+        m.set_container_padding(Some(3));
+        m.set_workspace_padding(Some(7));
+        m.update_workspaces_globals(None);
+
+        for (idx, workspace) in m.workspaces().iter().enumerate() {
+            assert_eq!(workspace.globals().container_padding, Some(3));
+            assert_eq!(workspace.globals().workspace_padding, Some(7));
+            assert_eq!(workspace.workspace_padding(), None);
+
+            if idx == 1 {
+                assert_eq!(workspace.container_padding(), Some(1));
+            } else {
+                assert_eq!(workspace.container_padding(), None);
+            }
+        }
+    }
 }
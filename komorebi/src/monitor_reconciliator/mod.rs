@@ -3,10 +3,14 @@
 use crate::border_manager;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::core::Rect;
+use crate::event_hooks;
+use crate::event_hooks::HookEvent;
 use crate::monitor;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator::hidden::Hidden;
 use crate::notify_subscribers;
+use crate::tray_manager;
+use crate::tray_manager::ToastLevel;
 use crate::Notification;
 use crate::NotificationEvent;
 use crate::State;
@@ -14,6 +18,7 @@ use crate::WindowManager;
 use crate::WindowsApi;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::DUPLICATE_MONITOR_SERIAL_IDS;
+use crate::FRAME_COMPENSATIONS;
 use crate::WORKSPACE_MATCHING_RULES;
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
@@ -246,6 +251,9 @@ where
             }
             MonitorNotification::ResolutionScalingChanged => {
                 tracing::debug!("handling resolution/scaling changed notification");
+                // Invisible borders are scaled with the display, so they are detected again
+                FRAME_COMPENSATIONS.lock().clear();
+
                 let offset = wm.work_area_offset;
                 for monitor in wm.monitors_mut() {
                     let mut should_update = false;
@@ -253,6 +261,11 @@ where
                     // Update sizes and work areas as necessary
                     if let Ok(reference) = WindowsApi::monitor(monitor.id()) {
                         if reference.work_area_size() != monitor.work_area_size() {
+                            // Resize adjustments are in pixels, so they are scaled to keep the
+                            // same proportions at the new resolution or orientation
+                            let previous = *monitor.work_area_size();
+                            monitor.scale_resize_dimensions(&previous, reference.work_area_size());
+
                             monitor.set_work_area_size(Rect {
                                 left: reference.work_area_size().left,
                                 top: reference.work_area_size().top,
@@ -298,6 +311,8 @@ where
             | MonitorNotification::SessionUnlocked
             | MonitorNotification::DisplayConnectionChange => {
                 tracing::debug!("handling display connection change notification");
+                FRAME_COMPENSATIONS.lock().clear();
+
                 let mut monitor_cache = MONITOR_CACHE
                     .get_or_init(|| Mutex::new(HashMap::new()))
                     .lock();
@@ -307,6 +322,9 @@ where
                 // Get the currently attached display devices
                 let attached_devices = attached_display_devices(display_provider)?;
 
+                // Whether any attached displays changed resolution or orientation along the way
+                let mut geometry_changed = false;
+
                 // Make sure that in our state any attached displays have the latest Win32 data
                 for monitor in wm.monitors_mut() {
                     for attached in &attached_devices {
@@ -319,6 +337,13 @@ where
                         };
 
                         if serial_number_ids_match || attached.device_id().eq(monitor.device_id()) {
+                            if attached.work_area_size() != monitor.work_area_size() {
+                                let previous = *monitor.work_area_size();
+                                monitor
+                                    .scale_resize_dimensions(&previous, attached.work_area_size());
+                                geometry_changed = true;
+                            }
+
                             monitor.set_id(attached.id());
                             monitor.set_device(attached.device().clone());
                             monitor.set_device_id(attached.device_id().clone());
@@ -332,6 +357,13 @@ where
 
                 if initial_monitor_count == attached_devices.len() {
                     tracing::debug!("monitor counts match, reconciliation not required");
+
+                    // WM_DISPLAYCHANGE won't find any differences left to retile for
+                    if geometry_changed {
+                        wm.retile_all(true)?;
+                        border_manager::send_notification(None);
+                    }
+
                     drop(wm);
                     continue 'receiver;
                 }
@@ -350,6 +382,15 @@ where
                         attached_devices.len()
                     );
 
+                    tray_manager::toast(
+                        ToastLevel::Info,
+                        "Monitor disconnected",
+                        &format!(
+                            "{} of {initial_monitor_count} monitors are still connected",
+                            attached_devices.len()
+                        ),
+                    );
+
                     // Windows to remove from `known_hwnds`
                     let mut windows_to_remove = Vec::new();
 
@@ -505,6 +546,12 @@ where
                         "monitor count mismatch ({post_removal_monitor_count} vs {post_addition_monitor_count}), adding connected monitors",
                     );
 
+                    tray_manager::toast(
+                        ToastLevel::Info,
+                        "Monitor connected",
+                        &format!("{post_addition_monitor_count} monitors are connected"),
+                    );
+
                     let known_hwnds = wm.known_hwnds.clone();
                     let offset = wm.work_area_offset;
                     let mouse_follows_focus = wm.mouse_follows_focus;
@@ -706,6 +753,9 @@ where
 
                 let final_count = wm.monitors().len();
 
+                // Commands keep referring to monitors by the indices from the configuration
+                wm.update_monitor_usr_idx_map();
+
                 if post_removal_monitor_count != final_count {
                     wm.retile_all(true)?;
                     // Second retile to fix DPI/resolution related jank
@@ -723,6 +773,20 @@ where
             },
             initial_state.has_been_modified(&wm),
         )?;
+
+        let initial_monitor_count = initial_state.monitors.elements().len();
+        let hook_event = match wm.monitors().len() {
+            count if count > initial_monitor_count => Some(HookEvent::MonitorAdded),
+            count if count < initial_monitor_count => Some(HookEvent::MonitorRemoved),
+            _ => None,
+        };
+
+        if let Some(hook_event) = hook_event {
+            event_hooks::run(hook_event, || Notification {
+                event: NotificationEvent::Monitor(notification),
+                state: wm.as_ref().into(),
+            });
+        }
     }
 
     Ok(())
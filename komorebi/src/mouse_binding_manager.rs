@@ -0,0 +1,461 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::Rect;
+use crate::hotkey_manager::mask_windows_key;
+use crate::hotkey_manager::Modifiers;
+use crate::reaper::HWNDS_CACHE;
+use crate::static_config::MouseAction;
+use crate::static_config::MouseBinding;
+use crate::window::Window;
+use crate::window_manager_event::WindowManagerEvent;
+use crate::windows_api::WindowsApi;
+use crate::winevent::WinEvent;
+use crate::winevent_listener;
+use crate::WindowManager;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::eyre::OptionExt;
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::POINT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::UI::WindowsAndMessaging::CallNextHookEx;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SetWindowsHookExW;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::UnhookWindowsHookEx;
+use windows::Win32::UI::WindowsAndMessaging::HC_ACTION;
+use windows::Win32::UI::WindowsAndMessaging::LLMHF_INJECTED;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::MSLLHOOKSTRUCT;
+use windows::Win32::UI::WindowsAndMessaging::WH_MOUSE_LL;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_MBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_MBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_MOUSEMOVE;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONUP;
+
+lazy_static! {
+    static ref BINDINGS: RwLock<HashMap<MouseChord, MouseAction>> = RwLock::new(HashMap::new());
+    /// The button which triggered the binding being handled, whose release ends it
+    static ref PRESSED: Mutex<Option<MouseButton>> = Mutex::new(None);
+}
+
+static HOOK: OnceLock<std::thread::JoinHandle<()>> = OnceLock::new();
+
+pub enum Notification {
+    Pressed(MouseAction, isize, POINT),
+    Dragged(POINT),
+    Released,
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(20))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+impl MouseButton {
+    /// The button of a mouse hook message, and whether it was pressed rather than released
+    fn from_message(message: u32) -> Option<(Self, bool)> {
+        match message {
+            WM_LBUTTONDOWN => Some((Self::Left, true)),
+            WM_LBUTTONUP => Some((Self::Left, false)),
+            WM_RBUTTONDOWN => Some((Self::Right, true)),
+            WM_RBUTTONUP => Some((Self::Right, false)),
+            WM_MBUTTONDOWN => Some((Self::Middle, true)),
+            WM_MBUTTONUP => Some((Self::Middle, false)),
+            _ => None,
+        }
+    }
+}
+
+/// A combination of at least one modifier and a mouse button, such as "win+left"
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MouseChord {
+    pub modifiers: Modifiers,
+    pub button: MouseButton,
+}
+
+impl FromStr for MouseChord {
+    type Err = color_eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut modifiers = Modifiers::default();
+        let mut button = None;
+
+        for part in s.split('+').map(|part| part.trim().to_lowercase()) {
+            if modifiers.set(&part) {
+                continue;
+            }
+
+            let parsed = match part.as_str() {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                name => return Err(anyhow!("unknown mouse button '{name}'")),
+            };
+
+            if button.replace(parsed).is_some() {
+                return Err(anyhow!("'{s}' has more than one mouse button"));
+            }
+        }
+
+        // without a modifier every click on a managed window would be taken over
+        if modifiers == Modifiers::default() {
+            return Err(anyhow!("'{s}' has no modifier"));
+        }
+
+        Ok(Self {
+            modifiers,
+            button: button.ok_or_eyre(format!("'{s}' has no mouse button"))?,
+        })
+    }
+}
+
+/// Replaces the active mouse bindings, installing the mouse hook the first time there are any
+pub fn set_bindings(bindings: &[MouseBinding]) {
+    let mut chords = HashMap::new();
+
+    for binding in bindings {
+        let chord = match MouseChord::from_str(&binding.buttons) {
+            Ok(chord) => chord,
+            Err(error) => {
+                tracing::error!("ignoring mouse binding '{}': {error}", binding.buttons);
+                continue;
+            }
+        };
+
+        if chords.contains_key(&chord) {
+            tracing::error!(
+                "ignoring mouse binding '{}': the chord is already bound earlier in the configuration",
+                binding.buttons
+            );
+            continue;
+        }
+
+        chords.insert(chord, binding.action);
+    }
+
+    tracing::info!("loaded {} mouse bindings", chords.len());
+
+    let install = !chords.is_empty();
+    *BINDINGS.write() = chords;
+
+    if install {
+        install_hook();
+    }
+}
+
+fn install_hook() {
+    HOOK.get_or_init(|| {
+        std::thread::spawn(move || {
+            let hook = match unsafe { SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook), None, 0) } {
+                Ok(hook) => hook,
+                Err(error) => {
+                    tracing::error!("could not install mouse hook: {error}");
+                    return;
+                }
+            };
+
+            tracing::info!("mouse hook installed");
+
+            // low-level hooks are called on the thread which installed them while it is waiting
+            // for messages, so it must never sleep
+            let mut msg: MSG = MSG::default();
+
+            loop {
+                unsafe {
+                    if !GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                        tracing::debug!("mouse hook thread shutdown");
+                        break;
+                    };
+
+                    let _ = TranslateMessage(&msg);
+                    DispatchMessageW(&msg);
+                }
+            }
+
+            unsafe {
+                let _ = UnhookWindowsHookEx(hook);
+            }
+        })
+    });
+}
+
+/// The managed window under a point, if there is one
+fn managed_window_at(point: POINT) -> Option<isize> {
+    let hwnd = WindowsApi::root_window(WindowsApi::window_from_point(point).ok()?);
+    HWNDS_CACHE.lock().contains_key(&hwnd).then_some(hwnd)
+}
+
+/// Swallows the presses and releases of mouse buttons which match a binding over a managed window
+/// and hands them off to be processed, passing everything else through untouched
+extern "system" fn mouse_hook(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    if code == HC_ACTION as i32 {
+        // SAFETY: lparam points to a MSLLHOOKSTRUCT for mouse hooks
+        let event = unsafe { &*(lparam.0 as *const MSLLHOOKSTRUCT) };
+        let injected = event.flags & LLMHF_INJECTED != 0;
+        let message = wparam.0 as u32;
+
+        if message == WM_MOUSEMOVE {
+            // dropping a few positions while the channel is full doesn't matter for a drag
+            if PRESSED.lock().is_some() {
+                let _ = event_tx().try_send(Notification::Dragged(event.pt));
+            }
+        } else if let Some((button, is_press)) =
+            MouseButton::from_message(message).filter(|_| !injected)
+        {
+            if is_press {
+                if handle_press(button, event.pt) {
+                    return LRESULT(1);
+                }
+            } else if PRESSED
+                .lock()
+                .take_if(|pressed| *pressed == button)
+                .is_some()
+            {
+                if event_tx().try_send(Notification::Released).is_err() {
+                    tracing::warn!("channel is full; dropping notification")
+                }
+
+                return LRESULT(1);
+            }
+        }
+    }
+
+    unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+/// Starts handling a binding if the button and held modifiers match one and the cursor is over a
+/// managed window, returning whether the press should be swallowed
+fn handle_press(button: MouseButton, point: POINT) -> bool {
+    let chord = MouseChord {
+        modifiers: Modifiers::pressed(),
+        button,
+    };
+
+    let Some(action) = BINDINGS.read().get(&chord).copied() else {
+        return false;
+    };
+
+    let Some(hwnd) = managed_window_at(point) else {
+        return false;
+    };
+
+    if event_tx()
+        .try_send(Notification::Pressed(action, hwnd, point))
+        .is_err()
+    {
+        tracing::warn!("channel is full; dropping notification")
+    }
+
+    if chord.modifiers.win {
+        mask_windows_key();
+    }
+
+    *PRESSED.lock() = Some(button);
+
+    true
+}
+
+/// A window being moved or resized by dragging the mouse
+struct Drag {
+    action: MouseAction,
+    window: Window,
+    origin: POINT,
+    rect: Rect,
+    from_left: bool,
+    from_top: bool,
+}
+
+impl Drag {
+    fn new(action: MouseAction, window: Window, origin: POINT, rect: Rect) -> Self {
+        // resizing moves whichever edges are nearest to where the drag started
+        Self {
+            action,
+            window,
+            origin,
+            rect,
+            from_left: origin.x < rect.left + rect.right / 2,
+            from_top: origin.y < rect.top + rect.bottom / 2,
+        }
+    }
+
+    /// Where the window should be once the cursor has been dragged to `point`
+    fn rect_at(&self, point: POINT) -> Rect {
+        let dx = point.x - self.origin.x;
+        let dy = point.y - self.origin.y;
+        let mut rect = self.rect;
+
+        match self.action {
+            MouseAction::Resize => {
+                if self.from_left {
+                    rect.left += dx;
+                    rect.right -= dx;
+                } else {
+                    rect.right += dx;
+                }
+
+                if self.from_top {
+                    rect.top += dy;
+                    rect.bottom -= dy;
+                } else {
+                    rect.bottom += dy;
+                }
+            }
+            MouseAction::Move | MouseAction::Close => {
+                rect.left += dx;
+                rect.top += dy;
+            }
+        }
+
+        rect
+    }
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+    let mut drag: Option<Drag> = None;
+
+    for notification in receiver {
+        match notification {
+            Notification::Pressed(action, hwnd, origin) => {
+                if wm.lock().is_paused {
+                    tracing::trace!("ignoring while paused");
+                    continue;
+                }
+
+                let window = Window::from(hwnd);
+
+                match action {
+                    MouseAction::Close => window.close()?,
+                    MouseAction::Move | MouseAction::Resize => {
+                        let rect = WindowsApi::window_rect(hwnd)?;
+
+                        // komorebi sees the same events as when a window is clicked and then
+                        // dragged by its title bar or edges, so the usual swaps and resizes happen
+                        // when the drag ends
+                        window.focus(false)?;
+                        winevent_listener::event_tx().send(WindowManagerEvent::FocusChange(
+                            WinEvent::SystemForeground,
+                            window,
+                        ))?;
+                        winevent_listener::event_tx().send(WindowManagerEvent::MoveResizeStart(
+                            WinEvent::SystemMoveSizeStart,
+                            window,
+                        ))?;
+
+                        drag = Some(Drag::new(action, window, origin, rect));
+                    }
+                }
+            }
+            Notification::Dragged(point) => {
+                if let Some(drag) = &drag {
+                    if let Err(error) =
+                        WindowsApi::position_window(drag.window.hwnd, &drag.rect_at(point), false)
+                    {
+                        tracing::warn!("could not position dragged window: {error}");
+                    }
+                }
+            }
+            Notification::Released => {
+                if let Some(drag) = drag.take() {
+                    winevent_listener::event_tx().send(WindowManagerEvent::MoveResizeEnd(
+                        WinEvent::SystemMoveSizeEnd,
+                        drag.window,
+                    ))?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mouse_chord() {
+        let chord = MouseChord::from_str("Win + Left").unwrap();
+
+        assert!(chord.modifiers.win);
+        assert_eq!(chord.button, MouseButton::Left);
+
+        assert!(MouseChord::from_str("left").is_err());
+        assert!(MouseChord::from_str("win+left+right").is_err());
+        assert!(MouseChord::from_str("win+x").is_err());
+    }
+
+    #[test]
+    fn test_resize_drag_moves_nearest_edges() {
+        let rect = Rect {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        };
+
+        let drag = Drag::new(
+            MouseAction::Resize,
+            Window::from(0),
+            POINT { x: 10, y: 90 },
+            rect,
+        );
+
+        assert_eq!(
+            drag.rect_at(POINT { x: 20, y: 100 }),
+            Rect {
+                left: 10,
+                top: 0,
+                right: 90,
+                bottom: 110,
+            }
+        );
+    }
+}
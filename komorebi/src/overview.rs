@@ -0,0 +1,539 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::border_manager;
+use crate::core::Rect;
+use crate::core::SocketMessage;
+use crate::window_manager::WindowManager;
+use crate::windows_api;
+use crate::windows_api::WindowsApi;
+use crate::workspace::Workspace;
+use crate::workspace_preview::Thumbnail;
+
+use color_eyre::eyre::anyhow;
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::COLORREF;
+use windows::Win32::Foundation::HINSTANCE;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::Graphics::Gdi::BeginPaint;
+use windows::Win32::Graphics::Gdi::CreateSolidBrush;
+use windows::Win32::Graphics::Gdi::DeleteObject;
+use windows::Win32::Graphics::Gdi::EndPaint;
+use windows::Win32::Graphics::Gdi::FillRect;
+use windows::Win32::Graphics::Gdi::PAINTSTRUCT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VIRTUAL_KEY;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_DOWN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_ESCAPE;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_H;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_J;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_K;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_L;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_LEFT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RETURN;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_RIGHT;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_SPACE;
+use windows::Win32::UI::Input::KeyboardAndMouse::VK_UP;
+use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::PostQuitMessage;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::CS_HREDRAW;
+use windows::Win32::UI::WindowsAndMessaging::CS_VREDRAW;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::WA_INACTIVE;
+use windows::Win32::UI::WindowsAndMessaging::WM_ACTIVATE;
+use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
+use windows::Win32::UI::WindowsAndMessaging::WM_KEYDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_KILLFOCUS;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONDOWN;
+use windows::Win32::UI::WindowsAndMessaging::WM_PAINT;
+use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOPMOST;
+use windows::Win32::UI::WindowsAndMessaging::WS_POPUP;
+use windows::Win32::UI::WindowsAndMessaging::WS_VISIBLE;
+
+/// The space between the thumbnails in the grid, and around its edges
+const GAP: i32 = 40;
+/// The width of the frame drawn around the selected thumbnail
+const FRAME_WIDTH: i32 = 6;
+const BACKGROUND_COLOUR: u32 = 0x001E1E1E;
+
+struct Cell {
+    hwnd: isize,
+    /// Where the thumbnail of the window is drawn in the overview window
+    rect: Rect,
+}
+
+struct Overview {
+    hwnd: isize,
+    columns: usize,
+    cells: Vec<Cell>,
+    selected: usize,
+    /// The window which is focused again if the overview is cancelled
+    previous_foreground_window: isize,
+    /// The thumbnails are removed when the overview is closed and these are dropped
+    _thumbnails: Vec<Thumbnail>,
+}
+
+impl Overview {
+    fn move_selection(&mut self, columns: isize, rows: isize) {
+        let next = self.selected as isize + columns + rows * self.columns as isize;
+        if (0..self.cells.len() as isize).contains(&next) {
+            self.selected = next as usize;
+            WindowsApi::invalidate_rect(self.hwnd, None, false);
+        }
+    }
+}
+
+lazy_static! {
+    static ref OVERVIEW: Mutex<Option<Overview>> = Mutex::new(None);
+}
+
+static CHANNEL: OnceLock<(Sender<isize>, Receiver<isize>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<isize>, Receiver<isize>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(1))
+}
+
+fn event_tx() -> Sender<isize> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<isize> {
+    channel().1.clone()
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    tracing::error!("restarting failed thread: {:?}", error)
+                } else {
+                    tracing::error!("restarting failed thread: {}", error)
+                }
+            }
+        }
+    });
+}
+
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    // the window which was picked in the overview is focused on its own workspace
+    for hwnd in receiver {
+        let mut wm = wm.lock();
+        if let Err(error) =
+            wm.process_command(SocketMessage::FocusWindowHwnd(hwnd), std::io::sink())
+        {
+            tracing::error!("could not focus window {hwnd} picked in the overview: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Shows every window on every workspace of the focused monitor in a grid of live thumbnails, or
+/// closes the overview if it is already shown
+pub fn toggle(wm: &WindowManager) -> Result<()> {
+    if OVERVIEW.lock().is_some() {
+        cancel();
+        return Ok(());
+    }
+
+    let monitor = wm
+        .focused_monitor()
+        .ok_or_else(|| anyhow!("there is no monitor"))?;
+    let area = *monitor.size();
+    let foreground_window = WindowsApi::foreground_window().unwrap_or_default();
+
+    let windows = monitor
+        .workspaces()
+        .iter()
+        .flat_map(Workspace::hwnds)
+        .filter(|hwnd| !WindowsApi::is_iconic(*hwnd))
+        .filter_map(|hwnd| WindowsApi::window_rect(hwnd).ok().map(|rect| (hwnd, rect)))
+        .collect::<Vec<_>>();
+
+    if windows.is_empty() {
+        tracing::info!("there are no windows to show in the overview");
+        return Ok(());
+    }
+
+    let columns = columns(windows.len());
+    let sizes = windows.iter().map(|(_, rect)| *rect).collect::<Vec<_>>();
+    let cells = grid(&area, columns, &sizes)
+        .into_iter()
+        .zip(&windows)
+        .map(|(rect, (hwnd, _))| Cell { hwnd: *hwnd, rect })
+        .collect::<Vec<_>>();
+
+    let hwnd = create_window(&area)?;
+
+    let mut thumbnails = vec![];
+    for cell in &cells {
+        match Thumbnail::register(hwnd, cell.hwnd).and_then(|thumbnail| {
+            thumbnail.show(&cell.rect)?;
+            Ok(thumbnail)
+        }) {
+            Ok(thumbnail) => thumbnails.push(thumbnail),
+            Err(error) => {
+                tracing::warn!(
+                    "could not show window {} in the overview: {error}",
+                    cell.hwnd
+                );
+            }
+        }
+    }
+
+    let selected = cells
+        .iter()
+        .position(|cell| cell.hwnd == foreground_window)
+        .unwrap_or_default();
+
+    *OVERVIEW.lock() = Option::from(Overview {
+        hwnd,
+        columns,
+        cells,
+        selected,
+        previous_foreground_window: foreground_window,
+        _thumbnails: thumbnails,
+    });
+
+    WindowsApi::invalidate_rect(hwnd, None, false);
+    WindowsApi::raise_and_focus_window(hwnd)
+}
+
+/// Closes the overview without focusing any window, returning the window which was focused before
+/// it was shown if it was still open
+pub fn close() -> Option<isize> {
+    let overview = OVERVIEW.lock().take()?;
+
+    let hwnd = overview.hwnd;
+    let previous_foreground_window = overview.previous_foreground_window;
+    drop(overview);
+
+    if let Err(error) = WindowsApi::close_window(hwnd) {
+        tracing::error!("could not close the overview: {error}");
+    }
+
+    Option::from(previous_foreground_window)
+}
+
+/// Closes the overview and focuses the window which was focused before it was shown
+fn cancel() {
+    if let Some(hwnd) = close().filter(|hwnd| *hwnd != 0) {
+        if let Err(error) = WindowsApi::raise_and_focus_window(hwnd) {
+            tracing::warn!("could not focus window {hwnd} again after the overview: {error}");
+        }
+    }
+}
+
+/// Closes the overview and focuses the window which was selected in it
+fn select() {
+    let hwnd = OVERVIEW
+        .lock()
+        .as_ref()
+        .and_then(|overview| overview.cells.get(overview.selected))
+        .map(|cell| cell.hwnd);
+
+    close();
+
+    if let Some(hwnd) = hwnd {
+        if event_tx().try_send(hwnd).is_err() {
+            tracing::warn!("channel is full; dropping notification")
+        }
+    }
+}
+
+/// The number of columns in a grid which is as close to square as possible
+fn columns(windows: usize) -> usize {
+    let mut columns = 1;
+    while columns * columns < windows {
+        columns += 1;
+    }
+
+    columns
+}
+
+/// Fits windows of the given sizes into the cells of a grid with `columns` columns which fills
+/// `area`, keeping their aspect ratios and never making them larger than they are. The rects are
+/// relative to the top left corner of `area`.
+fn grid(area: &Rect, columns: usize, sizes: &[Rect]) -> Vec<Rect> {
+    let rows = sizes.len().div_ceil(columns).max(1);
+    let cell_width = (area.right - GAP) / columns as i32 - GAP;
+    let cell_height = (area.bottom - GAP) / rows as i32 - GAP;
+
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(idx, size)| {
+            let column = (idx % columns) as i32;
+            let row = (idx / columns) as i32;
+
+            let size_width = size.right.max(1);
+            let size_height = size.bottom.max(1);
+
+            let (width, height) = if size_width <= cell_width && size_height <= cell_height {
+                (size_width, size_height)
+            } else if size_width * cell_height > size_height * cell_width {
+                (cell_width, size_height * cell_width / size_width)
+            } else {
+                (size_width * cell_height / size_height, cell_height)
+            };
+
+            Rect {
+                left: GAP + column * (cell_width + GAP) + (cell_width - width) / 2,
+                top: GAP + row * (cell_height + GAP) + (cell_height - height) / 2,
+                right: width,
+                bottom: height,
+            }
+        })
+        .collect()
+}
+
+fn create_window(area: &Rect) -> Result<isize> {
+    let name: Vec<u16> = "komorebi-overview\0".encode_utf16().collect();
+    let class_name = PCWSTR(name.as_ptr());
+
+    let h_module = WindowsApi::module_handle_w()?;
+
+    let window_class = WNDCLASSW {
+        style: CS_HREDRAW | CS_VREDRAW,
+        lpfnWndProc: Some(callback),
+        hInstance: h_module.into(),
+        lpszClassName: class_name,
+        hbrBackground: WindowsApi::create_solid_brush(BACKGROUND_COLOUR),
+        ..Default::default()
+    };
+
+    let _ = WindowsApi::register_class_w(&window_class);
+
+    let (hwnd_sender, hwnd_receiver) = mpsc::channel();
+
+    let name_cl = name.clone();
+    let instance = h_module.0 as isize;
+    let area = *area;
+    std::thread::spawn(move || -> Result<()> {
+        unsafe {
+            let hwnd = CreateWindowExW(
+                WS_EX_TOOLWINDOW | WS_EX_TOPMOST,
+                PCWSTR(name_cl.as_ptr()),
+                PCWSTR(name_cl.as_ptr()),
+                WS_POPUP | WS_VISIBLE,
+                area.left,
+                area.top,
+                area.right,
+                area.bottom,
+                None,
+                None,
+                Option::from(HINSTANCE(windows_api::as_ptr!(instance))),
+                None,
+            )?;
+
+            hwnd_sender.send(hwnd.0 as isize)?;
+
+            let mut msg: MSG = MSG::default();
+
+            loop {
+                if !GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                    tracing::debug!("overview window event processing thread shutdown");
+                    break;
+                };
+                // TODO: error handling
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+
+        Ok(())
+    });
+
+    Ok(hwnd_receiver.recv()?)
+}
+
+unsafe extern "system" fn callback(
+    hwnd: HWND,
+    msg: u32,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    unsafe {
+        match msg {
+            WM_PAINT => {
+                let mut paint = PAINTSTRUCT::default();
+                let hdc = BeginPaint(hwnd, &mut paint);
+
+                let background = CreateSolidBrush(COLORREF(BACKGROUND_COLOUR));
+                FillRect(hdc, &paint.rcPaint, background);
+
+                let selected = OVERVIEW
+                    .lock()
+                    .as_ref()
+                    .and_then(|overview| overview.cells.get(overview.selected))
+                    .map(|cell| cell.rect);
+
+                // the thumbnail is drawn over this, leaving a frame around it
+                if let Some(mut frame) = selected {
+                    frame.add_margin(FRAME_WIDTH);
+
+                    let highlight =
+                        CreateSolidBrush(COLORREF(border_manager::FOCUSED.load(Ordering::Relaxed)));
+                    FillRect(hdc, &frame.rect(), highlight);
+                    // TODO: error handling
+                    let _ = DeleteObject(highlight.into());
+                }
+
+                // TODO: error handling
+                let _ = DeleteObject(background.into());
+                let _ = EndPaint(hwnd, &paint);
+
+                LRESULT(0)
+            }
+            WM_KEYDOWN => {
+                match VIRTUAL_KEY(w_param.0 as u16) {
+                    VK_ESCAPE => cancel(),
+                    VK_RETURN | VK_SPACE => select(),
+                    key => {
+                        let (columns, rows) = match key {
+                            VK_LEFT | VK_H => (-1, 0),
+                            VK_RIGHT | VK_L => (1, 0),
+                            VK_UP | VK_K => (0, -1),
+                            VK_DOWN | VK_J => (0, 1),
+                            _ => (0, 0),
+                        };
+
+                        if let Some(overview) = OVERVIEW.lock().as_mut() {
+                            overview.move_selection(columns, rows);
+                        }
+                    }
+                }
+
+                LRESULT(0)
+            }
+            WM_LBUTTONDOWN => {
+                let x = l_param.0 as i32 & 0xFFFF;
+                let y = (l_param.0 as i32 >> 16) & 0xFFFF;
+
+                let clicked = OVERVIEW.lock().as_mut().is_some_and(|overview| {
+                    match overview
+                        .cells
+                        .iter()
+                        .position(|cell| cell.rect.contains_point((x, y)))
+                    {
+                        Some(idx) => {
+                            overview.selected = idx;
+                            true
+                        }
+                        None => false,
+                    }
+                });
+
+                if clicked {
+                    select();
+                }
+
+                LRESULT(0)
+            }
+            // clicking or switching to another window closes the overview, leaving that window
+            // focused
+            WM_ACTIVATE if (w_param.0 & 0xFFFF) as u32 == WA_INACTIVE => {
+                close();
+                LRESULT(0)
+            }
+            WM_KILLFOCUS => {
+                close();
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                PostQuitMessage(0);
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, w_param, l_param),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        assert_eq!(columns(1), 1);
+        assert_eq!(columns(2), 2);
+        assert_eq!(columns(4), 2);
+        assert_eq!(columns(5), 3);
+        assert_eq!(columns(10), 4);
+    }
+
+    #[test]
+    fn test_grid_fits_windows_into_cells() {
+        let area = Rect {
+            left: 0,
+            top: 0,
+            right: 1000,
+            bottom: 600,
+        };
+
+        let large = Rect {
+            left: 0,
+            top: 0,
+            right: 1920,
+            bottom: 1080,
+        };
+
+        let small = Rect {
+            left: 0,
+            top: 0,
+            right: 100,
+            bottom: 100,
+        };
+
+        let cells = grid(&area, 2, &[large, large, small]);
+
+        // cells are 440x240, so a 16:9 window is limited by the height of its cell
+        assert_eq!(
+            cells[0],
+            Rect {
+                left: 47,
+                top: 40,
+                right: 426,
+                bottom: 240,
+            }
+        );
+
+        assert_eq!(cells[1].left, 527);
+        assert_eq!(cells[1].top, 40);
+
+        // windows which already fit are centred in their cell without being enlarged
+        assert_eq!(
+            cells[2],
+            Rect {
+                left: 210,
+                top: 390,
+                right: 100,
+                bottom: 100,
+            }
+        );
+    }
+}
@@ -0,0 +1,119 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::state_snapshot;
+use crate::static_config::Rules;
+use crate::watchdog;
+use crate::DATA_DIR;
+use crate::HOME_DIR;
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+pub const DEFAULT_SOCKET_NAME: &str = "komorebi.sock";
+
+/// The static configuration file which komorebi was started with, if any
+pub static STATIC_CONFIG: OnceLock<PathBuf> = OnceLock::new();
+
+static SOCKET_NAME: OnceLock<String> = OnceLock::new();
+
+/// Changes the name of the socket in the data directory which komorebi listens for commands on,
+/// or which clients send commands to, from the default `komorebi.sock`. This can only be done
+/// once, before anything has been sent or received.
+pub fn set_socket_name(name: &str) {
+    if SOCKET_NAME.set(name.to_string()).is_err() {
+        tracing::warn!("the socket name has already been set");
+    }
+}
+
+pub fn socket_name() -> &'static str {
+    SOCKET_NAME
+        .get()
+        .map_or(DEFAULT_SOCKET_NAME, String::as_str)
+}
+
+/// The name that files which belong to a single instance of komorebi are prefixed with, so that
+/// instances started with different socket names don't overwrite each other's files
+fn instance_prefix() -> Option<&'static str> {
+    match socket_name() {
+        DEFAULT_SOCKET_NAME => None,
+        name => Option::from(name.trim_end_matches(".sock")),
+    }
+}
+
+pub(crate) fn instance_file(name: &str) -> PathBuf {
+    match instance_prefix() {
+        None => DATA_DIR.join(name),
+        Some(prefix) => DATA_DIR.join(format!("{prefix}.{name}")),
+    }
+}
+
+/// The socket which komorebi listens for commands on
+pub fn socket_path() -> PathBuf {
+    DATA_DIR.join(socket_name())
+}
+
+/// The file which the hwnds of all managed windows are written to, so that they can be restored
+/// if komorebi is stopped unexpectedly
+pub fn hwnd_json_path() -> PathBuf {
+    instance_file("komorebi.hwnd.json")
+}
+
+/// The directory which the plaintext and colour log files are written to
+pub fn log_dir() -> PathBuf {
+    match instance_prefix() {
+        None => DATA_DIR.join("logs"),
+        Some(prefix) => DATA_DIR.join("logs").join(prefix),
+    }
+}
+
+/// The file which `QuickSave` writes to and `QuickLoad` reads from
+pub fn quicksave_path() -> PathBuf {
+    instance_file("quicksave.json")
+}
+
+/// The locations of all of the files which komorebi reads and writes, returned by
+/// `komorebic query paths`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Paths {
+    /// The directory which configuration files are looked up in ($Env:KOMOREBI_CONFIG_HOME or
+    /// the home directory)
+    pub config_home: PathBuf,
+    /// The static configuration file which komorebi was started with
+    pub static_config: Option<PathBuf>,
+    /// The directory which everything komorebi writes at runtime is kept in
+    pub data_dir: PathBuf,
+    /// The socket which komorebi listens for commands on
+    pub socket: PathBuf,
+    /// The file which the hwnds of all managed windows are written to
+    pub hwnds: PathBuf,
+    /// The directory which log files are written to
+    pub logs: PathBuf,
+    /// The file which `komorebic quick-save` writes to and `komorebic quick-load` reads from
+    pub quicksave: PathBuf,
+    /// The state snapshot which is applied on startup unless `--clean-state` is passed
+    pub state_snapshot: PathBuf,
+    /// The rules file which is kept up to date when `persist_rules` is enabled
+    pub persisted_rules: PathBuf,
+    /// The state dump which is written whenever a supervised thread panics
+    pub crash_dump: PathBuf,
+}
+
+impl Paths {
+    pub fn current() -> Self {
+        Self {
+            config_home: HOME_DIR.clone(),
+            static_config: STATIC_CONFIG.get().cloned(),
+            data_dir: DATA_DIR.clone(),
+            socket: socket_path(),
+            hwnds: hwnd_json_path(),
+            logs: log_dir(),
+            quicksave: quicksave_path(),
+            state_snapshot: state_snapshot::state_file_path(),
+            persisted_rules: Rules::persisted_path(),
+            crash_dump: watchdog::crash_dump_path(),
+        }
+    }
+}
@@ -6,7 +6,6 @@ use net2::TcpStreamExt;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::fs::File;
-use std::fs::OpenOptions;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
@@ -17,17 +16,22 @@ use std::str::FromStr;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
 use uds_windows::UnixStream;
 
 use crate::animation::ANIMATION_DURATION_PER_ANIMATION;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
+use crate::core::config_generation::BorderOverflowOffset;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
-use crate::core::ApplicationIdentifier;
 use crate::core::Axis;
 use crate::core::BorderImplementation;
+use crate::core::CustomLayout;
+use crate::core::DirectionalFocusBehaviour;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::Layout;
 use crate::core::MoveBehaviour;
@@ -35,6 +39,8 @@ use crate::core::OperationDirection;
 use crate::core::Rect;
 use crate::core::Sizing;
 use crate::core::SocketMessage;
+use crate::core::SocketResponse;
+use crate::core::SplitDirection;
 use crate::core::StateQuery;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowKind;
@@ -48,44 +54,70 @@ use crate::border_manager::IMPLEMENTATION;
 use crate::border_manager::STYLE;
 use crate::colour::Rgb;
 use crate::config_generation::WorkspaceMatchingRule;
-use crate::current_virtual_desktop;
+use crate::event_hooks;
+use crate::event_hooks::HookEvent;
+use crate::hotkey_manager;
+use crate::metrics;
+use crate::metrics::Metrics;
 use crate::monitor::MonitorInformation;
 use crate::notify_subscribers;
+use crate::overview;
+use crate::paths::quicksave_path;
+use crate::paths::Paths;
+use crate::process_movement;
 use crate::stackbar_manager;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
 use crate::stackbar_manager::STACKBAR_FONT_SIZE;
+use crate::static_config;
+use crate::static_config::Rules;
 use crate::static_config::StaticConfig;
+use crate::subscription_throttle;
 use crate::theme_manager;
 use crate::transparency_manager;
+use crate::watchdog;
 use crate::window::RuleDebug;
 use crate::window::Window;
 use crate::window_manager;
+use crate::window_manager::PingResponse;
 use crate::window_manager::WindowManager;
 use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
 use crate::workspace::WorkspaceLayer;
 use crate::workspace::WorkspaceWindowLocation;
+use crate::workspace_preview::WorkspacePreview;
 use crate::GlobalState;
 use crate::Notification;
 use crate::NotificationEvent;
 use crate::State;
+use crate::Subscribers;
+use crate::BORDER_OVERFLOW_IDENTIFIERS;
+use crate::BORDER_OVERFLOW_OFFSETS;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
 use crate::DISPLAY_INDEX_PREFERENCES;
+use crate::FLOATING_WINDOW_Z_ORDER;
+use crate::FRAME_COMPENSATIONS;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::INITIAL_CONFIGURATION_LOADED;
 use crate::LAYERED_WHITELIST;
+use crate::LOG_FILTER;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MAX_CONTAINER_WINDOWS;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PERSIST_RULES;
+use crate::PRESENTATION_APPLICATIONS;
 use crate::REMOVE_TITLEBARS;
+use crate::STACK_APPLICATION_IDENTIFIERS;
+use crate::STACK_BY_SAME_EXE;
 use crate::SUBSCRIPTION_PIPES;
 use crate::SUBSCRIPTION_SOCKETS;
 use crate::SUBSCRIPTION_SOCKET_OPTIONS;
 use crate::TCP_CONNECTIONS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use crate::VIRTUAL_DESKTOP_AWARENESS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
 use stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
@@ -96,6 +128,9 @@ use stackbar_manager::STACKBAR_TAB_HEIGHT;
 use stackbar_manager::STACKBAR_TAB_WIDTH;
 use stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 
+/// How often the window manager is checked for a window awaited with `SocketMessage::WaitForWindow`
+const WAIT_FOR_WINDOW_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 #[tracing::instrument]
 pub fn listen_for_commands(wm: Arc<Mutex<WindowManager>>) {
     std::thread::spawn(move || loop {
@@ -118,10 +153,12 @@ pub fn listen_for_commands(wm: Arc<Mutex<WindowManager>>) {
                                 Ok(()) => {}
                                 Err(error) => tracing::error!("{}", error),
                             }
-                            match read_commands_uds(&wm_clone, stream) {
-                                Ok(()) => {}
-                                Err(error) => tracing::error!("{}", error),
-                            }
+                            watchdog::supervise(&wm_clone, "command", || {
+                                match read_commands_uds(&wm_clone, stream) {
+                                    Ok(()) => {}
+                                    Err(error) => tracing::error!("{}", error),
+                                }
+                            });
                         });
                     }
                     Err(error) => {
@@ -165,10 +202,12 @@ pub fn listen_for_commands_tcp(wm: Arc<Mutex<WindowManager>>, port: usize) {
 
                     tracing::info!("listening for incoming tcp messages from {}", &addr);
 
-                    match read_commands_tcp(&wm, &mut stream, &addr) {
-                        Ok(()) => {}
-                        Err(error) => tracing::error!("{}", error),
-                    }
+                    watchdog::supervise(&wm, "tcp command", || {
+                        match read_commands_tcp(&wm, &mut stream, &addr) {
+                            Ok(()) => {}
+                            Err(error) => tracing::error!("{}", error),
+                        }
+                    });
                 }
                 Err(error) => {
                     tracing::error!("{}", error);
@@ -189,26 +228,35 @@ impl WindowManager {
         message: SocketMessage,
         mut reply: impl std::io::Write,
     ) -> Result<()> {
-        if let Some(virtual_desktop_id) = &self.virtual_desktop_id {
-            if let Some(id) = current_virtual_desktop() {
-                if id != *virtual_desktop_id {
-                    tracing::info!(
-                        "ignoring events and commands while not on virtual desktop {:?}",
-                        virtual_desktop_id
-                    );
-                    return Ok(());
-                }
-            }
+        // This has to be applied before reconciling so that it can be enabled from a virtual
+        // desktop which would otherwise be ignored
+        if let SocketMessage::VirtualDesktopAwareness(enable) = message {
+            VIRTUAL_DESKTOP_AWARENESS.store(enable, Ordering::SeqCst);
         }
 
+        if !self.reconcile_virtual_desktop()? {
+            return Ok(());
+        }
+
+        let message = self.remap_monitor_indices(message);
+
         #[allow(clippy::useless_asref)]
         // We don't have From implemented for &mut WindowManager
         let initial_state = State::from(self.as_ref());
+        let initial_focus = (
+            self.focused_monitor_idx(),
+            self.focused_workspace_idx().unwrap_or_default(),
+        );
+
+        // Only the rules which this message adds are persisted, rather than every rule which
+        // happens to be known, so that rules from the configuration file never end up in rules.json
+        let rules_before = modifies_rules(&message).then(Rules::current);
 
         match message {
             SocketMessage::CycleFocusEmptyWorkspace(_)
             | SocketMessage::CycleFocusWorkspace(_)
-            | SocketMessage::FocusWorkspaceNumber(_) => {
+            | SocketMessage::FocusWorkspaceNumber(_)
+            | SocketMessage::FocusWorkspaceNumberWithMouse(_) => {
                 if let Some(monitor) = self.focused_monitor_mut() {
                     let idx = monitor.focused_workspace_idx();
                     monitor.set_last_focused_workspace(Option::from(idx));
@@ -231,16 +279,37 @@ impl WindowManager {
                 self.focus_container_in_direction(direction)?;
                 self.promote_container_to_front()?
             }
-            SocketMessage::EagerFocus(ref exe) => {
+            SocketMessage::EagerFocus(_)
+            | SocketMessage::FocusWindowHwnd(_)
+            | SocketMessage::FocusWindowUnderCursor => {
                 let focused_monitor_idx = self.focused_monitor_idx();
                 let focused_workspace_idx = self.focused_workspace_idx()?;
 
+                // the point may be over a child control rather than the window itself
+                let cursor_hwnd = match message {
+                    SocketMessage::FocusWindowUnderCursor => {
+                        Some(WindowsApi::root_window(WindowsApi::window_at_cursor_pos()?))
+                    }
+                    _ => None,
+                };
+
                 let mut window_location = None;
                 let mut monitor_workspace_indices = None;
 
                 'search: for (monitor_idx, monitor) in self.monitors().iter().enumerate() {
                     for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
-                        if let Some(location) = workspace.location_from_exe(exe) {
+                        let location = match message {
+                            SocketMessage::EagerFocus(ref exe) => workspace.location_from_exe(exe),
+                            SocketMessage::FocusWindowHwnd(hwnd) => {
+                                workspace.location_from_hwnd(hwnd)
+                            }
+                            SocketMessage::FocusWindowUnderCursor => {
+                                cursor_hwnd.and_then(|hwnd| workspace.location_from_hwnd(hwnd))
+                            }
+                            _ => None,
+                        };
+
+                        if let Some(location) = location {
                             window_location = Some(location);
                             monitor_workspace_indices = Some((monitor_idx, workspace_idx));
                             break 'search;
@@ -293,13 +362,20 @@ impl WindowManager {
                 }
             }
             SocketMessage::FocusWindow(direction) => {
-                let focused_workspace = self.focused_workspace()?;
-                match focused_workspace.layer() {
-                    WorkspaceLayer::Tiling => {
-                        self.focus_container_in_direction(direction)?;
-                    }
-                    WorkspaceLayer::Floating => {
-                        self.focus_floating_window_in_direction(direction)?;
+                let focused_on_screen = matches!(
+                    self.directional_focus_behaviour,
+                    DirectionalFocusBehaviour::Geometric
+                ) && self.focus_nearest_window_in_direction(direction)?;
+
+                if !focused_on_screen {
+                    let focused_workspace = self.focused_workspace()?;
+                    match focused_workspace.layer() {
+                        WorkspaceLayer::Tiling => {
+                            self.focus_container_in_direction(direction)?;
+                        }
+                        WorkspaceLayer::Floating => {
+                            self.focus_floating_window_in_direction(direction)?;
+                        }
                     }
                 }
             }
@@ -357,6 +433,9 @@ impl WindowManager {
             SocketMessage::Close => {
                 Window::from(WindowsApi::foreground_window()?).close()?;
             }
+            SocketMessage::CloseWindowHwnd(hwnd) => {
+                Window::from(hwnd).close()?;
+            }
             SocketMessage::Minimize => {
                 Window::from(WindowsApi::foreground_window()?).minimize();
             }
@@ -396,6 +475,7 @@ impl WindowManager {
             }
             SocketMessage::ToggleLock => self.toggle_lock()?,
             SocketMessage::ToggleFloat => self.toggle_float()?,
+            SocketMessage::ToggleWorkspaceFloat => self.toggle_workspace_float()?,
             SocketMessage::ToggleMonocle => self.toggle_monocle()?,
             SocketMessage::ToggleMaximize => self.toggle_maximize()?,
             SocketMessage::ContainerPadding(monitor_idx, workspace_idx, size) => {
@@ -418,6 +498,18 @@ impl WindowManager {
                     self.set_workspace_padding(monitor_idx, workspace_idx, size)?;
                 }
             }
+            SocketMessage::MonitorContainerPadding(monitor_idx, size) => {
+                self.set_monitor_container_padding(monitor_idx, size)?;
+            }
+            SocketMessage::MonitorWorkspacePadding(monitor_idx, size) => {
+                self.set_monitor_workspace_padding(monitor_idx, size)?;
+            }
+            SocketMessage::DefaultContainerPadding(size) => {
+                self.set_default_container_padding(size)?;
+            }
+            SocketMessage::DefaultWorkspacePadding(size) => {
+                self.set_default_workspace_padding(size)?;
+            }
             SocketMessage::InitialWorkspaceRule(identifier, ref id, monitor_idx, workspace_idx) => {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 let workspace_matching_rule = WorkspaceMatchingRule {
@@ -515,6 +607,30 @@ impl WindowManager {
                 let mut workspace_rules = WORKSPACE_MATCHING_RULES.lock();
                 workspace_rules.clear();
             }
+            SocketMessage::Exec(ref command, ref workspace, floating) => {
+                let target = match workspace {
+                    Some(workspace) => Option::from(
+                        self.monitor_workspace_index_by_name(workspace)
+                            .ok_or_else(|| anyhow!("there is no workspace named {workspace}"))?,
+                    ),
+                    None => None,
+                };
+
+                self.exec(command, target, floating)?;
+            }
+            SocketMessage::WaitForWindow(identifier, ref id, _) => {
+                // the socket listeners wait for the window before processing this message, so all
+                // that is left is to report whether it turned up in time
+                let hwnd = self
+                    .managed_window_matching(&IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    })
+                    .ok_or_else(|| anyhow!("timed out waiting for a window matching {id}"))?;
+
+                tracing::info!("window {hwnd} matching {id} is managed");
+            }
             SocketMessage::EnforceWorkspaceRules => {
                 {
                     let mut already_moved = self.already_moved_window_handles.lock();
@@ -543,74 +659,18 @@ impl WindowManager {
                 }
             }
             SocketMessage::IgnoreRule(identifier, ref id) => {
-                let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
-
-                let mut should_push = true;
-                for i in &*ignore_identifiers {
-                    if let MatchingRule::Simple(i) = i {
-                        if i.id.eq(id) {
-                            should_push = false;
-                        }
-                    }
-                }
-
-                if should_push {
-                    ignore_identifiers.push(MatchingRule::Simple(IdWithIdentifier {
-                        kind: identifier,
-                        id: id.clone(),
-                        matching_strategy: Option::from(MatchingStrategy::Legacy),
-                    }));
-                }
-
-                let offset = self.work_area_offset;
-
-                let mut hwnds_to_purge = vec![];
-                for (i, monitor) in self.monitors().iter().enumerate() {
-                    for container in monitor
-                        .focused_workspace()
-                        .ok_or_else(|| anyhow!("there is no workspace"))?
-                        .containers()
-                    {
-                        for window in container.windows() {
-                            match identifier {
-                                ApplicationIdentifier::Path => {
-                                    if window.path()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Exe => {
-                                    if window.exe()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Class => {
-                                    if window.class()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                                ApplicationIdentifier::Title => {
-                                    if window.title()? == *id {
-                                        hwnds_to_purge.push((i, window.hwnd));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-
-                for (monitor_idx, hwnd) in hwnds_to_purge {
-                    let monitor = self
-                        .monitors_mut()
-                        .get_mut(monitor_idx)
-                        .ok_or_else(|| anyhow!("there is no monitor"))?;
-
-                    monitor
-                        .focused_workspace_mut()
-                        .ok_or_else(|| anyhow!("there is no focused workspace"))?
-                        .remove_window(hwnd)?;
-
-                    monitor.update_focused_workspace(offset)?;
-                }
+                self.add_ignore_rule(IdWithIdentifier {
+                    kind: identifier,
+                    id: id.clone(),
+                    matching_strategy: Option::from(MatchingStrategy::Legacy),
+                })?;
+            }
+            SocketMessage::IgnoreRuleWithMatchingStrategy(identifier, ref id, ref strategy) => {
+                self.add_ignore_rule(IdWithIdentifier {
+                    kind: identifier,
+                    id: id.clone(),
+                    matching_strategy: Option::from(strategy.clone()),
+                })?;
             }
             SocketMessage::FocusedWorkspaceContainerPadding(adjustment) => {
                 let focused_monitor_idx = self.focused_monitor_idx();
@@ -724,6 +784,11 @@ impl WindowManager {
                 let direction = self.direction_from_monitor_idx(monitor_idx);
                 self.move_container_to_monitor(monitor_idx, None, true, direction)?;
             }
+            SocketMessage::MoveContainerToMonitorAlias(ref alias) => {
+                let monitor_idx = self.monitor_idx_from_alias(alias)?;
+                let direction = self.direction_from_monitor_idx(monitor_idx);
+                self.move_container_to_monitor(monitor_idx, None, true, direction)?;
+            }
             SocketMessage::SwapWorkspacesToMonitorNumber(monitor_idx) => {
                 self.swap_focused_monitor(monitor_idx)?;
             }
@@ -740,6 +805,12 @@ impl WindowManager {
             SocketMessage::SendContainerToWorkspaceNumber(workspace_idx) => {
                 self.move_container_to_workspace(workspace_idx, false, None)?;
             }
+            SocketMessage::MoveContainerToVirtualDesktopNumber(desktop_idx) => {
+                self.move_container_to_virtual_desktop(desktop_idx, true)?;
+            }
+            SocketMessage::SendContainerToVirtualDesktopNumber(desktop_idx) => {
+                self.move_container_to_virtual_desktop(desktop_idx, false)?;
+            }
             SocketMessage::CycleSendContainerToWorkspace(direction) => {
                 let focused_monitor = self
                     .focused_monitor()
@@ -760,6 +831,11 @@ impl WindowManager {
                 let direction = self.direction_from_monitor_idx(monitor_idx);
                 self.move_container_to_monitor(monitor_idx, None, false, direction)?;
             }
+            SocketMessage::SendContainerToMonitorAlias(ref alias) => {
+                let monitor_idx = self.monitor_idx_from_alias(alias)?;
+                let direction = self.direction_from_monitor_idx(monitor_idx);
+                self.move_container_to_monitor(monitor_idx, None, false, direction)?;
+            }
             SocketMessage::CycleSendContainerToMonitor(direction) => {
                 let monitor_idx = direction.next_idx(
                     self.focused_monitor_idx(),
@@ -827,6 +903,9 @@ impl WindowManager {
 
                 self.move_workspace_to_monitor(monitor_idx)?;
             }
+            SocketMessage::TogglePause if self.is_soft_stopped => {
+                self.soft_resume()?;
+            }
             SocketMessage::TogglePause => {
                 if self.is_paused {
                     tracing::info!("resuming");
@@ -837,6 +916,16 @@ impl WindowManager {
                 self.is_paused = !self.is_paused;
                 self.retile_all(true)?;
             }
+            SocketMessage::Overview => {
+                overview::toggle(self)?;
+            }
+            SocketMessage::ToggleSoftStop => {
+                if self.is_soft_stopped {
+                    self.soft_resume()?;
+                } else {
+                    self.soft_stop()?;
+                }
+            }
             SocketMessage::ToggleTiling => {
                 self.toggle_tiling()?;
             }
@@ -854,6 +943,10 @@ impl WindowManager {
                 self.focus_monitor(monitor_idx)?;
                 self.update_focused_workspace(self.mouse_follows_focus, true)?;
             }
+            SocketMessage::FocusMonitorAlias(ref alias) => {
+                self.focus_monitor(self.monitor_idx_from_alias(alias)?)?;
+                self.update_focused_workspace(self.mouse_follows_focus, true)?;
+            }
             SocketMessage::FocusMonitorAtCursor => {
                 if let Some(monitor_idx) = self.monitor_idx_from_current_pos() {
                     self.focus_monitor(monitor_idx)?;
@@ -867,12 +960,23 @@ impl WindowManager {
                 border_manager::destroy_all_borders()?;
                 self.retile_all(true)?
             }
+            SocketMessage::BalanceWorkspace => self.balance_workspace()?,
             SocketMessage::FlipLayout(layout_flip) => self.flip_layout(layout_flip)?,
+            SocketMessage::ToggleSplitDirection => self.toggle_split_direction()?,
+            SocketMessage::SplitHorizontal => {
+                self.set_split_direction(SplitDirection::Horizontal)?;
+            }
+            SocketMessage::SplitVertical => self.set_split_direction(SplitDirection::Vertical)?,
             SocketMessage::ChangeLayout(layout) => self.change_workspace_layout_default(layout)?,
             SocketMessage::CycleLayout(direction) => self.cycle_layout(direction)?,
             SocketMessage::ChangeLayoutCustom(ref path) => {
                 self.change_workspace_custom_layout(path)?;
             }
+            SocketMessage::SaveCustomLayout(ref path) => {
+                self.focused_workspace()?
+                    .effective_custom_layout()?
+                    .to_path(path)?;
+            }
             SocketMessage::WorkspaceLayoutCustom(monitor_idx, workspace_idx, ref path) => {
                 self.set_workspace_layout_custom(monitor_idx, workspace_idx, path)?;
             }
@@ -1122,7 +1226,8 @@ impl WindowManager {
                     .ok_or_else(|| anyhow!("there is no monitor"))?
                     .set_last_focused_workspace(Option::from(idx));
             }
-            SocketMessage::FocusWorkspaceNumber(workspace_idx) => {
+            SocketMessage::FocusWorkspaceNumber(workspace_idx)
+            | SocketMessage::FocusWorkspaceNumberWithMouse(workspace_idx) => {
                 // This is to ensure that even on an empty workspace on a secondary monitor, the
                 // secondary monitor where the cursor is focused will be used as the target for
                 // the workspace switch op
@@ -1141,6 +1246,10 @@ impl WindowManager {
                 if self.focused_workspace_idx().unwrap_or_default() != workspace_idx {
                     self.focus_workspace(workspace_idx)?;
                 }
+
+                if matches!(message, SocketMessage::FocusWorkspaceNumberWithMouse(_)) {
+                    self.center_cursor_in_focused_workspace()?;
+                }
             }
             SocketMessage::FocusWorkspaceNumbers(workspace_idx) => {
                 // This is to ensure that even on an empty workspace on a secondary monitor, the
@@ -1325,6 +1434,10 @@ impl WindowManager {
 
                 tracing::info!("replying to global state done");
             }
+            SocketMessage::Ping => {
+                let pong = serde_json::to_string_pretty(&PingResponse::from(&*self))?;
+                reply.write_all(pong.as_bytes())?;
+            }
             SocketMessage::VisibleWindows => {
                 let mut monitor_visible_windows = HashMap::new();
 
@@ -1353,6 +1466,19 @@ impl WindowManager {
 
                 reply.write_all(monitors_state.as_bytes())?;
             }
+            SocketMessage::PreviewLayout(layout) => {
+                let preview = self
+                    .focused_workspace()?
+                    .preview_layout(&Layout::Default(layout));
+
+                reply.write_all(serde_json::to_string_pretty(&preview)?.as_bytes())?;
+            }
+            SocketMessage::PreviewLayoutCustom(ref path) => {
+                let layout = Layout::Custom(CustomLayout::from_path(path)?);
+                let preview = self.focused_workspace()?.preview_layout(&layout);
+
+                reply.write_all(serde_json::to_string_pretty(&preview)?.as_bytes())?;
+            }
             SocketMessage::Query(query) => {
                 let response = match query {
                     StateQuery::FocusedMonitorIndex => self.focused_monitor_idx().to_string(),
@@ -1377,6 +1503,13 @@ impl WindowManager {
                             .focused_workspace_name()
                             .unwrap_or_else(|| focused_monitor.focused_workspace_idx().to_string())
                     }
+                    StateQuery::Metrics => {
+                        serde_json::to_string_pretty(&Metrics::snapshot(self.known_hwnds.len()))?
+                    }
+                    StateQuery::Paths => serde_json::to_string_pretty(&Paths::current())?,
+                    StateQuery::WorkspacePreviews => {
+                        serde_json::to_string_pretty(&WorkspacePreview::all(self))?
+                    }
                 };
 
                 reply.write_all(response.as_bytes())?;
@@ -1584,9 +1717,51 @@ impl WindowManager {
                     }
                 }
             }
+            SocketMessage::FocusFollowsMouseHoverDelay(delay) => {
+                process_movement::HOVER_DELAY.store(delay, Ordering::SeqCst);
+            }
+            SocketMessage::FocusFollowsMouseExclusionZone(zone) => {
+                let mut zones = process_movement::EXCLUSION_ZONES.lock();
+                if !zones.contains(&zone) {
+                    zones.push(zone);
+                }
+            }
+            SocketMessage::FocusFollowsMouseIgnoreRule(identifier, ref id) => {
+                let mut identifiers = process_movement::EXCLUDED_IDENTIFIERS.lock();
+
+                let mut should_push = true;
+                for i in &*identifiers {
+                    if let MatchingRule::Simple(i) = i {
+                        if i.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    }));
+                }
+            }
+            SocketMessage::ClearFocusFollowsMouseExclusions => {
+                process_movement::EXCLUSION_ZONES.lock().clear();
+                process_movement::EXCLUDED_IDENTIFIERS.lock().clear();
+            }
             SocketMessage::ReloadConfiguration => {
                 Self::reload_configuration();
             }
+            SocketMessage::ApplySpecificConfiguration(ref path) => {
+                static_config::apply_app_specific_configuration(path)?;
+            }
+            SocketMessage::DumpRules(ref path) => {
+                Rules::current().write(path)?;
+            }
+            SocketMessage::LoadRules(ref path) => {
+                Rules::read(path)?.apply()?;
+            }
             SocketMessage::ReplaceConfiguration(ref config) => {
                 // Check that this is a valid static config file first
                 if StaticConfig::read(config).is_ok() {
@@ -1619,6 +1794,15 @@ impl WindowManager {
             SocketMessage::ReloadStaticConfiguration(ref pathbuf) => {
                 self.reload_static_configuration(pathbuf)?;
             }
+            SocketMessage::ReloadBindings => {
+                hotkey_manager::reload_bindings()?;
+            }
+            SocketMessage::EnterKeybindingMode(ref mode) => {
+                hotkey_manager::enter_mode(mode)?;
+            }
+            SocketMessage::ExitKeybindingMode => {
+                hotkey_manager::exit_mode();
+            }
             SocketMessage::CompleteConfiguration => {
                 if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
                     INITIAL_CONFIGURATION_LOADED.store(true, Ordering::SeqCst);
@@ -1667,6 +1851,25 @@ impl WindowManager {
                     }));
                 }
             }
+            SocketMessage::IdentifyStackApplication(identifier, ref id) => {
+                let mut identifiers = STACK_APPLICATION_IDENTIFIERS.lock();
+                let mut should_push = true;
+                for i in &*identifiers {
+                    if let MatchingRule::Simple(i) = i {
+                        if i.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    }));
+                }
+            }
             SocketMessage::IdentifyLayeredApplication(identifier, ref id) => {
                 let mut identifiers = LAYERED_WHITELIST.lock();
 
@@ -1687,13 +1890,68 @@ impl WindowManager {
                     }));
                 }
             }
+            SocketMessage::IdentifyBorderOverflowApplication(identifier, ref id) => {
+                let mut identifiers = BORDER_OVERFLOW_IDENTIFIERS.lock();
+
+                let mut should_push = true;
+                for i in &*identifiers {
+                    if let MatchingRule::Simple(i) = i {
+                        if i.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    }));
+                }
+
+                FRAME_COMPENSATIONS.lock().clear();
+            }
+            SocketMessage::IdentifyBorderOverflowWithOffset(identifier, ref id, offset) => {
+                let mut offsets = BORDER_OVERFLOW_OFFSETS.lock();
+
+                let matching_rule = MatchingRule::Simple(IdWithIdentifier {
+                    kind: identifier,
+                    id: id.clone(),
+                    matching_strategy: Option::from(MatchingStrategy::Legacy),
+                });
+
+                // identifying the same application again replaces its offset
+                offsets.retain(|existing| existing.matching_rule != matching_rule);
+                offsets.push(BorderOverflowOffset {
+                    matching_rule,
+                    offset,
+                });
+
+                FRAME_COMPENSATIONS.lock().clear();
+            }
             SocketMessage::ManageFocusedWindow => {
                 self.manage_focused_window()?;
             }
             SocketMessage::UnmanageFocusedWindow => {
                 self.unmanage_focused_window()?;
             }
-            SocketMessage::InvisibleBorders(_rect) => {}
+            SocketMessage::ManageWindowByHwnd(hwnd) => {
+                self.manage_window(hwnd)?;
+            }
+            SocketMessage::UnmanageWindowByHwnd(hwnd) => {
+                self.unmanage_window(hwnd)?;
+            }
+            SocketMessage::MoveWindowToWorkspace(hwnd, monitor_idx, workspace_idx) => {
+                self.move_window_to_workspace(hwnd, monitor_idx, workspace_idx)?;
+            }
+            SocketMessage::InvisibleBorders(_rect) => {
+                // invisible borders are detected for each window, and can be overridden for an
+                // application with IdentifyBorderOverflowWithOffset
+                tracing::warn!(
+                    "InvisibleBorders is deprecated and has no effect, use IdentifyBorderOverflowWithOffset instead"
+                );
+            }
             SocketMessage::WorkAreaOffset(rect) => {
                 self.work_area_offset = Option::from(rect);
                 self.retile_all(false)?;
@@ -1713,54 +1971,30 @@ impl WindowManager {
                 self.retile_all(true)?;
             }
             SocketMessage::QuickSave => {
-                let workspace = self.focused_workspace()?;
-                let resize = workspace.resize_dimensions();
-
-                let quicksave_json = std::env::temp_dir().join("komorebi.quicksave.json");
-
-                let file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .create(true)
-                    .open(quicksave_json)?;
-
-                serde_json::to_writer_pretty(&file, &resize)?;
+                self.save_focused_workspace(&quicksave_path(), &[])?;
+            }
+            SocketMessage::QuickSaveWithOptions(ref options) => {
+                self.save_focused_workspace(&quicksave_path(), options)?;
             }
             SocketMessage::QuickLoad => {
-                let workspace = self.focused_workspace_mut()?;
-
-                let quicksave_json = std::env::temp_dir().join("komorebi.quicksave.json");
+                let quicksave_json = quicksave_path();
 
                 let file = File::open(&quicksave_json)
                     .map_err(|_| anyhow!("no quicksave found at {}", quicksave_json.display()))?;
 
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
-
-                workspace.set_resize_dimensions(resize);
-                self.update_focused_workspace(false, false)?;
+                self.load_focused_workspace(serde_json::from_reader(file)?)?;
             }
             SocketMessage::Save(ref path) => {
-                let workspace = self.focused_workspace_mut()?;
-                let resize = workspace.resize_dimensions();
-
-                let file = OpenOptions::new()
-                    .write(true)
-                    .truncate(true)
-                    .create(true)
-                    .open(path)?;
-
-                serde_json::to_writer_pretty(&file, &resize)?;
+                self.save_focused_workspace(path, &[])?;
+            }
+            SocketMessage::SaveWithOptions(ref path, ref options) => {
+                self.save_focused_workspace(path, options)?;
             }
             SocketMessage::Load(ref path) => {
-                let workspace = self.focused_workspace_mut()?;
-
                 let file =
                     File::open(path).map_err(|_| anyhow!("no file found at {}", path.display()))?;
 
-                let resize: Vec<Option<Rect>> = serde_json::from_reader(file)?;
-
-                workspace.set_resize_dimensions(resize);
-                self.update_focused_workspace(false, false)?;
+                self.load_focused_workspace(serde_json::from_reader(file)?)?;
             }
             SocketMessage::AddSubscriberSocket(ref socket) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
@@ -1778,6 +2012,7 @@ impl WindowManager {
             SocketMessage::RemoveSubscriberSocket(ref socket) => {
                 let mut sockets = SUBSCRIPTION_SOCKETS.lock();
                 sockets.remove(socket);
+                subscription_throttle::remove(socket);
             }
             SocketMessage::AddSubscriberPipe(ref subscriber) => {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
@@ -1792,12 +2027,34 @@ impl WindowManager {
                 let mut pipes = SUBSCRIPTION_PIPES.lock();
                 pipes.remove(subscriber);
             }
+            SocketMessage::ListSubscribers => {
+                let subscribers = serde_json::to_string_pretty(&Subscribers::current())?;
+                reply.write_all(subscribers.as_bytes())?;
+            }
             SocketMessage::MouseFollowsFocus(enable) => {
                 self.mouse_follows_focus = enable;
             }
             SocketMessage::ToggleMouseFollowsFocus => {
                 self.mouse_follows_focus = !self.mouse_follows_focus;
             }
+            SocketMessage::VirtualDesktopAwareness(_) => {
+                // Already applied before reconciling the current virtual desktop
+            }
+            SocketMessage::StackBySameExe(enable) => {
+                STACK_BY_SAME_EXE.store(enable, Ordering::SeqCst);
+            }
+            SocketMessage::MaxContainerWindows(max_windows) => {
+                // existing stacks are left as they are, the limit applies to windows added later
+                MAX_CONTAINER_WINDOWS.store(max_windows, Ordering::SeqCst);
+            }
+            SocketMessage::LogLevel(level) => {
+                if let Some(handle) = LOG_FILTER.get() {
+                    handle.reload(
+                        EnvFilter::default().add_directive(LevelFilter::from(level).into()),
+                    )?;
+                    tracing::info!("log level set to {level}");
+                }
+            }
             SocketMessage::ResizeDelta(delta) => {
                 self.resize_delta = delta;
             }
@@ -1852,6 +2109,10 @@ impl WindowManager {
                 let mut hiding_behaviour = HIDING_BEHAVIOUR.lock();
                 *hiding_behaviour = behaviour;
             }
+            SocketMessage::FloatingWindowZOrder(z_order) => {
+                *FLOATING_WINDOW_Z_ORDER.lock() = z_order;
+                self.update_focused_workspace(false, false)?;
+            }
             SocketMessage::ToggleCrossMonitorMoveBehaviour => {
                 match self.cross_monitor_move_behaviour {
                     MoveBehaviour::Swap => {
@@ -1866,6 +2127,12 @@ impl WindowManager {
             SocketMessage::CrossMonitorMoveBehaviour(behaviour) => {
                 self.cross_monitor_move_behaviour = behaviour;
             }
+            SocketMessage::MonocleWithNewWindowBehaviour(behaviour) => {
+                self.monocle_with_new_window_behaviour = behaviour;
+            }
+            SocketMessage::DirectionalFocusBehaviour(behaviour) => {
+                self.directional_focus_behaviour = behaviour;
+            }
             SocketMessage::UnmanagedWindowOperationBehaviour(behaviour) => {
                 self.unmanaged_window_operation_behaviour = behaviour;
             }
@@ -2031,8 +2298,7 @@ impl WindowManager {
             SocketMessage::SocketSchema => {
                 #[cfg(feature = "schemars")]
                 {
-                    let socket_message = schemars::schema_for!(SocketMessage);
-                    let schema = serde_json::to_string_pretty(&socket_message)?;
+                    let schema = serde_json::to_string_pretty(&crate::socket_protocol_schema())?;
 
                     reply.write_all(schema.as_bytes())?;
                 }
@@ -2058,6 +2324,29 @@ impl WindowManager {
 
                 reply.write_all(config.as_bytes())?;
             }
+            SocketMessage::IdentifyPresentationApplication(identifier, ref id) => {
+                let mut identifiers = PRESENTATION_APPLICATIONS.lock();
+
+                let mut should_push = true;
+                for i in &*identifiers {
+                    if let MatchingRule::Simple(i) = i {
+                        if i.id.eq(id) {
+                            should_push = false;
+                        }
+                    }
+                }
+
+                if should_push {
+                    identifiers.push(MatchingRule::Simple(IdWithIdentifier {
+                        kind: identifier,
+                        id: id.clone(),
+                        matching_strategy: Option::from(MatchingStrategy::Legacy),
+                    }));
+                }
+            }
+            SocketMessage::TogglePresentationMode => {
+                self.toggle_presentation_mode();
+            }
             SocketMessage::RemoveTitleBar(identifier, ref id) => {
                 let mut identifiers = NO_TITLEBAR.lock();
 
@@ -2084,21 +2373,44 @@ impl WindowManager {
                 self.update_focused_workspace(false, false)?;
             }
             SocketMessage::DebugWindow(hwnd) => {
-                let window = Window::from(hwnd);
-                let mut rule_debug = RuleDebug::default();
-                let _ = window.should_manage(None, &mut rule_debug);
+                let mut rule_debug = RuleDebug::for_window(Window::from(hwnd));
+                rule_debug.managed_location = self.known_hwnds.get(&hwnd).copied();
                 let schema = serde_json::to_string_pretty(&rule_debug)?;
 
                 reply.write_all(schema.as_bytes())?;
             }
+            SocketMessage::DebugVisibleWindows => {
+                let rule_debugs: Vec<_> = WindowsApi::visible_windows()?
+                    .into_iter()
+                    .map(|window| {
+                        let mut rule_debug = RuleDebug::for_window(window);
+                        rule_debug.managed_location = self.known_hwnds.get(&window.hwnd).copied();
+                        rule_debug
+                    })
+                    .collect();
+
+                reply.write_all(serde_json::to_string_pretty(&rule_debugs)?.as_bytes())?;
+            }
             SocketMessage::Theme(theme) => {
                 theme_manager::send_notification(theme);
             }
             // Deprecated commands
-            SocketMessage::AltFocusHack(_)
-            | SocketMessage::IdentifyBorderOverflowApplication(_, _) => {}
+            SocketMessage::AltFocusHack(_) => {}
         };
 
+        if let Some(rules_before) = rules_before {
+            Rules::record_runtime_changes(&rules_before, &Rules::current());
+
+            if PERSIST_RULES.load(Ordering::SeqCst) {
+                let path = Rules::persisted_path();
+                if let Err(error) = Rules::runtime().write(&path) {
+                    tracing::warn!("could not persist rules to {}: {error}", path.display());
+                }
+            }
+        }
+
+        self.reconcile_owned_windows()?;
+
         // Update list of known_hwnds and their monitor/workspace index pair
         self.update_known_hwnds();
 
@@ -2110,6 +2422,18 @@ impl WindowManager {
             initial_state.has_been_modified(self.as_ref()),
         )?;
 
+        let focus = (
+            self.focused_monitor_idx(),
+            self.focused_workspace_idx().unwrap_or_default(),
+        );
+
+        if focus != initial_focus {
+            event_hooks::run(HookEvent::WorkspaceFocused, || Notification {
+                event: NotificationEvent::Socket(message.clone()),
+                state: self.as_ref().into(),
+            });
+        }
+
         border_manager::send_notification(None);
         transparency_manager::send_notification();
         stackbar_manager::send_notification();
@@ -2117,6 +2441,185 @@ impl WindowManager {
         tracing::info!("processed");
         Ok(())
     }
+
+    /// Process a command, writing a [`SocketResponse::Error`] back to the client if it fails so
+    /// that callers such as komorebic can detect and surface the failure
+    pub fn process_command_with_error_reply(
+        &mut self,
+        message: SocketMessage,
+        mut reply: impl std::io::Write,
+    ) -> Result<()> {
+        let started = Instant::now();
+        let result = self.process_command(message.clone(), &mut reply);
+        metrics::record_command(&message, started.elapsed(), result.is_err());
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                reply_with_error(&mut reply, &error.to_string());
+                Err(error)
+            }
+        }
+    }
+
+    /// Translates the monitor indices in a command from the ones used in the configuration, which
+    /// follow `display_index_preferences`, to the current indices of those monitors, so that
+    /// bindings keep targeting the same monitors however Windows happens to enumerate them
+    fn remap_monitor_indices(&self, message: SocketMessage) -> SocketMessage {
+        let m = |usr_idx| self.monitor_idx_from_usr_idx(usr_idx);
+
+        match message {
+            SocketMessage::MoveContainerToMonitorNumber(m_idx) => {
+                SocketMessage::MoveContainerToMonitorNumber(m(m_idx))
+            }
+            SocketMessage::SendContainerToMonitorNumber(m_idx) => {
+                SocketMessage::SendContainerToMonitorNumber(m(m_idx))
+            }
+            SocketMessage::SendContainerToMonitorWorkspaceNumber(m_idx, w_idx) => {
+                SocketMessage::SendContainerToMonitorWorkspaceNumber(m(m_idx), w_idx)
+            }
+            SocketMessage::MoveContainerToMonitorWorkspaceNumber(m_idx, w_idx) => {
+                SocketMessage::MoveContainerToMonitorWorkspaceNumber(m(m_idx), w_idx)
+            }
+            SocketMessage::MoveWorkspaceToMonitorNumber(m_idx) => {
+                SocketMessage::MoveWorkspaceToMonitorNumber(m(m_idx))
+            }
+            SocketMessage::SwapWorkspacesToMonitorNumber(m_idx) => {
+                SocketMessage::SwapWorkspacesToMonitorNumber(m(m_idx))
+            }
+            SocketMessage::LockMonitorWorkspaceContainer(m_idx, w_idx, c_idx) => {
+                SocketMessage::LockMonitorWorkspaceContainer(m(m_idx), w_idx, c_idx)
+            }
+            SocketMessage::UnlockMonitorWorkspaceContainer(m_idx, w_idx, c_idx) => {
+                SocketMessage::UnlockMonitorWorkspaceContainer(m(m_idx), w_idx, c_idx)
+            }
+            SocketMessage::EnsureWorkspaces(m_idx, count) => {
+                SocketMessage::EnsureWorkspaces(m(m_idx), count)
+            }
+            SocketMessage::EnsureNamedWorkspaces(m_idx, names) => {
+                SocketMessage::EnsureNamedWorkspaces(m(m_idx), names)
+            }
+            SocketMessage::FocusMonitorNumber(m_idx) => SocketMessage::FocusMonitorNumber(m(m_idx)),
+            SocketMessage::FocusMonitorWorkspaceNumber(m_idx, w_idx) => {
+                SocketMessage::FocusMonitorWorkspaceNumber(m(m_idx), w_idx)
+            }
+            SocketMessage::ContainerPadding(m_idx, w_idx, size) => {
+                SocketMessage::ContainerPadding(m(m_idx), w_idx, size)
+            }
+            SocketMessage::WorkspacePadding(m_idx, w_idx, size) => {
+                SocketMessage::WorkspacePadding(m(m_idx), w_idx, size)
+            }
+            SocketMessage::MonitorContainerPadding(m_idx, size) => {
+                SocketMessage::MonitorContainerPadding(m(m_idx), size)
+            }
+            SocketMessage::MonitorWorkspacePadding(m_idx, size) => {
+                SocketMessage::MonitorWorkspacePadding(m(m_idx), size)
+            }
+            SocketMessage::WorkspaceTiling(m_idx, w_idx, tile) => {
+                SocketMessage::WorkspaceTiling(m(m_idx), w_idx, tile)
+            }
+            SocketMessage::WorkspaceName(m_idx, w_idx, name) => {
+                SocketMessage::WorkspaceName(m(m_idx), w_idx, name)
+            }
+            SocketMessage::WorkspaceLayout(m_idx, w_idx, layout) => {
+                SocketMessage::WorkspaceLayout(m(m_idx), w_idx, layout)
+            }
+            SocketMessage::WorkspaceLayoutCustom(m_idx, w_idx, path) => {
+                SocketMessage::WorkspaceLayoutCustom(m(m_idx), w_idx, path)
+            }
+            SocketMessage::WorkspaceLayoutRule(m_idx, w_idx, threshold, layout) => {
+                SocketMessage::WorkspaceLayoutRule(m(m_idx), w_idx, threshold, layout)
+            }
+            SocketMessage::WorkspaceLayoutCustomRule(m_idx, w_idx, threshold, path) => {
+                SocketMessage::WorkspaceLayoutCustomRule(m(m_idx), w_idx, threshold, path)
+            }
+            SocketMessage::ClearWorkspaceLayoutRules(m_idx, w_idx) => {
+                SocketMessage::ClearWorkspaceLayoutRules(m(m_idx), w_idx)
+            }
+            SocketMessage::MonitorWorkAreaOffset(m_idx, rect) => {
+                SocketMessage::MonitorWorkAreaOffset(m(m_idx), rect)
+            }
+            SocketMessage::InitialWorkspaceRule(identifier, id, m_idx, w_idx) => {
+                SocketMessage::InitialWorkspaceRule(identifier, id, m(m_idx), w_idx)
+            }
+            SocketMessage::WorkspaceRule(identifier, id, m_idx, w_idx) => {
+                SocketMessage::WorkspaceRule(identifier, id, m(m_idx), w_idx)
+            }
+            SocketMessage::ClearWorkspaceRules(m_idx, w_idx) => {
+                SocketMessage::ClearWorkspaceRules(m(m_idx), w_idx)
+            }
+            SocketMessage::MoveWindowToWorkspace(hwnd, m_idx, w_idx) => {
+                SocketMessage::MoveWindowToWorkspace(hwnd, m(m_idx), w_idx)
+            }
+            message => message,
+        }
+    }
+}
+
+/// Whether a command adds or removes rules which should be written to the persisted rules file
+fn modifies_rules(message: &SocketMessage) -> bool {
+    matches!(
+        message,
+        SocketMessage::IgnoreRule(..)
+            | SocketMessage::IgnoreRuleWithMatchingStrategy(..)
+            | SocketMessage::ManageRule(..)
+            | SocketMessage::IdentifyObjectNameChangeApplication(..)
+            | SocketMessage::IdentifyTrayApplication(..)
+            | SocketMessage::IdentifyStackApplication(..)
+            | SocketMessage::IdentifyLayeredApplication(..)
+            | SocketMessage::IdentifyBorderOverflowApplication(..)
+            | SocketMessage::IdentifyBorderOverflowWithOffset(..)
+            | SocketMessage::RemoveTitleBar(..)
+            | SocketMessage::IdentifyPresentationApplication(..)
+            | SocketMessage::InitialWorkspaceRule(..)
+            | SocketMessage::InitialNamedWorkspaceRule(..)
+            | SocketMessage::WorkspaceRule(..)
+            | SocketMessage::NamedWorkspaceRule(..)
+            | SocketMessage::ClearWorkspaceRules(..)
+            | SocketMessage::ClearNamedWorkspaceRules(..)
+            | SocketMessage::ClearAllWorkspaceRules
+            | SocketMessage::LoadRules(..)
+    )
+}
+
+fn reply_with_error(mut reply: impl std::io::Write, message: &str) {
+    let response = SocketResponse::Error {
+        message: message.to_string(),
+    };
+
+    match response.as_bytes() {
+        Ok(bytes) => {
+            if let Err(error) = reply.write_all(&bytes) {
+                tracing::warn!("could not write error response to client: {error}");
+            }
+        }
+        Err(error) => tracing::error!("could not serialize error response: {error}"),
+    }
+}
+
+/// Blocks a `SocketMessage::WaitForWindow` until a managed window matches it or its timeout has
+/// elapsed, only holding the window manager lock while checking so that events can still be
+/// processed in the meantime
+fn wait_for_window(wm: &Arc<Mutex<WindowManager>>, message: &SocketMessage) {
+    let SocketMessage::WaitForWindow(identifier, id, timeout) = message else {
+        return;
+    };
+
+    let identifier = IdWithIdentifier {
+        kind: *identifier,
+        id: id.clone(),
+        matching_strategy: Option::from(MatchingStrategy::Legacy),
+    };
+    let timeout = Duration::from_millis(*timeout);
+    let started = Instant::now();
+
+    while started.elapsed() < timeout {
+        if wm.lock().managed_window_matching(&identifier).is_some() {
+            return;
+        }
+
+        std::thread::sleep(WAIT_FOR_WINDOW_POLL_INTERVAL);
+    }
 }
 
 pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream) -> Result<()> {
@@ -2128,19 +2631,32 @@ pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream)
     for line in reader.lines() {
         let message = SocketMessage::from_str(&line?)?;
 
+        wait_for_window(wm, &message);
+
         match wm.try_lock_for(Duration::from_secs(1)) {
             None => {
                 tracing::warn!(
                     "could not acquire window manager lock, not processing message: {message}"
                 );
+
+                reply_with_error(
+                    &mut stream,
+                    &format!(
+                        "could not acquire window manager lock, not processing message: {message}"
+                    ),
+                );
             }
             Some(mut wm) => {
                 if wm.is_paused {
                     return match message {
                         SocketMessage::TogglePause
+                        | SocketMessage::ToggleSoftStop
                         | SocketMessage::State
                         | SocketMessage::GlobalState
-                        | SocketMessage::Stop => Ok(wm.process_command(message, &mut stream)?),
+                        | SocketMessage::Ping
+                        | SocketMessage::Stop => {
+                            Ok(wm.process_command_with_error_reply(message, &mut stream)?)
+                        }
                         _ => {
                             tracing::trace!("ignoring while paused");
                             Ok(())
@@ -2148,7 +2664,7 @@ pub fn read_commands_uds(wm: &Arc<Mutex<WindowManager>>, mut stream: UnixStream)
                     };
                 }
 
-                wm.process_command(message.clone(), &mut stream)?;
+                wm.process_command_with_error_reply(message.clone(), &mut stream)?;
             }
         }
     }
@@ -2181,14 +2697,20 @@ pub fn read_commands_tcp(
                     break;
                 };
 
+                wait_for_window(wm, &message);
+
                 let mut wm = wm.lock();
 
                 if wm.is_paused {
                     return match message {
                         SocketMessage::TogglePause
+                        | SocketMessage::ToggleSoftStop
                         | SocketMessage::State
                         | SocketMessage::GlobalState
-                        | SocketMessage::Stop => Ok(wm.process_command(message, stream)?),
+                        | SocketMessage::Ping
+                        | SocketMessage::Stop => {
+                            Ok(wm.process_command_with_error_reply(message, stream)?)
+                        }
                         _ => {
                             tracing::trace!("ignoring while paused");
                             Ok(())
@@ -2196,7 +2718,7 @@ pub fn read_commands_tcp(
                     };
                 }
 
-                wm.process_command(message.clone(), &mut *stream)?;
+                wm.process_command_with_error_reply(message.clone(), &mut *stream)?;
             }
         }
     }
@@ -2210,6 +2732,7 @@ mod tests {
     use crate::window_manager::WindowManager;
     use crate::Rect;
     use crate::SocketMessage;
+    use crate::SocketResponse;
     use crate::WindowManagerEvent;
     use crossbeam_channel::bounded;
     use crossbeam_channel::Receiver;
@@ -2272,4 +2795,35 @@ mod tests {
 
         std::fs::remove_file(socket_path).unwrap();
     }
+
+    #[test]
+    fn test_error_response_on_failed_command() {
+        let (_sender, receiver): (Sender<WindowManagerEvent>, Receiver<WindowManagerEvent>) =
+            bounded(1);
+        let socket_name = format!("komorebi-test-{}.sock", Uuid::new_v4());
+        let socket_path = PathBuf::from(&socket_name);
+        let mut wm = WindowManager::new(receiver, Some(socket_path.clone())).unwrap();
+        let m = monitor::new(
+            0,
+            Rect::default(),
+            Rect::default(),
+            "TestMonitor".to_string(),
+            "TestDevice".to_string(),
+            "TestDeviceID".to_string(),
+            Some("TestMonitorID".to_string()),
+        );
+
+        wm.monitors_mut().push_back(m);
+
+        // there is only one monitor, so this command should fail
+        let mut reply = vec![];
+        assert!(wm
+            .process_command_with_error_reply(SocketMessage::FocusMonitorNumber(5), &mut reply)
+            .is_err());
+
+        let response: SocketResponse = serde_json::from_slice(&reply).unwrap();
+        assert!(matches!(response, SocketResponse::Error { .. }));
+
+        std::fs::remove_file(socket_path).unwrap();
+    }
 }
@@ -8,18 +8,21 @@ use color_eyre::Result;
 use crossbeam_utils::atomic::AtomicConsume;
 use parking_lot::Mutex;
 
-use crate::core::OperationDirection;
+use crate::core::MonocleWithNewWindowBehaviour;
 use crate::core::Rect;
-use crate::core::Sizing;
 use crate::core::WindowContainerBehaviour;
 
 use crate::border_manager;
-use crate::border_manager::BORDER_OFFSET;
-use crate::border_manager::BORDER_WIDTH;
-use crate::current_virtual_desktop;
+use crate::com;
+use crate::container::Container;
+use crate::event_hooks;
+use crate::event_hooks::HookEvent;
+use crate::metrics;
 use crate::notify_subscribers;
 use crate::stackbar_manager;
 use crate::transparency_manager;
+use crate::watchdog;
+use crate::window;
 use crate::window::should_act;
 use crate::window::RuleDebug;
 use crate::window_manager::WindowManager;
@@ -30,35 +33,41 @@ use crate::workspace::WorkspaceLayer;
 use crate::workspace_reconciliator;
 use crate::workspace_reconciliator::ALT_TAB_HWND;
 use crate::workspace_reconciliator::ALT_TAB_HWND_INSTANT;
+use crate::FocusNotification;
 use crate::Notification;
 use crate::NotificationEvent;
 use crate::State;
 use crate::FLOATING_APPLICATIONS;
 use crate::HIDDEN_HWNDS;
 use crate::REGEX_IDENTIFIERS;
+use crate::STACK_APPLICATION_IDENTIFIERS;
+use crate::STACK_BY_SAME_EXE;
+use crate::STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
 
 #[tracing::instrument]
 pub fn listen_for_events(wm: Arc<Mutex<WindowManager>>) {
     let receiver = wm.lock().incoming_events.clone();
 
-    std::thread::spawn(move || {
-        tracing::info!("listening");
-        loop {
-            if let Ok(event) = receiver.recv() {
-                let mut guard = wm.lock();
-                match guard.process_event(event) {
-                    Ok(()) => {}
-                    Err(error) => {
-                        if cfg!(debug_assertions) {
-                            tracing::error!("{:?}", error)
-                        } else {
-                            tracing::error!("{}", error)
+    std::thread::spawn(move || loop {
+        watchdog::supervise(&wm, "event", || {
+            tracing::info!("listening");
+            loop {
+                if let Ok(event) = receiver.recv() {
+                    let mut guard = wm.lock();
+                    match guard.process_event(event) {
+                        Ok(()) => {}
+                        Err(error) => {
+                            if cfg!(debug_assertions) {
+                                tracing::error!("{:?}", error)
+                            } else {
+                                tracing::error!("{}", error)
+                            }
                         }
                     }
                 }
             }
-        }
+        });
     });
 }
 
@@ -71,6 +80,15 @@ impl WindowManager {
             return Ok(());
         }
 
+        metrics::EVENTS_PROCESSED.fetch_add(1, Ordering::SeqCst);
+
+        // this has to happen before checking if the window should be managed, because it won't be
+        // once it has been destroyed
+        if let WindowManagerEvent::Destroy(_, window) = event {
+            window::record_destroyed(window.hwnd);
+            self.forget_stashed_window(window.hwnd);
+        }
+
         let mut rule_debug = RuleDebug::default();
 
         let should_manage = event.window().should_manage(Some(event), &mut rule_debug)?;
@@ -120,21 +138,26 @@ impl WindowManager {
             }
         }
 
-        if let Some(virtual_desktop_id) = &self.virtual_desktop_id {
-            if let Some(id) = current_virtual_desktop() {
-                if id != *virtual_desktop_id {
-                    tracing::info!(
-                        "ignoring events and commands while not on virtual desktop {:?}",
-                        virtual_desktop_id
-                    );
-                    return Ok(());
-                }
-            }
+        if !self.reconcile_virtual_desktop()? {
+            return Ok(());
+        }
+
+        // Windows on other virtual desktops keep sending events, such as when they are cloaked
+        // while switching away from them, which must not touch the workspaces of this one
+        if self.virtual_desktop_id.is_some()
+            && !matches!(event, WindowManagerEvent::Destroy(_, _))
+            && !com::is_window_on_current_virtual_desktop(event.hwnd()).unwrap_or(true)
+        {
+            tracing::trace!("ignoring event for a window on another virtual desktop");
+            return Ok(());
         }
 
         #[allow(clippy::useless_asref)]
         // We don't have From implemented for &mut WindowManager
         let initial_state = State::from(self.as_ref());
+        let initial_monitor_idx = self.focused_monitor_idx();
+        let initial_workspace_idx = self.focused_workspace_idx().unwrap_or_default();
+        let initially_managed = self.known_hwnds.contains_key(&event.hwnd());
 
         // Make sure we have the most recently focused monitor from any event
         match event {
@@ -186,11 +209,11 @@ impl WindowManager {
                 if self.focused_workspace()?.contains_window(window.hwnd) {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
+                }
 
-                    let mut already_moved_window_handles = self.already_moved_window_handles.lock();
+                let mut already_moved_window_handles = self.already_moved_window_handles.lock();
 
-                    already_moved_window_handles.remove(&window.hwnd);
-                }
+                already_moved_window_handles.remove(&window.hwnd);
             }
             WindowManagerEvent::Minimize(_, window) => {
                 let mut hide = false;
@@ -243,14 +266,16 @@ impl WindowManager {
                     }
                 }
 
+                // Windows hidden by us when switching workspaces or moving them elsewhere are
+                // still known, so initial workspace rules must not be applied to them again
                 if hide {
                     self.focused_workspace_mut()?.remove_window(window.hwnd)?;
                     self.update_focused_workspace(false, false)?;
-                }
 
-                let mut already_moved_window_handles = self.already_moved_window_handles.lock();
+                    let mut already_moved_window_handles = self.already_moved_window_handles.lock();
 
-                already_moved_window_handles.remove(&window.hwnd);
+                    already_moved_window_handles.remove(&window.hwnd);
+                }
             }
             WindowManagerEvent::FocusChange(_, window) => {
                 // don't want to trigger the full workspace updates when there are no managed
@@ -359,16 +384,51 @@ impl WindowManager {
                         }
                     }
 
+                    // The first window of an application launched with `SocketMessage::Exec`
+                    // goes straight to the workspace which was requested for it
+                    let mut launched_floating = false;
+                    if proceed
+                        && !matches!(event, WindowManagerEvent::Manage(_))
+                        && !self.known_hwnds.contains_key(&window.hwnd)
+                    {
+                        if let Some(launch) = self.take_pending_launch(window) {
+                            match launch.target {
+                                Some(target) if target != focused_pair => {
+                                    self.place_launched_window(window, target, launch.floating)?;
+                                    proceed = false;
+                                }
+                                _ => launched_floating = launch.floating,
+                            }
+                        }
+                    }
+
                     if proceed {
                         let mut behaviour = self.window_management_behaviour(
                             focused_monitor_idx,
                             focused_workspace_idx,
                         );
+
+                        // dialogs and tool windows float above the managed window which owns them
+                        let owner = window
+                            .owner()
+                            .filter(|owner| self.known_hwnds.contains_key(owner));
+
+                        // remember where the window opened so that a soft stop can put it back
+                        if !self.known_hwnds.contains_key(&window.hwnd) {
+                            if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                                self.pre_tiling_rects.entry(window.hwnd).or_insert(rect);
+                            }
+
+                            window.refresh_minimum_size();
+                        }
+
                         let workspace = self.focused_workspace_mut()?;
                         let workspace_contains_window = workspace.contains_window(window.hwnd);
                         let monocle_container = workspace.monocle_container().clone();
 
                         if !workspace_contains_window && !needs_reconciliation {
+                            window::record_shown(window);
+
                             let floating_applications = FLOATING_APPLICATIONS.lock();
                             let mut should_float = false;
 
@@ -390,17 +450,73 @@ impl WindowManager {
                                 }
                             }
 
+                            let is_owned =
+                                owner.is_some() && !matches!(event, WindowManagerEvent::Manage(_));
+
                             behaviour.float_override = behaviour.float_override
+                                || is_owned
+                                || launched_floating
                                 || (should_float
                                     && !matches!(event, WindowManagerEvent::Manage(_)));
 
+                            // windows of stack applications, of tray and multi-window
+                            // applications, or of any application when stacking by exe is
+                            // enabled, join the container which already holds one of their
+                            // windows instead of taking up a tile of their own
+                            let mut stack_container_idx = None;
+                            let stack_tray_and_multi_window_applications =
+                                STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS.load(Ordering::SeqCst);
+                            let stack_by_same_exe = STACK_BY_SAME_EXE.load(Ordering::SeqCst);
+                            let has_stack_applications =
+                                !STACK_APPLICATION_IDENTIFIERS.lock().is_empty();
+
+                            if !behaviour.float_override
+                                && (stack_by_same_exe
+                                    || stack_tray_and_multi_window_applications
+                                    || has_stack_applications)
+                            {
+                                if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+                                    (window.title(), window.exe(), window.class(), window.path())
+                                {
+                                    let regex_identifiers = REGEX_IDENTIFIERS.lock();
+                                    let should_stack = stack_by_same_exe
+                                        || (has_stack_applications
+                                            && should_act(
+                                                &title,
+                                                &exe_name,
+                                                &class,
+                                                &path,
+                                                &STACK_APPLICATION_IDENTIFIERS.lock(),
+                                                &regex_identifiers,
+                                            )
+                                            .is_some())
+                                        || (stack_tray_and_multi_window_applications
+                                            && should_act(
+                                                &title,
+                                                &exe_name,
+                                                &class,
+                                                &path,
+                                                &TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock(),
+                                                &regex_identifiers,
+                                            )
+                                            .is_some());
+
+                                    if should_stack {
+                                        stack_container_idx =
+                                            workspace.container_idx_for_exe(&exe_name);
+                                    }
+                                }
+                            }
+
                             if behaviour.float_override {
                                 // Center floating windows if we are already on the `Floating`
                                 // layer and the window doesn't match a `floating_windows` rule and
-                                // the workspace is not a floating workspace
+                                // the workspace is not a floating workspace, leaving owned windows
+                                // where their application placed them
                                 let center_spawned_floats =
                                     matches!(workspace.layer, WorkspaceLayer::Floating)
                                         && !should_float
+                                        && !is_owned
                                         && workspace.tile;
                                 workspace.floating_windows_mut().push_back(window);
                                 workspace.set_layer(WorkspaceLayer::Floating);
@@ -409,6 +525,19 @@ impl WindowManager {
                                     floating_window.center(&workspace.globals().work_area)?;
                                 }
                                 self.update_focused_workspace(false, false)?;
+
+                                if let Some(owner) = owner.filter(|_| is_owned) {
+                                    self.owned_windows.insert(window.hwnd, owner);
+                                }
+                            } else if let Some(idx) = stack_container_idx {
+                                workspace.focus_container(idx);
+                                workspace
+                                    .focused_container_mut()
+                                    .ok_or_else(|| anyhow!("there is no focused container"))?
+                                    .add_window(window);
+                                workspace.set_layer(WorkspaceLayer::Tiling);
+                                self.update_focused_workspace(true, false)?;
+                                stackbar_manager::send_notification();
                             } else {
                                 match behaviour.current_behaviour {
                                     WindowContainerBehaviour::Create => {
@@ -416,6 +545,17 @@ impl WindowManager {
                                         workspace.set_layer(WorkspaceLayer::Tiling);
                                         self.update_focused_workspace(false, false)?;
                                     }
+                                    WindowContainerBehaviour::Append
+                                        if workspace
+                                            .focused_container()
+                                            .is_some_and(Container::is_full) =>
+                                    {
+                                        // the focused stack is full, so the window spills over
+                                        // into a new container of its own
+                                        workspace.new_container_for_window(window);
+                                        workspace.set_layer(WorkspaceLayer::Tiling);
+                                        self.update_focused_workspace(false, false)?;
+                                    }
                                     WindowContainerBehaviour::Append => {
                                         workspace
                                             .focused_container_mut()
@@ -444,6 +584,11 @@ impl WindowManager {
                                 // it is still empty.
                                 window.focus(self.mouse_follows_focus)?;
                             }
+
+                            // new tiled windows join the layout behind the monocle container
+                            if monocle_container.is_some() && !behaviour.float_override {
+                                self.monocle_new_window(window)?;
+                            }
                         }
 
                         if workspace_contains_window {
@@ -483,6 +628,10 @@ impl WindowManager {
                 let pending_move_op = Arc::make_mut(&mut self.pending_move_op);
                 *pending_move_op = None;
 
+                // Some windows change their minimum size along with their size, such as when
+                // their content reflows
+                window.refresh_minimum_size();
+
                 // If the window handles don't match then something went wrong and the pending move
                 // is not related to this current move, if so abort this operation.
                 if let Some((_, _, w_hwnd)) = pending {
@@ -651,6 +800,18 @@ impl WindowManager {
                                 }
                                 WindowContainerBehaviour::Append => {
                                     match workspace.container_idx_from_current_point() {
+                                        // a full stack can't take another window, so the
+                                        // containers are swapped instead
+                                        Some(target_idx)
+                                            if workspace
+                                                .containers()
+                                                .get(target_idx)
+                                                .is_some_and(Container::is_full) =>
+                                        {
+                                            workspace
+                                                .swap_containers(focused_container_idx, target_idx);
+                                            self.update_focused_workspace(false, false)?;
+                                        }
                                         Some(target_idx) => {
                                             workspace.move_window_to_container(target_idx)?;
                                             self.update_focused_workspace(false, false)?;
@@ -669,48 +830,7 @@ impl WindowManager {
                         }
                     } else {
                         tracing::info!("resizing with mouse");
-                        let mut ops = vec![];
-
-                        macro_rules! resize_op {
-                            ($coordinate:expr, $comparator:tt, $direction:expr) => {{
-                                let adjusted = $coordinate * 2;
-                                let sizing = if adjusted $comparator 0 {
-                                    Sizing::Decrease
-                                } else {
-                                    Sizing::Increase
-                                };
-
-                                ($direction, sizing, adjusted.abs())
-                            }};
-                        }
-
-                        if resize.left != 0 {
-                            ops.push(resize_op!(resize.left, >, OperationDirection::Left));
-                        }
-
-                        if resize.top != 0 {
-                            ops.push(resize_op!(resize.top, >, OperationDirection::Up));
-                        }
-
-                        // TODO: Determine if this is still needed
-                        let top_left_constant = BORDER_WIDTH.load(Ordering::SeqCst)
-                            + BORDER_OFFSET.load(Ordering::SeqCst);
-
-                        if resize.right != 0
-                            && (resize.left == top_left_constant || resize.left == 0)
-                        {
-                            ops.push(resize_op!(resize.right, <, OperationDirection::Right));
-                        }
-
-                        if resize.bottom != 0
-                            && (resize.top == top_left_constant || resize.top == 0)
-                        {
-                            ops.push(resize_op!(resize.bottom, <, OperationDirection::Down));
-                        }
-
-                        for (edge, sizing, delta) in ops {
-                            self.resize_window(edge, sizing, delta, true)?;
-                        }
+                        self.resize_by_edge_deltas(resize)?;
 
                         self.update_focused_workspace(false, false)?;
                     }
@@ -726,6 +846,8 @@ impl WindowManager {
             window.center(&self.focused_monitor_work_area()?)?;
         }
 
+        self.reconcile_owned_windows()?;
+
         // Update list of known_hwnds and their monitor/workspace index pair
         self.update_known_hwnds();
 
@@ -737,6 +859,44 @@ impl WindowManager {
             initial_state.has_been_modified(self.as_ref()),
         )?;
 
+        if !initially_managed && self.known_hwnds.contains_key(&event.hwnd()) {
+            event_hooks::run(HookEvent::WindowManaged, || Notification {
+                event: NotificationEvent::WindowManager(event),
+                state: self.as_ref().into(),
+            });
+        }
+
+        let monitor_idx = self.focused_monitor_idx();
+        let workspace_idx = self.focused_workspace_idx().unwrap_or_default();
+        let focus_notification = if monitor_idx != initial_monitor_idx {
+            Some(FocusNotification::MonitorFocusChange(
+                monitor_idx,
+                workspace_idx,
+            ))
+        } else if workspace_idx != initial_workspace_idx {
+            Some(FocusNotification::WorkspaceFocusChange(
+                monitor_idx,
+                workspace_idx,
+            ))
+        } else {
+            None
+        };
+
+        if let Some(focus_notification) = focus_notification {
+            notify_subscribers(
+                Notification {
+                    event: NotificationEvent::Focus(focus_notification),
+                    state: self.as_ref().into(),
+                },
+                true,
+            )?;
+
+            event_hooks::run(HookEvent::WorkspaceFocused, || Notification {
+                event: NotificationEvent::Focus(focus_notification),
+                state: self.as_ref().into(),
+            });
+        }
+
         border_manager::send_notification(Some(event.hwnd()));
         transparency_manager::send_notification();
         stackbar_manager::send_notification();
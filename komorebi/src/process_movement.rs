@@ -1,19 +1,56 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
 
+use lazy_static::lazy_static;
 use parking_lot::Mutex;
 use winput::message_loop;
 use winput::message_loop::Event;
 use winput::Action;
 
+use crate::core::config_generation::MatchingRule;
 use crate::core::FocusFollowsMouseImplementation;
+use crate::core::Rect;
 
 use crate::window_manager::WindowManager;
 
+/// How long (in milliseconds) the cursor has to rest before the window under it is raised
+pub static HOVER_DELAY: AtomicU64 = AtomicU64::new(0);
+
+lazy_static! {
+    /// Screen regions (e.g. a bar) in which mouse movements never trigger a raise
+    pub static ref EXCLUSION_ZONES: Arc<Mutex<Vec<Rect>>> = Arc::new(Mutex::new(vec![]));
+    /// Windows which never trigger a raise when the cursor moves over them
+    pub static ref EXCLUDED_IDENTIFIERS: Arc<Mutex<Vec<MatchingRule>>> =
+        Arc::new(Mutex::new(vec![]));
+}
+
 #[tracing::instrument]
 pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
-    std::thread::spawn(move || {
-        let mut ignore_movement = false;
+    let ignore_movement = Arc::new(AtomicBool::new(false));
+    let (hover_tx, hover_rx) = crossbeam_channel::unbounded::<()>();
+
+    {
+        let wm = wm.clone();
+        let ignore_movement = ignore_movement.clone();
+
+        std::thread::spawn(move || {
+            while hover_rx.recv().is_ok() {
+                let delay = Duration::from_millis(HOVER_DELAY.load(Ordering::SeqCst));
+
+                // Keep waiting until the cursor has stopped moving for the whole delay
+                while hover_rx.recv_timeout(delay).is_ok() {}
 
+                if !ignore_movement.load(Ordering::SeqCst) {
+                    raise_window_at_cursor_pos(&wm);
+                }
+            }
+        });
+    }
+
+    std::thread::spawn(move || {
         let receiver = message_loop::start().expect("could not start winput message loop");
 
         loop {
@@ -25,14 +62,15 @@ pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
                 match receiver.next_event() {
                     // Don't want to send any raise events while we are dragging or resizing
                     Event::MouseButton { action, .. } => match action {
-                        Action::Press => ignore_movement = true,
-                        Action::Release => ignore_movement = false,
+                        Action::Press => ignore_movement.store(true, Ordering::SeqCst),
+                        Action::Release => ignore_movement.store(false, Ordering::SeqCst),
                     },
                     Event::MouseMoveRelative { .. } => {
-                        if !ignore_movement {
-                            match wm.lock().raise_window_at_cursor_pos() {
-                                Ok(()) => {}
-                                Err(error) => tracing::error!("{}", error),
+                        if !ignore_movement.load(Ordering::SeqCst) {
+                            if HOVER_DELAY.load(Ordering::SeqCst) == 0 {
+                                raise_window_at_cursor_pos(&wm);
+                            } else if let Err(error) = hover_tx.send(()) {
+                                tracing::error!("{}", error);
                             }
                         }
                     }
@@ -42,3 +80,10 @@ pub fn listen_for_movements(wm: Arc<Mutex<WindowManager>>) {
         }
     });
 }
+
+fn raise_window_at_cursor_pos(wm: &Arc<Mutex<WindowManager>>) {
+    match wm.lock().raise_window_at_cursor_pos() {
+        Ok(()) => {}
+        Err(error) => tracing::error!("{}", error),
+    }
+}
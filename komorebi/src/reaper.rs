@@ -2,12 +2,12 @@
 
 use crate::border_manager;
 use crate::notify_subscribers;
+use crate::paths;
 use crate::winevent::WinEvent;
 use crate::NotificationEvent;
 use crate::Window;
 use crate::WindowManager;
 use crate::WindowManagerEvent;
-use crate::DATA_DIR;
 
 use crossbeam_channel::Receiver;
 use crossbeam_channel::Sender;
@@ -128,7 +128,7 @@ fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> color_eyre::Result<()>
         }
 
         // Save to file
-        let hwnd_json = DATA_DIR.join("komorebi.hwnd.json");
+        let hwnd_json = paths::hwnd_json_path();
         let file = OpenOptions::new()
             .write(true)
             .truncate(true)
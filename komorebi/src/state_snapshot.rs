@@ -0,0 +1,87 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::paths;
+use crate::State;
+
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// How long the state has to remain unchanged before a new snapshot is written to disk
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The location of the state snapshot which is kept up to date while komorebi is running, and
+/// which is applied on startup unless komorebi is started with `--clean-state`
+pub fn state_file_path() -> PathBuf {
+    paths::instance_file("state.json")
+}
+
+pub struct Notification(State);
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(50))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+/// Queues a `State` to be serialized and written to the state file once mutations settle down
+pub fn send_notification(state: State) {
+    if event_tx().try_send(Notification(state)).is_err() {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+pub fn listen_for_notifications() {
+    std::thread::spawn(move || loop {
+        match handle_notifications() {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                tracing::warn!("restarting failed thread: {}", error);
+            }
+        }
+    });
+}
+
+pub fn handle_notifications() -> color_eyre::Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    while let Ok(mut notification) = receiver.recv() {
+        // Only the most recent state matters, so keep replacing it until
+        // nothing new has come in for the whole debounce window
+        while let Ok(latest) = receiver.recv_timeout(DEBOUNCE) {
+            notification = latest;
+        }
+
+        write_state_file(&serde_json::to_string(&notification.0)?)?;
+    }
+
+    Ok(())
+}
+
+/// Writes to a temporary file first and then renames it over the state file, so that external
+/// readers never observe a partially written snapshot
+fn write_state_file(state: &str) -> color_eyre::Result<()> {
+    let state_file = state_file_path();
+    let tmp_file = state_file.with_extension("json.tmp");
+
+    std::fs::write(&tmp_file, state)?;
+    std::fs::rename(&tmp_file, &state_file)?;
+
+    tracing::debug!("wrote state snapshot to {}", state_file.display());
+
+    Ok(())
+}
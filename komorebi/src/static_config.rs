@@ -18,6 +18,7 @@ use crate::config_generation::WorkspaceMatchingRule;
 use crate::core::config_generation::ApplicationConfiguration;
 use crate::core::config_generation::ApplicationConfigurationGenerator;
 use crate::core::config_generation::ApplicationOptions;
+use crate::core::config_generation::BorderOverflowOffset;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
 use crate::core::resolve_home_path;
@@ -25,9 +26,12 @@ use crate::core::AnimationStyle;
 use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::DefaultLayout;
+use crate::core::DirectionalFocusBehaviour;
+use crate::core::FloatingZOrder;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::MonocleWithNewWindowBehaviour;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
 use crate::core::Rect;
@@ -37,9 +41,13 @@ use crate::core::StackbarMode;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
 use crate::current_virtual_desktop;
+use crate::event_hooks;
+use crate::event_hooks::EventHook;
+use crate::hotkey_manager;
 use crate::monitor;
 use crate::monitor::Monitor;
 use crate::monitor_reconciliator;
+use crate::paths;
 use crate::ring::Ring;
 use crate::stackbar_manager::STACKBAR_FOCUSED_TEXT_COLOUR;
 use crate::stackbar_manager::STACKBAR_FONT_FAMILY;
@@ -52,6 +60,7 @@ use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
 use crate::theme_manager;
 use crate::transparency_manager;
+use crate::tray_manager;
 use crate::window;
 use crate::window_manager::WindowManager;
 use crate::window_manager_event::WindowManagerEvent;
@@ -61,27 +70,42 @@ use crate::AspectRatio;
 use crate::Axis;
 use crate::CrossBoundaryBehaviour;
 use crate::PredefinedAspectRatio;
-use crate::DATA_DIR;
+use crate::AUTO_NAME_WORKSPACES;
+use crate::BORDER_OVERFLOW_IDENTIFIERS;
+use crate::BORDER_OVERFLOW_OFFSETS;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::FLOATING_APPLICATIONS;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
+use crate::FLOATING_WINDOW_Z_ORDER;
+use crate::FRAME_COMPENSATIONS;
 use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MAX_CONTAINER_WINDOWS;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
 use crate::OBJECT_NAME_CHANGE_TITLE_IGNORE_LIST;
+use crate::PERSIST_RULES;
+use crate::PRESENTATION_APPLICATIONS;
 use crate::REGEX_IDENTIFIERS;
+use crate::RUNTIME_RULES;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::STACK_APPLICATION_IDENTIFIERS;
+use crate::STACK_BY_SAME_EXE;
+use crate::STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS;
+use crate::TRANSIENT_APPLICATIONS;
+use crate::TRANSIENT_WINDOW_LIFETIME;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use crate::VIRTUAL_DESKTOP_AWARENESS;
 use crate::WINDOWS_11;
 use crate::WORKSPACE_MATCHING_RULES;
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 use crossbeam_channel::Receiver;
 use hotwatch::EventKind;
@@ -143,10 +167,11 @@ pub struct WorkspaceConfig {
     /// Workspace padding (default: global)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace_padding: Option<i32>,
-    /// Initial workspace application rules
+    /// Initial workspace application rules, applied once when a window first appears so that it
+    /// can be moved to another workspace afterwards without being sent back
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_workspace_rules: Option<Vec<MatchingRule>>,
-    /// Permanent workspace application rules
+    /// Permanent workspace application rules, enforced every time a matching window is seen
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspace_rules: Option<Vec<MatchingRule>>,
     /// Apply this monitor's window-based work area offset (default: true)
@@ -248,6 +273,9 @@ impl From<&Workspace> for WorkspaceConfig {
 pub struct MonitorConfig {
     /// Workspace configurations
     pub workspaces: Vec<WorkspaceConfig>,
+    /// Alias which commands can use to refer to this monitor instead of its index, e.g. "main"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
     /// Monitor-specific work area offset (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub work_area_offset: Option<Rect>,
@@ -293,6 +321,7 @@ impl From<&Monitor> for MonitorConfig {
 
         Self {
             workspaces,
+            alias: None,
             work_area_offset: value.work_area_offset(),
             window_based_work_area_offset: value.window_based_work_area_offset(),
             window_based_work_area_offset_limit: Some(value.window_based_work_area_offset_limit()),
@@ -316,7 +345,8 @@ pub enum AppSpecificConfigurationPath {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The `komorebi.json` static configuration file reference for `v0.1.36`
 pub struct StaticConfig {
-    /// DEPRECATED from v0.1.22: no longer required
+    /// DEPRECATED from v0.1.22: no longer required, invisible borders are detected for each window
+    /// and can be overridden for an application with `border_overflow_offsets`
     #[serde(skip_serializing_if = "Option::is_none")]
     pub invisible_borders: Option<Rect>,
     /// DISCOURAGED: Minimum width for a window to be eligible for tiling
@@ -341,6 +371,12 @@ pub struct StaticConfig {
     /// Determine what happens when an action is called on a window at a monitor boundary (default: Monitor)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cross_boundary_behaviour: Option<CrossBoundaryBehaviour>,
+    /// Determine what happens when a new window opens while a monocle container is active (default: Background)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monocle_with_new_window_behaviour: Option<MonocleWithNewWindowBehaviour>,
+    /// Determine which windows directional focus commands can reach (default: Layer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directional_focus_behaviour: Option<DirectionalFocusBehaviour>,
     /// Determine what happens when commands are sent while an unmanaged window is in the foreground (default: Op)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unmanaged_window_operation_behaviour: Option<OperationBehaviour>,
@@ -400,6 +436,9 @@ pub struct StaticConfig {
     /// Which Windows signal to use when hiding windows (default: Cloak)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub window_hiding_behaviour: Option<HidingBehaviour>,
+    /// Where floating windows are kept in the z-order relative to tiled windows (default: Normal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_window_z_order: Option<FloatingZOrder>,
     /// Global work area (space used for tiling) offset (default: None)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub global_work_area_offset: Option<Rect>,
@@ -413,12 +452,42 @@ pub struct StaticConfig {
     /// Identify applications which should be managed as floating windows
     #[serde(skip_serializing_if = "Option::is_none")]
     pub floating_applications: Option<Vec<MatchingRule>>,
-    /// Identify border overflow applications
+    /// Identify transient windows such as splash screens and tooltips which should never be
+    /// managed, in addition to those which are recognised automatically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transient_applications: Option<Vec<MatchingRule>>,
+    /// Windows which are destroyed within this many milliseconds of being shown, e.g. splash
+    /// screens, are remembered as transient and windows like them are not managed for the next
+    /// five minutes (default: 0, disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transient_window_lifetime: Option<u64>,
+    /// Identify border overflow applications, whose invisible borders are measured every time they
+    /// are positioned instead of once when they start being managed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub border_overflow_applications: Option<Vec<MatchingRule>>,
+    /// Identify applications whose invisible borders should be compensated for with an explicit
+    /// offset for each edge instead of the one which is detected automatically
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_overflow_offsets: Option<Vec<BorderOverflowOffset>>,
     /// Identify tray and multi-window applications
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tray_and_multi_window_applications: Option<Vec<MatchingRule>>,
+    /// Stack all windows of tray and multi-window applications into the container which holds the
+    /// first of their windows instead of giving each window its own tile (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_tray_and_multi_window_applications: Option<bool>,
+    /// Stack every new window into the container which already holds a window of the same
+    /// application instead of giving it its own tile (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_by_same_exe: Option<bool>,
+    /// Maximum number of windows in a container, after which new windows spill over into a new
+    /// container instead of growing the stack (default: unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_container_windows: Option<usize>,
+    /// Stack all windows of these applications into the container which already holds one of
+    /// their windows on the same workspace instead of giving each window its own tile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stack_applications: Option<Vec<MatchingRule>>,
     /// Identify applications that have the WS_EX_LAYERED extended window style
     #[serde(skip_serializing_if = "Option::is_none")]
     pub layered_applications: Option<Vec<MatchingRule>>,
@@ -431,7 +500,8 @@ pub struct StaticConfig {
     /// Set monitor index preferences
     #[serde(skip_serializing_if = "Option::is_none")]
     pub monitor_index_preferences: Option<HashMap<usize, Rect>>,
-    /// Set display index preferences
+    /// Set display index preferences, pinning monitors by serial number or device id to the
+    /// indices used for them in this file and in commands, regardless of enumeration order
     #[serde(skip_serializing_if = "Option::is_none")]
     pub display_index_preferences: Option<HashMap<usize, String>>,
     /// Stackbar configuration options
@@ -456,9 +526,99 @@ pub struct StaticConfig {
     /// HEAVILY DISCOURAGED: Identify applications for which komorebi should forcibly remove title bars
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remove_titlebar_applications: Option<Vec<MatchingRule>>,
+    /// Identify applications whose windows may be shared on screen, which are lowered to the bottom
+    /// of the Z order instead of being hidden when presentation mode is toggled on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presentation_applications: Option<Vec<MatchingRule>>,
     /// Aspect ratio to resize with when toggling floating mode for a window
     #[serde(skip_serializing_if = "Option::is_none")]
     pub floating_window_aspect_ratio: Option<AspectRatio>,
+    /// Maintain independent workspaces for each Windows virtual desktop instead of ignoring events
+    /// and commands on virtual desktops other than the one komorebi was started on (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virtual_desktop_awareness: Option<bool>,
+    /// Save rules added through socket messages (e.g. `komorebic ignore-rule`) to rules.json in
+    /// the data directory and load them again when komorebi starts (default: false). Rules from
+    /// the configuration file are never saved, so removing one from it is enough to remove it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persist_rules: Option<bool>,
+    /// Key chords which send messages to komorebi, handled by a built-in keyboard hook so that
+    /// basic setups don't need a separate hotkey daemon
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keybindings: Option<Vec<KeyBinding>>,
+    /// Named sets of key bindings which take precedence over the regular ones while the mode is
+    /// active, entered with the EnterKeybindingMode message and left by pressing Escape, e.g. a
+    /// "resize" mode where the arrow keys resize the focused window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keybinding_modes: Option<HashMap<String, Vec<KeyBinding>>>,
+    /// Modifier and mouse button combinations which move, resize or close the managed window
+    /// under the cursor, e.g. "win+left" to drag tiles around
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mouse_bindings: Option<Vec<MouseBinding>>,
+    /// Move tiled windows within the layout on Win+Arrow instead of letting Windows snap them to
+    /// the edges of the screen, which pulls them out of the layout. This installs a global keyboard
+    /// hook, which is otherwise only installed when keybindings are configured (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snap_interception: Option<bool>,
+    /// Show an icon for komorebi in the notification area with a menu to pause, reload the
+    /// configuration, show the state and stop (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tray_icon: Option<bool>,
+    /// Show toast notifications for configuration reload errors, monitors being connected or
+    /// disconnected, recoveries from crashes and commands ignored because the focused window is
+    /// not managed, which also shows the notification area icon (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub toast_notifications: Option<bool>,
+    /// Name workspaces without a name of their own after the application with the most windows on
+    /// them, e.g. "firefox", which is exposed as `auto_name` in the state (default: false)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_name_workspaces: Option<bool>,
+    /// Commands to run when specific events occur, which receive the notification for the event
+    /// as JSON on stdin
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_hooks: Option<Vec<EventHook>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KeyBinding {
+    /// Modifiers and a key joined by "+", e.g. "win+shift+h" (modifiers: win, ctrl, alt, shift)
+    pub keys: String,
+    /// Messages sent to komorebi, in order, when the chord is pressed
+    pub messages: Vec<SocketMessage>,
+}
+
+impl PartialEq for KeyBinding {
+    fn eq(&self, other: &Self) -> bool {
+        // SocketMessage doesn't implement PartialEq, so messages are compared in serialized form
+        self.keys == other.keys
+            && self.messages.len() == other.messages.len()
+            && self
+                .messages
+                .iter()
+                .zip(&other.messages)
+                .all(|(a, b)| a.as_bytes().ok() == b.as_bytes().ok())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct MouseBinding {
+    /// Modifiers and a mouse button joined by "+", e.g. "win+left" (buttons: left, right, middle)
+    pub buttons: String,
+    /// What happens to the managed window under the cursor
+    pub action: MouseAction,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub enum MouseAction {
+    /// Drag the window, swapping it with the tile it is dropped on or moving it to another monitor
+    Move,
+    /// Drag the edges of the window nearest to the cursor, resizing the tile
+    Resize,
+    /// Close the window
+    Close,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -682,6 +842,12 @@ impl From<&WindowManager> for StaticConfig {
             monitors.push(MonitorConfig::from(m));
         }
 
+        for (alias, usr_idx) in &value.monitor_aliases {
+            if let Some(monitor) = monitors.get_mut(value.monitor_idx_from_usr_idx(*usr_idx)) {
+                monitor.alias = Option::from(alias.clone());
+            }
+        }
+
         let border_colours = if border_manager::FOCUSED.load(Ordering::SeqCst) == 0 {
             None
         } else {
@@ -707,6 +873,10 @@ impl From<&WindowManager> for StaticConfig {
             float_override: Option::from(value.window_management_behaviour.float_override),
             cross_monitor_move_behaviour: Option::from(value.cross_monitor_move_behaviour),
             cross_boundary_behaviour: Option::from(value.cross_boundary_behaviour),
+            monocle_with_new_window_behaviour: Option::from(
+                value.monocle_with_new_window_behaviour,
+            ),
+            directional_focus_behaviour: Option::from(value.directional_focus_behaviour),
             unmanaged_window_operation_behaviour: Option::from(
                 value.unmanaged_window_operation_behaviour,
             ),
@@ -737,12 +907,27 @@ impl From<&WindowManager> for StaticConfig {
             ),
             monitors: Option::from(monitors),
             window_hiding_behaviour: Option::from(*HIDING_BEHAVIOUR.lock()),
+            floating_window_z_order: Option::from(*FLOATING_WINDOW_Z_ORDER.lock()),
             global_work_area_offset: value.work_area_offset,
             ignore_rules: None,
             floating_applications: None,
+            transient_applications: None,
+            transient_window_lifetime: Option::from(
+                TRANSIENT_WINDOW_LIFETIME.load(Ordering::SeqCst),
+            ),
             manage_rules: None,
-            border_overflow_applications: None,
+            border_overflow_applications: Option::from(BORDER_OVERFLOW_IDENTIFIERS.lock().clone()),
+            border_overflow_offsets: Option::from(BORDER_OVERFLOW_OFFSETS.lock().clone()),
             tray_and_multi_window_applications: None,
+            stack_tray_and_multi_window_applications: Option::from(
+                STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS.load(Ordering::SeqCst),
+            ),
+            stack_by_same_exe: Option::from(STACK_BY_SAME_EXE.load(Ordering::SeqCst)),
+            max_container_windows: match MAX_CONTAINER_WINDOWS.load(Ordering::SeqCst) {
+                0 => None,
+                max_windows => Option::from(max_windows),
+            },
+            stack_applications: None,
             layered_applications: None,
             object_name_change_applications: Option::from(
                 OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
@@ -766,7 +951,24 @@ impl From<&WindowManager> for StaticConfig {
             slow_application_identifiers: Option::from(SLOW_APPLICATION_IDENTIFIERS.lock().clone()),
             bar_configurations: None,
             remove_titlebar_applications: Option::from(NO_TITLEBAR.lock().clone()),
+            presentation_applications: Option::from(PRESENTATION_APPLICATIONS.lock().clone()),
             floating_window_aspect_ratio: Option::from(*FLOATING_WINDOW_TOGGLE_ASPECT_RATIO.lock()),
+            virtual_desktop_awareness: Option::from(
+                VIRTUAL_DESKTOP_AWARENESS.load(Ordering::SeqCst),
+            ),
+            persist_rules: Option::from(PERSIST_RULES.load(Ordering::SeqCst)),
+            keybindings: None,
+            keybinding_modes: None,
+            mouse_bindings: None,
+            snap_interception: Option::from(
+                hotkey_manager::SNAP_INTERCEPTION.load(Ordering::SeqCst),
+            ),
+            tray_icon: Option::from(tray_manager::TRAY_ICON_ENABLED.load(Ordering::SeqCst)),
+            toast_notifications: Option::from(
+                tray_manager::TOAST_NOTIFICATIONS_ENABLED.load(Ordering::SeqCst),
+            ),
+            auto_name_workspaces: Option::from(AUTO_NAME_WORKSPACES.load(Ordering::SeqCst)),
+            event_hooks: Option::from(event_hooks::hooks()),
         }
     }
 }
@@ -778,6 +980,46 @@ impl StaticConfig {
             .floating_window_aspect_ratio
             .unwrap_or(AspectRatio::Predefined(PredefinedAspectRatio::Standard));
 
+        VIRTUAL_DESKTOP_AWARENESS.store(
+            self.virtual_desktop_awareness.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
+        PERSIST_RULES.store(self.persist_rules.unwrap_or_default(), Ordering::SeqCst);
+
+        AUTO_NAME_WORKSPACES.store(
+            self.auto_name_workspaces.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
+        hotkey_manager::SNAP_INTERCEPTION
+            .store(self.snap_interception.unwrap_or_default(), Ordering::SeqCst);
+
+        tray_manager::configure(
+            self.tray_icon.unwrap_or_default(),
+            self.toast_notifications.unwrap_or_default(),
+        );
+
+        event_hooks::set_hooks(self.event_hooks.as_deref().unwrap_or_default());
+
+        STACK_TRAY_AND_MULTI_WINDOW_APPLICATIONS.store(
+            self.stack_tray_and_multi_window_applications
+                .unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
+        STACK_BY_SAME_EXE.store(self.stack_by_same_exe.unwrap_or_default(), Ordering::SeqCst);
+
+        MAX_CONTAINER_WINDOWS.store(
+            self.max_container_windows.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
+        TRANSIENT_WINDOW_LIFETIME.store(
+            self.transient_window_lifetime.unwrap_or_default(),
+            Ordering::SeqCst,
+        );
+
         if let Some(monitor_index_preferences) = &self.monitor_index_preferences {
             let mut preferences = MONITOR_INDEX_PREFERENCES.lock();
             preferences.clone_from(monitor_index_preferences);
@@ -793,6 +1035,10 @@ impl StaticConfig {
             *window_hiding_behaviour = behaviour;
         }
 
+        *FLOATING_WINDOW_Z_ORDER.lock() = self
+            .floating_window_z_order
+            .unwrap_or(FloatingZOrder::Normal);
+
         if let Some(height) = self.minimum_window_height {
             window::MINIMUM_HEIGHT.store(height, Ordering::SeqCst);
         }
@@ -918,7 +1164,11 @@ impl StaticConfig {
         let mut transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
         let mut slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
         let mut floating_applications = FLOATING_APPLICATIONS.lock();
+        let mut transient_applications = TRANSIENT_APPLICATIONS.lock();
         let mut no_titlebar_applications = NO_TITLEBAR.lock();
+        let mut presentation_applications = PRESENTATION_APPLICATIONS.lock();
+        let mut border_overflow_identifiers = BORDER_OVERFLOW_IDENTIFIERS.lock();
+        let mut stack_application_identifiers = STACK_APPLICATION_IDENTIFIERS.lock();
 
         if let Some(rules) = &mut self.ignore_rules {
             populate_rules(rules, &mut ignore_identifiers, &mut regex_identifiers)?;
@@ -928,6 +1178,10 @@ impl StaticConfig {
             populate_rules(rules, &mut floating_applications, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.transient_applications {
+            populate_rules(rules, &mut transient_applications, &mut regex_identifiers)?;
+        }
+
         if let Some(rules) = &mut self.manage_rules {
             populate_rules(rules, &mut manage_identifiers, &mut regex_identifiers)?;
         }
@@ -963,6 +1217,14 @@ impl StaticConfig {
             )?;
         }
 
+        if let Some(rules) = &mut self.stack_applications {
+            populate_rules(
+                rules,
+                &mut stack_application_identifiers,
+                &mut regex_identifiers,
+            )?;
+        }
+
         if let Some(rules) = &mut self.transparency_ignore_rules {
             populate_rules(rules, &mut transparency_blacklist, &mut regex_identifiers)?;
         }
@@ -979,6 +1241,30 @@ impl StaticConfig {
             populate_rules(rules, &mut no_titlebar_applications, &mut regex_identifiers)?;
         }
 
+        if let Some(rules) = &mut self.presentation_applications {
+            populate_rules(
+                rules,
+                &mut presentation_applications,
+                &mut regex_identifiers,
+            )?;
+        }
+
+        if let Some(rules) = &mut self.border_overflow_applications {
+            populate_rules(
+                rules,
+                &mut border_overflow_identifiers,
+                &mut regex_identifiers,
+            )?;
+        }
+
+        if let Some(offsets) = &mut self.border_overflow_offsets {
+            populate_border_overflow_offsets(
+                offsets,
+                &mut BORDER_OVERFLOW_OFFSETS.lock(),
+                &mut regex_identifiers,
+            )?;
+        }
+
         if let Some(stackbar) = &self.stackbar {
             if let Some(height) = &stackbar.height {
                 STACKBAR_TAB_HEIGHT.store(*height, Ordering::SeqCst);
@@ -1031,6 +1317,7 @@ impl StaticConfig {
                     &mut floating_applications,
                     &mut transparency_blacklist,
                     &mut slow_application_identifiers,
+                    &mut border_overflow_identifiers,
                     &mut regex_identifiers,
                 )?,
                 AppSpecificConfigurationPath::Multiple(paths) => {
@@ -1045,6 +1332,7 @@ impl StaticConfig {
                             &mut floating_applications,
                             &mut transparency_blacklist,
                             &mut slow_application_identifiers,
+                            &mut border_overflow_identifiers,
                             &mut regex_identifiers,
                         )?
                     }
@@ -1052,6 +1340,9 @@ impl StaticConfig {
             }
         }
 
+        // The border overflow rules may have changed
+        FRAME_COMPENSATIONS.lock().clear();
+
         Ok(())
     }
 
@@ -1059,6 +1350,16 @@ impl StaticConfig {
         Ok(serde_json::from_str(raw)?)
     }
 
+    /// Maps the alias of each configured monitor to the index of its configuration
+    fn monitor_aliases(&self) -> HashMap<String, usize> {
+        self.monitors
+            .iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(usr_idx, monitor)| Some((monitor.alias.clone()?, usr_idx)))
+            .collect()
+    }
+
     pub fn read(path: &PathBuf) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let mut value: Self = serde_json::from_str(&content)?;
@@ -1109,11 +1410,12 @@ impl StaticConfig {
     ) -> Result<WindowManager> {
         let mut value = Self::read(path)?;
         value.apply_globals()?;
+        hotkey_manager::set_bindings(path, &value);
 
         let listener = match unix_listener {
             Some(listener) => listener,
             None => {
-                let socket = DATA_DIR.join("komorebi.sock");
+                let socket = paths::socket_path();
 
                 match std::fs::remove_file(&socket) {
                     Ok(()) => {}
@@ -1133,6 +1435,7 @@ impl StaticConfig {
         let mut wm = WindowManager {
             monitors: Ring::default(),
             monitor_usr_idx_map: HashMap::new(),
+            monitor_aliases: value.monitor_aliases(),
             incoming_events: incoming,
             command_listener: listener,
             is_paused: false,
@@ -1150,6 +1453,10 @@ impl StaticConfig {
             cross_boundary_behaviour: value
                 .cross_boundary_behaviour
                 .unwrap_or(CrossBoundaryBehaviour::Monitor),
+            monocle_with_new_window_behaviour: value
+                .monocle_with_new_window_behaviour
+                .unwrap_or_default(),
+            directional_focus_behaviour: value.directional_focus_behaviour.unwrap_or_default(),
             unmanaged_window_operation_behaviour: value
                 .unmanaged_window_operation_behaviour
                 .unwrap_or(OperationBehaviour::Op),
@@ -1162,6 +1469,11 @@ impl StaticConfig {
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
             known_hwnds: HashMap::new(),
+            owned_windows: HashMap::new(),
+            virtual_desktop_workspaces: HashMap::new(),
+            pending_launches: HashMap::new(),
+            pre_tiling_rects: HashMap::new(),
+            is_soft_stopped: false,
         };
 
         match value.focus_follows_mouse {
@@ -1178,12 +1490,11 @@ impl StaticConfig {
             // Editing in Notepad sends a NoticeWrite while editing in (Neo)Vim sends
             // a NoticeRemove, presumably because of the use of swap files?
             EventKind::Modify(_) | EventKind::Remove(_) => {
-                let socket = DATA_DIR.join("komorebi.sock");
-                let mut stream =
-                    UnixStream::connect(socket).expect("could not connect to komorebi.sock");
+                let mut stream = UnixStream::connect(paths::socket_path())
+                    .expect("could not connect to the komorebi socket");
                 stream
                     .write_all(&bytes)
-                    .expect("could not write to komorebi.sock");
+                    .expect("could not write to the komorebi socket");
             }
             _ => {}
         })?;
@@ -1363,6 +1674,7 @@ impl StaticConfig {
         let mut value = Self::read(path)?;
 
         value.apply_globals()?;
+        hotkey_manager::set_bindings(path, &value);
 
         let configs_with_preference: Vec<_> =
             DISPLAY_INDEX_PREFERENCES.read().keys().copied().collect();
@@ -1544,6 +1856,14 @@ impl StaticConfig {
             wm.cross_boundary_behaviour = val;
         }
 
+        if let Some(val) = value.monocle_with_new_window_behaviour {
+            wm.monocle_with_new_window_behaviour = val;
+        }
+
+        if let Some(val) = value.directional_focus_behaviour {
+            wm.directional_focus_behaviour = val;
+        }
+
         if let Some(val) = value.unmanaged_window_operation_behaviour {
             wm.unmanaged_window_operation_behaviour = val;
         }
@@ -1557,6 +1877,7 @@ impl StaticConfig {
         }
 
         wm.work_area_offset = value.global_work_area_offset;
+        wm.monitor_aliases = value.monitor_aliases();
 
         match value.focus_follows_mouse {
             None => WindowsApi::disable_focus_follows_mouse()?,
@@ -1611,37 +1932,356 @@ fn populate_rules(
 ) -> Result<()> {
     for matching_rule in matching_rules {
         if !identifiers.contains(matching_rule) {
-            match matching_rule {
-                MatchingRule::Simple(simple) => {
-                    if simple.matching_strategy.is_none() {
-                        simple.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                    }
+            prepare_rule(matching_rule, regex_identifiers)?;
+            identifiers.push(matching_rule.clone());
+        }
+    }
 
-                    if matches!(simple.matching_strategy, Some(MatchingStrategy::Regex)) {
-                        let re = Regex::new(&simple.id)?;
-                        regex_identifiers.insert(simple.id.clone(), re);
-                    }
+    Ok(())
+}
+
+/// Adds border overflow offsets to the ones the window manager already knows about, replacing the
+/// offset of any application which has already been identified with one
+fn populate_border_overflow_offsets(
+    offsets: &mut Vec<BorderOverflowOffset>,
+    identifiers: &mut Vec<BorderOverflowOffset>,
+    regex_identifiers: &mut HashMap<String, Regex>,
+) -> Result<()> {
+    for offset in offsets {
+        prepare_rule(&mut offset.matching_rule, regex_identifiers)?;
+        identifiers.retain(|existing| existing.matching_rule != offset.matching_rule);
+        identifiers.push(offset.clone());
+    }
+
+    Ok(())
+}
+
+/// Defaults the matching strategy of a rule to `Legacy` and compiles any regular expressions it
+/// uses
+fn prepare_rule(
+    matching_rule: &mut MatchingRule,
+    regex_identifiers: &mut HashMap<String, Regex>,
+) -> Result<()> {
+    match matching_rule {
+        MatchingRule::Simple(simple) => {
+            if simple.matching_strategy.is_none() {
+                simple.matching_strategy = Option::from(MatchingStrategy::Legacy);
+            }
+
+            if matches!(simple.matching_strategy, Some(MatchingStrategy::Regex)) {
+                let re = Regex::new(&simple.id)?;
+                regex_identifiers.insert(simple.id.clone(), re);
+            }
+        }
+        MatchingRule::Composite(composite) => {
+            for rule in composite {
+                if rule.matching_strategy.is_none() {
+                    rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
                 }
-                MatchingRule::Composite(composite) => {
-                    for rule in composite {
-                        if rule.matching_strategy.is_none() {
-                            rule.matching_strategy = Option::from(MatchingStrategy::Legacy);
-                        }
 
-                        if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
-                            let re = Regex::new(&rule.id)?;
-                            regex_identifiers.insert(rule.id.clone(), re);
-                        }
-                    }
+                if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
+                    let re = Regex::new(&rule.id)?;
+                    regex_identifiers.insert(rule.id.clone(), re);
                 }
             }
-            identifiers.push(matching_rule.clone());
         }
     }
 
     Ok(())
 }
 
+/// A snapshot of every rule which can be added at runtime through socket messages, so that they
+/// can be dumped to a file and loaded again after a restart
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Rules {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_rules: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub manage_rules: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub floating_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transient_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tray_and_multi_window_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stack_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layered_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub object_name_change_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transparency_ignore_rules: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slow_application_identifiers: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub remove_titlebar_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub border_overflow_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub border_overflow_offsets: Vec<BorderOverflowOffset>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub presentation_applications: Vec<MatchingRule>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_rules: Vec<WorkspaceMatchingRule>,
+}
+
+impl Rules {
+    /// The location of the rules file which is kept up to date when `persist_rules` is enabled
+    pub fn persisted_path() -> PathBuf {
+        paths::instance_file("rules.json")
+    }
+
+    /// The rules which are currently known to the window manager
+    pub fn current() -> Self {
+        Self {
+            ignore_rules: IGNORE_IDENTIFIERS.lock().clone(),
+            manage_rules: MANAGE_IDENTIFIERS.lock().clone(),
+            floating_applications: FLOATING_APPLICATIONS.lock().clone(),
+            transient_applications: TRANSIENT_APPLICATIONS.lock().clone(),
+            tray_and_multi_window_applications: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().clone(),
+            stack_applications: STACK_APPLICATION_IDENTIFIERS.lock().clone(),
+            layered_applications: LAYERED_WHITELIST.lock().clone(),
+            object_name_change_applications: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
+            transparency_ignore_rules: TRANSPARENCY_BLACKLIST.lock().clone(),
+            slow_application_identifiers: SLOW_APPLICATION_IDENTIFIERS.lock().clone(),
+            remove_titlebar_applications: NO_TITLEBAR.lock().clone(),
+            border_overflow_applications: BORDER_OVERFLOW_IDENTIFIERS.lock().clone(),
+            border_overflow_offsets: BORDER_OVERFLOW_OFFSETS.lock().clone(),
+            presentation_applications: PRESENTATION_APPLICATIONS.lock().clone(),
+            workspace_rules: WORKSPACE_MATCHING_RULES.lock().clone(),
+        }
+    }
+
+    /// The rules which have been added through socket messages since komorebi started, along
+    /// with the ones which were loaded from the persisted rules file
+    pub fn runtime() -> Self {
+        RUNTIME_RULES.lock().clone()
+    }
+
+    /// Loads the persisted rules file, remembering the rules it adds as ones added at runtime so
+    /// that they are saved to it again along with any new ones
+    pub fn load_persisted() -> Result<()> {
+        let before = Self::current();
+        let result = Self::read(&Self::persisted_path()).and_then(Self::apply);
+        Self::record_runtime_changes(&before, &Self::current());
+
+        result
+    }
+
+    /// Records the rules which a socket message added or removed, given the rules from before
+    /// and after it was processed
+    pub fn record_runtime_changes(before: &Self, after: &Self) {
+        let mut runtime = RUNTIME_RULES.lock();
+
+        record_changes(
+            &mut runtime.ignore_rules,
+            &before.ignore_rules,
+            &after.ignore_rules,
+        );
+        record_changes(
+            &mut runtime.manage_rules,
+            &before.manage_rules,
+            &after.manage_rules,
+        );
+        record_changes(
+            &mut runtime.floating_applications,
+            &before.floating_applications,
+            &after.floating_applications,
+        );
+        record_changes(
+            &mut runtime.transient_applications,
+            &before.transient_applications,
+            &after.transient_applications,
+        );
+        record_changes(
+            &mut runtime.tray_and_multi_window_applications,
+            &before.tray_and_multi_window_applications,
+            &after.tray_and_multi_window_applications,
+        );
+        record_changes(
+            &mut runtime.stack_applications,
+            &before.stack_applications,
+            &after.stack_applications,
+        );
+        record_changes(
+            &mut runtime.layered_applications,
+            &before.layered_applications,
+            &after.layered_applications,
+        );
+        record_changes(
+            &mut runtime.object_name_change_applications,
+            &before.object_name_change_applications,
+            &after.object_name_change_applications,
+        );
+        record_changes(
+            &mut runtime.transparency_ignore_rules,
+            &before.transparency_ignore_rules,
+            &after.transparency_ignore_rules,
+        );
+        record_changes(
+            &mut runtime.slow_application_identifiers,
+            &before.slow_application_identifiers,
+            &after.slow_application_identifiers,
+        );
+        record_changes(
+            &mut runtime.remove_titlebar_applications,
+            &before.remove_titlebar_applications,
+            &after.remove_titlebar_applications,
+        );
+        record_changes(
+            &mut runtime.border_overflow_applications,
+            &before.border_overflow_applications,
+            &after.border_overflow_applications,
+        );
+        record_changes(
+            &mut runtime.border_overflow_offsets,
+            &before.border_overflow_offsets,
+            &after.border_overflow_offsets,
+        );
+        record_changes(
+            &mut runtime.presentation_applications,
+            &before.presentation_applications,
+            &after.presentation_applications,
+        );
+        record_changes(
+            &mut runtime.workspace_rules,
+            &before.workspace_rules,
+            &after.workspace_rules,
+        );
+    }
+
+    pub fn read(path: &PathBuf) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn write(&self, path: &PathBuf) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds these rules to the ones the window manager already knows about, skipping any which
+    /// are already present
+    pub fn apply(mut self) -> Result<()> {
+        for (rules, identifiers) in [
+            (&mut self.ignore_rules, &*IGNORE_IDENTIFIERS),
+            (&mut self.manage_rules, &*MANAGE_IDENTIFIERS),
+            (&mut self.floating_applications, &*FLOATING_APPLICATIONS),
+            (&mut self.transient_applications, &*TRANSIENT_APPLICATIONS),
+            (
+                &mut self.tray_and_multi_window_applications,
+                &*TRAY_AND_MULTI_WINDOW_IDENTIFIERS,
+            ),
+            (
+                &mut self.stack_applications,
+                &*STACK_APPLICATION_IDENTIFIERS,
+            ),
+            (&mut self.layered_applications, &*LAYERED_WHITELIST),
+            (
+                &mut self.object_name_change_applications,
+                &*OBJECT_NAME_CHANGE_ON_LAUNCH,
+            ),
+            (
+                &mut self.transparency_ignore_rules,
+                &*TRANSPARENCY_BLACKLIST,
+            ),
+            (
+                &mut self.slow_application_identifiers,
+                &*SLOW_APPLICATION_IDENTIFIERS,
+            ),
+            (&mut self.remove_titlebar_applications, &*NO_TITLEBAR),
+            (
+                &mut self.border_overflow_applications,
+                &*BORDER_OVERFLOW_IDENTIFIERS,
+            ),
+            (
+                &mut self.presentation_applications,
+                &*PRESENTATION_APPLICATIONS,
+            ),
+        ] {
+            populate_rules(
+                rules,
+                &mut identifiers.lock(),
+                &mut REGEX_IDENTIFIERS.lock(),
+            )?;
+        }
+
+        populate_border_overflow_offsets(
+            &mut self.border_overflow_offsets,
+            &mut BORDER_OVERFLOW_OFFSETS.lock(),
+            &mut REGEX_IDENTIFIERS.lock(),
+        )?;
+
+        let mut workspace_matching_rules = WORKSPACE_MATCHING_RULES.lock();
+        for rule in self.workspace_rules {
+            if !workspace_matching_rules.contains(&rule) {
+                workspace_matching_rules.push(rule);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the rules which appear in `after` but not in `before` to `recorded`, and forgets any
+/// recorded rules which are no longer in `after`
+fn record_changes<T: Clone + PartialEq>(recorded: &mut Vec<T>, before: &[T], after: &[T]) {
+    recorded.retain(|rule| after.contains(rule));
+
+    for rule in after {
+        if !before.contains(rule) && !recorded.contains(rule) {
+            recorded.push(rule.clone());
+        }
+    }
+}
+
+/// Applies every identifier list from an `applications.yaml` or `applications.json` file to the
+/// running window manager in one go
+pub fn apply_app_specific_configuration(path: &PathBuf) -> Result<()> {
+    match path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_string())
+    {
+        Some(ext) if ext == "yaml" || ext == "json" => {}
+        _ => bail!(
+            "{} is not an applications.yaml or applications.json file",
+            path.display()
+        ),
+    }
+
+    let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
+    let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
+    let mut manage_identifiers = MANAGE_IDENTIFIERS.lock();
+    let mut tray_and_multi_window_identifiers = TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock();
+    let mut object_name_change_identifiers = OBJECT_NAME_CHANGE_ON_LAUNCH.lock();
+    let mut layered_identifiers = LAYERED_WHITELIST.lock();
+    let mut transparency_blacklist = TRANSPARENCY_BLACKLIST.lock();
+    let mut slow_application_identifiers = SLOW_APPLICATION_IDENTIFIERS.lock();
+    let mut floating_applications = FLOATING_APPLICATIONS.lock();
+    let mut border_overflow_identifiers = BORDER_OVERFLOW_IDENTIFIERS.lock();
+
+    handle_asc_file(
+        path,
+        &mut ignore_identifiers,
+        &mut object_name_change_identifiers,
+        &mut layered_identifiers,
+        &mut tray_and_multi_window_identifiers,
+        &mut manage_identifiers,
+        &mut floating_applications,
+        &mut transparency_blacklist,
+        &mut slow_application_identifiers,
+        &mut border_overflow_identifiers,
+        &mut regex_identifiers,
+    )?;
+
+    // The border overflow rules may have changed
+    FRAME_COMPENSATIONS.lock().clear();
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn handle_asc_file(
     path: &PathBuf,
@@ -1653,6 +2293,7 @@ fn handle_asc_file(
     floating_applications: &mut Vec<MatchingRule>,
     transparency_blacklist: &mut Vec<MatchingRule>,
     slow_application_identifiers: &mut Vec<MatchingRule>,
+    border_overflow_identifiers: &mut Vec<MatchingRule>,
     regex_identifiers: &mut HashMap<String, Regex>,
 ) -> Result<()> {
     match path.extension() {
@@ -1701,7 +2342,13 @@ fn handle_asc_file(
                                         regex_identifiers,
                                     )?;
                                 }
-                                ApplicationOptions::BorderOverflow => {} // deprecated
+                                ApplicationOptions::BorderOverflow => {
+                                    populate_option(
+                                        &mut entry,
+                                        border_overflow_identifiers,
+                                        regex_identifiers,
+                                    )?;
+                                }
                             }
                         }
                     }
@@ -1759,6 +2406,14 @@ fn handle_asc_file(
                                     regex_identifiers,
                                 )?;
                             }
+
+                            if let Some(rules) = &mut entry.border_overflow {
+                                populate_rules(
+                                    rules,
+                                    border_overflow_identifiers,
+                                    regex_identifiers,
+                                )?;
+                            }
                         }
                     }
                 }
@@ -1772,8 +2427,21 @@ fn handle_asc_file(
 
 #[cfg(test)]
 mod tests {
+    use super::record_changes;
     use crate::StaticConfig;
 
+    #[test]
+    fn test_record_changes_only_keeps_rules_added_at_runtime() {
+        // "config" was known before any socket message, "added" came from one
+        let mut recorded = vec![];
+        record_changes(&mut recorded, &["config"], &["config", "added"]);
+        assert_eq!(recorded, vec!["added"]);
+
+        // rules which are cleared at runtime are forgotten too
+        record_changes(&mut recorded, &["config", "added"], &["config"]);
+        assert!(recorded.is_empty());
+    }
+
     #[test]
     fn backwards_compat() {
         let root = vec!["0.1.17", "0.1.18", "0.1.19"];
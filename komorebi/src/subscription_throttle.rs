@@ -0,0 +1,102 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::SUBSCRIPTION_SOCKETS;
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::time::Instant;
+use uds_windows::UnixStream;
+
+/// How often notifications which are being held back for throttled subscribers are checked
+const FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+struct Throttle {
+    interval: Duration,
+    last_sent: Instant,
+    /// The most recent notification which arrived before the interval had elapsed
+    pending: Option<String>,
+}
+
+static THROTTLES: OnceLock<Mutex<HashMap<String, Throttle>>> = OnceLock::new();
+
+fn throttles() -> &'static Mutex<HashMap<String, Throttle>> {
+    THROTTLES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Whether a notification can be pushed to a throttled subscriber socket right away. If it can't,
+/// it replaces whichever notification was already being held back for the socket, and is pushed
+/// once the interval has elapsed
+pub fn send_now(socket: &str, max_per_second: u32, notification: &str) -> bool {
+    let interval = Duration::from_secs(1) / max_per_second.max(1);
+    let mut throttles = throttles().lock();
+
+    match throttles.get_mut(socket) {
+        Some(throttle) if throttle.last_sent.elapsed() < throttle.interval => {
+            throttle.interval = interval;
+            throttle.pending = Option::from(notification.to_string());
+            false
+        }
+        _ => {
+            throttles.insert(
+                socket.to_string(),
+                Throttle {
+                    interval,
+                    last_sent: Instant::now(),
+                    pending: None,
+                },
+            );
+
+            true
+        }
+    }
+}
+
+pub fn remove(socket: &str) {
+    throttles().lock().remove(socket);
+}
+
+pub fn listen_for_notifications() {
+    std::thread::spawn(|| loop {
+        std::thread::sleep(FLUSH_INTERVAL);
+        flush_pending();
+    });
+}
+
+fn flush_pending() {
+    let due: Vec<(String, String)> = {
+        let mut throttles = throttles().lock();
+        throttles
+            .iter_mut()
+            .filter(|(_, throttle)| throttle.last_sent.elapsed() >= throttle.interval)
+            .filter_map(|(socket, throttle)| {
+                let notification = throttle.pending.take()?;
+                throttle.last_sent = Instant::now();
+                Option::from((socket.clone(), notification))
+            })
+            .collect()
+    };
+
+    if due.is_empty() {
+        return;
+    }
+
+    // Stale sockets are cleaned up the next time notify_subscribers fails to reach them
+    let sockets = SUBSCRIPTION_SOCKETS.lock();
+    for (socket, notification) in due {
+        if let Some(path) = sockets.get(&socket) {
+            match UnixStream::connect(path)
+                .and_then(|mut stream| stream.write_all(notification.as_bytes()))
+            {
+                Ok(()) => {
+                    tracing::debug!("pushed throttled notification to subscriber: {socket}");
+                }
+                Err(error) => {
+                    tracing::debug!("could not push throttled notification to {socket}: {error}");
+                }
+            }
+        }
+    }
+}
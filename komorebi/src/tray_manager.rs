@@ -0,0 +1,442 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::SocketMessage;
+use crate::paths;
+use crate::state_snapshot;
+use crate::window_manager::WindowManager;
+use crate::windows_api;
+use crate::windows_api::WindowsApi;
+
+use color_eyre::Result;
+use crossbeam_channel::Receiver;
+use crossbeam_channel::Sender;
+use lazy_static::lazy_static;
+use parking_lot::Mutex;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::HINSTANCE;
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::LPARAM;
+use windows::Win32::Foundation::LRESULT;
+use windows::Win32::Foundation::WPARAM;
+use windows::Win32::UI::Shell::Shell_NotifyIconW;
+use windows::Win32::UI::Shell::NIF_ICON;
+use windows::Win32::UI::Shell::NIF_INFO;
+use windows::Win32::UI::Shell::NIF_MESSAGE;
+use windows::Win32::UI::Shell::NIF_TIP;
+use windows::Win32::UI::Shell::NIIF_ERROR;
+use windows::Win32::UI::Shell::NIIF_INFO;
+use windows::Win32::UI::Shell::NIIF_WARNING;
+use windows::Win32::UI::Shell::NIM_ADD;
+use windows::Win32::UI::Shell::NIM_DELETE;
+use windows::Win32::UI::Shell::NIM_MODIFY;
+use windows::Win32::UI::Shell::NOTIFYICONDATAW;
+use windows::Win32::UI::WindowsAndMessaging::AppendMenuW;
+use windows::Win32::UI::WindowsAndMessaging::CreatePopupMenu;
+use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
+use windows::Win32::UI::WindowsAndMessaging::DefWindowProcW;
+use windows::Win32::UI::WindowsAndMessaging::DestroyMenu;
+use windows::Win32::UI::WindowsAndMessaging::DispatchMessageW;
+use windows::Win32::UI::WindowsAndMessaging::GetMessageW;
+use windows::Win32::UI::WindowsAndMessaging::LoadIconW;
+use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
+use windows::Win32::UI::WindowsAndMessaging::PostQuitMessage;
+use windows::Win32::UI::WindowsAndMessaging::RegisterWindowMessageW;
+use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
+use windows::Win32::UI::WindowsAndMessaging::TrackPopupMenuEx;
+use windows::Win32::UI::WindowsAndMessaging::TranslateMessage;
+use windows::Win32::UI::WindowsAndMessaging::IDI_APPLICATION;
+use windows::Win32::UI::WindowsAndMessaging::MF_GRAYED;
+use windows::Win32::UI::WindowsAndMessaging::MF_SEPARATOR;
+use windows::Win32::UI::WindowsAndMessaging::MF_STRING;
+use windows::Win32::UI::WindowsAndMessaging::MSG;
+use windows::Win32::UI::WindowsAndMessaging::TPM_NONOTIFY;
+use windows::Win32::UI::WindowsAndMessaging::TPM_RETURNCMD;
+use windows::Win32::UI::WindowsAndMessaging::TPM_RIGHTBUTTON;
+use windows::Win32::UI::WindowsAndMessaging::WM_APP;
+use windows::Win32::UI::WindowsAndMessaging::WM_DESTROY;
+use windows::Win32::UI::WindowsAndMessaging::WM_LBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WM_NULL;
+use windows::Win32::UI::WindowsAndMessaging::WM_RBUTTONUP;
+use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
+use windows::Win32::UI::WindowsAndMessaging::WS_EX_TOOLWINDOW;
+use windows::Win32::UI::WindowsAndMessaging::WS_OVERLAPPED;
+
+pub static TRAY_ICON_ENABLED: AtomicBool = AtomicBool::new(false);
+pub static TOAST_NOTIFICATIONS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The message which the shell sends to the tray window when the icon is clicked
+const WM_TRAY_ICON: u32 = WM_APP + 1;
+const TRAY_ICON_ID: u32 = 1;
+
+/// The message which explorer broadcasts when the taskbar is created again after a restart, at
+/// which point every icon has to be added again
+static TASKBAR_CREATED: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Notification {
+    TogglePause,
+    ReloadConfiguration,
+    ShowState,
+    Stop,
+}
+
+impl Notification {
+    const MENU: [(Self, &'static str); 4] = [
+        (Self::TogglePause, "Pause / resume tiling"),
+        (Self::ReloadConfiguration, "Reload configuration"),
+        (Self::ShowState, "Show state"),
+        (Self::Stop, "Stop and restore windows"),
+    ];
+
+    /// The menu item identifiers start at 1, because TrackPopupMenuEx returns 0 when the menu is
+    /// dismissed without picking anything
+    fn from_menu_id(id: usize) -> Option<Self> {
+        Self::MENU
+            .get(id.checked_sub(1)?)
+            .map(|(notification, _)| *notification)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+lazy_static! {
+    /// The hidden window which owns the icon and receives its messages
+    static ref TRAY: Mutex<Option<isize>> = Mutex::new(None);
+}
+
+static CHANNEL: OnceLock<(Sender<Notification>, Receiver<Notification>)> = OnceLock::new();
+
+pub fn channel() -> &'static (Sender<Notification>, Receiver<Notification>) {
+    CHANNEL.get_or_init(|| crossbeam_channel::bounded(5))
+}
+
+fn event_tx() -> Sender<Notification> {
+    channel().0.clone()
+}
+
+fn event_rx() -> Receiver<Notification> {
+    channel().1.clone()
+}
+
+pub fn send_notification(notification: Notification) {
+    if event_tx().try_send(notification).is_err() {
+        tracing::warn!("channel is full; dropping notification")
+    }
+}
+
+pub fn listen_for_notifications(wm: Arc<Mutex<WindowManager>>) {
+    std::thread::spawn(move || loop {
+        match handle_notifications(wm.clone()) {
+            Ok(()) => {
+                tracing::warn!("restarting finished thread");
+            }
+            Err(error) => {
+                if cfg!(debug_assertions) {
+                    tracing::error!("restarting failed thread: {:?}", error)
+                } else {
+                    tracing::error!("restarting failed thread: {}", error)
+                }
+            }
+        }
+    });
+}
+
+pub fn handle_notifications(wm: Arc<Mutex<WindowManager>>) -> Result<()> {
+    tracing::info!("listening");
+
+    let receiver = event_rx();
+
+    for notification in receiver {
+        let message = match notification {
+            Notification::TogglePause => SocketMessage::TogglePause,
+            Notification::ReloadConfiguration => match paths::STATIC_CONFIG.get() {
+                Some(path) => SocketMessage::ReloadStaticConfiguration(path.clone()),
+                None => {
+                    tracing::warn!("komorebi was not started with a static configuration file");
+                    continue;
+                }
+            },
+            Notification::ShowState => {
+                // the state snapshot is kept up to date for as long as komorebi is running
+                if let Err(error) = Command::new("cmd.exe")
+                    .args(["/C", "start", ""])
+                    .arg(state_snapshot::state_file_path())
+                    .spawn()
+                {
+                    tracing::error!("could not open the state snapshot: {error}");
+                }
+
+                continue;
+            }
+            Notification::Stop => {
+                // the process exits before the window would get to remove the icon itself
+                remove();
+                SocketMessage::Stop
+            }
+        };
+
+        let mut wm = wm.lock();
+        if let Err(error) = wm.process_command(message.clone(), std::io::sink()) {
+            tracing::error!("could not handle {message} from the tray icon: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds the icon to the notification area, or removes it. Toast notifications are shown from the
+/// icon, so it is also added when only they are enabled.
+pub fn configure(tray_icon: bool, toast_notifications: bool) {
+    TRAY_ICON_ENABLED.store(tray_icon, Ordering::SeqCst);
+    TOAST_NOTIFICATIONS_ENABLED.store(toast_notifications, Ordering::SeqCst);
+
+    if !icon_required() {
+        remove();
+        return;
+    }
+
+    let mut tray = TRAY.lock();
+    if tray.is_some() {
+        return;
+    }
+
+    match create_window() {
+        Ok(hwnd) => {
+            add_icon(hwnd);
+            *tray = Option::from(hwnd);
+        }
+        Err(error) => tracing::error!("could not create the tray icon window: {error}"),
+    }
+}
+
+fn icon_required() -> bool {
+    TRAY_ICON_ENABLED.load(Ordering::SeqCst) || TOAST_NOTIFICATIONS_ENABLED.load(Ordering::SeqCst)
+}
+
+/// Shows a toast notification from the tray icon if toast notifications are enabled, for events
+/// which would otherwise only be visible in the log file
+pub fn toast(level: ToastLevel, title: &str, message: &str) {
+    if !TOAST_NOTIFICATIONS_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+
+    let Some(hwnd) = *TRAY.lock() else {
+        return;
+    };
+
+    let mut data = icon_data(hwnd);
+    data.uFlags = NIF_INFO;
+    data.dwInfoFlags = match level {
+        ToastLevel::Info => NIIF_INFO,
+        ToastLevel::Warning => NIIF_WARNING,
+        ToastLevel::Error => NIIF_ERROR,
+    };
+
+    copy_wide(&mut data.szInfoTitle, title);
+    copy_wide(&mut data.szInfo, message);
+
+    if !unsafe { Shell_NotifyIconW(NIM_MODIFY, &data) }.as_bool() {
+        tracing::error!("could not show toast notification: {title}");
+    }
+}
+
+/// Copies `src` into a fixed size, null terminated UTF-16 buffer, truncating it if it is too long
+fn copy_wide(dst: &mut [u16], src: &str) {
+    let len = dst.len().saturating_sub(1);
+    for (dst, src) in dst.iter_mut().take(len).zip(src.encode_utf16()) {
+        *dst = src;
+    }
+}
+
+fn remove() {
+    let Some(hwnd) = TRAY.lock().take() else {
+        return;
+    };
+
+    delete_icon(hwnd);
+
+    if let Err(error) = WindowsApi::close_window(hwnd) {
+        tracing::error!("could not close the tray icon window: {error}");
+    }
+}
+
+fn icon_data(hwnd: isize) -> NOTIFYICONDATAW {
+    NOTIFYICONDATAW {
+        cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+        hWnd: HWND(windows_api::as_ptr!(hwnd)),
+        uID: TRAY_ICON_ID,
+        ..Default::default()
+    }
+}
+
+fn add_icon(hwnd: isize) {
+    let mut data = icon_data(hwnd);
+    data.uFlags = NIF_ICON | NIF_MESSAGE | NIF_TIP;
+    data.uCallbackMessage = WM_TRAY_ICON;
+    data.hIcon = unsafe { LoadIconW(None, IDI_APPLICATION) }.unwrap_or_default();
+
+    copy_wide(&mut data.szTip, "komorebi");
+
+    if !unsafe { Shell_NotifyIconW(NIM_ADD, &data) }.as_bool() {
+        tracing::error!("could not add the tray icon");
+    }
+}
+
+fn delete_icon(hwnd: isize) {
+    let data = icon_data(hwnd);
+    // TODO: error handling
+    let _ = unsafe { Shell_NotifyIconW(NIM_DELETE, &data) };
+}
+
+/// Shows the context menu at the cursor and returns the item which was picked, if any
+fn show_menu(hwnd: HWND) -> Option<Notification> {
+    let point = WindowsApi::cursor_pos().ok()?;
+
+    unsafe {
+        let menu = CreatePopupMenu().ok()?;
+
+        for (idx, (notification, label)) in Notification::MENU.iter().enumerate() {
+            if *notification == Notification::Stop {
+                // TODO: error handling
+                let _ = AppendMenuW(menu, MF_SEPARATOR, 0, PCWSTR::null());
+            }
+
+            let flags = if *notification == Notification::ReloadConfiguration
+                && paths::STATIC_CONFIG.get().is_none()
+            {
+                MF_STRING | MF_GRAYED
+            } else {
+                MF_STRING
+            };
+
+            let label: Vec<u16> = format!("{label}\0").encode_utf16().collect();
+            // TODO: error handling
+            let _ = AppendMenuW(menu, flags, idx + 1, PCWSTR(label.as_ptr()));
+        }
+
+        // the menu is only dismissed when clicking elsewhere if its owner is in the foreground
+        let _ = SetForegroundWindow(hwnd);
+        let picked = TrackPopupMenuEx(
+            menu,
+            (TPM_RETURNCMD | TPM_RIGHTBUTTON | TPM_NONOTIFY).0,
+            point.x,
+            point.y,
+            hwnd,
+            None,
+        );
+        let _ = PostMessageW(Option::from(hwnd), WM_NULL, WPARAM(0), LPARAM(0));
+        let _ = DestroyMenu(menu);
+
+        Notification::from_menu_id(picked.0 as usize)
+    }
+}
+
+fn create_window() -> Result<isize> {
+    let name: Vec<u16> = "komorebi-tray\0".encode_utf16().collect();
+    let class_name = PCWSTR(name.as_ptr());
+
+    let h_module = WindowsApi::module_handle_w()?;
+
+    let window_class = WNDCLASSW {
+        lpfnWndProc: Some(callback),
+        hInstance: h_module.into(),
+        lpszClassName: class_name,
+        ..Default::default()
+    };
+
+    let _ = WindowsApi::register_class_w(&window_class);
+
+    let taskbar_created: Vec<u16> = "TaskbarCreated\0".encode_utf16().collect();
+    TASKBAR_CREATED.store(
+        unsafe { RegisterWindowMessageW(PCWSTR(taskbar_created.as_ptr())) },
+        Ordering::SeqCst,
+    );
+
+    let (hwnd_sender, hwnd_receiver) = mpsc::channel();
+
+    let name_cl = name.clone();
+    let instance = h_module.0 as isize;
+    std::thread::spawn(move || -> Result<()> {
+        unsafe {
+            // never shown, but not message-only either, because those don't receive the
+            // TaskbarCreated broadcast
+            let hwnd = CreateWindowExW(
+                WS_EX_TOOLWINDOW,
+                PCWSTR(name_cl.as_ptr()),
+                PCWSTR(name_cl.as_ptr()),
+                WS_OVERLAPPED,
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Option::from(HINSTANCE(windows_api::as_ptr!(instance))),
+                None,
+            )?;
+
+            hwnd_sender.send(hwnd.0 as isize)?;
+
+            let mut msg: MSG = MSG::default();
+
+            loop {
+                if !GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                    tracing::debug!("tray icon window event processing thread shutdown");
+                    break;
+                };
+                // TODO: error handling
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+
+        Ok(())
+    });
+
+    Ok(hwnd_receiver.recv()?)
+}
+
+unsafe extern "system" fn callback(
+    hwnd: HWND,
+    msg: u32,
+    w_param: WPARAM,
+    l_param: LPARAM,
+) -> LRESULT {
+    unsafe {
+        match msg {
+            WM_TRAY_ICON => {
+                if matches!(l_param.0 as u32, WM_LBUTTONUP | WM_RBUTTONUP) {
+                    if let Some(notification) = show_menu(hwnd) {
+                        send_notification(notification);
+                    }
+                }
+
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                PostQuitMessage(0);
+                LRESULT(0)
+            }
+            _ if msg == TASKBAR_CREATED.load(Ordering::SeqCst) => {
+                if icon_required() {
+                    add_icon(hwnd.0 as isize);
+                }
+
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, w_param, l_param),
+        }
+    }
+}
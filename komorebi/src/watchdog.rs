@@ -0,0 +1,79 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::paths;
+use crate::tray_manager;
+use crate::tray_manager::ToastLevel;
+use crate::window_manager::State;
+use crate::WindowManager;
+
+use parking_lot::Mutex;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// The location of the state dump which is written whenever a supervised thread panics
+pub fn crash_dump_path() -> PathBuf {
+    paths::instance_file("crash.json")
+}
+
+/// Runs `f`, and if it panics, restores all hidden windows and writes a crash dump instead of
+/// letting the panic take down the calling thread, so that the caller can restart whatever loop
+/// `f` was driving
+pub fn supervise<F: FnOnce()>(wm: &Arc<Mutex<WindowManager>>, thread: &str, f: F) {
+    if std::panic::catch_unwind(AssertUnwindSafe(f)).is_err() {
+        recover(wm, thread);
+    }
+}
+
+/// Brings the window manager back to a usable state after a panic on the `thread` thread
+///
+/// The panic hook will already have logged the panic message and location, so this only
+/// needs to make sure that no windows are left hidden and that the state at the time of
+/// the crash is persisted for debugging
+pub fn recover(wm: &Arc<Mutex<WindowManager>>, thread: &str) {
+    tracing::error!("{thread} thread panicked, attempting to recover");
+
+    let mut wm = wm.lock();
+
+    let crash_dump = crash_dump_path();
+    match serde_json::to_string_pretty(&State::from(&*wm)) {
+        Ok(state) => match std::fs::write(&crash_dump, state) {
+            Ok(()) => tracing::info!("wrote crash dump to {}", crash_dump.display()),
+            Err(error) => tracing::error!("could not write crash dump: {}", error),
+        },
+        Err(error) => tracing::error!("could not serialize crash dump: {}", error),
+    }
+
+    if let Err(error) = wm.restore_all_windows(false) {
+        tracing::error!("could not restore hidden windows: {}", error);
+    }
+
+    let mut relayout = || -> color_eyre::Result<()> {
+        for monitor in wm.monitors_mut() {
+            monitor.load_focused_workspace(false)?;
+        }
+
+        wm.retile_all(false)
+    };
+
+    if let Err(error) = relayout() {
+        tracing::error!(
+            "could not restore layout, leaving all windows visible: {}",
+            error
+        );
+        if let Err(error) = wm.restore_all_windows(false) {
+            tracing::error!("could not restore hidden windows: {}", error);
+        }
+    } else {
+        tracing::info!("recovered from panic on {thread} thread");
+    }
+
+    tray_manager::toast(
+        ToastLevel::Error,
+        "komorebi recovered from a crash",
+        &format!(
+            "The {thread} thread was restarted, the state at the time was written to {}",
+            crash_dump.display()
+        ),
+    );
+}
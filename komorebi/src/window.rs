@@ -11,10 +11,13 @@ use crate::animation::ANIMATION_MANAGER;
 use crate::animation::ANIMATION_STYLE_GLOBAL;
 use crate::animation::ANIMATION_STYLE_PER_ANIMATION;
 use crate::border_manager;
+use crate::com::set_cloak;
 use crate::com::SetCloak;
+use crate::core::config_generation::BorderOverflowOffset;
 use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
 use crate::core::config_generation::MatchingStrategy;
+use crate::core::config_generation::WorkspaceMatchingRule;
 use crate::core::ApplicationIdentifier;
 use crate::core::HidingBehaviour;
 use crate::core::Rect;
@@ -27,6 +30,9 @@ use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api;
 use crate::windows_api::WindowsApi;
 use crate::AnimationStyle;
+use crate::BORDER_OVERFLOW_IDENTIFIERS;
+use crate::BORDER_OVERFLOW_OFFSETS;
+use crate::CLOAKED_HWNDS;
 use crate::FLOATING_APPLICATIONS;
 use crate::FLOATING_WINDOW_TOGGLE_ASPECT_RATIO;
 use crate::HIDDEN_HWNDS;
@@ -34,11 +40,24 @@ use crate::HIDING_BEHAVIOUR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MINIMUM_SIZES;
 use crate::NO_TITLEBAR;
+use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::OFFSCREEN_WINDOW_RECTS;
 use crate::PERMAIGNORE_CLASSES;
+use crate::PRESENTATION_APPLICATIONS;
+use crate::PRESENTATION_MODE;
+use crate::RECENTLY_SHOWN_WINDOWS;
 use crate::REGEX_IDENTIFIERS;
+use crate::SHORT_LIVED_WINDOWS;
+use crate::SHORT_LIVED_WINDOW_EXPIRY;
 use crate::SLOW_APPLICATION_COMPENSATION_TIME;
 use crate::SLOW_APPLICATION_IDENTIFIERS;
+use crate::STACK_APPLICATION_IDENTIFIERS;
+use crate::TRANSIENT_APPLICATIONS;
+use crate::TRANSIENT_WINDOW_LIFETIME;
+use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use crate::WORKSPACE_MATCHING_RULES;
 use crate::WSL2_UI_PROCESSES;
 use color_eyre::eyre;
 use color_eyre::Result;
@@ -57,6 +76,7 @@ use std::sync::atomic::AtomicI32;
 use std::sync::atomic::Ordering;
 use std::thread;
 use std::time::Duration;
+use std::time::Instant;
 use strum::Display;
 use strum::EnumString;
 use windows::Win32::Foundation::HWND;
@@ -70,6 +90,25 @@ pub struct Window {
     pub hwnd: isize,
 }
 
+/// How a window was hidden by the Cloak hiding behaviour, so that it can be shown again the same way
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CloakMethod {
+    Dwm,
+    ApplicationView,
+    Hide,
+}
+
+/// How the invisible borders of a window are compensated for when it is positioned, which is
+/// resolved once per window so that rules don't have to be matched every time it is moved
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameCompensation {
+    /// The offset which was detected when the window started being managed, or the explicit
+    /// offset which its application has been identified with
+    Fixed(Rect),
+    /// Border overflow applications are measured again every time they are positioned
+    Measured,
+}
+
 impl From<isize> for Window {
     fn from(value: isize) -> Self {
         Self { hwnd: value }
@@ -465,6 +504,80 @@ impl Window {
         }
     }
 
+    /// Whether this window belongs to an application which has been identified as one which may be
+    /// shared on screen, and which should not be hidden in presentation mode
+    pub fn is_presentation_application(self) -> bool {
+        let presentation_applications = PRESENTATION_APPLICATIONS.lock().clone();
+        if presentation_applications.is_empty() {
+            return false;
+        }
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        should_act(
+            &self.title().unwrap_or_default(),
+            &self.exe().unwrap_or_default(),
+            &self.class().unwrap_or_default(),
+            &self.path().unwrap_or_default(),
+            &presentation_applications,
+            &regex_identifiers,
+        )
+        .is_some()
+    }
+
+    /// How the invisible borders of this window should be compensated for: with the explicit
+    /// offset its application has been identified with, by measuring them every time if it is a
+    /// border overflow application, or otherwise with the offset which is detected now
+    pub fn frame_compensation(self) -> Result<FrameCompensation> {
+        let border_overflow_offsets = BORDER_OVERFLOW_OFFSETS.lock().clone();
+        let border_overflow_identifiers = BORDER_OVERFLOW_IDENTIFIERS.lock().clone();
+
+        if !border_overflow_offsets.is_empty() || !border_overflow_identifiers.is_empty() {
+            let title = self.title().unwrap_or_default();
+            let exe_name = self.exe().unwrap_or_default();
+            let class = self.class().unwrap_or_default();
+            let path = self.path().unwrap_or_default();
+
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+            let matches = |rules: &[MatchingRule]| {
+                should_act(&title, &exe_name, &class, &path, rules, &regex_identifiers).is_some()
+            };
+
+            if let Some(offset) = border_overflow_offsets
+                .iter()
+                .find(|offset| matches(std::slice::from_ref(&offset.matching_rule)))
+            {
+                let offset = offset.offset;
+                return Ok(FrameCompensation::Fixed(Rect {
+                    left: -offset.left,
+                    top: -offset.top,
+                    right: offset.left + offset.right,
+                    bottom: offset.top + offset.bottom,
+                }));
+            }
+
+            if matches(&border_overflow_identifiers) {
+                return Ok(FrameCompensation::Measured);
+            }
+        }
+
+        Ok(FrameCompensation::Fixed(WindowsApi::shadow_rect(
+            self.hwnd,
+        )?))
+    }
+
+    /// The minimum size that the window reported when it was managed or last resized, so that
+    /// retiling never has to wait on the window to answer
+    pub fn minimum_size(self) -> Option<(i32, i32)> {
+        let cached = MINIMUM_SIZES.lock().get(&self.hwnd).copied();
+        cached.unwrap_or_else(|| self.refresh_minimum_size())
+    }
+
+    pub fn refresh_minimum_size(self) -> Option<(i32, i32)> {
+        let minimum_size = WindowsApi::minimum_size(self.hwnd);
+        MINIMUM_SIZES.lock().insert(self.hwnd, minimum_size);
+        minimum_size
+    }
+
     pub fn is_maximized(self) -> bool {
         WindowsApi::is_zoomed(self.hwnd)
     }
@@ -477,7 +590,27 @@ impl Window {
         WindowsApi::is_window_visible(self.hwnd)
     }
 
+    /// The window which owns this one, such as the main window of an application for one of its
+    /// dialogs or tool windows
+    pub fn owner(self) -> Option<isize> {
+        WindowsApi::owner_window(self.hwnd).ok()
+    }
+
     pub fn hide_with_border(self, hide_border: bool) {
+        // Hidden, minimized and cloaked windows are no longer rendered, which freezes screen
+        // sharing streams of them, so in presentation mode they are only pushed behind everything
+        if PRESENTATION_MODE.load(Ordering::SeqCst) && self.is_presentation_application() {
+            if let Err(error) = WindowsApi::lower_window(self.hwnd) {
+                tracing::error!("could not lower window {}: {error}", self.hwnd);
+            }
+
+            if hide_border {
+                border_manager::hide_border(self.hwnd);
+            }
+
+            return;
+        }
+
         let mut programmatically_hidden_hwnds = HIDDEN_HWNDS.lock();
         if !programmatically_hidden_hwnds.contains(&self.hwnd) {
             programmatically_hidden_hwnds.push(self.hwnd);
@@ -487,7 +620,8 @@ impl Window {
         match *hiding_behaviour {
             HidingBehaviour::Hide => WindowsApi::hide_window(self.hwnd),
             HidingBehaviour::Minimize => WindowsApi::minimize_window(self.hwnd),
-            HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 2),
+            HidingBehaviour::Cloak => self.cloak(),
+            HidingBehaviour::MoveOffscreen => self.move_offscreen(),
         }
         if hide_border {
             border_manager::hide_border(self.hwnd);
@@ -512,7 +646,8 @@ impl Window {
             HidingBehaviour::Hide | HidingBehaviour::Minimize => {
                 WindowsApi::restore_window(self.hwnd);
             }
-            HidingBehaviour::Cloak => SetCloak(self.hwnd(), 1, 0),
+            HidingBehaviour::Cloak => self.uncloak(),
+            HidingBehaviour::MoveOffscreen => self.move_onscreen(),
         }
         if restore_border {
             border_manager::show_border(self.hwnd);
@@ -523,6 +658,77 @@ impl Window {
         self.restore_with_border(true);
     }
 
+    /// Cloaks the window with DWM if it belongs to komorebi, as DWM doesn't allow a process to
+    /// cloak the windows of other processes, and through its application view otherwise, hiding it
+    /// instead if it cannot be cloaked at all
+    fn cloak(self) {
+        let cloaked = if self.process_id() == WindowsApi::current_process_id() {
+            WindowsApi::dwm_cloak_window(self.hwnd, true).map(|()| CloakMethod::Dwm)
+        } else {
+            set_cloak(self.hwnd(), 1, 2).map(|()| CloakMethod::ApplicationView)
+        };
+
+        let method = cloaked.unwrap_or_else(|error| {
+            tracing::debug!(
+                "window {} could not be cloaked, hiding it instead: {error}",
+                self.hwnd
+            );
+            WindowsApi::hide_window(self.hwnd);
+            CloakMethod::Hide
+        });
+
+        CLOAKED_HWNDS.lock().insert(self.hwnd, method);
+    }
+
+    /// Reverses whichever method was used to cloak the window
+    fn uncloak(self) {
+        match CLOAKED_HWNDS.lock().remove(&self.hwnd) {
+            Some(CloakMethod::Dwm) => {
+                if let Err(error) = WindowsApi::dwm_cloak_window(self.hwnd, false) {
+                    tracing::error!("could not uncloak window {}: {error}", self.hwnd);
+                }
+            }
+            Some(CloakMethod::Hide) => WindowsApi::restore_window(self.hwnd),
+            Some(CloakMethod::ApplicationView) | None => SetCloak(self.hwnd(), 1, 0),
+        }
+    }
+
+    /// Moves the window past the bottom right corner of the virtual screen, remembering where it
+    /// was so that it can be moved back
+    fn move_offscreen(self) {
+        let Ok(rect) = WindowsApi::window_rect(self.hwnd) else {
+            return;
+        };
+
+        // a window which is hidden again while already off-screen keeps its original position
+        OFFSCREEN_WINDOW_RECTS
+            .lock()
+            .entry(self.hwnd)
+            .or_insert(rect);
+
+        let screen = WindowsApi::virtual_screen_rect();
+        let offscreen = Rect {
+            left: screen.left + screen.right,
+            top: screen.top + screen.bottom,
+            ..rect
+        };
+
+        if let Err(error) = WindowsApi::move_window(self.hwnd, &offscreen, false) {
+            tracing::error!("could not move window {} off-screen: {error}", self.hwnd);
+        }
+    }
+
+    fn move_onscreen(self) {
+        if let Some(rect) = OFFSCREEN_WINDOW_RECTS.lock().remove(&self.hwnd) {
+            if let Err(error) = WindowsApi::move_window(self.hwnd, &rect, true) {
+                tracing::error!(
+                    "could not move window {} back on-screen: {error}",
+                    self.hwnd
+                );
+            }
+        }
+    }
+
     pub fn minimize(self) {
         let exe = self.exe().unwrap_or_default();
         if !exe.contains("komorebi-bar") {
@@ -851,6 +1057,8 @@ impl Window {
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct RuleDebug {
+    #[serde(default)]
+    pub hwnd: isize,
     pub should_manage: bool,
     pub is_window: bool,
     pub has_minimum_width: bool,
@@ -872,6 +1080,162 @@ pub struct RuleDebug {
     pub matches_floating_applications: Option<MatchingRule>,
     pub matches_wsl2_gui: Option<String>,
     pub matches_no_titlebar: Option<MatchingRule>,
+    pub matches_transient_applications: Option<MatchingRule>,
+    pub transient_heuristic: Option<TransientHeuristic>,
+    pub matches_workspace_rule: Option<WorkspaceMatchingRule>,
+    pub matches_border_overflow: Option<MatchingRule>,
+    pub matches_border_overflow_offset: Option<BorderOverflowOffset>,
+    pub matches_tray_and_multi_window: Option<MatchingRule>,
+    pub matches_stack_application: Option<MatchingRule>,
+    pub matches_object_name_change_on_launch: Option<MatchingRule>,
+    pub matches_slow_application: Option<MatchingRule>,
+    pub matches_presentation_application: Option<MatchingRule>,
+    /// The monitor and workspace indices of the workspace which the window is managed on
+    pub managed_location: Option<(usize, usize)>,
+}
+
+impl RuleDebug {
+    /// Checks whether komorebi should manage `window`, filling in its title, exe and class even if
+    /// it was rejected before they would have been looked up, so that it can be identified
+    pub fn for_window(window: Window) -> Self {
+        let mut debug = Self {
+            hwnd: window.hwnd,
+            ..Default::default()
+        };
+
+        if let Ok(should_manage) = window.should_manage(None, &mut debug) {
+            debug.should_manage = should_manage;
+        }
+
+        debug.title = debug.title.or_else(|| window.title().ok());
+        debug.exe_name = debug.exe_name.or_else(|| window.exe().ok());
+        debug.class = debug.class.or_else(|| window.class().ok());
+        debug.path = debug.path.or_else(|| window.path().ok());
+
+        debug.match_application_rules();
+
+        debug
+    }
+
+    /// Records which of the rules that change how a window is handled once it is managed, rather
+    /// than whether it is managed at all, match the window
+    fn match_application_rules(&mut self) {
+        let (Some(title), Some(exe_name), Some(class), Some(path)) =
+            (&self.title, &self.exe_name, &self.class, &self.path)
+        else {
+            return;
+        };
+
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+        let matches = |identifiers: &[MatchingRule]| {
+            should_act(
+                title,
+                exe_name,
+                class,
+                path,
+                identifiers,
+                &regex_identifiers,
+            )
+        };
+
+        self.matches_workspace_rule = WORKSPACE_MATCHING_RULES
+            .lock()
+            .iter()
+            .find(|rule| matches(std::slice::from_ref(&rule.matching_rule)).is_some())
+            .cloned();
+        self.matches_border_overflow = matches(&BORDER_OVERFLOW_IDENTIFIERS.lock());
+        self.matches_border_overflow_offset = BORDER_OVERFLOW_OFFSETS
+            .lock()
+            .iter()
+            .find(|offset| matches(std::slice::from_ref(&offset.matching_rule)).is_some())
+            .cloned();
+        self.matches_tray_and_multi_window = matches(&TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock());
+        self.matches_stack_application = matches(&STACK_APPLICATION_IDENTIFIERS.lock());
+        self.matches_object_name_change_on_launch = matches(&OBJECT_NAME_CHANGE_ON_LAUNCH.lock());
+        self.matches_slow_application = matches(&SLOW_APPLICATION_IDENTIFIERS.lock());
+        self.matches_presentation_application = matches(&PRESENTATION_APPLICATIONS.lock());
+    }
+}
+
+/// Why a window was recognised as transient, such as a splash screen or a tooltip, which komorebi
+/// never attempts to manage
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransientHeuristic {
+    /// The window is a tool window which doesn't appear in the taskbar
+    ToolWindow,
+    /// The window has no width or no height
+    ZeroSize,
+    /// A window with the same exe, class and title was destroyed shortly after being shown
+    ShortLived,
+}
+
+fn transient_heuristic(
+    hwnd: isize,
+    title: &str,
+    exe_name: &str,
+    class: &str,
+    ex_style: &ExtendedWindowStyle,
+) -> Option<TransientHeuristic> {
+    if ex_style.contains(ExtendedWindowStyle::TOOLWINDOW)
+        && !ex_style.contains(ExtendedWindowStyle::APPWINDOW)
+    {
+        return Some(TransientHeuristic::ToolWindow);
+    }
+
+    if let Ok(rect) = WindowsApi::window_rect(hwnd) {
+        if rect.right == 0 || rect.bottom == 0 {
+            return Some(TransientHeuristic::ZeroSize);
+        }
+    }
+
+    let mut short_lived = SHORT_LIVED_WINDOWS.lock();
+    if short_lived.is_empty() {
+        return None;
+    }
+
+    short_lived.retain(|_, recorded_at| recorded_at.elapsed() < SHORT_LIVED_WINDOW_EXPIRY);
+    if short_lived.contains_key(&(exe_name.to_string(), class.to_string(), title.to_string())) {
+        return Some(TransientHeuristic::ShortLived);
+    }
+
+    None
+}
+
+/// Remembers when a window which is about to be managed was shown, so that windows like it can be
+/// recognised as transient if it is destroyed again soon after
+pub fn record_shown(window: Window) {
+    let lifetime = Duration::from_millis(TRANSIENT_WINDOW_LIFETIME.load(Ordering::SeqCst));
+    if lifetime.is_zero() {
+        return;
+    }
+
+    if let (Ok(exe_name), Ok(class), Ok(title)) = (window.exe(), window.class(), window.title()) {
+        let mut recently_shown = RECENTLY_SHOWN_WINDOWS.lock();
+        recently_shown.retain(|_, (shown_at, _)| shown_at.elapsed() < lifetime);
+        recently_shown.insert(window.hwnd, (Instant::now(), (exe_name, class, title)));
+    }
+}
+
+/// Treats windows with the same exe, class and title as a destroyed window as transient for the
+/// next `SHORT_LIVED_WINDOW_EXPIRY` if it only lived for a moment after being shown, e.g. a splash
+/// screen
+pub fn record_destroyed(hwnd: isize) {
+    let lifetime = Duration::from_millis(TRANSIENT_WINDOW_LIFETIME.load(Ordering::SeqCst));
+
+    let Some((shown_at, identity)) = RECENTLY_SHOWN_WINDOWS.lock().remove(&hwnd) else {
+        return;
+    };
+
+    if shown_at.elapsed() < lifetime {
+        tracing::info!(
+            "treating windows with exe {}, class {} and title {} as transient",
+            identity.0,
+            identity.1,
+            identity.2
+        );
+
+        SHORT_LIVED_WINDOWS.lock().insert(identity, Instant::now());
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -942,6 +1306,40 @@ fn window_is_eligible(
         return false;
     }
 
+    // events which remove windows must still be handled for any which were managed before they
+    // were recognised as transient, and explicitly managing a window always takes precedence
+    let skip_transient_check = matches!(
+        event,
+        Some(
+            WindowManagerEvent::Destroy(_, _)
+                | WindowManagerEvent::Hide(_, _)
+                | WindowManagerEvent::Cloak(_, _)
+                | WindowManagerEvent::Minimize(_, _)
+                | WindowManagerEvent::Manage(_)
+                | WindowManagerEvent::Unmanage(_)
+        )
+    );
+
+    if !managed_override && !skip_transient_check {
+        let transient_identifiers = TRANSIENT_APPLICATIONS.lock();
+        if let Some(rule) = should_act(
+            title,
+            exe_name,
+            class,
+            path,
+            &transient_identifiers,
+            &regex_identifiers,
+        ) {
+            debug.matches_transient_applications = Some(rule);
+            return false;
+        }
+
+        if let Some(heuristic) = transient_heuristic(hwnd, title, exe_name, class, ex_style) {
+            debug.transient_heuristic = Some(heuristic);
+            return false;
+        }
+    }
+
     let layered_whitelist = LAYERED_WHITELIST.lock();
     let mut allow_layered = if let Some(rule) = should_act(
         title,
@@ -1,15 +1,19 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::env::temp_dir;
 use std::fs::OpenOptions;
 use std::io::ErrorKind;
 use std::net::Shutdown;
 use std::num::NonZeroUsize;
+use std::os::windows::process::CommandExt;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use color_eyre::eyre::anyhow;
 use color_eyre::eyre::bail;
@@ -19,15 +23,21 @@ use hotwatch::notify::ErrorKind as NotifyErrorKind;
 use hotwatch::EventKind;
 use hotwatch::Hotwatch;
 use parking_lot::Mutex;
+use regex::Regex;
 use serde::Deserialize;
 use serde::Serialize;
+use sysinfo::Pid;
+use sysinfo::ProcessesToUpdate;
 use uds_windows::UnixListener;
 use uds_windows::UnixStream;
+use windows::Win32::System::Threading::CREATE_NO_WINDOW;
 
 use crate::animation::AnimationEngine;
 use crate::animation::ANIMATION_ENABLED_GLOBAL;
 use crate::animation::ANIMATION_ENABLED_PER_ANIMATION;
+use crate::core::config_generation::IdWithIdentifier;
 use crate::core::config_generation::MatchingRule;
+use crate::core::config_generation::MatchingStrategy;
 use crate::core::custom_layout::CustomLayout;
 use crate::core::Arrangement;
 use crate::core::Axis;
@@ -35,28 +45,36 @@ use crate::core::BorderImplementation;
 use crate::core::BorderStyle;
 use crate::core::CycleDirection;
 use crate::core::DefaultLayout;
+use crate::core::DirectionalFocusBehaviour;
 use crate::core::FocusFollowsMouseImplementation;
 use crate::core::HidingBehaviour;
 use crate::core::Layout;
+use crate::core::MonocleWithNewWindowBehaviour;
 use crate::core::MoveBehaviour;
 use crate::core::OperationBehaviour;
 use crate::core::OperationDirection;
 use crate::core::Rect;
 use crate::core::Sizing;
+use crate::core::SplitDirection;
 use crate::core::StackbarLabel;
 use crate::core::WindowContainerBehaviour;
 use crate::core::WindowManagementBehaviour;
+use crate::core::WorkspaceSaveOption;
 
 use crate::border_manager;
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::border_manager::STYLE;
+use crate::com;
 use crate::config_generation::WorkspaceMatchingRule;
 use crate::container::Container;
 use crate::core::StackbarMode;
 use crate::current_virtual_desktop;
+use crate::hotkey_manager;
 use crate::load_configuration;
 use crate::monitor::Monitor;
+use crate::paths;
+use crate::process_movement;
 use crate::ring::Ring;
 use crate::should_act;
 use crate::should_act_individual;
@@ -67,14 +85,19 @@ use crate::stackbar_manager::STACKBAR_TAB_BACKGROUND_COLOUR;
 use crate::stackbar_manager::STACKBAR_TAB_HEIGHT;
 use crate::stackbar_manager::STACKBAR_TAB_WIDTH;
 use crate::stackbar_manager::STACKBAR_UNFOCUSED_TEXT_COLOUR;
+use crate::state_snapshot;
 use crate::static_config::StaticConfig;
 use crate::transparency_manager;
 use crate::transparency_manager::TRANSPARENCY_ALPHA;
 use crate::transparency_manager::TRANSPARENCY_ENABLED;
+use crate::tray_manager;
+use crate::tray_manager::ToastLevel;
+use crate::virtual_desktops;
 use crate::window::Window;
 use crate::window_manager_event::WindowManagerEvent;
 use crate::windows_api::WindowsApi;
 use crate::winevent_listener;
+use crate::workspace::SavedWorkspace;
 use crate::workspace::Workspace;
 use crate::workspace::WorkspaceLayer;
 use crate::BorderColours;
@@ -83,27 +106,39 @@ use crate::CrossBoundaryBehaviour;
 use crate::Rgb;
 use crate::CUSTOM_FFM;
 use crate::DATA_DIR;
+use crate::DEFAULT_CONTAINER_PADDING;
+use crate::DEFAULT_WORKSPACE_PADDING;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::DUPLICATE_MONITOR_SERIAL_IDS;
+use crate::FRAME_COMPENSATIONS;
 use crate::HIDING_BEHAVIOUR;
 use crate::HOME_DIR;
 use crate::IGNORE_IDENTIFIERS;
 use crate::LAYERED_WHITELIST;
 use crate::MANAGE_IDENTIFIERS;
+use crate::MINIMUM_SIZES;
 use crate::MONITOR_INDEX_PREFERENCES;
 use crate::NO_TITLEBAR;
 use crate::OBJECT_NAME_CHANGE_ON_LAUNCH;
+use crate::PRESENTATION_APPLICATIONS;
+use crate::PRESENTATION_MODE;
 use crate::REGEX_IDENTIFIERS;
 use crate::REMOVE_TITLEBARS;
+use crate::STACK_APPLICATION_IDENTIFIERS;
+use crate::STARTED_AT;
 use crate::SUBSCRIPTION_SOCKETS;
 use crate::TRANSPARENCY_BLACKLIST;
 use crate::TRAY_AND_MULTI_WINDOW_IDENTIFIERS;
+use crate::VIRTUAL_DESKTOP_AWARENESS;
 use crate::WORKSPACE_MATCHING_RULES;
 
 #[derive(Debug)]
 pub struct WindowManager {
     pub monitors: Ring<Monitor>,
     pub monitor_usr_idx_map: HashMap<usize, usize>,
+    /// Maps each monitor alias from the configuration to the index used for that monitor in
+    /// configuration and commands
+    pub monitor_aliases: HashMap<String, usize>,
     pub incoming_events: Receiver<WindowManagerEvent>,
     pub command_listener: UnixListener,
     pub is_paused: bool,
@@ -112,6 +147,8 @@ pub struct WindowManager {
     pub window_management_behaviour: WindowManagementBehaviour,
     pub cross_monitor_move_behaviour: MoveBehaviour,
     pub cross_boundary_behaviour: CrossBoundaryBehaviour,
+    pub monocle_with_new_window_behaviour: MonocleWithNewWindowBehaviour,
+    pub directional_focus_behaviour: DirectionalFocusBehaviour,
     pub unmanaged_window_operation_behaviour: OperationBehaviour,
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
@@ -123,6 +160,18 @@ pub struct WindowManager {
     pub uncloack_to_ignore: usize,
     /// Maps each known window hwnd to the (monitor, workspace) index pair managing it
     pub known_hwnds: HashMap<isize, (usize, usize)>,
+    /// Maps each floating window which is owned by a managed window, such as a dialog, to the hwnd
+    /// of its owner
+    pub owned_windows: HashMap<isize, isize>,
+    /// Per-monitor workspaces of virtual desktops other than the current one
+    pub virtual_desktop_workspaces: HashMap<Vec<u8>, Vec<Ring<Workspace>>>,
+    /// Maps the process id of each application launched with `SocketMessage::Exec` to the
+    /// placement requested for its first window
+    pub pending_launches: HashMap<u32, PendingLaunch>,
+    /// Where each managed window was before komorebi started tiling it
+    pub pre_tiling_rects: HashMap<isize, Rect>,
+    /// Whether all windows have been handed back to Windows with `SocketMessage::ToggleSoftStop`
+    pub is_soft_stopped: bool,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -131,6 +180,8 @@ pub struct WindowManager {
 pub struct State {
     pub monitors: Ring<Monitor>,
     pub monitor_usr_idx_map: HashMap<usize, usize>,
+    #[serde(default)]
+    pub monitor_aliases: HashMap<String, usize>,
     pub is_paused: bool,
     pub resize_delta: i32,
     pub new_window_behaviour: WindowContainerBehaviour,
@@ -141,6 +192,14 @@ pub struct State {
     pub focus_follows_mouse: Option<FocusFollowsMouseImplementation>,
     pub mouse_follows_focus: bool,
     pub has_pending_raise_op: bool,
+    pub keybinding_mode: Option<String>,
+    /// The name of the socket which this instance of komorebi listens for commands on
+    #[serde(default = "default_socket_name")]
+    pub socket_name: String,
+}
+
+fn default_socket_name() -> String {
+    paths::DEFAULT_SOCKET_NAME.to_string()
 }
 
 impl State {
@@ -151,6 +210,10 @@ impl State {
             return true;
         }
 
+        if self.monitor_aliases != new.monitor_aliases {
+            return true;
+        }
+
         if self.is_paused != new.is_paused {
             return true;
         }
@@ -187,6 +250,10 @@ impl State {
             return true;
         }
 
+        if self.keybinding_mode != new.keybinding_mode {
+            return true;
+        }
+
         false
     }
 }
@@ -211,11 +278,14 @@ pub struct GlobalState {
     pub transparency_alpha: u8,
     pub transparency_blacklist: Vec<MatchingRule>,
     pub remove_titlebars: bool,
+    pub presentation_mode: bool,
+    pub presentation_applications: Vec<MatchingRule>,
     #[serde(alias = "float_identifiers")]
     pub ignore_identifiers: Vec<MatchingRule>,
     pub manage_identifiers: Vec<MatchingRule>,
     pub layered_whitelist: Vec<MatchingRule>,
     pub tray_and_multi_window_identifiers: Vec<MatchingRule>,
+    pub stack_application_identifiers: Vec<MatchingRule>,
     pub name_change_on_launch_identifiers: Vec<MatchingRule>,
     pub monitor_index_preferences: HashMap<usize, Rect>,
     pub display_index_preferences: HashMap<usize, String>,
@@ -268,10 +338,13 @@ impl Default for GlobalState {
             transparency_alpha: TRANSPARENCY_ALPHA.load(Ordering::SeqCst),
             transparency_blacklist: TRANSPARENCY_BLACKLIST.lock().clone(),
             remove_titlebars: REMOVE_TITLEBARS.load(Ordering::SeqCst),
+            presentation_mode: PRESENTATION_MODE.load(Ordering::SeqCst),
+            presentation_applications: PRESENTATION_APPLICATIONS.lock().clone(),
             ignore_identifiers: IGNORE_IDENTIFIERS.lock().clone(),
             manage_identifiers: MANAGE_IDENTIFIERS.lock().clone(),
             layered_whitelist: LAYERED_WHITELIST.lock().clone(),
             tray_and_multi_window_identifiers: TRAY_AND_MULTI_WINDOW_IDENTIFIERS.lock().clone(),
+            stack_application_identifiers: STACK_APPLICATION_IDENTIFIERS.lock().clone(),
             name_change_on_launch_identifiers: OBJECT_NAME_CHANGE_ON_LAUNCH.lock().clone(),
             monitor_index_preferences: MONITOR_INDEX_PREFERENCES.lock().clone(),
             display_index_preferences: DISPLAY_INDEX_PREFERENCES.read().clone(),
@@ -285,6 +358,28 @@ impl Default for GlobalState {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PingResponse {
+    pub message: String,
+    pub version: String,
+    pub uptime_secs: u64,
+    pub managed_windows: usize,
+    pub is_paused: bool,
+}
+
+impl From<&WindowManager> for PingResponse {
+    fn from(wm: &WindowManager) -> Self {
+        Self {
+            message: String::from("pong"),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: STARTED_AT.elapsed().as_secs(),
+            managed_windows: wm.known_hwnds.len(),
+            is_paused: wm.is_paused,
+        }
+    }
+}
+
 impl AsRef<Self> for WindowManager {
     fn as_ref(&self) -> &Self {
         self
@@ -343,7 +438,10 @@ impl From<&WindowManager> for State {
                             layer: workspace.layer,
                             globals: workspace.globals,
                             locked_containers: workspace.locked_containers.clone(),
+                            floated_containers: workspace.floated_containers.clone(),
                             workspace_config: None,
+                            auto_name: workspace.dominant_application(),
+                            minimum_size_floats: workspace.minimum_size_floats.clone(),
                         })
                         .collect::<VecDeque<_>>();
                     ws.focus(monitor.workspaces.focused_idx());
@@ -360,6 +458,7 @@ impl From<&WindowManager> for State {
         Self {
             monitors: stripped_monitors,
             monitor_usr_idx_map: wm.monitor_usr_idx_map.clone(),
+            monitor_aliases: wm.monitor_aliases.clone(),
             is_paused: wm.is_paused,
             work_area_offset: wm.work_area_offset,
             resize_delta: wm.resize_delta,
@@ -370,12 +469,30 @@ impl From<&WindowManager> for State {
             mouse_follows_focus: wm.mouse_follows_focus,
             has_pending_raise_op: wm.has_pending_raise_op,
             unmanaged_window_operation_behaviour: wm.unmanaged_window_operation_behaviour,
+            keybinding_mode: hotkey_manager::active_mode(),
+            socket_name: paths::socket_name().to_string(),
         }
     }
 }
 
 impl_ring_elements!(WindowManager, Monitor);
 
+/// How long the first window of an application launched with `SocketMessage::Exec` is waited for
+/// before its requested placement is forgotten
+const PENDING_LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How many ancestors of the process of a new window are checked for a pending launch, as
+/// applications are often started by cmd.exe or a launcher which exits straight away
+const PENDING_LAUNCH_ANCESTORS: usize = 3;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PendingLaunch {
+    /// Monitor and workspace index pair for the window, or the focused workspace if `None`
+    pub target: Option<(usize, usize)>,
+    pub floating: bool,
+    pub launched_at: Instant,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct EnforceWorkspaceRuleOp {
     hwnd: isize,
@@ -406,7 +523,7 @@ impl WindowManager {
         incoming: Receiver<WindowManagerEvent>,
         custom_socket_path: Option<PathBuf>,
     ) -> Result<Self> {
-        let socket = custom_socket_path.unwrap_or_else(|| DATA_DIR.join("komorebi.sock"));
+        let socket = custom_socket_path.unwrap_or_else(paths::socket_path);
 
         match std::fs::remove_file(&socket) {
             Ok(()) => {}
@@ -424,6 +541,7 @@ impl WindowManager {
         Ok(Self {
             monitors: Ring::default(),
             monitor_usr_idx_map: HashMap::new(),
+            monitor_aliases: HashMap::new(),
             incoming_events: incoming,
             command_listener: listener,
             is_paused: false,
@@ -432,6 +550,8 @@ impl WindowManager {
             window_management_behaviour: WindowManagementBehaviour::default(),
             cross_monitor_move_behaviour: MoveBehaviour::Swap,
             cross_boundary_behaviour: CrossBoundaryBehaviour::Workspace,
+            monocle_with_new_window_behaviour: MonocleWithNewWindowBehaviour::Background,
+            directional_focus_behaviour: DirectionalFocusBehaviour::Layer,
             unmanaged_window_operation_behaviour: OperationBehaviour::Op,
             resize_delta: 50,
             focus_follows_mouse: None,
@@ -442,159 +562,252 @@ impl WindowManager {
             already_moved_window_handles: Arc::new(Mutex::new(HashSet::new())),
             uncloack_to_ignore: 0,
             known_hwnds: HashMap::new(),
+            owned_windows: HashMap::new(),
+            virtual_desktop_workspaces: HashMap::new(),
+            pending_launches: HashMap::new(),
+            pre_tiling_rects: HashMap::new(),
+            is_soft_stopped: false,
         })
     }
 
+    /// Loads the connected monitors and adopts the windows which are already open, returning
+    /// where each adopted window was before komorebi started tiling it
     #[tracing::instrument(skip(self))]
-    pub fn init(&mut self) -> Result<()> {
+    pub fn init(&mut self) -> Result<HashMap<isize, Rect>> {
         tracing::info!("initialising");
         WindowsApi::load_monitor_information(self)?;
-        WindowsApi::load_workspace_information(&mut self.monitors)
+        WindowsApi::load_workspace_information(&mut self.monitors)?;
+
+        let mut adopted = HashMap::new();
+        for monitor in self.monitors() {
+            for workspace in monitor.workspaces() {
+                for hwnd in workspace.hwnds() {
+                    if let Ok(rect) = WindowsApi::window_rect(hwnd) {
+                        adopted.insert(hwnd, rect);
+                    }
+                }
+            }
+        }
+
+        self.pre_tiling_rects.clone_from(&adopted);
+
+        Ok(adopted)
     }
 
+    /// Restores the workspaces of a previous session, dropping any windows which no longer exist
+    /// and adopting windows which have been opened since onto the focused workspace of the monitor
+    /// they are on. Returns whether the state could be applied.
     #[tracing::instrument(skip(self, state))]
-    pub fn apply_state(&mut self, state: State) {
-        let mut can_apply = true;
-
+    pub fn apply_state(&mut self, mut state: State) -> bool {
         let state_monitors_len = state.monitors.elements().len();
         let current_monitors_len = self.monitors.elements().len();
         if state_monitors_len != current_monitors_len {
             tracing::warn!(
                 "cannot apply state from {}; state file has {state_monitors_len} monitors, but only {current_monitors_len} are currently connected",
-                temp_dir().join("komorebi.state.json").to_string_lossy()
+                state_snapshot::state_file_path().to_string_lossy()
             );
 
-            return;
+            return false;
         }
 
-        for monitor in state.monitors.elements() {
-            for workspace in monitor.workspaces() {
-                for container in workspace.containers() {
-                    for window in container.windows() {
-                        if window.exe().is_err() {
-                            can_apply = false;
-                            break;
-                        }
-                    }
-                }
-
-                if let Some(window) = workspace.maximized_window() {
-                    if window.exe().is_err() {
-                        can_apply = false;
-                        break;
-                    }
-                }
+        let mut restored = HashSet::new();
+        let mut missing = 0;
 
-                if let Some(container) = workspace.monocle_container() {
-                    for window in container.windows() {
-                        if window.exe().is_err() {
-                            can_apply = false;
-                            break;
+        for monitor in state.monitors.elements_mut() {
+            for workspace in monitor.workspaces_mut() {
+                for hwnd in workspace.hwnds() {
+                    if Window::from(hwnd).exe().is_ok() {
+                        restored.insert(hwnd);
+                    } else {
+                        missing += 1;
+                        if let Err(error) = workspace.remove_window(hwnd) {
+                            tracing::warn!("cannot drop window {hwnd} from the state: {error}");
                         }
                     }
                 }
-
-                for window in workspace.floating_windows() {
-                    if window.exe().is_err() {
-                        can_apply = false;
-                        break;
-                    }
-                }
             }
         }
 
-        if can_apply {
-            tracing::info!(
-                "applying state from {}",
-                temp_dir().join("komorebi.state.json").to_string_lossy()
+        if missing > 0 {
+            tracing::warn!(
+                "{missing} windows referenced in {} no longer exist and will not be restored",
+                state_snapshot::state_file_path().to_string_lossy()
             );
+        }
 
-            let offset = self.work_area_offset;
-            let mouse_follows_focus = self.mouse_follows_focus;
-            for (monitor_idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
-                let mut focused_workspace = 0;
-                for (workspace_idx, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
-                    if let Some(state_monitor) = state.monitors.elements().get(monitor_idx) {
-                        if let Some(state_workspace) = state_monitor.workspaces().get(workspace_idx)
-                        {
-                            // to make sure padding changes get applied for users after a quick restart
-                            let container_padding = workspace.container_padding();
-                            let workspace_padding = workspace.workspace_padding();
-
-                            *workspace = state_workspace.clone();
+        // windows which were opened after the state was written, grouped by the monitor they
+        // were adopted on
+        let unknown = self
+            .monitors()
+            .iter()
+            .map(|monitor| {
+                monitor
+                    .workspaces()
+                    .iter()
+                    .flat_map(Workspace::hwnds)
+                    .filter(|hwnd| !restored.contains(hwnd))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
 
-                            workspace.set_container_padding(container_padding);
-                            workspace.set_workspace_padding(workspace_padding);
+        tracing::info!(
+            "applying state from {}",
+            state_snapshot::state_file_path().to_string_lossy()
+        );
 
-                            if state_monitor.focused_workspace_idx() == workspace_idx {
-                                focused_workspace = workspace_idx;
-                            }
+        let offset = self.work_area_offset;
+        let mouse_follows_focus = self.mouse_follows_focus;
+        for (monitor_idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            let mut focused_workspace = 0;
+            for (workspace_idx, workspace) in monitor.workspaces_mut().iter_mut().enumerate() {
+                if let Some(state_monitor) = state.monitors.elements().get(monitor_idx) {
+                    if let Some(state_workspace) = state_monitor.workspaces().get(workspace_idx) {
+                        // to make sure padding changes get applied for users after a quick restart
+                        let container_padding = workspace.container_padding();
+                        let workspace_padding = workspace.workspace_padding();
+
+                        *workspace = state_workspace.clone();
+
+                        workspace.set_container_padding(container_padding);
+                        workspace.set_workspace_padding(workspace_padding);
+
+                        if state_monitor.focused_workspace_idx() == workspace_idx {
+                            focused_workspace = workspace_idx;
                         }
                     }
                 }
+            }
 
-                if let Err(error) = monitor.focus_workspace(focused_workspace) {
-                    tracing::warn!(
-                        "cannot focus workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
-                        temp_dir().join("komorebi.state.json").to_string_lossy(),
-                        error,
-                    );
-                }
-
-                if let Err(error) = monitor.load_focused_workspace(mouse_follows_focus) {
-                    tracing::warn!(
-                        "cannot load focused workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
-                        temp_dir().join("komorebi.state.json").to_string_lossy(),
-                        error,
-                    );
-                }
-
-                if let Err(error) = monitor.update_focused_workspace(offset) {
-                    tracing::warn!(
-                        "cannot update workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
-                        temp_dir().join("komorebi.state.json").to_string_lossy(),
-                        error,
-                    );
+            if let Some(workspace) = monitor.workspaces_mut().get_mut(focused_workspace) {
+                for hwnd in unknown.get(monitor_idx).into_iter().flatten() {
+                    tracing::warn!("adopting window {hwnd}, which is not in the state");
+                    workspace.new_container_for_window(Window::from(*hwnd));
                 }
             }
 
-            let focused_monitor_idx = state.monitors.focused_idx();
-            let focused_workspace_idx = state
-                .monitors
-                .elements()
-                .get(focused_monitor_idx)
-                .map(|m| m.focused_workspace_idx())
-                .unwrap_or_default();
-
-            if let Err(error) = self.focus_monitor(focused_monitor_idx) {
+            if let Err(error) = monitor.focus_workspace(focused_workspace) {
                 tracing::warn!(
-                    "cannot focus monitor '{focused_monitor_idx}' from {}: {}",
-                    temp_dir().join("komorebi.state.json").to_string_lossy(),
+                    "cannot focus workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
+                    state_snapshot::state_file_path().to_string_lossy(),
                     error,
                 );
             }
 
-            if let Err(error) = self.focus_workspace(focused_workspace_idx) {
+            if let Err(error) = monitor.load_focused_workspace(mouse_follows_focus) {
                 tracing::warn!(
-                    "cannot focus workspace '{focused_workspace_idx}' on monitor '{focused_monitor_idx}' from {}: {}",
-                    temp_dir().join("komorebi.state.json").to_string_lossy(),
+                    "cannot load focused workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
+                    state_snapshot::state_file_path().to_string_lossy(),
                     error,
                 );
             }
 
-            if let Err(error) = self.update_focused_workspace(true, true) {
+            if let Err(error) = monitor.update_focused_workspace(offset) {
                 tracing::warn!(
-                    "cannot update focused workspace '{focused_workspace_idx}' on monitor '{focused_monitor_idx}' from {}: {}",
-                    temp_dir().join("komorebi.state.json").to_string_lossy(),
+                    "cannot update workspace '{focused_workspace}' on monitor '{monitor_idx}' from {}: {}",
+                    state_snapshot::state_file_path().to_string_lossy(),
                     error,
                 );
             }
-        } else {
+        }
+
+        let focused_monitor_idx = state.monitors.focused_idx();
+        let focused_workspace_idx = state
+            .monitors
+            .elements()
+            .get(focused_monitor_idx)
+            .map(|m| m.focused_workspace_idx())
+            .unwrap_or_default();
+
+        if let Err(error) = self.focus_monitor(focused_monitor_idx) {
+            tracing::warn!(
+                "cannot focus monitor '{focused_monitor_idx}' from {}: {}",
+                state_snapshot::state_file_path().to_string_lossy(),
+                error,
+            );
+        }
+
+        if let Err(error) = self.focus_workspace(focused_workspace_idx) {
+            tracing::warn!(
+                "cannot focus workspace '{focused_workspace_idx}' on monitor '{focused_monitor_idx}' from {}: {}",
+                state_snapshot::state_file_path().to_string_lossy(),
+                error,
+            );
+        }
+
+        if let Err(error) = self.update_focused_workspace(true, true) {
             tracing::warn!(
-                "cannot apply state from {}; some windows referenced in the state file no longer exist",
-                temp_dir().join("komorebi.state.json").to_string_lossy()
+                "cannot update focused workspace '{focused_workspace_idx}' on monitor '{focused_monitor_idx}' from {}: {}",
+                state_snapshot::state_file_path().to_string_lossy(),
+                error,
             );
         }
+
+        true
+    }
+
+    /// Resizes the tiles of the windows adopted by [`WindowManager::init`] towards where each
+    /// window was before it was tiled, so that windows which were arranged side by side keep their
+    /// relative sizes
+    #[tracing::instrument(skip(self, adopted))]
+    pub fn infer_resize_dimensions(&mut self, adopted: &HashMap<isize, Rect>) -> Result<()> {
+        let focused_monitor_idx = self.focused_monitor_idx();
+
+        for monitor_idx in 0..self.monitors().len() {
+            self.focus_monitor(monitor_idx)?;
+
+            let work_area = self.focused_monitor_work_area()?;
+            let workspace = self.focused_workspace()?;
+
+            // only the default layouts can be resized
+            if !matches!(workspace.layout(), Layout::Default(_)) || workspace.containers().len() < 2
+            {
+                continue;
+            }
+
+            let focused_container_idx = workspace.focused_container_idx();
+
+            for container_idx in 0..workspace.containers().len() {
+                let workspace = self.focused_workspace_mut()?;
+
+                let Some(hwnd) = workspace
+                    .containers()
+                    .get(container_idx)
+                    .and_then(|container| container.focused_window())
+                    .map(|window| window.hwnd)
+                else {
+                    continue;
+                };
+
+                let (Some(original), Some(tiled)) = (
+                    adopted.get(&hwnd),
+                    workspace.latest_layout().get(container_idx),
+                ) else {
+                    continue;
+                };
+
+                // a window which was as wide or as tall as the monitor says nothing about how it
+                // was arranged next to the others
+                if original.right >= work_area.right || original.bottom >= work_area.bottom {
+                    continue;
+                }
+
+                let resize = Rect {
+                    left: original.left - tiled.left,
+                    top: original.top - tiled.top,
+                    right: original.right - tiled.right,
+                    bottom: original.bottom - tiled.bottom,
+                };
+
+                workspace.focus_container(container_idx);
+                self.resize_by_edge_deltas(resize)?;
+            }
+
+            self.focused_workspace_mut()?
+                .focus_container(focused_container_idx);
+        }
+
+        self.focus_monitor(focused_monitor_idx)?;
+        self.update_focused_workspace(false, false)
     }
 
     #[tracing::instrument]
@@ -606,7 +819,17 @@ impl WindowManager {
     #[tracing::instrument(skip(self))]
     pub fn reload_static_configuration(&mut self, pathbuf: &PathBuf) -> Result<()> {
         tracing::info!("reloading static configuration");
-        StaticConfig::reload(pathbuf, self)
+
+        let reloaded = StaticConfig::reload(pathbuf, self);
+        if let Err(error) = &reloaded {
+            tray_manager::toast(
+                ToastLevel::Error,
+                "Could not reload the configuration",
+                &error.to_string(),
+            );
+        }
+
+        reloaded
     }
 
     pub fn window_management_behaviour(
@@ -1016,6 +1239,73 @@ impl WindowManager {
             }
 
             workspace.update()?;
+            workspace.enforce_floating_window_z_order()?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds an ignore rule and stops managing any windows on focused workspaces which match it
+    #[tracing::instrument(skip(self))]
+    pub fn add_ignore_rule(&mut self, rule: IdWithIdentifier) -> Result<()> {
+        {
+            let mut regex_identifiers = REGEX_IDENTIFIERS.lock();
+            let mut ignore_identifiers = IGNORE_IDENTIFIERS.lock();
+
+            if matches!(rule.matching_strategy, Some(MatchingStrategy::Regex)) {
+                regex_identifiers.insert(rule.id.clone(), Regex::new(&rule.id)?);
+            }
+
+            let rule = MatchingRule::Simple(rule.clone());
+            if !ignore_identifiers.contains(&rule) {
+                ignore_identifiers.push(rule);
+            }
+        }
+
+        let offset = self.work_area_offset;
+
+        let mut hwnds_to_purge = vec![];
+        {
+            let regex_identifiers = REGEX_IDENTIFIERS.lock();
+            for (i, monitor) in self.monitors().iter().enumerate() {
+                for container in monitor
+                    .focused_workspace()
+                    .ok_or_else(|| anyhow!("there is no workspace"))?
+                    .containers()
+                {
+                    for window in container.windows() {
+                        // Windows which can no longer be queried are left to the reaper
+                        if let (Ok(title), Ok(exe), Ok(class), Ok(path)) =
+                            (window.title(), window.exe(), window.class(), window.path())
+                        {
+                            if should_act_individual(
+                                &title,
+                                &exe,
+                                &class,
+                                &path,
+                                &rule,
+                                &regex_identifiers,
+                            ) {
+                                hwnds_to_purge.push((i, window.hwnd));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for (monitor_idx, hwnd) in hwnds_to_purge {
+            let monitor = self
+                .monitors_mut()
+                .get_mut(monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+            monitor
+                .focused_workspace_mut()
+                .ok_or_else(|| anyhow!("there is no focused workspace"))?
+                .remove_window(hwnd)?;
+
+            monitor.update_focused_workspace(offset)?;
         }
 
         Ok(())
@@ -1023,20 +1313,88 @@ impl WindowManager {
 
     #[tracing::instrument(skip(self))]
     pub fn manage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
-        let event = WindowManagerEvent::Manage(Window::from(hwnd));
-        Ok(winevent_listener::event_tx().send(event)?)
+        self.manage_window(WindowsApi::foreground_window()?)
     }
 
     #[tracing::instrument(skip(self))]
     pub fn unmanage_focused_window(&mut self) -> Result<()> {
-        let hwnd = WindowsApi::foreground_window()?;
-        let event = WindowManagerEvent::Unmanage(Window::from(hwnd));
+        self.unmanage_window(WindowsApi::foreground_window()?)
+    }
+
+    /// Forces a window to be managed on the focused workspace, whether or not it is focused
+    #[tracing::instrument(skip(self))]
+    pub fn manage_window(&mut self, hwnd: isize) -> Result<()> {
+        if !WindowsApi::is_window(hwnd) {
+            bail!("{hwnd} is not a window");
+        }
+
+        if self.known_hwnds.contains_key(&hwnd) {
+            bail!("{hwnd} is already managed");
+        }
+
+        let event = WindowManagerEvent::Manage(Window::from(hwnd));
         Ok(winevent_listener::event_tx().send(event)?)
     }
 
+    /// Releases a managed window from whichever workspace it is on, whether or not it is focused
+    #[tracing::instrument(skip(self))]
+    pub fn unmanage_window(&mut self, hwnd: isize) -> Result<()> {
+        let focused_pair = (self.focused_monitor_idx(), self.focused_workspace_idx()?);
+
+        match self.known_hwnds.get(&hwnd).copied() {
+            Some((monitor_idx, workspace_idx)) if (monitor_idx, workspace_idx) != focused_pair => {
+                self.monitors_mut()
+                    .get_mut(monitor_idx)
+                    .and_then(|monitor| monitor.workspaces_mut().get_mut(workspace_idx))
+                    .ok_or_else(|| anyhow!("there is no workspace for window {hwnd}"))?
+                    .remove_window(hwnd)?;
+
+                // windows on workspaces which are not visible are hidden, and nothing would show
+                // this one again once it is no longer managed
+                Window::from(hwnd).restore();
+
+                Ok(())
+            }
+            _ => {
+                let event = WindowManagerEvent::Unmanage(Window::from(hwnd));
+                Ok(winevent_listener::event_tx().send(event)?)
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn raise_window_at_cursor_pos(&mut self) -> Result<()> {
+        let cursor_pos = WindowsApi::cursor_pos()?;
+
+        if process_movement::EXCLUSION_ZONES
+            .lock()
+            .iter()
+            .any(|zone| zone.contains_point((cursor_pos.x, cursor_pos.y)))
+        {
+            return Ok(());
+        }
+
+        let cursor_window = Window::from(WindowsApi::window_from_point(cursor_pos)?);
+        if let (Ok(title), Ok(exe), Ok(class), Ok(path)) = (
+            cursor_window.title(),
+            cursor_window.exe(),
+            cursor_window.class(),
+            cursor_window.path(),
+        ) {
+            if should_act(
+                &title,
+                &exe,
+                &class,
+                &path,
+                &process_movement::EXCLUDED_IDENTIFIERS.lock(),
+                &REGEX_IDENTIFIERS.lock(),
+            )
+            .is_some()
+            {
+                return Ok(());
+            }
+        }
+
         let mut hwnd = None;
 
         let workspace = self.focused_workspace()?;
@@ -1389,6 +1747,52 @@ impl WindowManager {
             }
         }
 
+        self.focused_workspace()?.enforce_floating_window_z_order()
+    }
+
+    /// Resizes the focused container by how far each edge of its window has moved from where it
+    /// was tiled, as when a tiled window is resized with the mouse
+    #[tracing::instrument(skip(self))]
+    pub fn resize_by_edge_deltas(&mut self, resize: Rect) -> Result<()> {
+        let mut ops = vec![];
+
+        macro_rules! resize_op {
+            ($coordinate:expr, $comparator:tt, $direction:expr) => {{
+                let adjusted = $coordinate * 2;
+                let sizing = if adjusted $comparator 0 {
+                    Sizing::Decrease
+                } else {
+                    Sizing::Increase
+                };
+
+                ($direction, sizing, adjusted.abs())
+            }};
+        }
+
+        if resize.left != 0 {
+            ops.push(resize_op!(resize.left, >, OperationDirection::Left));
+        }
+
+        if resize.top != 0 {
+            ops.push(resize_op!(resize.top, >, OperationDirection::Up));
+        }
+
+        // TODO: Determine if this is still needed
+        let top_left_constant =
+            BORDER_WIDTH.load(Ordering::SeqCst) + BORDER_OFFSET.load(Ordering::SeqCst);
+
+        if resize.right != 0 && (resize.left == top_left_constant || resize.left == 0) {
+            ops.push(resize_op!(resize.right, <, OperationDirection::Right));
+        }
+
+        if resize.bottom != 0 && (resize.top == top_left_constant || resize.top == 0) {
+            ops.push(resize_op!(resize.bottom, <, OperationDirection::Down));
+        }
+
+        for (edge, sizing, delta) in ops {
+            self.resize_window(edge, sizing, delta, true)?;
+        }
+
         Ok(())
     }
 
@@ -1505,6 +1909,7 @@ impl WindowManager {
                                 workspace.layout_flip(),
                                 focused_idx,
                                 len,
+                                &workspace.split_directions(),
                             )
                             .is_some()
                         {
@@ -1514,6 +1919,7 @@ impl WindowManager {
                                 workspace.container_padding(),
                                 workspace.layout_flip(),
                                 &[],
+                                &workspace.split_directions(),
                             );
 
                             let mut direction = direction;
@@ -1579,7 +1985,7 @@ impl WindowManager {
 
         let state = &State::from(&*self);
         std::fs::write(
-            temp_dir().join("komorebi.state.json"),
+            state_snapshot::state_file_path(),
             serde_json::to_string_pretty(&state)?,
         )?;
 
@@ -1599,8 +2005,7 @@ impl WindowManager {
             }
         }
 
-        let socket = DATA_DIR.join("komorebi.sock");
-        let _ = std::fs::remove_file(socket);
+        let _ = std::fs::remove_file(paths::socket_path());
 
         std::process::exit(0)
     }
@@ -1656,24 +2061,41 @@ impl WindowManager {
             }
         }
 
+        if !ignore_restore {
+            for workspaces in self.virtual_desktop_workspaces.values() {
+                for workspace in workspaces.iter().flat_map(|ring| ring.elements()) {
+                    for container in workspace.containers() {
+                        for window in container.windows() {
+                            window.restore();
+                        }
+                    }
+                }
+            }
+        }
+
         Ok(())
     }
 
+    /// Pauses komorebi and hands every managed window back to Windows, showing the windows of
+    /// every workspace and moving them back to where they were before they were tiled
     #[tracing::instrument(skip(self))]
-    pub fn remove_all_accents(&mut self) -> Result<()> {
-        tracing::info!("removing all window accents");
+    pub fn soft_stop(&mut self) -> Result<()> {
+        tracing::info!("soft stopping");
+
+        self.is_paused = true;
+        self.is_soft_stopped = true;
+        self.restore_all_windows(false)?;
 
         for monitor in self.monitors() {
             for workspace in monitor.workspaces() {
-                if let Some(monocle) = workspace.monocle_container() {
-                    for window in monocle.windows() {
-                        window.remove_accent()?
-                    }
-                }
+                for hwnd in workspace.hwnds() {
+                    let window = Window::from(hwnd);
+                    window.restore();
 
-                for containers in workspace.containers() {
-                    for window in containers.windows() {
-                        window.remove_accent()?;
+                    if let Some(rect) = self.pre_tiling_rects.get(&hwnd) {
+                        if let Err(error) = WindowsApi::position_window(hwnd, rect, false) {
+                            tracing::warn!("could not move window {hwnd} back: {error}");
+                        }
                     }
                 }
             }
@@ -1682,28 +2104,150 @@ impl WindowManager {
         Ok(())
     }
 
-    #[tracing::instrument(skip(self))]
-    fn handle_unmanaged_window_behaviour(&self) -> Result<()> {
-        if matches!(
-            self.unmanaged_window_operation_behaviour,
-            OperationBehaviour::NoOp
-        ) {
-            let workspace = self.focused_workspace()?;
-            let focused_hwnd = WindowsApi::foreground_window()?;
-            if !workspace.contains_managed_window(focused_hwnd) {
-                bail!("ignoring commands while active window is not managed by komorebi");
+    /// Toggles presentation mode, bringing back the windows of presentation applications which
+    /// were hidden on workspaces that are not focused when it is enabled, and hiding them properly
+    /// again when it is disabled
+    pub fn toggle_presentation_mode(&self) {
+        let enabled = !PRESENTATION_MODE.load(Ordering::SeqCst);
+        PRESENTATION_MODE.store(enabled, Ordering::SeqCst);
+
+        tracing::info!(
+            "presentation mode {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+
+        for monitor in self.monitors() {
+            let focused_workspace_idx = monitor.focused_workspace_idx();
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                if workspace_idx == focused_workspace_idx {
+                    continue;
+                }
+
+                for hwnd in workspace.hwnds() {
+                    let window = Window::from(hwnd);
+                    if !window.is_presentation_application() {
+                        continue;
+                    }
+
+                    if enabled {
+                        window.restore_with_border(false);
+                    }
+
+                    window.hide();
+                }
             }
         }
-
-        Ok(())
     }
 
-    pub fn update_focused_workspace_by_monitor_idx(&mut self, idx: usize) -> Result<()> {
-        let offset = self.work_area_offset;
+    /// Takes back control of the windows handed to Windows by `soft_stop`, dropping the windows
+    /// which were closed in the meantime and adopting those which were opened onto the focused
+    /// workspace of the monitor they are on, before retiling everything
+    #[tracing::instrument(skip(self))]
+    pub fn soft_resume(&mut self) -> Result<()> {
+        tracing::info!("resuming from soft stop");
 
-        self.monitors_mut()
-            .get_mut(idx)
-            .ok_or_else(|| anyhow!("there is no monitor"))?
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                for hwnd in workspace.hwnds() {
+                    if !WindowsApi::is_window(hwnd) {
+                        workspace.remove_window(hwnd)?;
+                    }
+                }
+            }
+        }
+
+        for window in WindowsApi::alt_tab_windows()? {
+            if self.known_hwnds.contains_key(&window.hwnd) {
+                continue;
+            }
+
+            let Some(monitor_idx) = self.monitor_idx_from_window(window) else {
+                continue;
+            };
+
+            if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                self.pre_tiling_rects.insert(window.hwnd, rect);
+            }
+
+            if let Some(workspace) = self
+                .monitors_mut()
+                .get_mut(monitor_idx)
+                .and_then(Monitor::focused_workspace_mut)
+            {
+                tracing::info!(
+                    "adopting window {}, which was opened while soft stopped",
+                    window.hwnd
+                );
+                workspace.new_container_for_window(window);
+            }
+        }
+
+        self.is_paused = false;
+        self.is_soft_stopped = false;
+
+        let mouse_follows_focus = self.mouse_follows_focus;
+        for monitor in self.monitors_mut() {
+            monitor.load_focused_workspace(mouse_follows_focus)?;
+        }
+
+        self.update_known_hwnds();
+        self.retile_all(true)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn remove_all_accents(&mut self) -> Result<()> {
+        tracing::info!("removing all window accents");
+
+        for monitor in self.monitors() {
+            for workspace in monitor.workspaces() {
+                if let Some(monocle) = workspace.monocle_container() {
+                    for window in monocle.windows() {
+                        window.remove_accent()?
+                    }
+                }
+
+                for containers in workspace.containers() {
+                    for window in containers.windows() {
+                        window.remove_accent()?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    fn handle_unmanaged_window_behaviour(&self) -> Result<()> {
+        if matches!(
+            self.unmanaged_window_operation_behaviour,
+            OperationBehaviour::NoOp
+        ) {
+            let workspace = self.focused_workspace()?;
+            let focused_hwnd = WindowsApi::foreground_window()?;
+            if !workspace.contains_managed_window(focused_hwnd) {
+                tray_manager::toast(
+                    ToastLevel::Warning,
+                    "Command ignored",
+                    &format!(
+                        "The focused window ({}) is not managed by komorebi",
+                        Window::from(focused_hwnd).exe().unwrap_or_default()
+                    ),
+                );
+
+                bail!("ignoring commands while active window is not managed by komorebi");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn update_focused_workspace_by_monitor_idx(&mut self, idx: usize) -> Result<()> {
+        let offset = self.work_area_offset;
+
+        self.monitors_mut()
+            .get_mut(idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?
             .update_focused_workspace(offset)
     }
 
@@ -1933,6 +2477,104 @@ impl WindowManager {
         Ok(())
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn move_container_to_virtual_desktop(&mut self, idx: usize, follow: bool) -> Result<()> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        tracing::info!("moving container to virtual desktop");
+
+        let target = virtual_desktops()
+            .get(idx)
+            .cloned()
+            .ok_or_else(|| anyhow!("there is no virtual desktop at index {idx}"))?;
+
+        if current_virtual_desktop().as_ref() == Some(&target) {
+            bail!("the focused container is already on virtual desktop {idx}");
+        }
+
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let monitor_idx = self.focused_monitor_idx();
+        let workspace = self.focused_workspace_mut()?;
+
+        if workspace.maximized_window().is_some() {
+            bail!("cannot move native maximized window to another virtual desktop");
+        }
+
+        if workspace.monocle_container().is_some() {
+            bail!("cannot move a monocle container to another virtual desktop");
+        }
+
+        let focused_container = workspace
+            .focused_container()
+            .ok_or_else(|| anyhow!("there is no container"))?;
+
+        // Windows only lets a process move its own windows between virtual desktops, so check
+        // every window up front rather than leaving the container split across two desktops
+        let current_process_id = WindowsApi::current_process_id();
+        if let Some(window) = focused_container.windows().iter().find(|window| {
+            WindowsApi::window_thread_process_id(window.hwnd).0 != current_process_id
+        }) {
+            static WARNED: AtomicBool = AtomicBool::new(false);
+            if !WARNED.swap(true, Ordering::SeqCst) {
+                tracing::warn!(
+                    "windows only lets a process move its own windows between virtual desktops, so komorebi cannot move windows of other applications to another virtual desktop"
+                );
+            }
+
+            bail!(
+                "cannot move {} to virtual desktop {idx}, as it belongs to another process",
+                window.exe().unwrap_or_else(|_| window.hwnd.to_string())
+            );
+        }
+
+        for window in focused_container.windows() {
+            if !com::move_window_to_virtual_desktop(window.hwnd, &target)? {
+                bail!(
+                    "cannot move {} to virtual desktop {idx}, as access was denied",
+                    window.exe().unwrap_or_else(|_| window.hwnd.to_string())
+                );
+            }
+        }
+
+        let container = workspace
+            .remove_focused_container()
+            .ok_or_else(|| anyhow!("there is no container"))?;
+
+        let focused_hwnd = container.focused_window().map(|window| window.hwnd);
+
+        if VIRTUAL_DESKTOP_AWARENESS.load(Ordering::SeqCst) {
+            let monitors = &self.monitors;
+            let workspaces = self
+                .virtual_desktop_workspaces
+                .entry(target)
+                .or_insert_with(|| {
+                    monitors
+                        .elements()
+                        .iter()
+                        .map(Monitor::workspaces_without_windows)
+                        .collect()
+                });
+
+            if let Some(workspace) = workspaces
+                .get_mut(monitor_idx)
+                .and_then(|workspaces| workspaces.focused_mut())
+            {
+                workspace.add_container_to_back(container);
+            }
+        }
+
+        self.update_focused_workspace(mouse_follows_focus, !follow)?;
+
+        if follow {
+            // Focusing a window on another virtual desktop makes the shell switch to it
+            if let Some(hwnd) = focused_hwnd {
+                Window::from(hwnd).focus(mouse_follows_focus)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn remove_focused_workspace(&mut self) -> Option<Workspace> {
         let focused_monitor: &mut Monitor = self.focused_monitor_mut()?;
         let focused_workspace_idx = focused_monitor.focused_workspace_idx();
@@ -1972,6 +2614,67 @@ impl WindowManager {
         self.update_focused_workspace(mouse_follows_focus, true)
     }
 
+    /// Focuses the tiled or floating window on the focused workspace which is nearest to the
+    /// focused window in the given direction, based on where they are on the screen. Returns
+    /// whether there was such a window.
+    #[tracing::instrument(skip(self))]
+    pub fn focus_nearest_window_in_direction(
+        &mut self,
+        direction: OperationDirection,
+    ) -> Result<bool> {
+        self.handle_unmanaged_window_behaviour()?;
+
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let workspace = self.focused_workspace()?;
+
+        if workspace.maximized_window().is_some() || workspace.monocle_container().is_some() {
+            return Ok(false);
+        }
+
+        let focused_hwnd = WindowsApi::foreground_window()?;
+        let focused_rect = WindowsApi::window_rect(focused_hwnd)?;
+
+        // (is floating, index) of the visible window of every container and of every float
+        let mut candidates = vec![];
+        for (idx, container) in workspace.containers().iter().enumerate() {
+            if let Some(window) = container.focused_window() {
+                if window.hwnd != focused_hwnd {
+                    if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                        candidates.push(((false, idx), rect));
+                    }
+                }
+            }
+        }
+
+        for (idx, window) in workspace.floating_windows().iter().enumerate() {
+            if window.hwnd != focused_hwnd {
+                if let Ok(rect) = WindowsApi::window_rect(window.hwnd) {
+                    candidates.push(((true, idx), rect));
+                }
+            }
+        }
+
+        let Some((floating, idx)) = nearest_in_direction(direction, &focused_rect, &candidates)
+        else {
+            return Ok(false);
+        };
+
+        let workspace = self.focused_workspace_mut()?;
+        if floating {
+            workspace.set_layer(WorkspaceLayer::Floating);
+            workspace.floating_windows.focus(idx);
+            if let Some(window) = workspace.floating_windows().get(idx) {
+                window.focus(mouse_follows_focus)?;
+            }
+        } else {
+            workspace.set_layer(WorkspaceLayer::Tiling);
+            workspace.focus_container(idx);
+            self.focused_window()?.focus(mouse_follows_focus)?;
+        }
+
+        Ok(true)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn focus_floating_window_in_direction(
         &mut self,
@@ -2868,7 +3571,12 @@ impl WindowManager {
         }
 
         workspace.focus_container(workspace.containers().len().saturating_sub(1));
-        while workspace.focused_container_idx() > 0 {
+        while workspace.focused_container_idx() > 0
+            && !workspace
+                .containers()
+                .front()
+                .is_some_and(Container::is_full)
+        {
             workspace.move_window_to_container(0)?;
             workspace.focus_container(workspace.containers().len().saturating_sub(1));
         }
@@ -2931,6 +3639,7 @@ impl WindowManager {
                 workspace.layout_flip(),
                 workspace.focused_container_idx(),
                 len,
+                &workspace.split_directions(),
             )
             .is_some();
 
@@ -3050,6 +3759,35 @@ impl WindowManager {
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_workspace_float(&mut self) -> Result<()> {
+        if self.focused_workspace()?.floated_containers().is_some() {
+            tracing::info!("tiling floated containers");
+
+            let workspace = self.focused_workspace_mut()?;
+            workspace.tile_floated_containers();
+            workspace.set_layer(WorkspaceLayer::Tiling);
+        } else {
+            tracing::info!("floating all containers");
+
+            // the tiles of the windows in monocle or maximized have to be laid out first
+            let workspace = self.focused_workspace()?;
+            if workspace.monocle_container().is_some() {
+                self.monocle_off()?;
+                self.update_focused_workspace(false, false)?;
+            } else if workspace.maximized_window().is_some() {
+                self.unmaximize_window()?;
+                self.update_focused_workspace(false, false)?;
+            }
+
+            let workspace = self.focused_workspace_mut()?;
+            workspace.float_containers()?;
+            workspace.set_layer(WorkspaceLayer::Floating);
+        }
+
+        self.update_focused_workspace(self.mouse_follows_focus, true)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn toggle_float(&mut self) -> Result<()> {
         let hwnd = WindowsApi::foreground_window()?;
@@ -3166,6 +3904,45 @@ impl WindowManager {
         workspace.reintegrate_monocle_container()
     }
 
+    /// Handles a new window which was tiled on the focused workspace while it has a monocle
+    /// container, according to `monocle_with_new_window_behaviour`
+    #[tracing::instrument(skip(self))]
+    pub fn monocle_new_window(&mut self, window: Window) -> Result<()> {
+        let mouse_follows_focus = self.mouse_follows_focus;
+        let behaviour = self.monocle_with_new_window_behaviour;
+        let workspace = self.focused_workspace_mut()?;
+
+        match behaviour {
+            MonocleWithNewWindowBehaviour::Background => {
+                window.hide();
+
+                if let Some(monocle_window) = workspace
+                    .monocle_container()
+                    .as_ref()
+                    .and_then(Container::focused_window)
+                {
+                    monocle_window.focus(mouse_follows_focus)?;
+                }
+            }
+            MonocleWithNewWindowBehaviour::Focus => {
+                workspace.replace_monocle_container(window.hwnd)?;
+
+                for container in workspace.containers_mut() {
+                    container.hide(None);
+                }
+
+                self.update_focused_workspace(mouse_follows_focus, true)?;
+            }
+        }
+
+        tracing::info!(
+            "{} windows hidden behind the monocle container",
+            self.focused_workspace()?.monocle_hidden_window_count()
+        );
+
+        Ok(())
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn toggle_maximize(&mut self) -> Result<()> {
         self.handle_unmanaged_window_behaviour()?;
@@ -3196,6 +3973,82 @@ impl WindowManager {
         workspace.reintegrate_maximized_window()
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_split_direction(&mut self, split_direction: SplitDirection) -> Result<()> {
+        tracing::info!("setting split direction");
+
+        self.focused_container_mut()?
+            .set_split_direction(Option::from(split_direction));
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn toggle_split_direction(&mut self) -> Result<()> {
+        let workspace = self.focused_workspace()?;
+        let focused_idx = workspace.focused_container_idx();
+        let current = workspace
+            .focused_container()
+            .ok_or_else(|| anyhow!("there is no container"))?
+            .split_direction()
+            .unwrap_or_else(|| SplitDirection::default_for_idx(focused_idx));
+
+        self.set_split_direction(current.opposite())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn save_focused_workspace(
+        &self,
+        path: &Path,
+        options: &[WorkspaceSaveOption],
+    ) -> Result<()> {
+        tracing::info!("saving workspace");
+
+        let saved = self.focused_workspace()?.save(options);
+
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(&file, &saved)?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn load_focused_workspace(&mut self, saved: SavedWorkspace) -> Result<()> {
+        tracing::info!("loading workspace");
+
+        let name = match &saved {
+            SavedWorkspace::Snapshot(snapshot) => snapshot.name.clone(),
+            SavedWorkspace::ResizeDimensions(_) => None,
+        };
+
+        self.focused_workspace_mut()?.load(saved);
+
+        if let Some(name) = name {
+            let monitor_idx = self.focused_monitor_idx();
+            let workspace_idx = self.focused_workspace_idx()?;
+            self.set_workspace_name(monitor_idx, workspace_idx, name)?;
+        }
+
+        self.update_focused_workspace(false, false)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn balance_workspace(&mut self) -> Result<()> {
+        tracing::info!("balancing workspace");
+
+        let workspace = self.focused_workspace_mut()?;
+        for resize in workspace.resize_dimensions_mut() {
+            *resize = None;
+        }
+
+        self.update_focused_workspace(false, false)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn flip_layout(&mut self, layout_flip: Axis) -> Result<()> {
         let workspace = self.focused_workspace_mut()?;
@@ -3671,6 +4524,76 @@ impl WindowManager {
         self.update_focused_workspace(false, false)
     }
 
+    #[tracing::instrument(skip(self))]
+    pub fn set_monitor_container_padding(&mut self, monitor_idx: usize, size: i32) -> Result<()> {
+        tracing::info!("setting monitor container padding");
+
+        let offset = self.work_area_offset;
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        monitor.set_container_padding(Option::from(size));
+        monitor.update_workspaces_globals(offset);
+        monitor.update_focused_workspace(offset)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn set_monitor_workspace_padding(&mut self, monitor_idx: usize, size: i32) -> Result<()> {
+        tracing::info!("setting monitor workspace padding");
+
+        let offset = self.work_area_offset;
+
+        let monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor"))?;
+
+        monitor.set_workspace_padding(Option::from(size));
+        monitor.update_workspaces_globals(offset);
+        monitor.update_focused_workspace(offset)
+    }
+
+    /// Sets the global default container padding, which applies to workspaces without their own
+    /// container padding on monitors without their own default
+    #[tracing::instrument(skip(self))]
+    pub fn set_default_container_padding(&mut self, size: i32) -> Result<()> {
+        tracing::info!("setting default container padding");
+
+        DEFAULT_CONTAINER_PADDING.store(size, Ordering::SeqCst);
+
+        let offset = self.work_area_offset;
+        for monitor in self.monitors_mut() {
+            if monitor.container_padding().is_none() {
+                monitor.update_workspaces_globals(offset);
+                monitor.update_focused_workspace(offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the global default workspace padding, which applies to workspaces without their own
+    /// workspace padding on monitors without their own default
+    #[tracing::instrument(skip(self))]
+    pub fn set_default_workspace_padding(&mut self, size: i32) -> Result<()> {
+        tracing::info!("setting default workspace padding");
+
+        DEFAULT_WORKSPACE_PADDING.store(size, Ordering::SeqCst);
+
+        let offset = self.work_area_offset;
+        for monitor in self.monitors_mut() {
+            if monitor.workspace_padding().is_none() {
+                monitor.update_workspaces_globals(offset);
+                monitor.update_focused_workspace(offset)?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn focused_monitor_size(&self) -> Result<Rect> {
         Ok(*self
             .focused_monitor()
@@ -3698,6 +4621,67 @@ impl WindowManager {
         Ok(())
     }
 
+    /// Rebuilds the map from the monitor indices used in configuration and commands to the indices
+    /// of those monitors in `monitors`, which differ when a monitor with a display index preference
+    /// is enumerated in a different order or is disconnected
+    pub fn update_monitor_usr_idx_map(&mut self) {
+        let mut monitor_usr_idx_map = HashMap::new();
+        let mut added_monitor_idxs = Vec::new();
+        for (index, id) in &*DISPLAY_INDEX_PREFERENCES.read() {
+            if let Some(m_idx) = self.monitors.elements().iter().position(|m| {
+                m.serial_number_id().as_ref().is_some_and(|sn| sn == id) || m.device_id() == id
+            }) {
+                monitor_usr_idx_map.insert(*index, m_idx);
+                added_monitor_idxs.push(m_idx);
+            }
+        }
+
+        let max_usr_idx = self
+            .monitors
+            .elements()
+            .len()
+            .max(monitor_usr_idx_map.keys().max().map_or(0, |v| *v));
+
+        let mut available_usr_idxs = (0..max_usr_idx)
+            .filter(|i| !monitor_usr_idx_map.contains_key(i))
+            .collect::<Vec<_>>();
+
+        let not_added_monitor_idxs = (0..self.monitors.elements().len())
+            .filter(|i| !added_monitor_idxs.contains(i))
+            .collect::<Vec<_>>();
+
+        for i in not_added_monitor_idxs {
+            if let Some(next_usr_idx) = available_usr_idxs.first() {
+                monitor_usr_idx_map.insert(*next_usr_idx, i);
+                available_usr_idxs.remove(0);
+            } else if let Some(idx) = monitor_usr_idx_map.keys().max() {
+                monitor_usr_idx_map.insert(*idx, i);
+            }
+        }
+
+        self.monitor_usr_idx_map = monitor_usr_idx_map;
+    }
+
+    /// The index in `monitors` of the monitor which configuration and commands refer to as
+    /// `usr_idx`
+    pub fn monitor_idx_from_usr_idx(&self, usr_idx: usize) -> usize {
+        self.monitor_usr_idx_map
+            .get(&usr_idx)
+            .copied()
+            .unwrap_or(usr_idx)
+    }
+
+    /// The index in `monitors` of the monitor which has been given `alias` in the configuration
+    pub fn monitor_idx_from_alias(&self, alias: &str) -> Result<usize> {
+        let usr_idx = self
+            .monitor_aliases
+            .get(alias)
+            .copied()
+            .ok_or_else(|| anyhow!("there is no monitor with the alias '{alias}'"))?;
+
+        Ok(self.monitor_idx_from_usr_idx(usr_idx))
+    }
+
     pub fn monitor_idx_from_window(&mut self, window: Window) -> Option<usize> {
         let hmonitor = WindowsApi::monitor_from_window(window.hwnd);
 
@@ -3804,6 +4788,24 @@ impl WindowManager {
         self.update_focused_workspace(false, true)
     }
 
+    /// Moves the cursor to the centre of the focused window, or of the focused monitor when there
+    /// is no window on the focused workspace, regardless of whether mouse follows focus is enabled
+    pub fn center_cursor_in_focused_workspace(&self) -> Result<()> {
+        let workspace = self.focused_workspace()?;
+        let window = if workspace.layer() == &WorkspaceLayer::Floating {
+            workspace.focused_floating_window()
+        } else {
+            None
+        };
+
+        let rect = match window.or_else(|| self.focused_window().ok()) {
+            Some(window) => WindowsApi::window_rect(window.hwnd)?,
+            None => self.focused_monitor_work_area()?,
+        };
+
+        WindowsApi::center_cursor_in_rect(&rect)
+    }
+
     #[tracing::instrument(skip(self))]
     pub fn monitor_workspace_index_by_name(&mut self, name: &str) -> Option<(usize, usize)> {
         tracing::info!("looking up workspace by name");
@@ -3864,6 +4866,402 @@ impl WindowManager {
             .ok_or_else(|| anyhow!("there is no window"))
     }
 
+    /// Removes a destroyed window from the workspaces stashed for other virtual desktops
+    pub fn forget_stashed_window(&mut self, hwnd: isize) {
+        for monitors in self.virtual_desktop_workspaces.values_mut() {
+            for workspaces in monitors {
+                for workspace in workspaces.elements_mut() {
+                    workspace.forget_window(hwnd);
+                }
+            }
+        }
+    }
+
+    /// Makes sure that the workspaces being managed belong to the current virtual desktop,
+    /// returning `false` if events and commands should be ignored until the user switches back
+    ///
+    /// When `VIRTUAL_DESKTOP_AWARENESS` is enabled, the workspaces of the virtual desktop being
+    /// left are stashed and the workspaces of the virtual desktop being entered are either
+    /// restored from a previous stash or created without any windows, so that each virtual
+    /// desktop has its own independent window manager state
+    pub fn reconcile_virtual_desktop(&mut self) -> Result<bool> {
+        let Some(virtual_desktop_id) = self.virtual_desktop_id.clone() else {
+            return Ok(true);
+        };
+
+        let Some(id) = current_virtual_desktop() else {
+            return Ok(true);
+        };
+
+        if id == virtual_desktop_id {
+            return Ok(true);
+        }
+
+        if !VIRTUAL_DESKTOP_AWARENESS.load(Ordering::SeqCst) {
+            tracing::info!(
+                "ignoring events and commands while not on virtual desktop {:?}",
+                virtual_desktop_id
+            );
+
+            return Ok(false);
+        }
+
+        tracing::info!(
+            "switching from virtual desktop {:?} to {:?}",
+            virtual_desktop_id,
+            id
+        );
+
+        let mut stashed = self
+            .virtual_desktop_workspaces
+            .remove(&id)
+            .unwrap_or_default();
+
+        let mut leaving = vec![];
+        for (idx, monitor) in self.monitors_mut().iter_mut().enumerate() {
+            let workspaces = if idx < stashed.len() {
+                std::mem::take(&mut stashed[idx])
+            } else {
+                monitor.workspaces_without_windows()
+            };
+
+            leaving.push(std::mem::replace(&mut monitor.workspaces, workspaces));
+        }
+
+        self.virtual_desktop_workspaces
+            .insert(virtual_desktop_id, leaving);
+        self.virtual_desktop_id = Option::from(id);
+
+        // Windows can be closed or moved to another virtual desktop from Task View while their
+        // workspaces are stashed, so forget those which are not on this virtual desktop anymore
+        for monitor in self.monitors_mut() {
+            for workspace in monitor.workspaces_mut() {
+                for hwnd in workspace.hwnds() {
+                    if !WindowsApi::is_window(hwnd)
+                        || !com::is_window_on_current_virtual_desktop(hwnd).unwrap_or(true)
+                    {
+                        workspace.forget_window(hwnd);
+                    }
+                }
+            }
+        }
+
+        let offset = self.work_area_offset;
+        for monitor in self.monitors_mut() {
+            monitor.update_focused_workspace(offset)?;
+        }
+
+        self.update_known_hwnds();
+
+        Ok(true)
+    }
+
+    /// Runs `command` with cmd.exe and remembers where the first window of the launched process
+    /// should go, so that it is placed there when it is shown instead of being moved afterwards by
+    /// a workspace rule
+    pub fn exec(
+        &mut self,
+        command: &str,
+        target: Option<(usize, usize)>,
+        floating: bool,
+    ) -> Result<()> {
+        let child = Command::new("cmd.exe")
+            .arg("/C")
+            .raw_arg(command)
+            .creation_flags(CREATE_NO_WINDOW.0)
+            .spawn()?;
+
+        tracing::info!("launched '{command}' with pid {}", child.id());
+
+        self.pending_launches.insert(
+            child.id(),
+            PendingLaunch {
+                target,
+                floating,
+                launched_at: Instant::now(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns the placement requested for a window if its process, or one of the processes which
+    /// started it, was launched with [`Self::exec`], forgetting it so that only the first window
+    /// of an application is placed
+    pub fn take_pending_launch(&mut self, window: Window) -> Option<PendingLaunch> {
+        self.pending_launches
+            .retain(|_, launch| launch.launched_at.elapsed() < PENDING_LAUNCH_TIMEOUT);
+
+        if self.pending_launches.is_empty() {
+            return None;
+        }
+
+        let mut pid = window.process_id();
+        if let Some(launch) = self.pending_launches.remove(&pid) {
+            return Some(launch);
+        }
+
+        // processes keep the id of their parent after it has exited, which is what happens to
+        // cmd.exe as soon as it has started a gui application
+        let mut system = sysinfo::System::new();
+        system.refresh_processes(ProcessesToUpdate::All, false);
+
+        for _ in 0..PENDING_LAUNCH_ANCESTORS {
+            pid = system.process(Pid::from_u32(pid))?.parent()?.as_u32();
+            if let Some(launch) = self.pending_launches.remove(&pid) {
+                return Some(launch);
+            }
+        }
+
+        None
+    }
+
+    /// Adds a window launched with [`Self::exec`] to the workspace which was requested for it when
+    /// that is not the focused workspace
+    pub fn place_launched_window(
+        &mut self,
+        window: Window,
+        target: (usize, usize),
+        floating: bool,
+    ) -> Result<()> {
+        self.place_window(window, target, floating)?;
+
+        tracing::info!(
+            "placed launched window {} on its requested workspace",
+            window.hwnd
+        );
+
+        Ok(())
+    }
+
+    /// Moves a managed window to a workspace on any monitor without having to focus it first,
+    /// keeping it floating if it was floating
+    #[tracing::instrument(skip(self))]
+    pub fn move_window_to_workspace(
+        &mut self,
+        hwnd: isize,
+        monitor_idx: usize,
+        workspace_idx: usize,
+    ) -> Result<()> {
+        let (origin_monitor_idx, origin_workspace_idx) = self
+            .known_hwnds
+            .get(&hwnd)
+            .copied()
+            .ok_or_else(|| anyhow!("{hwnd} is not a managed window"))?;
+
+        if (origin_monitor_idx, origin_workspace_idx) == (monitor_idx, workspace_idx) {
+            return Ok(());
+        }
+
+        let target_is_visible = match self.monitors().get(monitor_idx) {
+            Some(monitor) if workspace_idx < monitor.workspaces().len() => {
+                monitor.focused_workspace_idx() == workspace_idx
+            }
+            _ => bail!("there is no workspace {workspace_idx} on monitor {monitor_idx}"),
+        };
+
+        let offset = self.work_area_offset;
+        let origin_monitor = self
+            .monitors_mut()
+            .get_mut(origin_monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at this idx"))?;
+        let origin_is_visible = origin_monitor.focused_workspace_idx() == origin_workspace_idx;
+        let origin_workspace = origin_monitor
+            .workspaces_mut()
+            .get_mut(origin_workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace at this idx"))?;
+
+        let floating = origin_workspace
+            .floating_windows()
+            .iter()
+            .any(|window| window.hwnd == hwnd);
+
+        origin_workspace.remove_window(hwnd)?;
+
+        if origin_is_visible {
+            origin_monitor.update_focused_workspace(offset)?;
+        }
+
+        let window = Window::from(hwnd);
+        if target_is_visible && !origin_is_visible {
+            window.restore();
+        }
+
+        self.place_window(window, (monitor_idx, workspace_idx), floating)
+    }
+
+    /// Adds an unmanaged window to a workspace on any monitor, hiding it if that workspace is not
+    /// visible
+    fn place_window(
+        &mut self,
+        window: Window,
+        (monitor_idx, workspace_idx): (usize, usize),
+        floating: bool,
+    ) -> Result<()> {
+        let origin_work_area = self
+            .monitor_idx_from_window(window)
+            .and_then(|idx| self.monitors().get(idx))
+            .map(|monitor| monitor.work_area_size);
+
+        let target_monitor = self
+            .monitors_mut()
+            .get_mut(monitor_idx)
+            .ok_or_else(|| anyhow!("there is no monitor at this idx"))?;
+        let target_work_area = target_monitor.work_area_size;
+        let is_visible = target_monitor.focused_workspace_idx() == workspace_idx;
+        let target_workspace = target_monitor
+            .workspaces_mut()
+            .get_mut(workspace_idx)
+            .ok_or_else(|| anyhow!("there is no workspace at this idx"))?;
+
+        if floating {
+            target_workspace.floating_windows_mut().push_back(window);
+
+            if let Some(origin_work_area) = origin_work_area {
+                let mut window = window;
+                window.move_to_area(&origin_work_area, &target_work_area)?;
+            }
+        } else {
+            target_workspace.new_container_for_window(window);
+        }
+
+        if is_visible {
+            target_workspace.update()?;
+        } else {
+            window.hide();
+        }
+
+        // an explicit placement takes precedence over initial workspace rules
+        self.already_moved_window_handles.lock().insert(window.hwnd);
+
+        Ok(())
+    }
+
+    /// Moves owned windows to the workspace of their owner when it has been moved to another one, so
+    /// that dialogs and tool windows aren't left behind, and stops tracking those which are no
+    /// longer floating or whose owner is no longer managed
+    pub fn reconcile_owned_windows(&mut self) -> Result<()> {
+        if self.owned_windows.is_empty() {
+            return Ok(());
+        }
+
+        let mut moves = vec![];
+        let mut untracked = vec![];
+
+        for (&owned, &owner) in &self.owned_windows {
+            let mut owned_location = None;
+            let mut owner_location = None;
+
+            for (m_idx, monitor) in self.monitors().iter().enumerate() {
+                for (w_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                    if workspace.floating_windows().iter().any(|w| w.hwnd == owned) {
+                        owned_location = Some((m_idx, w_idx));
+                    }
+
+                    if workspace.contains_window(owner) {
+                        owner_location = Some((m_idx, w_idx));
+                    }
+                }
+            }
+
+            match (owned_location, owner_location) {
+                (Some(origin), Some(target)) if origin != target => {
+                    moves.push((owned, origin, target));
+                }
+                (Some(_), Some(_)) => {}
+                _ => untracked.push(owned),
+            }
+        }
+
+        for hwnd in untracked {
+            self.owned_windows.remove(&hwnd);
+        }
+
+        for (
+            hwnd,
+            (origin_monitor_idx, origin_workspace_idx),
+            (target_monitor_idx, target_workspace_idx),
+        ) in moves
+        {
+            let origin_monitor = self
+                .monitors_mut()
+                .get_mut(origin_monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor at this idx"))?;
+            let origin_work_area = origin_monitor.work_area_size;
+            let origin_workspace = origin_monitor
+                .workspaces_mut()
+                .get_mut(origin_workspace_idx)
+                .ok_or_else(|| anyhow!("there is no workspace at this idx"))?;
+
+            let Some(idx) = origin_workspace
+                .floating_windows()
+                .iter()
+                .position(|w| w.hwnd == hwnd)
+            else {
+                continue;
+            };
+
+            let Some(window) = origin_workspace.floating_windows_mut().remove(idx) else {
+                continue;
+            };
+
+            let target_monitor = self
+                .monitors_mut()
+                .get_mut(target_monitor_idx)
+                .ok_or_else(|| anyhow!("there is no monitor at this idx"))?;
+            let target_work_area = target_monitor.work_area_size;
+            let is_visible = target_monitor.focused_workspace_idx() == target_workspace_idx;
+            target_monitor
+                .workspaces_mut()
+                .get_mut(target_workspace_idx)
+                .ok_or_else(|| anyhow!("there is no workspace at this idx"))?
+                .floating_windows_mut()
+                .push_back(window);
+
+            // keep the window in the same place relative to the monitor it is moved to
+            if origin_monitor_idx != target_monitor_idx {
+                let mut rect = WindowsApi::window_rect(window.hwnd)?;
+                rect.left += target_work_area.left - origin_work_area.left;
+                rect.top += target_work_area.top - origin_work_area.top;
+                window.set_position(&rect, false)?;
+            }
+
+            if is_visible {
+                window.restore();
+            } else {
+                window.hide();
+            }
+
+            tracing::info!("moved owned window {hwnd} to the workspace of its owner");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hwnd of a managed window which matches `identifier`, if there is one
+    pub fn managed_window_matching(&self, identifier: &IdWithIdentifier) -> Option<isize> {
+        let regex_identifiers = REGEX_IDENTIFIERS.lock();
+
+        self.known_hwnds.keys().copied().find(|&hwnd| {
+            let window = Window::from(hwnd);
+
+            if let (Ok(title), Ok(exe_name), Ok(class), Ok(path)) =
+                (window.title(), window.exe(), window.class(), window.path())
+            {
+                should_act_individual(
+                    &title,
+                    &exe_name,
+                    &class,
+                    &path,
+                    identifier,
+                    &regex_identifiers,
+                )
+            } else {
+                false
+            }
+        })
+    }
+
     /// Updates the list of `known_hwnds` and their monitor/workspace index pair
     ///
     /// [`known_hwnds`]: `Self.known_hwnds`
@@ -3895,6 +5293,16 @@ impl WindowManager {
         }
 
         if self.known_hwnds != known_hwnds {
+            // Forget where windows which are no longer managed were before they were tiled
+            self.pre_tiling_rects
+                .retain(|hwnd, _| known_hwnds.contains_key(hwnd));
+            FRAME_COMPENSATIONS
+                .lock()
+                .retain(|hwnd, _| known_hwnds.contains_key(hwnd));
+            MINIMUM_SIZES
+                .lock()
+                .retain(|hwnd, _| known_hwnds.contains_key(hwnd));
+
             // Update reaper cache
             {
                 let mut reaper_cache = crate::reaper::HWNDS_CACHE.lock();
@@ -3902,7 +5310,7 @@ impl WindowManager {
             }
 
             // Save to file
-            let hwnd_json = DATA_DIR.join("komorebi.hwnd.json");
+            let hwnd_json = paths::hwnd_json_path();
             match OpenOptions::new()
                 .write(true)
                 .truncate(true)
@@ -3927,6 +5335,34 @@ impl WindowManager {
     }
 }
 
+/// The candidate whose rect is nearest to `from` in the given direction, measured between their
+/// centres, with offsets across the direction counting double so that windows which are in line
+/// with `from` are preferred over closer ones which are off to the side
+fn nearest_in_direction<T: Copy>(
+    direction: OperationDirection,
+    from: &Rect,
+    candidates: &[(T, Rect)],
+) -> Option<T> {
+    let centre = |rect: &Rect| (rect.left + rect.right / 2, rect.top + rect.bottom / 2);
+    let (x, y) = centre(from);
+
+    candidates
+        .iter()
+        .filter_map(|(candidate, rect)| {
+            let (cx, cy) = centre(rect);
+            let (along, across) = match direction {
+                OperationDirection::Left => (x - cx, cy - y),
+                OperationDirection::Right => (cx - x, cy - y),
+                OperationDirection::Up => (y - cy, cx - x),
+                OperationDirection::Down => (cy - y, cx - x),
+            };
+
+            (along > 0).then_some((*candidate, along + across.abs() * 2))
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4974,4 +6410,42 @@ mod tests {
             assert!(*workspace.tile());
         }
     }
+
+    #[test]
+    fn test_nearest_in_direction() {
+        let rect = |left, top| Rect {
+            left,
+            top,
+            right: 400,
+            bottom: 400,
+        };
+
+        let focused = rect(1000, 1000);
+        let candidates = [
+            ("tile in line", rect(0, 1000)),
+            ("centred float", rect(600, 300)),
+            ("tile below", rect(1000, 1500)),
+        ];
+
+        // the float is closer but off to the side, so the tile in line wins
+        assert_eq!(
+            nearest_in_direction(OperationDirection::Left, &focused, &candidates),
+            Some("tile in line")
+        );
+
+        assert_eq!(
+            nearest_in_direction(OperationDirection::Up, &focused, &candidates),
+            Some("centred float")
+        );
+
+        assert_eq!(
+            nearest_in_direction(OperationDirection::Down, &focused, &candidates),
+            Some("tile below")
+        );
+
+        assert_eq!(
+            nearest_in_direction(OperationDirection::Right, &focused, &candidates),
+            None
+        );
+    }
 }
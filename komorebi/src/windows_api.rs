@@ -22,8 +22,12 @@ use windows::Win32::Foundation::POINT;
 use windows::Win32::Foundation::RECT;
 use windows::Win32::Foundation::WPARAM;
 use windows::Win32::Graphics::Dwm::DwmGetWindowAttribute;
+use windows::Win32::Graphics::Dwm::DwmRegisterThumbnail;
 use windows::Win32::Graphics::Dwm::DwmSetWindowAttribute;
+use windows::Win32::Graphics::Dwm::DwmUnregisterThumbnail;
+use windows::Win32::Graphics::Dwm::DwmUpdateThumbnailProperties;
 use windows::Win32::Graphics::Dwm::DWMWA_BORDER_COLOR;
+use windows::Win32::Graphics::Dwm::DWMWA_CLOAK;
 use windows::Win32::Graphics::Dwm::DWMWA_CLOAKED;
 use windows::Win32::Graphics::Dwm::DWMWA_COLOR_NONE;
 use windows::Win32::Graphics::Dwm::DWMWA_EXTENDED_FRAME_BOUNDS;
@@ -33,6 +37,10 @@ use windows::Win32::Graphics::Dwm::DWMWINDOWATTRIBUTE;
 use windows::Win32::Graphics::Dwm::DWM_CLOAKED_APP;
 use windows::Win32::Graphics::Dwm::DWM_CLOAKED_INHERITED;
 use windows::Win32::Graphics::Dwm::DWM_CLOAKED_SHELL;
+use windows::Win32::Graphics::Dwm::DWM_THUMBNAIL_PROPERTIES;
+use windows::Win32::Graphics::Dwm::DWM_TNP_RECTDESTINATION;
+use windows::Win32::Graphics::Dwm::DWM_TNP_SOURCECLIENTAREAONLY;
+use windows::Win32::Graphics::Dwm::DWM_TNP_VISIBLE;
 use windows::Win32::Graphics::Gdi::CreateSolidBrush;
 use windows::Win32::Graphics::Gdi::EnumDisplayMonitors;
 use windows::Win32::Graphics::Gdi::GetMonitorInfoW;
@@ -59,6 +67,7 @@ use windows::Win32::System::Threading::PROCESS_ACCESS_RIGHTS;
 use windows::Win32::System::Threading::PROCESS_NAME_WIN32;
 use windows::Win32::System::Threading::PROCESS_QUERY_INFORMATION;
 use windows::Win32::UI::HiDpi::GetDpiForMonitor;
+use windows::Win32::UI::HiDpi::GetDpiForWindow;
 use windows::Win32::UI::HiDpi::SetProcessDpiAwarenessContext;
 use windows::Win32::UI::HiDpi::DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2;
 use windows::Win32::UI::HiDpi::MDT_EFFECTIVE_DPI;
@@ -76,10 +85,12 @@ use windows::Win32::UI::WindowsAndMessaging::AllowSetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::BringWindowToTop;
 use windows::Win32::UI::WindowsAndMessaging::CreateWindowExW;
 use windows::Win32::UI::WindowsAndMessaging::EnumWindows;
+use windows::Win32::UI::WindowsAndMessaging::GetAncestor;
 use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::GetDesktopWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetLayeredWindowAttributes;
+use windows::Win32::UI::WindowsAndMessaging::GetSystemMetrics;
 use windows::Win32::UI::WindowsAndMessaging::GetTopWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetWindow;
 use windows::Win32::UI::WindowsAndMessaging::GetWindowLongPtrW;
@@ -95,6 +106,7 @@ use windows::Win32::UI::WindowsAndMessaging::PostMessageW;
 use windows::Win32::UI::WindowsAndMessaging::RealGetWindowClassW;
 use windows::Win32::UI::WindowsAndMessaging::RegisterClassW;
 use windows::Win32::UI::WindowsAndMessaging::RegisterDeviceNotificationW;
+use windows::Win32::UI::WindowsAndMessaging::SendMessageTimeoutW;
 use windows::Win32::UI::WindowsAndMessaging::SetCursorPos;
 use windows::Win32::UI::WindowsAndMessaging::SetForegroundWindow;
 use windows::Win32::UI::WindowsAndMessaging::SetLayeredWindowAttributes;
@@ -105,16 +117,24 @@ use windows::Win32::UI::WindowsAndMessaging::SystemParametersInfoW;
 use windows::Win32::UI::WindowsAndMessaging::WindowFromPoint;
 use windows::Win32::UI::WindowsAndMessaging::CW_USEDEFAULT;
 use windows::Win32::UI::WindowsAndMessaging::DEV_BROADCAST_DEVICEINTERFACE_W;
+use windows::Win32::UI::WindowsAndMessaging::GA_ROOT;
 use windows::Win32::UI::WindowsAndMessaging::GWL_EXSTYLE;
 use windows::Win32::UI::WindowsAndMessaging::GWL_STYLE;
 use windows::Win32::UI::WindowsAndMessaging::GW_HWNDNEXT;
+use windows::Win32::UI::WindowsAndMessaging::GW_OWNER;
 use windows::Win32::UI::WindowsAndMessaging::HDEVNOTIFY;
 use windows::Win32::UI::WindowsAndMessaging::HWND_BOTTOM;
 use windows::Win32::UI::WindowsAndMessaging::HWND_TOP;
 use windows::Win32::UI::WindowsAndMessaging::LWA_ALPHA;
+use windows::Win32::UI::WindowsAndMessaging::MINMAXINFO;
 use windows::Win32::UI::WindowsAndMessaging::REGISTER_NOTIFICATION_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SET_WINDOW_POS_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::SHOW_WINDOW_CMD;
+use windows::Win32::UI::WindowsAndMessaging::SMTO_ABORTIFHUNG;
+use windows::Win32::UI::WindowsAndMessaging::SM_CXVIRTUALSCREEN;
+use windows::Win32::UI::WindowsAndMessaging::SM_CYVIRTUALSCREEN;
+use windows::Win32::UI::WindowsAndMessaging::SM_XVIRTUALSCREEN;
+use windows::Win32::UI::WindowsAndMessaging::SM_YVIRTUALSCREEN;
 use windows::Win32::UI::WindowsAndMessaging::SPIF_SENDCHANGE;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETACTIVEWINDOWTRACKING;
 use windows::Win32::UI::WindowsAndMessaging::SPI_GETFOREGROUNDLOCKTIMEOUT;
@@ -132,6 +152,7 @@ use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_ACTION;
 use windows::Win32::UI::WindowsAndMessaging::SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS;
 use windows::Win32::UI::WindowsAndMessaging::WINDOW_LONG_PTR_INDEX;
 use windows::Win32::UI::WindowsAndMessaging::WM_CLOSE;
+use windows::Win32::UI::WindowsAndMessaging::WM_GETMINMAXINFO;
 use windows::Win32::UI::WindowsAndMessaging::WNDCLASSW;
 use windows::Win32::UI::WindowsAndMessaging::WNDENUMPROC;
 use windows::Win32::UI::WindowsAndMessaging::WS_DISABLED;
@@ -150,10 +171,12 @@ use crate::monitor::Monitor;
 use crate::ring::Ring;
 use crate::set_window_position::SetWindowPosition;
 use crate::windows_callbacks;
+use crate::FrameCompensation;
 use crate::Window;
 use crate::WindowManager;
 use crate::DISPLAY_INDEX_PREFERENCES;
 use crate::DUPLICATE_MONITOR_SERIAL_IDS;
+use crate::FRAME_COMPENSATIONS;
 use crate::MONITOR_INDEX_PREFERENCES;
 
 macro_rules! as_ptr {
@@ -257,7 +280,6 @@ impl WindowsApi {
 
     pub fn load_monitor_information(wm: &mut WindowManager) -> Result<()> {
         let monitors = &mut wm.monitors;
-        let monitor_usr_idx_map = &mut wm.monitor_usr_idx_map;
 
         let all_displays = win32_display_data::connected_displays_all()
             .flatten()
@@ -362,39 +384,7 @@ impl WindowsApi {
             .elements_mut()
             .retain(|m| m.name().ne("PLACEHOLDER"));
 
-        // Rebuild monitor index map
-        *monitor_usr_idx_map = HashMap::new();
-        let mut added_monitor_idxs = Vec::new();
-        for (index, id) in &*DISPLAY_INDEX_PREFERENCES.read() {
-            if let Some(m_idx) = monitors.elements().iter().position(|m| {
-                m.serial_number_id().as_ref().is_some_and(|sn| sn == id) || m.device_id() == id
-            }) {
-                monitor_usr_idx_map.insert(*index, m_idx);
-                added_monitor_idxs.push(m_idx);
-            }
-        }
-
-        let max_usr_idx = monitors
-            .elements()
-            .len()
-            .max(monitor_usr_idx_map.keys().max().map_or(0, |v| *v));
-
-        let mut available_usr_idxs = (0..max_usr_idx)
-            .filter(|i| !monitor_usr_idx_map.contains_key(i))
-            .collect::<Vec<_>>();
-
-        let not_added_monitor_idxs = (0..monitors.elements().len())
-            .filter(|i| !added_monitor_idxs.contains(i))
-            .collect::<Vec<_>>();
-
-        for i in not_added_monitor_idxs {
-            if let Some(next_usr_idx) = available_usr_idxs.first() {
-                monitor_usr_idx_map.insert(*next_usr_idx, i);
-                available_usr_idxs.remove(0);
-            } else if let Some(idx) = monitor_usr_idx_map.keys().max() {
-                monitor_usr_idx_map.insert(*idx, i);
-            }
-        }
+        wm.update_monitor_usr_idx_map();
 
         Ok(())
     }
@@ -432,6 +422,23 @@ impl WindowsApi {
                 for hwnd in windows_on_other_monitors {
                     workspace.remove_window(hwnd)?;
                 }
+
+                // windows are tiled in the order they were arranged on the monitor, from left to
+                // right and then from top to bottom, so that they keep their relative positions
+                workspace
+                    .containers_mut()
+                    .make_contiguous()
+                    .sort_by_cached_key(|container| {
+                        container.focused_window().map(|window| {
+                            let rect = Self::window_rect(window.hwnd).unwrap_or_default();
+                            (rect.left, rect.top)
+                        })
+                    });
+
+                let adopted = workspace.containers().len();
+                if adopted > 0 {
+                    tracing::warn!("adopting {adopted} existing windows on monitor {monitor_name}");
+                }
             }
         }
 
@@ -482,7 +489,7 @@ impl WindowsApi {
             flags |= SetWindowPosition::NO_Z_ORDER;
         }
 
-        let shadow_rect = Self::shadow_rect(hwnd).unwrap_or_default();
+        let shadow_rect = Self::frame_compensation(hwnd);
         let rect = Rect {
             left: layout.left + shadow_rect.left,
             top: layout.top + shadow_rect.top,
@@ -578,7 +585,7 @@ impl WindowsApi {
     pub fn move_window(hwnd: isize, layout: &Rect, repaint: bool) -> Result<()> {
         let hwnd = HWND(as_ptr!(hwnd));
 
-        let shadow_rect = Self::shadow_rect(hwnd).unwrap_or_default();
+        let shadow_rect = Self::frame_compensation(hwnd);
         let rect = Rect {
             left: layout.left + shadow_rect.left,
             top: layout.top + shadow_rect.top,
@@ -612,6 +619,32 @@ impl WindowsApi {
         }
     }
 
+    /// The minimum tracking size (width, height) that a window reports in response to
+    /// WM_GETMINMAXINFO, if it reports one at all
+    pub fn minimum_size(hwnd: isize) -> Option<(i32, i32)> {
+        let mut info = MINMAXINFO::default();
+        let mut result = 0;
+
+        let sent = unsafe {
+            SendMessageTimeoutW(
+                HWND(as_ptr!(hwnd)),
+                WM_GETMINMAXINFO,
+                WPARAM(0),
+                LPARAM(&mut info as *mut MINMAXINFO as isize),
+                SMTO_ABORTIFHUNG,
+                50,
+                Option::from(&mut result as *mut usize),
+            )
+        };
+
+        if sent.0 == 0 {
+            return None;
+        }
+
+        let (width, height) = (info.ptMinTrackSize.x, info.ptMinTrackSize.y);
+        (width > 0 || height > 0).then_some((width, height))
+    }
+
     pub fn hide_window(hwnd: isize) {
         Self::show_window(hwnd, SW_HIDE);
     }
@@ -673,6 +706,10 @@ impl WindowsApi {
         unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_HWNDNEXT)? }.process()
     }
 
+    pub fn owner_window(hwnd: isize) -> Result<isize> {
+        unsafe { GetWindow(HWND(as_ptr!(hwnd)), GW_OWNER)? }.process()
+    }
+
     pub fn alt_tab_windows() -> Result<Vec<Window>> {
         let mut hwnds = vec![];
         Self::enum_windows(
@@ -683,6 +720,17 @@ impl WindowsApi {
         Ok(hwnds)
     }
 
+    /// All visible top-level windows which aren't minimized, whether komorebi manages them or not
+    pub fn visible_windows() -> Result<Vec<Window>> {
+        let mut hwnds = vec![];
+        Self::enum_windows(
+            Some(windows_callbacks::visible_windows),
+            &mut hwnds as *mut Vec<Window> as isize,
+        )?;
+
+        Ok(hwnds)
+    }
+
     #[allow(dead_code)]
     pub fn top_visible_window() -> Result<isize> {
         let hwnd = Self::top_window()?;
@@ -714,15 +762,54 @@ impl WindowsApi {
         }
     }
 
+    /// frame_compensation is the offset which a window is grown by to cover
+    /// its invisible borders. It is resolved the first time the window is
+    /// positioned and cached until the window is no longer managed, its DPI
+    /// changes or the displays change, except for border overflow applications,
+    /// whose shadow_rect is measured every time.
+    ///
+    /// Minimized and maximized windows don't have their usual borders, so it is
+    /// never cached while the window is in either state.
+    fn frame_compensation(hwnd: HWND) -> Rect {
+        let dpi = unsafe { GetDpiForWindow(hwnd) };
+        let hwnd = hwnd.0 as isize;
+
+        let cached = FRAME_COMPENSATIONS
+            .lock()
+            .get(&hwnd)
+            .filter(|(cached_dpi, _)| *cached_dpi == dpi)
+            .map(|(_, compensation)| *compensation);
+
+        let compensation = match cached {
+            Some(compensation) => compensation,
+            None => match Window::from(hwnd).frame_compensation() {
+                Ok(compensation) => {
+                    if !Self::is_iconic(hwnd) && !Self::is_zoomed(hwnd) {
+                        FRAME_COMPENSATIONS.lock().insert(hwnd, (dpi, compensation));
+                    }
+
+                    compensation
+                }
+                // It will be resolved again the next time the window is positioned
+                Err(_) => FrameCompensation::Measured,
+            },
+        };
+
+        match compensation {
+            FrameCompensation::Fixed(rect) => rect,
+            FrameCompensation::Measured => Self::shadow_rect(hwnd).unwrap_or_default(),
+        }
+    }
+
     /// shadow_rect computes the offset of the shadow position of the window to
     /// the window painted region. The four values in the returned Rect can be
     /// added to a position rect to compute a size for set_window_pos that will
     /// fill the target area, ignoring shadows.
-    fn shadow_rect(hwnd: HWND) -> Result<Rect> {
-        let window_rect = Self::window_rect(hwnd.0 as isize)?;
+    pub fn shadow_rect(hwnd: isize) -> Result<Rect> {
+        let window_rect = Self::window_rect(hwnd)?;
 
         let mut srect = Default::default();
-        unsafe { GetWindowRect(hwnd, &mut srect) }.process()?;
+        unsafe { GetWindowRect(HWND(as_ptr!(hwnd)), &mut srect) }.process()?;
         let shadow_rect = Rect::from(srect);
 
         Ok(Rect {
@@ -768,10 +855,27 @@ impl WindowsApi {
         unsafe { WindowFromPoint(point) }.process()
     }
 
+    /// The rect which covers every monitor, with the width and height in right and bottom
+    pub fn virtual_screen_rect() -> Rect {
+        unsafe {
+            Rect {
+                left: GetSystemMetrics(SM_XVIRTUALSCREEN),
+                top: GetSystemMetrics(SM_YVIRTUALSCREEN),
+                right: GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                bottom: GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            }
+        }
+    }
+
     pub fn window_at_cursor_pos() -> Result<isize> {
         Self::window_from_point(Self::cursor_pos()?)
     }
 
+    /// The top-level window which contains the given window, which may be a child control
+    pub fn root_window(hwnd: isize) -> isize {
+        unsafe { GetAncestor(HWND(as_ptr!(hwnd)), GA_ROOT) }.0 as isize
+    }
+
     pub fn center_cursor_in_rect(rect: &Rect) -> Result<()> {
         Self::set_cursor_pos(rect.left + (rect.right / 2), rect.top + (rect.bottom / 2))
     }
@@ -1182,6 +1286,49 @@ impl WindowsApi {
         .process()
     }
 
+    /// Cloaks or uncloaks a window with DWM, which only allows a process to cloak its own windows
+    pub fn dwm_cloak_window(hwnd: isize, cloak: bool) -> Result<()> {
+        let cloak = BOOL::from(cloak);
+
+        unsafe {
+            DwmSetWindowAttribute(
+                HWND(as_ptr!(hwnd)),
+                DWMWA_CLOAK,
+                std::ptr::addr_of!(cloak).cast(),
+                4,
+            )
+        }
+        .process()
+    }
+
+    /// Registers a DWM thumbnail of `source` which can be drawn onto `destination`, which must be
+    /// a window owned by the calling process
+    pub fn dwm_register_thumbnail(destination: isize, source: isize) -> Result<isize> {
+        Ok(unsafe { DwmRegisterThumbnail(HWND(as_ptr!(destination)), HWND(as_ptr!(source))) }?)
+    }
+
+    /// Draws a DWM thumbnail at `rect` in the client area of its destination window, or hides it
+    /// if no rect is given
+    pub fn dwm_update_thumbnail(thumbnail: isize, rect: Option<&Rect>) -> Result<()> {
+        let properties = DWM_THUMBNAIL_PROPERTIES {
+            dwFlags: DWM_TNP_VISIBLE | DWM_TNP_RECTDESTINATION | DWM_TNP_SOURCECLIENTAREAONLY,
+            rcDestination: rect.map(Rect::rect).unwrap_or_default(),
+            fVisible: BOOL::from(rect.is_some()),
+            fSourceClientAreaOnly: BOOL::from(false),
+            ..Default::default()
+        };
+
+        unsafe { DwmUpdateThumbnailProperties(thumbnail, &properties) }?;
+
+        Ok(())
+    }
+
+    pub fn dwm_unregister_thumbnail(thumbnail: isize) -> Result<()> {
+        unsafe { DwmUnregisterThumbnail(thumbnail) }?;
+
+        Ok(())
+    }
+
     pub fn set_window_accent(hwnd: isize, color: Option<u32>) -> Result<()> {
         let col_ref = COLORREF(color.unwrap_or(DWMWA_COLOR_NONE));
         unsafe {
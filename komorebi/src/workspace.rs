@@ -6,6 +6,7 @@ use std::num::NonZeroUsize;
 use std::sync::atomic::Ordering;
 
 use color_eyre::eyre::anyhow;
+use color_eyre::eyre::bail;
 use color_eyre::Result;
 use getset::CopyGetters;
 use getset::Getters;
@@ -22,11 +23,14 @@ use crate::core::DefaultLayout;
 use crate::core::Layout;
 use crate::core::OperationDirection;
 use crate::core::Rect;
+use crate::core::SplitDirection;
+use crate::core::WorkspaceSaveOption;
 
 use crate::border_manager::BORDER_OFFSET;
 use crate::border_manager::BORDER_WIDTH;
 use crate::container::Container;
 use crate::locked_deque::LockedDeque;
+use crate::metrics;
 use crate::ring::Ring;
 use crate::should_act;
 use crate::stackbar_manager;
@@ -35,9 +39,12 @@ use crate::static_config::WorkspaceConfig;
 use crate::window::Window;
 use crate::window::WindowDetails;
 use crate::windows_api::WindowsApi;
+use crate::FloatingZOrder;
 use crate::WindowContainerBehaviour;
+use crate::AUTO_NAME_WORKSPACES;
 use crate::DEFAULT_CONTAINER_PADDING;
 use crate::DEFAULT_WORKSPACE_PADDING;
+use crate::FLOATING_WINDOW_Z_ORDER;
 use crate::INITIAL_CONFIGURATION_LOADED;
 use crate::NO_TITLEBAR;
 use crate::REGEX_IDENTIFIERS;
@@ -93,9 +100,35 @@ pub struct Workspace {
     pub layer: WorkspaceLayer,
     #[getset(get = "pub", get_mut = "pub", set = "pub")]
     pub locked_containers: BTreeSet<usize>,
+    /// The containers whose windows were all floated by toggling the workspace float, which are
+    /// tiled again when it is toggled back
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub", set = "pub")]
+    pub floated_containers: Option<Vec<Container>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[getset(get = "pub", set = "pub")]
     pub workspace_config: Option<WorkspaceConfig>,
+    /// The name of the application with the most windows on the workspace, which is only filled
+    /// in for the state sent to subscribers when workspaces without a name are auto-named
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub", set = "pub")]
+    pub auto_name: Option<String>,
+    /// The windows which were floated because the layout had no room for them at their minimum
+    /// size, which are tiled again as soon as there is room for them
+    #[serde(skip)]
+    #[getset(get = "pub")]
+    pub minimum_size_floats: Vec<MinimumSizeFloat>,
+}
+
+/// A window which was taken out of the layout by `Workspace::update` because it could not be
+/// tiled at its minimum size
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MinimumSizeFloat {
+    pub hwnd: isize,
+    /// The index of the container the window was taken from
+    pub container_idx: usize,
+    /// Whether the window was taken from a stack, rather than being in a container of its own
+    pub stacked: bool,
 }
 
 #[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -115,6 +148,35 @@ impl Display for WorkspaceLayer {
     }
 }
 
+/// A workspace as written to a file by `Save` or `QuickSave`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum SavedWorkspace {
+    /// Only the resize dimensions of the workspace
+    ResizeDimensions(Vec<Option<Rect>>),
+    /// The resize dimensions along with whichever other parts of the workspace were selected
+    Snapshot(WorkspaceSnapshot),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkspaceSnapshot {
+    pub resize_dimensions: Vec<Option<Rect>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout: Option<Layout>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layout_flip: Option<Axis>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tile: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_padding: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_padding: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
 impl_ring_elements!(Workspace, Container);
 impl_ring_elements!(Workspace, Window, "floating_window");
 
@@ -144,6 +206,9 @@ impl Default for Workspace {
             globals: Default::default(),
             workspace_config: None,
             locked_containers: Default::default(),
+            floated_containers: None,
+            auto_name: None,
+            minimum_size_floats: vec![],
         }
     }
 }
@@ -256,6 +321,26 @@ impl Workspace {
         Ok(())
     }
 
+    /// Creates a copy of this workspace's configuration without any of its containers or windows
+    pub fn without_windows(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            layout: self.layout.clone(),
+            layout_rules: self.layout_rules.clone(),
+            layout_flip: self.layout_flip,
+            workspace_padding: self.workspace_padding,
+            container_padding: self.container_padding,
+            tile: self.tile,
+            apply_window_based_work_area_offset: self.apply_window_based_work_area_offset,
+            window_container_behaviour: self.window_container_behaviour,
+            window_container_behaviour_rules: self.window_container_behaviour_rules.clone(),
+            float_override: self.float_override,
+            globals: self.globals,
+            workspace_config: self.workspace_config.clone(),
+            ..Default::default()
+        }
+    }
+
     pub fn hide(&mut self, omit: Option<isize>) {
         for window in self.floating_windows_mut().iter_mut().rev() {
             let mut should_hide = omit.is_none();
@@ -278,7 +363,9 @@ impl Workspace {
         }
 
         if let Some(window) = self.maximized_window() {
-            window.hide();
+            if omit != Some(window.hwnd) {
+                window.hide();
+            }
         }
 
         if let Some(container) = self.monocle_container_mut() {
@@ -319,18 +406,16 @@ impl Workspace {
         // Do this here to make sure that an error doesn't stop the restoration of other windows
         // Maximised windows and floating windows should always be drawn at the top of the Z order
         // when switching to a workspace
-        if let Some(window) = to_focus {
-            if self.maximized_window().is_none() && matches!(self.layer, WorkspaceLayer::Tiling) {
-                window.focus(mouse_follows_focus)?;
-            } else if let Some(maximized_window) = self.maximized_window() {
-                maximized_window.restore();
-                maximized_window.focus(mouse_follows_focus)?;
-            } else if let Some(floating_window) = self.focused_floating_window() {
-                floating_window.focus(mouse_follows_focus)?;
-            }
-        } else if let Some(maximized_window) = self.maximized_window() {
+        if let Some(maximized_window) = self.maximized_window() {
+            // hiding behaviours which hide or minimize windows bring them back in their normal
+            // placement, so the window is maximized again to match the state of the workspace
             maximized_window.restore();
+            maximized_window.maximize();
             maximized_window.focus(mouse_follows_focus)?;
+        } else if let Some(window) =
+            to_focus.filter(|_| matches!(self.layer, WorkspaceLayer::Tiling))
+        {
+            window.focus(mouse_follows_focus)?;
         } else if let Some(floating_window) = self.focused_floating_window() {
             floating_window.focus(mouse_follows_focus)?;
         }
@@ -338,15 +423,9 @@ impl Workspace {
         Ok(())
     }
 
-    pub fn update(&mut self) -> Result<()> {
-        if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
-            return Ok(());
-        }
-
-        let container_padding = self
-            .container_padding()
-            .or(self.globals().container_padding)
-            .unwrap_or_default();
+    /// The area which the containers of the workspace are laid out in, after applying any work
+    /// area offsets and the workspace padding
+    fn tiling_area(&self) -> Rect {
         let workspace_padding = self
             .workspace_padding()
             .or(self.globals().workspace_padding)
@@ -390,6 +469,156 @@ impl Workspace {
 
         adjusted_work_area.add_padding(workspace_padding);
 
+        adjusted_work_area
+    }
+
+    /// Calculates the rects which the containers of the workspace would occupy under `layout`
+    /// without applying it, so that layouts can be previewed
+    pub fn preview_layout(&self, layout: &Layout) -> Vec<Rect> {
+        let Some(len) = NonZeroUsize::new(self.containers().len()) else {
+            return vec![];
+        };
+
+        let container_padding = self
+            .container_padding()
+            .or(self.globals().container_padding)
+            .unwrap_or_default();
+
+        // manual resizes only make sense for the layout they were made in
+        let resize_dimensions = if layout == self.layout() {
+            self.resize_dimensions().clone()
+        } else {
+            vec![None; len.get()]
+        };
+
+        layout.as_boxed_arrangement().calculate(
+            &self.tiling_area(),
+            len,
+            Some(container_padding),
+            self.layout_flip(),
+            &resize_dimensions,
+            &self.split_directions(),
+        )
+    }
+
+    /// The layout of the workspace, including any manual resizes, as a custom layout of nested
+    /// areas which can be saved and loaded again
+    pub fn effective_custom_layout(&self) -> Result<CustomLayout> {
+        let container_padding = self
+            .container_padding()
+            .or(self.globals().container_padding)
+            .unwrap_or_default();
+
+        let rects = self
+            .preview_layout(self.layout())
+            .into_iter()
+            .map(|mut rect| {
+                rect.add_margin(container_padding);
+                rect
+            })
+            .collect::<Vec<_>>();
+
+        CustomLayout::from_rects(&self.tiling_area(), &rects)
+    }
+
+    /// The resize dimensions of the workspace, along with the other parts of its configuration
+    /// selected by `options`
+    pub fn save(&self, options: &[WorkspaceSaveOption]) -> SavedWorkspace {
+        if options.is_empty() {
+            return SavedWorkspace::ResizeDimensions(self.resize_dimensions().clone());
+        }
+
+        let mut snapshot = WorkspaceSnapshot {
+            resize_dimensions: self.resize_dimensions().clone(),
+            ..Default::default()
+        };
+
+        for option in options {
+            match option {
+                WorkspaceSaveOption::Layout => {
+                    snapshot.layout = Option::from(self.layout().clone());
+                    snapshot.layout_flip = self.layout_flip();
+                }
+                WorkspaceSaveOption::Tiling => snapshot.tile = Option::from(*self.tile()),
+                WorkspaceSaveOption::Padding => {
+                    snapshot.workspace_padding = self
+                        .workspace_padding()
+                        .or(self.globals().workspace_padding);
+                    snapshot.container_padding = self
+                        .container_padding()
+                        .or(self.globals().container_padding);
+                }
+                WorkspaceSaveOption::Name => snapshot.name.clone_from(self.name()),
+            }
+        }
+
+        SavedWorkspace::Snapshot(snapshot)
+    }
+
+    /// Applies a workspace saved with `save`, except for its name, which is also tracked by the
+    /// monitor of the workspace
+    pub fn load(&mut self, saved: SavedWorkspace) {
+        let snapshot = match saved {
+            SavedWorkspace::ResizeDimensions(resize_dimensions) => {
+                self.set_resize_dimensions(resize_dimensions);
+                return;
+            }
+            SavedWorkspace::Snapshot(snapshot) => snapshot,
+        };
+
+        self.set_resize_dimensions(snapshot.resize_dimensions);
+
+        if let Some(layout) = snapshot.layout {
+            self.set_layout(layout);
+            self.set_layout_flip(snapshot.layout_flip);
+        }
+
+        if let Some(tile) = snapshot.tile {
+            self.set_tile(tile);
+        }
+
+        if snapshot.workspace_padding.is_some() {
+            self.set_workspace_padding(snapshot.workspace_padding);
+        }
+
+        if snapshot.container_padding.is_some() {
+            self.set_container_padding(snapshot.container_padding);
+        }
+    }
+
+    /// Scales the resize adjustments of every container by how much the work area has grown or
+    /// shrunk in each direction, so that the layout keeps its proportions after a resolution or
+    /// orientation change
+    pub fn scale_resize_dimensions(&mut self, from: &Rect, to: &Rect) {
+        if from.right <= 0 || from.bottom <= 0 {
+            return;
+        }
+
+        let horizontal = to.right as f32 / from.right as f32;
+        let vertical = to.bottom as f32 / from.bottom as f32;
+        let scale = |value: i32, factor: f32| (value as f32 * factor).round() as i32;
+
+        for resize in self.resize_dimensions_mut().iter_mut().flatten() {
+            resize.left = scale(resize.left, horizontal);
+            resize.right = scale(resize.right, horizontal);
+            resize.top = scale(resize.top, vertical);
+            resize.bottom = scale(resize.bottom, vertical);
+        }
+    }
+
+    pub fn update(&mut self) -> Result<()> {
+        if !INITIAL_CONFIGURATION_LOADED.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        metrics::RETILES.fetch_add(1, Ordering::SeqCst);
+
+        let container_padding = self
+            .container_padding()
+            .or(self.globals().container_padding)
+            .unwrap_or_default();
+        let mut adjusted_work_area = self.tiling_area();
+
         self.enforce_resize_constraints();
 
         if !self.layout_rules().is_empty() {
@@ -433,18 +662,46 @@ impl Workspace {
                 };
             } else if let Some(window) = self.maximized_window_mut() {
                 window.maximize();
-            } else if !self.containers().is_empty() {
-                let mut layouts = self.layout().as_boxed_arrangement().calculate(
-                    &adjusted_work_area,
-                    NonZeroUsize::new(self.containers().len()).ok_or_else(|| {
-                        anyhow!(
-                            "there must be at least one container to calculate a workspace layout"
-                        )
-                    })?,
-                    Some(container_padding),
-                    self.layout_flip(),
-                    self.resize_dimensions(),
-                );
+            } else if !self.containers().is_empty() || !self.minimum_size_floats.is_empty() {
+                self.tile_minimum_size_floats();
+
+                let mut layouts = vec![];
+                while !self.containers().is_empty() {
+                    layouts = self.layout().as_boxed_arrangement().calculate(
+                        &adjusted_work_area,
+                        NonZeroUsize::new(self.containers().len()).ok_or_else(|| {
+                            anyhow!(
+                                "there must be at least one container to calculate a workspace layout"
+                            )
+                        })?,
+                        Some(container_padding),
+                        self.layout_flip(),
+                        self.resize_dimensions(),
+                        &self.split_directions(),
+                    );
+
+                    let minimums = self
+                        .containers()
+                        .iter()
+                        .map(|container| container.focused_window().and_then(|w| w.minimum_size()))
+                        .collect::<Vec<_>>();
+
+                    if minimums.iter().all(Option::is_none) {
+                        break;
+                    }
+
+                    let untileable = enforce_minimum_sizes(&mut layouts, &minimums);
+                    if !self.float_untileable_windows(&untileable) {
+                        break;
+                    }
+                }
+
+                // Every window was floated, so there is nothing left to lay out
+                if self.containers().is_empty() {
+                    layouts.clear();
+                }
+
+                self.forget_tiled_minimum_size_floats();
 
                 let should_remove_titlebars = REMOVE_TITLEBARS.load(Ordering::SeqCst);
                 let no_titlebar = NO_TITLEBAR.lock().clone();
@@ -519,7 +776,9 @@ impl Workspace {
         // without this check, if there are exactly two containers, when one is toggled to monocle
         // the resize dimensions will be truncated to len == 1, and when it is reintegrated, if it
         // had a resize adjustment before, that will have been lost
-        if self.monocle_container().is_none() {
+        //
+        // the same goes for containers which have been floated with the rest of the workspace
+        if self.monocle_container().is_none() && self.floated_containers().is_none() {
             self.resize_dimensions_mut().resize(container_count, None);
         }
 
@@ -722,6 +981,37 @@ impl Workspace {
         None
     }
 
+    pub fn location_from_hwnd(&self, hwnd: isize) -> Option<WorkspaceWindowLocation> {
+        for (container_idx, container) in self.containers().iter().enumerate() {
+            if let Some(window_idx) = container.idx_for_window(hwnd) {
+                return Some(WorkspaceWindowLocation::Container(
+                    container_idx,
+                    window_idx,
+                ));
+            }
+        }
+
+        if let Some(window) = self.maximized_window() {
+            if window.hwnd == hwnd {
+                return Some(WorkspaceWindowLocation::Maximized);
+            }
+        }
+
+        if let Some(container) = self.monocle_container() {
+            if let Some(window_idx) = container.idx_for_window(hwnd) {
+                return Some(WorkspaceWindowLocation::Monocle(window_idx));
+            }
+        }
+
+        for (window_idx, window) in self.floating_windows().iter().enumerate() {
+            if window.hwnd == hwnd {
+                return Some(WorkspaceWindowLocation::Floating(window_idx));
+            }
+        }
+
+        None
+    }
+
     pub fn contains_managed_window(&self, hwnd: isize) -> bool {
         for container in self.containers() {
             if container.contains_window(hwnd) {
@@ -768,6 +1058,41 @@ impl Workspace {
             && self.floating_windows().is_empty()
     }
 
+    /// The handles of every window on the workspace, wherever it is
+    pub fn hwnds(&self) -> Vec<isize> {
+        let mut hwnds = vec![];
+
+        for container in self.containers() {
+            hwnds.extend(container.windows().iter().map(|window| window.hwnd));
+        }
+
+        if let Some(window) = self.maximized_window() {
+            hwnds.push(window.hwnd);
+        }
+
+        if let Some(container) = self.monocle_container() {
+            hwnds.extend(container.windows().iter().map(|window| window.hwnd));
+        }
+
+        hwnds.extend(self.floating_windows().iter().map(|window| window.hwnd));
+
+        hwnds
+    }
+
+    /// The name which this workspace is given when workspaces without a name of their own are
+    /// auto-named, after the executable of the application with the most windows on it
+    pub fn dominant_application(&self) -> Option<String> {
+        if self.name.is_some() || !AUTO_NAME_WORKSPACES.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        dominant_application(
+            self.hwnds()
+                .into_iter()
+                .filter_map(|hwnd| Window::from(hwnd).exe().ok()),
+        )
+    }
+
     pub fn contains_window(&self, hwnd: isize) -> bool {
         for container in self.containers() {
             if container.contains_window(hwnd) {
@@ -945,6 +1270,53 @@ impl Workspace {
         Ok(())
     }
 
+    /// Removes a window without restoring, hiding or focusing any other windows, for workspaces
+    /// which are not being displayed, returning whether the window was on the workspace
+    pub fn forget_window(&mut self, hwnd: isize) -> bool {
+        if self.floating_windows().iter().any(|w| w.hwnd == hwnd) {
+            self.floating_windows_mut().retain(|w| w.hwnd != hwnd);
+            return true;
+        }
+
+        if let Some(container) = self.monocle_container_mut() {
+            if let Some(window_idx) = container.idx_for_window(hwnd) {
+                container.remove_window_by_idx(window_idx);
+
+                if container.windows().is_empty() {
+                    self.set_monocle_container(None);
+                    self.set_monocle_container_restore_idx(None);
+                }
+
+                return true;
+            }
+        }
+
+        if let Some(window) = self.maximized_window() {
+            if window.hwnd == hwnd {
+                self.set_maximized_window(None);
+                self.set_maximized_window_restore_idx(None);
+                return true;
+            }
+        }
+
+        let Some(container_idx) = self.container_idx_for_window(hwnd) else {
+            return false;
+        };
+
+        if let Some(container) = self.containers_mut().get_mut(container_idx) {
+            if let Some(window_idx) = container.idx_for_window(hwnd) {
+                container.remove_window_by_idx(window_idx);
+            }
+
+            if container.windows().is_empty() {
+                self.remove_container_by_idx(container_idx);
+                self.focus_previous_container();
+            }
+        }
+
+        true
+    }
+
     pub fn remove_focused_container(&mut self) -> Option<Container> {
         let focused_idx = self.focused_container_idx();
         let container = self.remove_container_by_idx(focused_idx);
@@ -960,6 +1332,14 @@ impl Workspace {
         container
     }
 
+    /// The direction that each container overrides the BSP split of its zone with, if any
+    pub fn split_directions(&self) -> Vec<Option<SplitDirection>> {
+        self.containers()
+            .iter()
+            .map(Container::split_direction)
+            .collect()
+    }
+
     pub fn new_idx_for_direction(&self, direction: OperationDirection) -> Option<usize> {
         let len = NonZeroUsize::new(self.containers().len())?;
 
@@ -968,6 +1348,7 @@ impl Workspace {
             self.layout_flip(),
             self.focused_container_idx(),
             len,
+            &self.split_directions(),
         )
     }
 
@@ -982,6 +1363,14 @@ impl Workspace {
     pub fn move_window_to_container(&mut self, target_container_idx: usize) -> Result<()> {
         let focused_idx = self.focused_container_idx();
 
+        if self
+            .containers()
+            .get(target_container_idx)
+            .is_some_and(Container::is_full)
+        {
+            bail!("the target container already holds the maximum number of windows");
+        }
+
         let container = self
             .focused_container_mut()
             .ok_or_else(|| anyhow!("there is no container"))?;
@@ -1057,6 +1446,13 @@ impl Workspace {
         Ok(())
     }
 
+    /// The index of a container which already holds a window of `exe` and has room for another
+    pub fn container_idx_for_exe(&self, exe: &str) -> Option<usize> {
+        self.containers()
+            .iter()
+            .position(|container| !container.is_full() && container.idx_from_exe(exe).is_some())
+    }
+
     pub fn new_container_for_window(&mut self, window: Window) {
         let next_idx = if self.containers().is_empty() {
             0
@@ -1118,6 +1514,134 @@ impl Workspace {
         Ok(())
     }
 
+    /// Raises or lowers the floating windows relative to the tiled windows according to the
+    /// configured floating window z-order
+    pub fn enforce_floating_window_z_order(&self) -> Result<()> {
+        // floating windows are hidden while there is a monocle container, and raising or lowering
+        // them would show them again
+        if self.monocle_container().is_some() {
+            return Ok(());
+        }
+
+        let focused_idx = self.focused_floating_window_idx();
+        let focused = self.floating_windows().get(focused_idx);
+        let others = self
+            .floating_windows()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != focused_idx)
+            .map(|(_, window)| window);
+
+        // the focused floating window always ends up above the others
+        match *FLOATING_WINDOW_Z_ORDER.lock() {
+            FloatingZOrder::AboveTiles => {
+                for window in others.chain(focused) {
+                    window.raise()?;
+                }
+            }
+            FloatingZOrder::BelowTiles => {
+                for window in focused.into_iter().chain(others) {
+                    window.lower()?;
+                }
+            }
+            FloatingZOrder::Normal => {}
+        }
+
+        Ok(())
+    }
+
+    /// Floats the windows of every container where their tiles were, keeping the containers so
+    /// that [`Workspace::tile_floated_containers`] can tile them again in the same order
+    pub fn float_containers(&mut self) -> Result<()> {
+        let layouts = self.latest_layout().clone();
+        let tile = *self.tile();
+        let containers = self.containers_mut().drain(..).collect::<Vec<_>>();
+
+        for (i, container) in containers.iter().enumerate() {
+            for window in container.windows() {
+                // the unfocused windows of stacks are hidden
+                window.restore();
+
+                if let (true, Some(layout)) = (tile, layouts.get(i)) {
+                    window.set_position(layout, false)?;
+                }
+
+                self.floating_windows_mut().push_back(*window);
+            }
+
+            // the focused window of each container should be above the rest of its stack
+            if let Some(window) = container.focused_window() {
+                window.raise()?;
+            }
+        }
+
+        self.focus_container(0);
+        self.set_floated_containers(Some(containers));
+
+        Ok(())
+    }
+
+    /// Tiles the windows which were floated by [`Workspace::float_containers`] in their original
+    /// containers again, leaving out any which have been closed or moved elsewhere since
+    pub fn tile_floated_containers(&mut self) {
+        let Some(containers) = self.floated_containers.take() else {
+            return;
+        };
+
+        let focused_hwnd = self.focused_floating_window().map(|window| window.hwnd);
+        let floating_hwnds = self
+            .floating_windows()
+            .iter()
+            .map(|window| window.hwnd)
+            .collect::<Vec<_>>();
+
+        let mut restored = vec![];
+        for mut container in containers {
+            container
+                .windows_mut()
+                .retain(|window| floating_hwnds.contains(&window.hwnd));
+
+            if container.windows().is_empty() {
+                continue;
+            }
+
+            let len = container.windows().len();
+            if container.focused_window_idx() >= len {
+                container.focus_window(len - 1);
+            }
+
+            container.load_focused_window();
+            restored.push(container);
+        }
+
+        self.floating_windows_mut().retain(|window| {
+            !restored
+                .iter()
+                .any(|container| container.contains_window(window.hwnd))
+        });
+
+        let len = self.floating_windows().len();
+        if self.focused_floating_window_idx() >= len {
+            self.floating_windows.focus(len.saturating_sub(1));
+        }
+
+        // containers which were created while the workspace was floating come after the restored
+        // ones
+        let focused_idx = focused_hwnd
+            .and_then(|hwnd| {
+                restored
+                    .iter()
+                    .position(|container| container.contains_window(hwnd))
+            })
+            .unwrap_or_default();
+
+        for container in restored.into_iter().rev() {
+            self.containers_mut().push_front(container);
+        }
+
+        self.focus_container(focused_idx);
+    }
+
     fn enforce_resize_constraints(&mut self) {
         match self.layout {
             Layout::Default(DefaultLayout::BSP) => self.enforce_resize_constraints_for_bsp(),
@@ -1429,6 +1953,26 @@ impl Workspace {
         Ok(())
     }
 
+    /// Makes the container holding `hwnd` the monocle container, reintegrating the previous
+    /// monocle container into the layout behind it
+    pub fn replace_monocle_container(&mut self, hwnd: isize) -> Result<()> {
+        self.reintegrate_monocle_container()?;
+        self.focus_container_by_window(hwnd)?;
+        self.new_monocle_container()
+    }
+
+    /// The number of tiled windows hidden behind the monocle container
+    pub fn monocle_hidden_window_count(&self) -> usize {
+        if self.monocle_container().is_none() {
+            return 0;
+        }
+
+        self.containers()
+            .iter()
+            .map(|container| container.windows().len())
+            .sum()
+    }
+
     pub fn new_maximized_window(&mut self) -> Result<()> {
         let focused_idx = self.focused_container_idx();
 
@@ -1634,6 +2178,315 @@ impl Workspace {
     fn focus_first_container(&mut self) {
         self.focus_container(0);
     }
+
+    /// Floats the focused window of each container whose layout cannot be grown to the minimum
+    /// size reported by that window, returning whether any windows were floated. The windows are
+    /// only floated until the next update which has room for them.
+    fn float_untileable_windows(&mut self, untileable: &[usize]) -> bool {
+        let mut floated = false;
+
+        for idx in untileable.iter().rev() {
+            let mut focused_idx = self.focused_container_idx();
+
+            let Some(container) = self.containers_mut().get_mut(*idx) else {
+                continue;
+            };
+
+            if let Some(window) = container.remove_focused_window() {
+                let stacked = !container.windows().is_empty();
+                if stacked {
+                    container.load_focused_window();
+                } else {
+                    self.remove_container_by_idx(*idx);
+                    if *idx < focused_idx {
+                        focused_idx -= 1;
+                    }
+                }
+
+                let previously_floated = self
+                    .minimum_size_floats
+                    .iter()
+                    .position(|float| float.hwnd == window.hwnd);
+
+                match previously_floated {
+                    Some(i) => {
+                        self.minimum_size_floats.remove(i);
+                    }
+                    None => tracing::info!(
+                        "floating window {} until there is room to tile it at its minimum size",
+                        window.hwnd
+                    ),
+                }
+
+                self.minimum_size_floats.push(MinimumSizeFloat {
+                    hwnd: window.hwnd,
+                    container_idx: *idx,
+                    stacked,
+                });
+
+                self.floating_windows_mut().push_back(window);
+                self.focus_container(focused_idx.min(self.containers().len().saturating_sub(1)));
+                floated = true;
+            }
+        }
+
+        floated
+    }
+
+    /// Puts windows which were floated by `float_untileable_windows` back where they were taken
+    /// from, so that they stay tiled if the layout has room for them now
+    fn tile_minimum_size_floats(&mut self) {
+        // they stay floating along with everything else while the whole workspace is floated
+        if self.floated_containers().is_some() {
+            return;
+        }
+
+        let mut focused_idx = self.focused_container_idx();
+
+        // in the reverse order to which they were floated, so that container indices line up
+        for float in self.minimum_size_floats.clone().into_iter().rev() {
+            let Some(window_idx) = self
+                .floating_windows()
+                .iter()
+                .position(|window| window.hwnd == float.hwnd)
+            else {
+                continue;
+            };
+
+            let Some(window) = self.floating_windows_mut().remove(window_idx) else {
+                continue;
+            };
+
+            let container_idx = float.container_idx.min(self.containers().len());
+            match self.containers_mut().get_mut(container_idx) {
+                Some(container) if float.stacked => container.add_window(window),
+                _ => {
+                    let mut container = Container::default();
+                    container.add_window(window);
+
+                    let insertion_idx = self.insert_container_at_idx(container_idx, container);
+                    if insertion_idx <= focused_idx {
+                        focused_idx += 1;
+                    }
+                }
+            }
+        }
+
+        self.focus_container(focused_idx.min(self.containers().len().saturating_sub(1)));
+    }
+
+    /// Forgets windows which `float_untileable_windows` floated once the layout has had room for
+    /// them, or once they are no longer floating on this workspace at all
+    fn forget_tiled_minimum_size_floats(&mut self) {
+        let floating_windows = self
+            .floating_windows()
+            .iter()
+            .map(|window| window.hwnd)
+            .collect::<Vec<_>>();
+
+        for float in &self.minimum_size_floats {
+            if !floating_windows.contains(&float.hwnd) && self.contains_window(float.hwnd) {
+                tracing::info!("tiling window {} again at its minimum size", float.hwnd);
+            }
+        }
+
+        self.minimum_size_floats
+            .retain(|float| floating_windows.contains(&float.hwnd));
+    }
+}
+
+/// Grows layouts which are smaller than the minimum (width, height) of the window they hold at the
+/// expense of their neighbours where possible, returning the indices of layouts that could not be
+/// grown enough
+fn enforce_minimum_sizes(layouts: &mut [Rect], minimums: &[Option<(i32, i32)>]) -> Vec<usize> {
+    let minimum_widths = minimums
+        .iter()
+        .map(|minimum| minimum.map_or(0, |(width, _)| width))
+        .collect::<Vec<_>>();
+
+    let minimum_heights = minimums
+        .iter()
+        .map(|minimum| minimum.map_or(0, |(_, height)| height))
+        .collect::<Vec<_>>();
+
+    let mut untileable = enforce_minimum_widths(layouts, &minimum_widths);
+
+    // Heights are handled by transposing the layouts so that the same logic can be reused
+    let mut transposed = layouts.iter().map(transpose).collect::<Vec<_>>();
+    untileable.extend(enforce_minimum_widths(&mut transposed, &minimum_heights));
+
+    for (layout, transposed) in layouts.iter_mut().zip(transposed) {
+        *layout = transpose(&transposed);
+    }
+
+    untileable.sort_unstable();
+    untileable.dedup();
+    untileable
+}
+
+const fn transpose(rect: &Rect) -> Rect {
+    Rect {
+        left: rect.top,
+        top: rect.left,
+        right: rect.bottom,
+        bottom: rect.right,
+    }
+}
+
+fn enforce_minimum_widths(layouts: &mut [Rect], minimums: &[i32]) -> Vec<usize> {
+    let mut untileable = vec![];
+
+    for i in 0..layouts.len() {
+        let deficit = minimums.get(i).copied().unwrap_or_default() - layouts[i].right;
+        if deficit <= 0 {
+            continue;
+        }
+
+        if !move_split_line(layouts, minimums, i, deficit, true)
+            && !move_split_line(layouts, minimums, i, deficit, false)
+        {
+            untileable.push(i);
+        }
+    }
+
+    untileable
+}
+
+/// Moves the vertical split line on the right (or left) edge of the layout at `idx` by `deficit`,
+/// taking the space from every layout on the other side of the line, as long as none of them end
+/// up smaller than their own minimum width
+fn move_split_line(
+    layouts: &mut [Rect],
+    minimums: &[i32],
+    idx: usize,
+    deficit: i32,
+    right_edge: bool,
+) -> bool {
+    let near_edge = |rect: &Rect| {
+        if right_edge {
+            rect.left + rect.right
+        } else {
+            rect.left
+        }
+    };
+
+    let far_edge = |rect: &Rect| {
+        if right_edge {
+            rect.left
+        } else {
+            rect.left + rect.right
+        }
+    };
+
+    let line = near_edge(&layouts[idx]);
+    let overlaps = |a: &Rect, top: i32, bottom: i32, gap: i32| {
+        a.top < bottom + gap && top < a.top + a.bottom + gap
+    };
+
+    // The closest edge on the other side of the line which overlaps this layout
+    let target = &layouts[idx];
+    let other_line = layouts
+        .iter()
+        .enumerate()
+        .filter(|(j, rect)| {
+            *j != idx
+                && overlaps(rect, target.top, target.top + target.bottom, 0)
+                && if right_edge {
+                    far_edge(rect) >= line
+                } else {
+                    far_edge(rect) <= line
+                }
+        })
+        .map(|(_, rect)| far_edge(rect))
+        .reduce(|a, b| if right_edge { a.min(b) } else { a.max(b) });
+
+    let Some(other_line) = other_line else {
+        return false;
+    };
+
+    let gap = (other_line - line).abs();
+
+    // Collect every layout along the same stretch of the split line
+    let mut near = vec![idx];
+    let mut far = vec![];
+    let (mut top, mut bottom) = (target.top, target.top + target.bottom);
+
+    loop {
+        let mut changed = false;
+
+        for (j, rect) in layouts.iter().enumerate() {
+            if near.contains(&j) || far.contains(&j) || !overlaps(rect, top, bottom, gap) {
+                continue;
+            }
+
+            if near_edge(rect) == line {
+                near.push(j);
+            } else if far_edge(rect) == other_line {
+                far.push(j);
+            } else {
+                continue;
+            }
+
+            top = top.min(rect.top);
+            bottom = bottom.max(rect.top + rect.bottom);
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    if far.is_empty()
+        || far.iter().any(|j| {
+            layouts[*j].right - deficit < minimums.get(*j).copied().unwrap_or_default().max(1)
+        })
+    {
+        return false;
+    }
+
+    for j in near {
+        if !right_edge {
+            layouts[j].left -= deficit;
+        }
+
+        layouts[j].right += deficit;
+    }
+
+    for j in far {
+        if right_edge {
+            layouts[j].left += deficit;
+        }
+
+        layouts[j].right -= deficit;
+    }
+
+    true
+}
+
+/// The most common of `exes` without its extension, e.g. "firefox" for firefox.exe, where ties go
+/// to whichever application was seen first
+fn dominant_application(exes: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut counts: Vec<(String, usize)> = vec![];
+
+    for exe in exes {
+        let exe = exe.to_lowercase();
+        let name = exe.strip_suffix(".exe").unwrap_or(&exe);
+
+        match counts.iter_mut().find(|(seen, _)| seen == name) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name.to_string(), 1)),
+        }
+    }
+
+    let mut dominant: Option<(String, usize)> = None;
+    for (name, count) in counts {
+        if dominant.as_ref().is_none_or(|(_, max)| count > *max) {
+            dominant = Option::from((name, count));
+        }
+    }
+
+    dominant.map(|(name, _)| name)
 }
 
 #[cfg(test)]
@@ -1645,6 +2498,26 @@ mod tests {
     use std::collections::BTreeSet;
     use std::collections::HashMap;
 
+    #[test]
+    fn test_dominant_application() {
+        let exes = |exes: &[&str]| exes.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        assert_eq!(dominant_application(exes(&[])), None);
+        assert_eq!(
+            dominant_application(exes(&["Code.exe", "firefox.exe", "firefox.exe"])),
+            Some(String::from("firefox"))
+        );
+        assert_eq!(
+            dominant_application(exes(&["Code.exe", "firefox.exe", "code.EXE"])),
+            Some(String::from("code"))
+        );
+        // ties go to the application which was seen first
+        assert_eq!(
+            dominant_application(exes(&["wezterm-gui.exe", "firefox.exe"])),
+            Some(String::from("wezterm-gui"))
+        );
+    }
+
     #[test]
     fn test_locked_containers_with_new_window() {
         let mut ws = Workspace::default();
@@ -2257,4 +3130,350 @@ mod tests {
             assert!(workspace.contains_window(0));
         }
     }
+
+    #[test]
+    fn test_without_windows() {
+        let mut workspace = Workspace::default();
+        workspace.set_name(Some(String::from("main")));
+        workspace.set_layout(Layout::Default(DefaultLayout::Columns));
+        workspace.set_container_padding(Some(5));
+
+        let mut container = Container::default();
+        container.windows_mut().push_back(Window::from(0));
+        workspace.add_container_to_back(container);
+        workspace.floating_windows_mut().push_back(Window::from(1));
+
+        let fresh = workspace.without_windows();
+
+        // Configuration should be carried over
+        assert_eq!(fresh.name(), &Some(String::from("main")));
+        assert_eq!(fresh.layout(), &Layout::Default(DefaultLayout::Columns));
+        assert_eq!(fresh.container_padding(), Some(5));
+
+        // Windows should not be carried over
+        assert!(fresh.containers().is_empty());
+        assert!(fresh.floating_windows().is_empty());
+        assert!(!fresh.contains_window(0));
+    }
+
+    #[test]
+    fn test_enforce_minimum_sizes_grows_at_neighbours_expense() {
+        let mut layouts = vec![
+            Rect {
+                left: 0,
+                top: 0,
+                right: 500,
+                bottom: 1000,
+            },
+            Rect {
+                left: 510,
+                top: 0,
+                right: 490,
+                bottom: 500,
+            },
+            Rect {
+                left: 510,
+                top: 510,
+                right: 490,
+                bottom: 490,
+            },
+        ];
+
+        let untileable = enforce_minimum_sizes(&mut layouts, &[Some((700, 0)), None, None]);
+        assert!(untileable.is_empty());
+
+        // The split line should have moved for every layout on the other side of it
+        assert_eq!(layouts[0].right, 700);
+        assert_eq!(layouts[1].left, 710);
+        assert_eq!(layouts[1].right, 290);
+        assert_eq!(layouts[2].left, 710);
+        assert_eq!(layouts[2].right, 290);
+    }
+
+    #[test]
+    fn test_enforce_minimum_sizes_reports_untileable_layouts() {
+        let mut layouts = vec![
+            Rect {
+                left: 0,
+                top: 0,
+                right: 500,
+                bottom: 1000,
+            },
+            Rect {
+                left: 510,
+                top: 0,
+                right: 490,
+                bottom: 1000,
+            },
+        ];
+
+        let untileable = enforce_minimum_sizes(&mut layouts, &[Some((700, 0)), Some((400, 0))]);
+        assert_eq!(untileable, vec![0]);
+
+        // Layouts which cannot be grown should be left untouched
+        assert_eq!(layouts[0].right, 500);
+        assert_eq!(layouts[1].left, 510);
+    }
+
+    #[test]
+    fn test_tile_floated_containers() {
+        let mut ws = Workspace::default();
+
+        let mut floated = vec![];
+        for hwnds in [vec![1, 2], vec![3]] {
+            let mut container = Container::default();
+            for hwnd in hwnds {
+                container.windows_mut().push_back(Window::from(hwnd));
+            }
+            floated.push(container);
+        }
+
+        ws.set_floated_containers(Some(floated));
+
+        // window 2 was closed while floating and window 4 was floating to begin with
+        for hwnd in [1, 3, 4] {
+            ws.floating_windows_mut().push_back(Window::from(hwnd));
+        }
+        ws.floating_windows.focus(1);
+
+        ws.tile_floated_containers();
+
+        let tiled = ws
+            .containers()
+            .iter()
+            .map(|container| {
+                container
+                    .windows()
+                    .iter()
+                    .map(|window| window.hwnd)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(tiled, vec![vec![1], vec![3]]);
+        assert_eq!(ws.floating_windows().len(), 1);
+        assert_eq!(ws.floating_windows()[0].hwnd, 4);
+        assert_eq!(ws.focused_container_idx(), 1);
+        assert!(ws.floated_containers().is_none());
+    }
+
+    #[test]
+    fn test_minimum_size_floats_are_tiled_again() {
+        let mut ws = Workspace::default();
+
+        for hwnd in [1, 2, 3] {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(hwnd));
+            ws.containers_mut().push_back(container);
+        }
+
+        ws.focus_container(2);
+
+        assert!(ws.float_untileable_windows(&[1]));
+        assert_eq!(ws.containers().len(), 2);
+        assert_eq!(ws.floating_windows()[0].hwnd, 2);
+        assert_eq!(ws.focused_container_idx(), 1);
+
+        // floating it again when there is still no room does not forget where it came from
+        ws.tile_minimum_size_floats();
+        assert!(ws.float_untileable_windows(&[1]));
+        ws.forget_tiled_minimum_size_floats();
+        assert_eq!(
+            ws.minimum_size_floats(),
+            &vec![MinimumSizeFloat {
+                hwnd: 2,
+                container_idx: 1,
+                stacked: false,
+            }]
+        );
+
+        // once there is room, it is back where it was and no longer floating
+        ws.tile_minimum_size_floats();
+        ws.forget_tiled_minimum_size_floats();
+
+        let tiled = ws
+            .containers()
+            .iter()
+            .map(|container| container.focused_window().map(|window| window.hwnd))
+            .collect::<Vec<_>>();
+
+        assert_eq!(tiled, vec![Some(1), Some(2), Some(3)]);
+        assert!(ws.floating_windows().is_empty());
+        assert!(ws.minimum_size_floats().is_empty());
+        assert_eq!(ws.focused_container_idx(), 2);
+    }
+
+    #[test]
+    fn test_preview_layout() {
+        let mut ws = Workspace::default();
+        ws.set_workspace_padding(Some(0));
+        ws.set_container_padding(Some(0));
+        ws.globals_mut().work_area = Rect {
+            left: 0,
+            top: 0,
+            right: 1000,
+            bottom: 500,
+        };
+
+        assert!(ws
+            .preview_layout(&Layout::Default(DefaultLayout::Columns))
+            .is_empty());
+
+        for hwnd in [1, 2] {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(hwnd));
+            ws.containers_mut().push_back(container);
+        }
+
+        let preview = ws.preview_layout(&Layout::Default(DefaultLayout::Columns));
+
+        assert_eq!(
+            preview,
+            vec![
+                Rect {
+                    left: 0,
+                    top: 0,
+                    right: 500,
+                    bottom: 500,
+                },
+                Rect {
+                    left: 500,
+                    top: 0,
+                    right: 500,
+                    bottom: 500,
+                },
+            ]
+        );
+
+        // previewing a layout does not apply it
+        assert_eq!(ws.layout(), &Layout::Default(DefaultLayout::BSP));
+    }
+
+    #[test]
+    fn test_effective_custom_layout() {
+        let mut ws = Workspace::default();
+        ws.set_workspace_padding(Some(0));
+        ws.set_container_padding(Some(0));
+        ws.globals_mut().work_area = Rect {
+            left: 0,
+            top: 0,
+            right: 1000,
+            bottom: 600,
+        };
+
+        for hwnd in [1, 2, 3] {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(hwnd));
+            ws.containers_mut().push_back(container);
+        }
+
+        let bsp = ws.preview_layout(&Layout::Default(DefaultLayout::BSP));
+        let custom = ws.effective_custom_layout().unwrap();
+
+        assert!(custom.is_valid());
+        assert_eq!(ws.preview_layout(&Layout::Custom(custom)), bsp);
+    }
+
+    #[test]
+    fn test_replace_monocle_container() {
+        let mut workspace = Workspace::default();
+
+        for i in 0..3 {
+            let mut container = Container::default();
+            container.windows_mut().push_back(Window::from(i));
+            workspace.add_container_to_back(container);
+        }
+
+        // Window 1 is in the monocle container, windows 0 and 2 are hidden behind it
+        workspace.focus_container(1);
+        workspace.new_monocle_container().unwrap();
+        assert_eq!(workspace.monocle_hidden_window_count(), 2);
+
+        // A new window opens behind the monocle container and takes it over
+        workspace.new_container_for_window(Window::from(3));
+        workspace.replace_monocle_container(3).unwrap();
+
+        let monocle = workspace.monocle_container().as_ref().unwrap();
+        assert_eq!(monocle.focused_window().unwrap().hwnd, 3);
+        assert_eq!(workspace.monocle_hidden_window_count(), 3);
+
+        // The previous monocle container is back at its original index
+        assert!(workspace.containers()[1].contains_window(1));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut ws = Workspace::default();
+        ws.set_name(Option::from(String::from("coding")));
+        ws.set_layout(Layout::Default(DefaultLayout::Columns));
+        ws.set_tile(false);
+        ws.set_resize_dimensions(vec![None, Some(Rect::default())]);
+
+        // Saving without any options keeps the format which only has the resize dimensions
+        let saved = serde_json::to_string(&ws.save(&[])).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SavedWorkspace>(&saved).unwrap(),
+            SavedWorkspace::ResizeDimensions(vec![None, Some(Rect::default())])
+        );
+
+        let saved = ws.save(&[WorkspaceSaveOption::Layout, WorkspaceSaveOption::Tiling]);
+        let saved: SavedWorkspace =
+            serde_json::from_str(&serde_json::to_string(&saved).unwrap()).unwrap();
+
+        // The name wasn't selected when saving
+        assert!(matches!(
+            saved,
+            SavedWorkspace::Snapshot(WorkspaceSnapshot { name: None, .. })
+        ));
+
+        let mut loaded = Workspace::default();
+        loaded.load(saved);
+
+        assert_eq!(loaded.resize_dimensions(), ws.resize_dimensions());
+        assert_eq!(loaded.layout(), &Layout::Default(DefaultLayout::Columns));
+        assert!(!loaded.tile());
+    }
+
+    #[test]
+    fn test_scale_resize_dimensions() {
+        let mut ws = Workspace::default();
+        ws.set_resize_dimensions(vec![
+            None,
+            Some(Rect {
+                left: 0,
+                top: -50,
+                right: 100,
+                bottom: 25,
+            }),
+        ]);
+
+        // Rotating a 1920x1080 display to portrait makes the work area narrower and taller
+        ws.scale_resize_dimensions(
+            &Rect {
+                left: 0,
+                top: 0,
+                right: 1920,
+                bottom: 1080,
+            },
+            &Rect {
+                left: 0,
+                top: 0,
+                right: 1080,
+                bottom: 1920,
+            },
+        );
+
+        assert_eq!(
+            ws.resize_dimensions(),
+            &vec![
+                None,
+                Some(Rect {
+                    left: 0,
+                    top: -89,
+                    right: 56,
+                    bottom: 44,
+                })
+            ]
+        );
+    }
 }
@@ -0,0 +1,157 @@
+#![deny(clippy::unwrap_used, clippy::expect_used)]
+
+use crate::core::Rect;
+use crate::window::Window;
+use crate::window_manager::WindowManager;
+use crate::windows_api::WindowsApi;
+
+use color_eyre::Result;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A window shown in a workspace preview, positioned relative to the top left corner of its
+/// monitor
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PreviewWindow {
+    pub hwnd: isize,
+    pub rect: Rect,
+}
+
+/// The arrangement of the windows on a workspace, returned by `komorebic query workspace-previews`.
+/// Windows on workspaces which are not visible are only cloaked, so a `Thumbnail` of each of them
+/// can be drawn at its rect to show a live preview of any workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WorkspacePreview {
+    pub monitor_idx: usize,
+    pub workspace_idx: usize,
+    pub name: Option<String>,
+    /// Whether this is the workspace which is currently shown on its monitor
+    pub visible: bool,
+    /// The width of the preview, which is the width of the monitor unless it has been scaled
+    pub width: i32,
+    /// The height of the preview, which is the height of the monitor unless it has been scaled
+    pub height: i32,
+    /// The windows of the workspace from bottom to top
+    pub windows: Vec<PreviewWindow>,
+}
+
+impl WorkspacePreview {
+    /// Previews of every workspace on every monitor
+    pub fn all(wm: &WindowManager) -> Vec<Self> {
+        let mut previews = vec![];
+
+        for (monitor_idx, monitor) in wm.monitors().iter().enumerate() {
+            let size = *monitor.size();
+
+            for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+                let mut windows = vec![];
+
+                if let Some(window) = workspace.maximized_window() {
+                    windows.push(*window);
+                } else if let Some(window) = workspace
+                    .monocle_container()
+                    .as_ref()
+                    .and_then(|container| container.focused_window())
+                {
+                    windows.push(*window);
+                } else {
+                    windows.extend(
+                        workspace
+                            .containers()
+                            .iter()
+                            .filter_map(|container| container.focused_window().copied()),
+                    );
+                }
+
+                windows.extend(workspace.floating_windows().iter().copied());
+
+                previews.push(Self {
+                    monitor_idx,
+                    workspace_idx,
+                    name: workspace.name().clone(),
+                    visible: workspace_idx == monitor.focused_workspace_idx(),
+                    width: size.right,
+                    height: size.bottom,
+                    windows: windows
+                        .into_iter()
+                        .filter_map(|window| preview_window(window, &size))
+                        .collect(),
+                });
+            }
+        }
+
+        previews
+    }
+
+    /// Scales the preview down or up to be `width` pixels wide, keeping its aspect ratio
+    #[must_use]
+    pub fn scaled(&self, width: i32) -> Self {
+        let current_width = self.width.max(1);
+
+        Self {
+            width,
+            height: self.height * width / current_width,
+            windows: self
+                .windows
+                .iter()
+                .map(|window| PreviewWindow {
+                    hwnd: window.hwnd,
+                    rect: window.rect.scale(current_width, width),
+                })
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+fn preview_window(window: Window, monitor_size: &Rect) -> Option<PreviewWindow> {
+    // minimized windows have no meaningful position to preview them at
+    if WindowsApi::is_iconic(window.hwnd) {
+        return None;
+    }
+
+    let rect = WindowsApi::window_rect(window.hwnd).ok()?;
+
+    Option::from(PreviewWindow {
+        hwnd: window.hwnd,
+        rect: Rect {
+            left: rect.left - monitor_size.left,
+            top: rect.top - monitor_size.top,
+            ..rect
+        },
+    })
+}
+
+/// A live DWM thumbnail of a window, drawn onto a window owned by the calling process, which is
+/// removed again when it is dropped
+#[derive(Debug)]
+pub struct Thumbnail(isize);
+
+impl Thumbnail {
+    /// Registers a thumbnail of `source` which can be drawn onto `destination`
+    pub fn register(destination: isize, source: isize) -> Result<Self> {
+        Ok(Self(WindowsApi::dwm_register_thumbnail(
+            destination,
+            source,
+        )?))
+    }
+
+    /// Draws the thumbnail at `rect` in the client area of the destination window
+    pub fn show(&self, rect: &Rect) -> Result<()> {
+        WindowsApi::dwm_update_thumbnail(self.0, Option::from(rect))
+    }
+
+    pub fn hide(&self) -> Result<()> {
+        WindowsApi::dwm_update_thumbnail(self.0, None)
+    }
+}
+
+impl Drop for Thumbnail {
+    fn drop(&mut self) {
+        if let Err(error) = WindowsApi::dwm_unregister_thumbnail(self.0) {
+            tracing::warn!("could not unregister thumbnail: {error}");
+        }
+    }
+}
@@ -11,9 +11,10 @@ fn main() -> io::Result<()> {
     current_exe.pop();
     let komorebic_exe = current_exe.join("komorebic.exe");
 
-    Command::new(komorebic_exe)
+    let status = Command::new(komorebic_exe)
         .args(std::env::args_os().skip(1))
         .creation_flags(CREATE_NO_WINDOW)
-        .status()
-        .map(|_| ())
+        .status()?;
+
+    std::process::exit(status.code().unwrap_or(1))
 }
@@ -0,0 +1,416 @@
+use std::io::BufReader;
+use std::io::Read;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+use color_eyre::Result;
+use komorebi_client::send_message_and_wait;
+use komorebi_client::send_query;
+use komorebi_client::Container;
+use komorebi_client::Notification;
+use komorebi_client::SocketMessage;
+use komorebi_client::State;
+use komorebi_client::Window;
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEventKind;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::List;
+use ratatui::widgets::ListItem;
+use ratatui::widgets::ListState;
+use ratatui::widgets::Paragraph;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+
+const SUBSCRIBER: &str = "komorebic-inspect.sock";
+
+/// How long to wait for a key press before checking for a new state from komorebi
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const HELP: &str = "↑/k ↓/j select  enter/f focus  m mark window / move marked window to workspace  c close window  q quit";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Node {
+    Monitor {
+        monitor: usize,
+    },
+    Workspace {
+        monitor: usize,
+        workspace: usize,
+    },
+    /// A container is represented by its focused window
+    Container {
+        hwnd: isize,
+    },
+    Window {
+        hwnd: isize,
+    },
+}
+
+impl Node {
+    const fn hwnd(self) -> Option<isize> {
+        match self {
+            Self::Container { hwnd } | Self::Window { hwnd } => Some(hwnd),
+            Self::Monitor { .. } | Self::Workspace { .. } => None,
+        }
+    }
+}
+
+struct Row {
+    node: Node,
+    depth: usize,
+    label: String,
+    /// Whether this is the focused element of its parent
+    focused: bool,
+    /// Whether this is on the path from the focused monitor down to the focused window
+    on_focus_path: bool,
+}
+
+/// The user-facing index of the monitor at `idx` in the state, which is what commands which take
+/// a monitor index expect
+fn usr_monitor_idx(state: &State, idx: usize) -> usize {
+    state
+        .monitor_usr_idx_map
+        .iter()
+        .find_map(|(usr_idx, actual_idx)| (*actual_idx == idx).then_some(*usr_idx))
+        .unwrap_or(idx)
+}
+
+fn window_label(window: Window) -> String {
+    format!(
+        "{} - {} ({})",
+        window.exe().unwrap_or_default(),
+        window.title().unwrap_or_default(),
+        window.hwnd
+    )
+}
+
+fn push_container(
+    rows: &mut Vec<Row>,
+    container: &Container,
+    label: String,
+    focused: bool,
+    on_focus_path: bool,
+) {
+    let Some(focused_window) = container.focused_window() else {
+        return;
+    };
+
+    rows.push(Row {
+        node: Node::Container {
+            hwnd: focused_window.hwnd,
+        },
+        depth: 2,
+        label,
+        focused,
+        on_focus_path,
+    });
+
+    for (idx, window) in container.windows().iter().enumerate() {
+        let window_focused = idx == container.focused_window_idx();
+        rows.push(Row {
+            node: Node::Window { hwnd: window.hwnd },
+            depth: 3,
+            label: window_label(*window),
+            focused: window_focused,
+            on_focus_path: on_focus_path && window_focused,
+        });
+    }
+}
+
+fn rows(state: &State) -> Vec<Row> {
+    let mut rows = vec![];
+
+    for (monitor_idx, monitor) in state.monitors.elements().iter().enumerate() {
+        let monitor_focused = monitor_idx == state.monitors.focused_idx();
+        rows.push(Row {
+            node: Node::Monitor {
+                monitor: monitor_idx,
+            },
+            depth: 0,
+            label: format!(
+                "Monitor {}: {}",
+                usr_monitor_idx(state, monitor_idx),
+                monitor.name()
+            ),
+            focused: monitor_focused,
+            on_focus_path: monitor_focused,
+        });
+
+        for (workspace_idx, workspace) in monitor.workspaces().iter().enumerate() {
+            let workspace_focused = workspace_idx == monitor.focused_workspace_idx();
+            let on_focus_path = monitor_focused && workspace_focused;
+
+            rows.push(Row {
+                node: Node::Workspace {
+                    monitor: monitor_idx,
+                    workspace: workspace_idx,
+                },
+                depth: 1,
+                label: workspace.name().as_ref().map_or_else(
+                    || format!("Workspace {workspace_idx}"),
+                    |name| format!("Workspace {workspace_idx}: {name}"),
+                ),
+                focused: workspace_focused,
+                on_focus_path,
+            });
+
+            if let Some(window) = workspace.maximized_window() {
+                rows.push(Row {
+                    node: Node::Window { hwnd: window.hwnd },
+                    depth: 2,
+                    label: format!("Maximized: {}", window_label(*window)),
+                    focused: true,
+                    on_focus_path,
+                });
+            }
+
+            if let Some(container) = workspace.monocle_container() {
+                push_container(
+                    &mut rows,
+                    container,
+                    String::from("Monocle"),
+                    true,
+                    on_focus_path,
+                );
+            }
+
+            for (container_idx, container) in workspace.containers().iter().enumerate() {
+                let container_focused = workspace.monocle_container().is_none()
+                    && container_idx == workspace.focused_container_idx();
+
+                push_container(
+                    &mut rows,
+                    container,
+                    format!("Container {container_idx}"),
+                    container_focused,
+                    on_focus_path && container_focused,
+                );
+            }
+
+            for window in workspace.floating_windows() {
+                rows.push(Row {
+                    node: Node::Window { hwnd: window.hwnd },
+                    depth: 2,
+                    label: format!("Floating: {}", window_label(*window)),
+                    focused: false,
+                    on_focus_path: false,
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+struct Inspector {
+    state: State,
+    rows: Vec<Row>,
+    list: ListState,
+    /// A window which has been marked to be moved to the next workspace that `m` is pressed on
+    marked: Option<isize>,
+    status: String,
+}
+
+impl Inspector {
+    fn new(state: State) -> Self {
+        let rows = rows(&state);
+        let selected = rows.iter().rposition(|row| row.on_focus_path);
+
+        Self {
+            state,
+            rows,
+            list: ListState::default().with_selected(selected),
+            marked: None,
+            status: String::new(),
+        }
+    }
+
+    fn selected(&self) -> Option<Node> {
+        self.list
+            .selected()
+            .and_then(|idx| self.rows.get(idx))
+            .map(|row| row.node)
+    }
+
+    fn update(&mut self, state: State) {
+        let selected = self.selected();
+
+        self.state = state;
+        self.rows = rows(&self.state);
+
+        // Keep the same node selected if it still exists after the update
+        if let Some(idx) =
+            selected.and_then(|node| self.rows.iter().position(|row| row.node == node))
+        {
+            self.list.select(Some(idx));
+        }
+    }
+
+    fn send(&mut self, message: SocketMessage) {
+        self.status = match send_message_and_wait(&message) {
+            Ok(()) => format!("sent {message}"),
+            Err(error) => format!("{message} failed: {error}"),
+        };
+    }
+
+    fn focus(&mut self) {
+        match self.selected() {
+            Some(Node::Monitor { monitor }) => {
+                let monitor = usr_monitor_idx(&self.state, monitor);
+                self.send(SocketMessage::FocusMonitorNumber(monitor));
+            }
+            Some(Node::Workspace { monitor, workspace }) => {
+                let monitor = usr_monitor_idx(&self.state, monitor);
+                self.send(SocketMessage::FocusMonitorWorkspaceNumber(
+                    monitor, workspace,
+                ));
+            }
+            Some(Node::Container { hwnd } | Node::Window { hwnd }) => {
+                self.send(SocketMessage::FocusWindowHwnd(hwnd));
+            }
+            None => {}
+        }
+    }
+
+    fn close(&mut self) {
+        if let Some(hwnd) = self.selected().and_then(Node::hwnd) {
+            self.send(SocketMessage::CloseWindowHwnd(hwnd));
+        }
+    }
+
+    fn mark_or_move(&mut self) {
+        match self.selected() {
+            Some(Node::Workspace { monitor, workspace }) => match self.marked.take() {
+                Some(hwnd) => {
+                    let monitor = usr_monitor_idx(&self.state, monitor);
+                    self.send(SocketMessage::MoveWindowToWorkspace(
+                        hwnd, monitor, workspace,
+                    ));
+                }
+                None => self.status = String::from("mark a window with m first"),
+            },
+            Some(node) => {
+                if let Some(hwnd) = node.hwnd() {
+                    self.marked = Some(hwnd);
+                    self.status =
+                        String::from("select a workspace and press m to move the marked window");
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tree, footer] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(2)]).areas(frame.area());
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let mut style = Style::default();
+
+                if row.on_focus_path {
+                    style = style.fg(Color::Green).add_modifier(Modifier::BOLD);
+                } else if row.focused {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+
+                if self.marked.is_some() && row.node.hwnd() == self.marked {
+                    style = style.fg(Color::Yellow);
+                }
+
+                ListItem::new(format!("{}{}", "  ".repeat(row.depth), row.label)).style(style)
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::bordered().title(" komorebi "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(list, tree, &mut self.list);
+        frame.render_widget(
+            Paragraph::new(vec![Line::from(self.status.as_str()), Line::from(HELP)]),
+            footer,
+        );
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal, states: &Receiver<State>) -> Result<()> {
+        loop {
+            if let Some(state) = states.try_iter().last() {
+                self.update(state);
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(POLL_INTERVAL)? {
+                continue;
+            }
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Esc => {
+                        if self.marked.take().is_none() {
+                            return Ok(());
+                        }
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => self.list.select_previous(),
+                    KeyCode::Down | KeyCode::Char('j') => self.list.select_next(),
+                    KeyCode::Enter | KeyCode::Char('f') => self.focus(),
+                    KeyCode::Char('m') => self.mark_or_move(),
+                    KeyCode::Char('c') => self.close(),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Shows the monitor, workspace, container and window tree of the running instance of komorebi in
+/// the terminal, redrawing it whenever a notification is received
+pub fn inspect() -> Result<()> {
+    let state = serde_json::from_str::<State>(&send_query(&SocketMessage::State)?)?;
+    let listener = komorebi_client::subscribe(SUBSCRIBER)?;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut notification = String::new();
+            if BufReader::new(stream)
+                .read_to_string(&mut notification)
+                .is_err()
+            {
+                continue;
+            }
+
+            if let Ok(notification) = serde_json::from_str::<Notification>(&notification) {
+                if sender.send(notification.state).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    let mut terminal = ratatui::init();
+    let result = Inspector::new(state).run(&mut terminal, &receiver);
+    ratatui::restore();
+
+    komorebi_client::send_message(&SocketMessage::RemoveSubscriberSocket(
+        SUBSCRIBER.to_string(),
+    ))?;
+
+    result
+}
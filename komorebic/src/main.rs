@@ -1,7 +1,8 @@
 #![warn(clippy::all)]
 #![allow(clippy::missing_errors_doc, clippy::doc_markdown)]
 
-use chrono::Utc;
+mod inspect;
+
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::BufRead;
@@ -14,16 +15,21 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
+use clap::Arg;
 use clap::CommandFactory;
 use clap::Parser;
 use clap::ValueEnum;
+use clap_complete::generate;
+use clap_complete::Shell;
+use clap_complete_nushell::Nushell;
 use color_eyre::eyre::anyhow;
 use color_eyre::eyre::bail;
 use color_eyre::Result;
 use dirs::data_local_dir;
 use fs_tail::TailedFile;
 use komorebi_client::resolve_home_path;
-use komorebi_client::send_message;
+use komorebi_client::send_message_and_wait;
+use komorebi_client::send_message_and_wait_with_timeout;
 use komorebi_client::send_query;
 use komorebi_client::AppSpecificConfigurationPath;
 use komorebi_client::ApplicationSpecificConfiguration;
@@ -33,6 +39,7 @@ use miette::Report;
 use miette::SourceOffset;
 use miette::SourceSpan;
 use paste::paste;
+use regex::Regex;
 use serde::Deserialize;
 use sysinfo::ProcessesToUpdate;
 use which::which;
@@ -46,17 +53,24 @@ use komorebi_client::ApplicationIdentifier;
 use komorebi_client::Axis;
 use komorebi_client::CycleDirection;
 use komorebi_client::DefaultLayout;
+use komorebi_client::DirectionalFocusBehaviour;
+use komorebi_client::FloatingZOrder;
 use komorebi_client::FocusFollowsMouseImplementation;
 use komorebi_client::HidingBehaviour;
+use komorebi_client::MatchingStrategy;
+use komorebi_client::MonocleWithNewWindowBehaviour;
 use komorebi_client::MoveBehaviour;
 use komorebi_client::OperationBehaviour;
 use komorebi_client::OperationDirection;
 use komorebi_client::Rect;
 use komorebi_client::Sizing;
 use komorebi_client::SocketMessage;
+use komorebi_client::SocketResponse;
 use komorebi_client::StateQuery;
 use komorebi_client::StaticConfig;
+use komorebi_client::SubscribeOptions;
 use komorebi_client::WindowKind;
+use komorebi_client::WorkspaceSaveOption;
 
 lazy_static! {
     static ref HAS_CUSTOM_CONFIG_HOME: AtomicBool = AtomicBool::new(false);
@@ -163,12 +177,18 @@ gen_enum_subcommand_args! {
     CycleStackIndex: CycleDirection,
     FlipLayout: Axis,
     ChangeLayout: DefaultLayout,
+    PreviewLayout: DefaultLayout,
     CycleLayout: CycleDirection,
     WatchConfiguration: BooleanState,
     MouseFollowsFocus: BooleanState,
+    VirtualDesktopAwareness: BooleanState,
+    StackBySameExe: BooleanState,
     Query: StateQuery,
     WindowHidingBehaviour: HidingBehaviour,
+    FloatingWindowZOrder: FloatingZOrder,
     CrossMonitorMoveBehaviour: MoveBehaviour,
+    MonocleNewWindowBehaviour: MonocleWithNewWindowBehaviour,
+    DirectionalFocusBehaviour: DirectionalFocusBehaviour,
     UnmanagedWindowOperationBehaviour: OperationBehaviour,
     PromoteWindow: OperationDirection,
 }
@@ -191,8 +211,11 @@ gen_target_subcommand_args! {
     MoveToWorkspace,
     SendToMonitor,
     SendToWorkspace,
+    MoveToVirtualDesktop,
+    SendToVirtualDesktop,
     FocusMonitor,
     FocusWorkspace,
+    FocusWorkspaceWithMouse,
     FocusWorkspaces,
     MoveWorkspaceToMonitor,
     SwapWorkspacesWithMonitor,
@@ -219,6 +242,25 @@ gen_named_target_subcommand_args! {
     ClearNamedWorkspaceLayoutRules
 }
 
+macro_rules! gen_monitor_alias_subcommand_args {
+    // SubCommand Pattern
+    ( $( $name:ident ),+ $(,)? ) => {
+        $(
+            #[derive(clap::Parser)]
+            pub struct $name {
+                /// Target monitor alias
+                alias: String,
+            }
+        )+
+    };
+}
+
+gen_monitor_alias_subcommand_args! {
+    MoveToMonitorAlias,
+    SendToMonitorAlias,
+    FocusMonitorAlias,
+}
+
 // Thanks to @danielhenrymantilla for showing me how to use cfg_attr with an optional argument like
 // this on the Rust Programming Language Community Discord Server
 macro_rules! gen_workspace_subcommand_args {
@@ -411,6 +453,22 @@ struct GlobalWorkAreaOffset {
     bottom: i32,
 }
 
+#[derive(Parser)]
+struct IdentifyBorderOverflowWithOffset {
+    #[clap(value_enum)]
+    identifier: ApplicationIdentifier,
+    /// Identifier as a string
+    id: String,
+    /// Size of the invisible border on the left edge of the window
+    left: i32,
+    /// Size of the invisible border on the top edge of the window
+    top: i32,
+    /// Size of the invisible border on the right edge of the window
+    right: i32,
+    /// Size of the invisible border on the bottom edge of the window
+    bottom: i32,
+}
+
 #[derive(Parser)]
 struct MonitorWorkAreaOffset {
     /// Monitor index (zero-indexed)
@@ -487,6 +545,16 @@ pub struct MoveToMonitorWorkspace {
     target_workspace: usize,
 }
 
+#[derive(Parser)]
+pub struct MoveWindowToWorkspace {
+    /// Handle of the window to move
+    hwnd: isize,
+    /// Target monitor index (zero-indexed)
+    target_monitor: usize,
+    /// Workspace index on the target monitor (zero-indexed)
+    target_workspace: usize,
+}
+
 macro_rules! gen_focused_workspace_padding_subcommand_args {
     // SubCommand Pattern
     ( $( $name:ident ),+ $(,)? ) => {
@@ -503,6 +571,24 @@ macro_rules! gen_focused_workspace_padding_subcommand_args {
 gen_focused_workspace_padding_subcommand_args! {
     FocusedWorkspaceContainerPadding,
     FocusedWorkspacePadding,
+    DefaultContainerPadding,
+    DefaultWorkspacePadding,
+}
+
+#[derive(Parser)]
+struct MonitorContainerPadding {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// Pixels to pad with as an integer
+    size: i32,
+}
+
+#[derive(Parser)]
+struct MonitorWorkspacePadding {
+    /// Monitor index (zero-indexed)
+    monitor: usize,
+    /// Pixels to pad with as an integer
+    size: i32,
 }
 
 macro_rules! gen_padding_subcommand_args {
@@ -584,13 +670,26 @@ macro_rules! gen_application_target_subcommand_args {
 }
 
 gen_application_target_subcommand_args! {
-    IgnoreRule,
     ManageRule,
+    FocusFollowsMouseIgnoreRule,
     IdentifyTrayApplication,
+    IdentifyStackApplication,
     IdentifyLayeredApplication,
     IdentifyObjectNameChangeApplication,
     IdentifyBorderOverflowApplication,
     RemoveTitleBar,
+    IdentifyPresentationApplication,
+}
+
+#[derive(Parser)]
+struct IgnoreRule {
+    #[clap(value_enum)]
+    identifier: ApplicationIdentifier,
+    /// Identifier as a string
+    id: String,
+    /// How the identifier should be matched against windows (e.g. contains, regex)
+    #[clap(long, value_enum)]
+    matching_strategy: Option<MatchingStrategy>,
 }
 
 #[derive(Parser)]
@@ -651,6 +750,30 @@ struct ClearNamedWorkspaceRules {
     workspace: String,
 }
 
+#[derive(Parser)]
+struct Exec {
+    /// Command to run with cmd.exe
+    command: String,
+    /// Name of the workspace to place the first window of the application on (default: focused
+    /// workspace)
+    #[clap(short, long)]
+    workspace: Option<String>,
+    /// Place the first window of the application as a floating window
+    #[clap(short, long)]
+    float: bool,
+}
+
+#[derive(Parser)]
+struct WaitForWindow {
+    #[clap(value_enum)]
+    identifier: ApplicationIdentifier,
+    /// Identifier as a string
+    id: String,
+    /// How long to wait for the window to be managed, in milliseconds
+    #[clap(short, long, default_value = "10000")]
+    timeout: u64,
+}
+
 #[derive(Parser)]
 struct ToggleFocusFollowsMouse {
     #[clap(value_enum, short, long, default_value = "windows")]
@@ -665,6 +788,30 @@ struct FocusFollowsMouse {
     boolean_state: BooleanState,
 }
 
+#[derive(Parser)]
+struct MaxContainerWindows {
+    /// Maximum number of windows in a container (0 for no limit)
+    max_windows: usize,
+}
+
+#[derive(Parser)]
+struct FocusFollowsMouseHoverDelay {
+    /// Milliseconds the cursor has to rest before the window under it is raised (0 to disable)
+    delay: u64,
+}
+
+#[derive(Parser)]
+struct FocusFollowsMouseExclusionZone {
+    /// Left value of the excluded region
+    left: i32,
+    /// Top value of the excluded region
+    top: i32,
+    /// Width of the excluded region
+    right: i32,
+    /// Height of the excluded region
+    bottom: i32,
+}
+
 #[derive(Parser)]
 struct Border {
     #[clap(value_enum)]
@@ -789,9 +936,26 @@ struct Start {
     /// Start masir in a background process for focus-follows-mouse
     #[clap(long)]
     masir: bool,
-    /// Do not attempt to auto-apply a dumped state temp file from a previously running instance of komorebi
+    /// Do not attempt to auto-apply the state file from a previously running instance of komorebi
     #[clap(long)]
     clean_state: bool,
+    /// How often komorebi should start a new log file
+    #[clap(value_enum, long)]
+    log_rotation: Option<komorebi_client::LogRotation>,
+    /// Maximum number of rotated log files to keep
+    #[clap(long)]
+    log_retention: Option<usize>,
+    /// Start a new log file whenever the current one reaches this size in megabytes, instead of
+    /// on the schedule given by --log-rotation
+    #[clap(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "log_rotation")]
+    log_max_size: Option<u64>,
+}
+
+#[derive(Parser)]
+struct LogLevel {
+    /// Maximum verbosity of the messages written to the logs
+    #[clap(value_enum)]
+    level: komorebi_client::LogLevel,
 }
 
 #[derive(Parser)]
@@ -813,6 +977,20 @@ struct Stop {
     ignore_restore: bool,
 }
 
+#[derive(Parser)]
+struct Manage {
+    /// Handle of the window to manage instead of the focused window
+    #[clap(long)]
+    hwnd: Option<isize>,
+}
+
+#[derive(Parser)]
+struct Unmanage {
+    /// Handle of the window to unmanage instead of the focused window
+    #[clap(long)]
+    hwnd: Option<isize>,
+}
+
 #[derive(Parser)]
 struct Kill {
     /// Kill whkd if it is running as a background process
@@ -833,6 +1011,16 @@ struct Kill {
 struct SaveResize {
     /// File to which the resize layout dimensions should be saved
     path: PathBuf,
+    /// Other parts of the workspace to save along with the resize layout dimensions
+    #[clap(long, value_enum, value_delimiter = ',')]
+    include: Vec<WorkspaceSaveOption>,
+}
+
+#[derive(Parser)]
+struct QuickSaveResize {
+    /// Other parts of the workspace to save along with the resize layout dimensions
+    #[clap(long, value_enum, value_delimiter = ',')]
+    include: Vec<WorkspaceSaveOption>,
 }
 
 #[derive(Parser)]
@@ -847,10 +1035,28 @@ struct LoadCustomLayout {
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct SaveCustomLayout {
+    /// JSON or YAML file to which the custom layout definition should be written
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct PreviewLayoutCustom {
+    /// JSON or YAML file from which the custom layout definition should be loaded
+    path: PathBuf,
+}
+
 #[derive(Parser)]
 struct SubscribeSocket {
     /// Name of the socket to send event notifications to
     socket: String,
+    /// Only send notifications when the window manager state has changed
+    #[clap(long)]
+    filter_state_changes: bool,
+    /// Send at most this many notifications per second, skipping any which are superseded by a more recent notification in the meantime
+    #[clap(long)]
+    max_notifications_per_second: Option<u32>,
 }
 
 #[derive(Parser)]
@@ -935,21 +1141,89 @@ struct Check {
     komorebi_config: Option<PathBuf>,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum CompletionShell {
+    Bash,
+    Elvish,
+    Fish,
+    Nushell,
+    Powershell,
+    Zsh,
+}
+
+#[derive(Parser)]
+struct Completions {
+    /// Shell to generate completions for
+    #[clap(value_enum)]
+    shell: CompletionShell,
+    /// Generate a PowerShell module with an Invoke-Komorebic* function for every subcommand instead of completions
+    #[clap(long)]
+    module: bool,
+}
+
+#[derive(Parser)]
+struct VisibleWindows {
+    /// Show every visible top-level window with the details komorebi uses to decide whether it should be managed
+    #[clap(long)]
+    all: bool,
+}
+
+#[derive(Parser)]
+struct FocusedWindow {
+    /// Number of seconds to wait before looking up the foreground window, to give time to focus another window
+    #[clap(short, long, default_value_t = 3)]
+    delay: u64,
+}
+
+#[derive(Parser)]
+struct Quickstart {
+    /// Also write an example whkdrc with key bindings for komorebic commands
+    #[clap(long)]
+    whkd: bool,
+}
+
 #[derive(Parser)]
 struct ReplaceConfiguration {
     /// Static configuration JSON file from which the configuration should be loaded
     path: PathBuf,
 }
 
+#[derive(Parser)]
+struct ApplyAppSpecificConfiguration {
+    /// applications.yaml or applications.json file from which identifiers should be loaded
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct DumpRules {
+    /// JSON file to which the rules added at runtime should be written
+    path: PathBuf,
+}
+
+#[derive(Parser)]
+struct LoadRules {
+    /// JSON file written by dump-rules from which rules should be loaded
+    path: PathBuf,
+}
+
 #[derive(Parser)]
 struct EagerFocus {
     /// Case-sensitive exe identifier
     exe: String,
 }
 
+#[derive(Parser)]
+struct EnterKeybindingMode {
+    /// Name of a mode in keybinding_modes in the static configuration file
+    mode: String,
+}
+
 #[derive(Parser)]
 #[clap(author, about, version = build::CLAP_LONG_VERSION)]
 struct Opts {
+    /// Name of the socket of the komorebi instance to send commands to (default: komorebi.sock)
+    #[clap(long, global = true)]
+    socket_name: Option<String>,
     #[clap(subcommand)]
     subcmd: SubCommand,
 }
@@ -959,7 +1233,7 @@ enum SubCommand {
     #[clap(hide = true)]
     Docgen,
     /// Gather example configurations for a new-user quickstart
-    Quickstart,
+    Quickstart(Quickstart),
     /// Start komorebi.exe as a background process
     Start(Start),
     /// Stop the komorebi.exe process and restore all hidden windows
@@ -982,13 +1256,28 @@ enum SubCommand {
     State,
     /// Show a JSON representation of the current global state
     GlobalState,
+    /// Show a live view of the monitor, workspace, container and window tree, from which windows can
+    /// be focused, moved and closed
+    Inspect,
+    /// Check that komorebi is running and responsive
+    Ping,
     /// Launch the komorebi-gui debugging tool
     Gui,
     /// Show a JSON representation of visible windows
-    VisibleWindows,
+    VisibleWindows(VisibleWindows),
+    /// Show why komorebi does or doesn't manage the foreground window
+    FocusedWindow(FocusedWindow),
     /// Show information about connected monitors
     #[clap(alias = "monitor-info")]
     MonitorInformation,
+    /// Show a JSON representation of the rects the containers of the focused workspace would occupy
+    /// under a layout, without applying it
+    #[clap(arg_required_else_help = true)]
+    PreviewLayout(PreviewLayout),
+    /// Show a JSON representation of the rects the containers of the focused workspace would occupy
+    /// under a custom layout, without applying it
+    #[clap(arg_required_else_help = true)]
+    PreviewLayoutCustom(PreviewLayoutCustom),
     /// Query the current window manager state
     #[clap(arg_required_else_help = true)]
     Query(Query),
@@ -1006,19 +1295,24 @@ enum SubCommand {
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "unsubscribe")]
     UnsubscribePipe(UnsubscribePipe),
+    /// Show the sockets and named pipes currently subscribed to komorebi events
+    ListSubscribers,
     /// Tail komorebi.exe's process logs (cancel with Ctrl-C)
     Log,
+    /// Change the verbosity of komorebi.exe's process logs
+    #[clap(arg_required_else_help = true)]
+    LogLevel(LogLevel),
     /// Quicksave the current resize layout dimensions
     #[clap(alias = "quick-save")]
-    QuickSaveResize,
-    /// Load the last quicksaved resize layout dimensions
+    QuickSaveResize(QuickSaveResize),
+    /// Load the last quicksaved resize layout dimensions, and any other parts of the workspace saved with them
     #[clap(alias = "quick-load")]
     QuickLoadResize,
     /// Save the current resize layout dimensions to a file
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "save")]
     SaveResize(SaveResize),
-    /// Load the resize layout dimensions from a file
+    /// Load the resize layout dimensions, and any other parts of the workspace saved with them, from a file
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "load")]
     LoadResize(LoadResize),
@@ -1043,6 +1337,8 @@ enum SubCommand {
     /// Focus the first managed window matching the given exe
     #[clap(arg_required_else_help = true)]
     EagerFocus(EagerFocus),
+    /// Focus the managed window under the cursor
+    FocusWindowUnderCursor,
     /// Stack the focused window in the specified direction
     #[clap(arg_required_else_help = true)]
     Stack(Stack),
@@ -1071,6 +1367,9 @@ enum SubCommand {
     /// Move the focused window to the specified monitor
     #[clap(arg_required_else_help = true)]
     MoveToMonitor(MoveToMonitor),
+    /// Move the focused window to the monitor with the specified alias
+    #[clap(arg_required_else_help = true)]
+    MoveToMonitorAlias(MoveToMonitorAlias),
     /// Move the focused window to the monitor in the given cycle direction
     #[clap(arg_required_else_help = true)]
     CycleMoveToMonitor(CycleMoveToMonitor),
@@ -1086,6 +1385,9 @@ enum SubCommand {
     /// Send the focused window to the specified monitor
     #[clap(arg_required_else_help = true)]
     SendToMonitor(SendToMonitor),
+    /// Send the focused window to the monitor with the specified alias
+    #[clap(arg_required_else_help = true)]
+    SendToMonitorAlias(SendToMonitorAlias),
     /// Send the focused window to the monitor in the given cycle direction
     #[clap(arg_required_else_help = true)]
     CycleSendToMonitor(CycleSendToMonitor),
@@ -1104,13 +1406,31 @@ enum SubCommand {
     /// Move the focused window to the specified monitor workspace
     #[clap(arg_required_else_help = true)]
     MoveToMonitorWorkspace(MoveToMonitorWorkspace),
+    /// Move the window with the given handle to the specified monitor workspace without focusing it
+    #[clap(arg_required_else_help = true)]
+    MoveWindowToWorkspace(MoveWindowToWorkspace),
     /// Send the focused window to the last focused monitor workspace
     SendToLastWorkspace,
     /// Move the focused window to the last focused monitor workspace
     MoveToLastWorkspace,
+    /// Send the focused window to the specified Windows virtual desktop
+    ///
+    /// Windows only lets a process move its own windows between virtual desktops, so for the
+    /// windows of most applications this returns an error and leaves the window in place
+    #[clap(arg_required_else_help = true)]
+    SendToVirtualDesktop(SendToVirtualDesktop),
+    /// Move the focused window to the specified Windows virtual desktop
+    ///
+    /// Windows only lets a process move its own windows between virtual desktops, so for the
+    /// windows of most applications this returns an error and leaves the window in place
+    #[clap(arg_required_else_help = true)]
+    MoveToVirtualDesktop(MoveToVirtualDesktop),
     /// Focus the specified monitor
     #[clap(arg_required_else_help = true)]
     FocusMonitor(FocusMonitor),
+    /// Focus the monitor with the specified alias
+    #[clap(arg_required_else_help = true)]
+    FocusMonitorAlias(FocusMonitorAlias),
     /// Focus the monitor at the current cursor location
     FocusMonitorAtCursor,
     /// Focus the last focused workspace on the focused monitor
@@ -1118,6 +1438,9 @@ enum SubCommand {
     /// Focus the specified workspace on the focused monitor
     #[clap(arg_required_else_help = true)]
     FocusWorkspace(FocusWorkspace),
+    /// Focus the specified workspace on the focused monitor and move the cursor to it
+    #[clap(arg_required_else_help = true)]
+    FocusWorkspaceWithMouse(FocusWorkspaceWithMouse),
     /// Focus the specified workspace on all monitors
     #[clap(arg_required_else_help = true)]
     FocusWorkspaces(FocusWorkspaces),
@@ -1152,8 +1475,9 @@ enum SubCommand {
     /// Set the resize delta (used by resize-edge and resize-axis)
     #[clap(arg_required_else_help = true)]
     ResizeDelta(ResizeDelta),
-    /// Set the invisible border dimensions around each window
+    /// DEPRECATED since v0.1.22
     #[clap(arg_required_else_help = true)]
+    #[clap(hide = true)]
     InvisibleBorders(InvisibleBorders),
     /// Set offsets to exclude parts of the work area from tiling
     #[clap(arg_required_else_help = true)]
@@ -1185,9 +1509,18 @@ enum SubCommand {
     #[clap(hide = true)]
     #[clap(arg_required_else_help = true)]
     LoadCustomLayout(LoadCustomLayout),
+    /// Save the layout of the focused workspace, including manual resizes, as a custom layout file
+    #[clap(arg_required_else_help = true)]
+    SaveCustomLayout(SaveCustomLayout),
     /// Flip the layout on the focused workspace
     #[clap(arg_required_else_help = true)]
     FlipLayout(FlipLayout),
+    /// Toggle the direction in which the BSP layout splits the focused container
+    ToggleSplitDirection,
+    /// Split the focused container so that the next window is placed beside it in the BSP layout
+    SplitHorizontal,
+    /// Split the focused container so that the next window is placed below it in the BSP layout
+    SplitVertical,
     /// Promote the focused window to the top of the tree
     Promote,
     /// Promote the user focus to the top of the tree
@@ -1196,6 +1529,8 @@ enum SubCommand {
     PromoteWindow(PromoteWindow),
     /// Force the retiling of all managed windows
     Retile,
+    /// Clear all resize adjustments on the focused workspace, returning to the default proportions of its layout
+    BalanceWorkspace,
     /// Set the monitor index preference for a monitor identified using its size
     #[clap(arg_required_else_help = true)]
     MonitorIndexPreference(MonitorIndexPreference),
@@ -1220,6 +1555,18 @@ enum SubCommand {
     /// Set the workspace padding for the specified workspace
     #[clap(arg_required_else_help = true)]
     NamedWorkspacePadding(NamedWorkspacePadding),
+    /// Set the default container padding for workspaces on the specified monitor without their own
+    #[clap(arg_required_else_help = true)]
+    MonitorContainerPadding(MonitorContainerPadding),
+    /// Set the default workspace padding for workspaces on the specified monitor without their own
+    #[clap(arg_required_else_help = true)]
+    MonitorWorkspacePadding(MonitorWorkspacePadding),
+    /// Set the default container padding for workspaces without their own, on monitors without their own default
+    #[clap(arg_required_else_help = true)]
+    DefaultContainerPadding(DefaultContainerPadding),
+    /// Set the default workspace padding for workspaces without their own, on monitors without their own default
+    #[clap(arg_required_else_help = true)]
+    DefaultWorkspacePadding(DefaultWorkspacePadding),
     /// Set the layout for the specified workspace
     #[clap(arg_required_else_help = true)]
     WorkspaceLayout(WorkspaceLayout),
@@ -1279,10 +1626,18 @@ enum SubCommand {
     ToggleWorkspaceLayer,
     /// Toggle window tiling on the focused workspace
     TogglePause,
+    /// Hand all windows back to Windows where they were before they were tiled, or take them back
+    /// and retile them
+    ToggleSoftStop,
+    /// Show every window on the workspaces of the focused monitor in a grid of live thumbnails to
+    /// pick one to focus, or close the overview if it is already shown
+    Overview,
     /// Toggle window tiling on the focused workspace
     ToggleTiling,
     /// Toggle floating mode for the focused window
     ToggleFloat,
+    /// Float every window on the focused workspace where its tile was, or tile them again
+    ToggleWorkspaceFloat,
     /// Toggle monocle mode for the focused container
     ToggleMonocle,
     /// Toggle native maximization for the focused window
@@ -1291,15 +1646,32 @@ enum SubCommand {
     ToggleLock,
     /// Restore all hidden windows (debugging command)
     RestoreWindows,
-    /// Force komorebi to manage the focused window
-    Manage,
-    /// Unmanage a window that was forcibly managed
-    Unmanage,
+    /// Force komorebi to manage the focused window, or the window with the given handle
+    Manage(Manage),
+    /// Unmanage the focused window, or the window with the given handle
+    Unmanage(Unmanage),
     /// Replace the configuration of a running instance of komorebi from a static configuration file
     #[clap(arg_required_else_help = true)]
     ReplaceConfiguration(ReplaceConfiguration),
+    /// Apply all identifiers from an application-specific configuration file to a running instance of komorebi
+    #[clap(arg_required_else_help = true)]
+    #[clap(alias = "apply-asc")]
+    ApplyAppSpecificConfiguration(ApplyAppSpecificConfiguration),
+    /// Write every ignore, manage, identifier and workspace rule of a running instance of komorebi to a file
+    #[clap(arg_required_else_help = true)]
+    DumpRules(DumpRules),
+    /// Add every rule from a file written by dump-rules to a running instance of komorebi
+    #[clap(arg_required_else_help = true)]
+    LoadRules(LoadRules),
     /// Reload legacy komorebi.ahk or komorebi.ps1 configurations (if they exist)
     ReloadConfiguration,
+    /// Reload the keybindings from the static configuration file
+    ReloadBindings,
+    /// Enter a keybinding mode from the static configuration file until Escape is pressed
+    #[clap(arg_required_else_help = true)]
+    EnterKeybindingMode(EnterKeybindingMode),
+    /// Leave the active keybinding mode
+    ExitKeybindingMode,
     /// Enable or disable watching of legacy komorebi.ahk or komorebi.ps1 configurations (if they exist)
     #[clap(arg_required_else_help = true)]
     WatchConfiguration(WatchConfiguration),
@@ -1312,11 +1684,20 @@ enum SubCommand {
     /// Set the window behaviour when switching workspaces / cycling stacks
     #[clap(arg_required_else_help = true)]
     WindowHidingBehaviour(WindowHidingBehaviour),
+    /// Set where floating windows are kept in the z-order relative to tiled windows
+    #[clap(arg_required_else_help = true)]
+    FloatingWindowZOrder(FloatingWindowZOrder),
     /// Set the behaviour when moving windows across monitor boundaries
     #[clap(arg_required_else_help = true)]
     CrossMonitorMoveBehaviour(CrossMonitorMoveBehaviour),
     /// Toggle the behaviour when moving windows across monitor boundaries
     ToggleCrossMonitorMoveBehaviour,
+    /// Set the behaviour when a new window opens while a monocle container is active
+    #[clap(arg_required_else_help = true)]
+    MonocleNewWindowBehaviour(MonocleNewWindowBehaviour),
+    /// Set which windows directional focus commands can reach
+    #[clap(arg_required_else_help = true)]
+    DirectionalFocusBehaviour(DirectionalFocusBehaviour),
     /// Set the operation behaviour when the focused window is not managed
     #[clap(arg_required_else_help = true)]
     UnmanagedWindowOperationBehaviour(UnmanagedWindowOperationBehaviour),
@@ -1349,12 +1730,21 @@ enum SubCommand {
     ClearAllWorkspaceRules,
     /// Enforce all workspace rules, including initial workspace rules that have already been applied
     EnforceWorkspaceRules,
+    /// Run a command and place the first window of the launched application on a workspace
+    #[clap(arg_required_else_help = true)]
+    Exec(Exec),
+    /// Wait until a window of the specified application is managed, failing after a timeout
+    #[clap(arg_required_else_help = true)]
+    WaitForWindow(WaitForWindow),
     /// Identify an application that sends EVENT_OBJECT_NAMECHANGE on launch
     #[clap(arg_required_else_help = true)]
     IdentifyObjectNameChangeApplication(IdentifyObjectNameChangeApplication),
     /// Identify an application that closes to the system tray
     #[clap(arg_required_else_help = true)]
     IdentifyTrayApplication(IdentifyTrayApplication),
+    /// Identify an application whose windows should all be stacked in the same container
+    #[clap(arg_required_else_help = true)]
+    IdentifyStackApplication(IdentifyStackApplication),
     /// Identify an application that has WS_EX_LAYERED, but should still be managed
     #[clap(arg_required_else_help = true)]
     IdentifyLayeredApplication(IdentifyLayeredApplication),
@@ -1363,10 +1753,21 @@ enum SubCommand {
     RemoveTitleBar(RemoveTitleBar),
     /// Toggle title bars for whitelisted applications
     ToggleTitleBars,
-    /// Identify an application that has overflowing borders
-    #[clap(hide = true)]
+    /// Identify an application whose windows may be shared on screen and should not be hidden in
+    /// presentation mode
+    #[clap(arg_required_else_help = true)]
+    IdentifyPresentationApplication(IdentifyPresentationApplication),
+    /// Toggle lowering the windows of presentation applications instead of hiding them on
+    /// workspace changes
+    TogglePresentationMode,
+    /// Identify an application whose invisible borders should be measured every time it is positioned
+    #[clap(arg_required_else_help = true)]
     #[clap(alias = "identify-border-overflow")]
     IdentifyBorderOverflowApplication(IdentifyBorderOverflowApplication),
+    /// Identify an application whose invisible borders should be compensated for with an explicit
+    /// offset for each edge
+    #[clap(arg_required_else_help = true)]
+    IdentifyBorderOverflowWithOffset(IdentifyBorderOverflowWithOffset),
     /// Enable or disable borders
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "active-window-border")]
@@ -1420,11 +1821,35 @@ enum SubCommand {
     #[clap(hide = true)]
     #[clap(arg_required_else_help = true)]
     ToggleFocusFollowsMouse(ToggleFocusFollowsMouse),
+    /// Set a delay before the komorebi implementation of focus follows mouse raises a window
+    #[clap(hide = true)]
+    #[clap(arg_required_else_help = true)]
+    FocusFollowsMouseHoverDelay(FocusFollowsMouseHoverDelay),
+    /// Exclude a screen region from triggering the komorebi implementation of focus follows mouse
+    #[clap(hide = true)]
+    #[clap(arg_required_else_help = true)]
+    FocusFollowsMouseExclusionZone(FocusFollowsMouseExclusionZone),
+    /// Exclude an application from triggering the komorebi implementation of focus follows mouse
+    #[clap(hide = true)]
+    #[clap(arg_required_else_help = true)]
+    FocusFollowsMouseIgnoreRule(FocusFollowsMouseIgnoreRule),
+    /// Clear all focus follows mouse exclusion zones and ignore rules
+    #[clap(hide = true)]
+    ClearFocusFollowsMouseExclusions,
     /// Enable or disable mouse follows focus on all workspaces
     #[clap(arg_required_else_help = true)]
     MouseFollowsFocus(MouseFollowsFocus),
     /// Toggle mouse follows focus on all workspaces
     ToggleMouseFollowsFocus,
+    /// Enable or disable independent workspaces for each Windows virtual desktop
+    #[clap(arg_required_else_help = true)]
+    VirtualDesktopAwareness(VirtualDesktopAwareness),
+    /// Enable or disable stacking new windows into the container of an existing window of the same application
+    #[clap(arg_required_else_help = true)]
+    StackBySameExe(StackBySameExe),
+    /// Set the maximum number of windows in a container, after which new windows get their own container
+    #[clap(arg_required_else_help = true)]
+    MaxContainerWindows(MaxContainerWindows),
     /// Generate common app-specific configurations and fixes to use in komorebi.ahk
     #[clap(arg_required_else_help = true)]
     #[clap(alias = "ahk-asc")]
@@ -1450,7 +1875,7 @@ enum SubCommand {
     ApplicationSpecificConfigurationSchema,
     /// Generate a JSON Schema of subscription notifications
     NotificationSchema,
-    /// Generate a JSON Schema of socket messages
+    /// Generate versioned JSON Schemas of socket messages, notifications and the window manager state
     SocketSchema,
     /// Generate a JSON Schema of the static configuration file
     StaticConfigSchema,
@@ -1460,17 +1885,152 @@ enum SubCommand {
     EnableAutostart(EnableAutostart),
     /// Deletes the komorebi.lnk shortcut in shell:startup to disable autostart
     DisableAutostart,
+    /// Generate shell completions, or a PowerShell module with a function wrapping each subcommand
+    Completions(Completions),
 }
 
 // print_query is a helper that queries komorebi and prints the response.
-// panics on error.
+// panics on error, and exits with a non-zero code if komorebi responds with an error payload.
 fn print_query(message: &SocketMessage) {
     match send_query(message) {
-        Ok(response) => println!("{response}"),
+        Ok(response) => {
+            if let Ok(SocketResponse::Error { message }) =
+                serde_json::from_str::<SocketResponse>(&response)
+            {
+                eprintln!("Error: {message}");
+                std::process::exit(1);
+            }
+
+            println!("{response}")
+        }
         Err(error) => panic!("{}", error),
     }
 }
 
+const POWERSHELL_MODULE_HEADER: &str = r#"# Generated by 'komorebic completions powershell --module'
+
+function Get-KomorebiWorkspaceName {
+    param($WordToComplete)
+
+    $state = komorebic state | ConvertFrom-Json
+    $state.monitors.elements.workspaces.elements.name |
+        Where-Object { $_ -and $_ -like "$WordToComplete*" }
+}
+"#;
+
+/// Positional arguments which take the name of a workspace are completed with the names of the
+/// workspaces known to the running instance of komorebi in the PowerShell module
+fn takes_workspace_name(arg: &Arg) -> bool {
+    arg.get_id() == "workspace"
+        && arg
+            .get_help()
+            .is_some_and(|help| help.to_string().to_lowercase().contains("name"))
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+/// Generates a PowerShell module with an Invoke-Komorebic* function for every subcommand. The
+/// positional arguments of a subcommand become parameters of its function, and anything else is
+/// passed through to komorebic as-is.
+fn powershell_module(cli: &clap::Command) -> String {
+    let mut module = String::from(POWERSHELL_MODULE_HEADER);
+
+    for cmd in cli.get_subcommands().filter(|cmd| !cmd.is_hide_set()) {
+        let name = cmd.get_name();
+        let synopsis = cmd
+            .get_about()
+            .map(|about| about.to_string())
+            .unwrap_or_default();
+
+        let mut params = vec![];
+        let mut arguments = vec![];
+
+        for arg in cmd.get_positionals().filter(|arg| !arg.is_hide_set()) {
+            let param = format!("${}", pascal_case(arg.get_id().as_str()));
+
+            if takes_workspace_name(arg) {
+                params.push(format!(
+                    "        [ArgumentCompleter({{ param($c, $p, $word) Get-KomorebiWorkspaceName $word }})]\n        {param}"
+                ));
+            } else {
+                params.push(format!("        {param}"));
+            }
+
+            arguments.push(param);
+        }
+
+        arguments.push(String::from("@args"));
+
+        let param_block = if params.is_empty() {
+            String::new()
+        } else {
+            format!("    param(\n{}\n    )\n\n", params.join(",\n"))
+        };
+
+        module.push_str(&format!(
+            "\nfunction Invoke-Komorebic{function} {{\n    <#\n    .SYNOPSIS\n    {synopsis}\n    #>\n{param_block}    komorebic {name} {arguments}\n}}\n",
+            function = pascal_case(name),
+            arguments = arguments.join(" "),
+        ));
+    }
+
+    module
+        .push_str("\nExport-ModuleMember -Function Get-KomorebiWorkspaceName, Invoke-Komorebic*\n");
+
+    module
+}
+
+/// Keys in a static configuration file which don't match any option, and which would otherwise be
+/// silently ignored when the configuration is loaded
+fn unknown_config_keys(config_source: &str) -> Vec<String> {
+    let mut unknown = vec![];
+    let mut deserializer = serde_json::Deserializer::from_str(config_source);
+    let _ = serde_ignored::deserialize::<_, _, StaticConfig>(&mut deserializer, |path| {
+        unknown.push(path.to_string());
+    });
+
+    unknown
+}
+
+/// Collects the identifiers of matching rules anywhere in `value` which use the Regex matching
+/// strategy but can't be compiled, along with the reason why
+fn invalid_regex_identifiers(value: &serde_json::Value, invalid: &mut Vec<(String, String)>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if map
+                .get("matching_strategy")
+                .and_then(serde_json::Value::as_str)
+                == Some("Regex")
+            {
+                if let Some(id) = map.get("id").and_then(serde_json::Value::as_str) {
+                    if let Err(error) = Regex::new(id) {
+                        invalid.push((id.to_string(), error.to_string()));
+                    }
+                }
+            }
+
+            for value in map.values() {
+                invalid_regex_identifiers(value, invalid);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                invalid_regex_identifiers(value, invalid);
+            }
+        }
+        _ => {}
+    }
+}
+
 fn startup_dir() -> Result<PathBuf> {
     let startup = dirs::home_dir()
         .expect("unable to obtain user's home folder")
@@ -1493,6 +2053,10 @@ fn startup_dir() -> Result<PathBuf> {
 fn main() -> Result<()> {
     let opts: Opts = Opts::parse();
 
+    if let Some(socket_name) = &opts.socket_name {
+        komorebi_client::set_socket_name(socket_name);
+    }
+
     match opts.subcmd {
         SubCommand::Docgen => {
             let mut cli = Opts::command();
@@ -1502,7 +2066,6 @@ fn main() -> Result<()> {
             let ignore = [
                 "docgen",
                 "alt-focus-hack",
-                "identify-border-overflow-application",
                 "load-custom-layout",
                 "workspace-custom-layout",
                 "named-workspace-custom-layout",
@@ -1524,7 +2087,7 @@ fn main() -> Result<()> {
                 }
             }
         }
-        SubCommand::Quickstart => {
+        SubCommand::Quickstart(args) => {
             let local_appdata_dir = data_local_dir().expect("could not find localdata dir");
             let data_dir = local_appdata_dir.join("komorebi");
             std::fs::create_dir_all(&*WHKD_CONFIG_DIR)?;
@@ -1546,11 +2109,16 @@ fn main() -> Result<()> {
             let applications_json = include_str!("../applications.json");
             std::fs::write(HOME_DIR.join("applications.json"), applications_json)?;
 
-            let whkdrc = include_str!("../../docs/whkdrc.sample");
-            std::fs::write(WHKD_CONFIG_DIR.join("whkdrc"), whkdrc)?;
+            if args.whkd {
+                let whkdrc = include_str!("../../docs/whkdrc.sample");
+                std::fs::write(WHKD_CONFIG_DIR.join("whkdrc"), whkdrc)?;
 
-            println!("Example komorebi.json, komorebi.bar.json, whkdrc and latest applications.json files created");
-            println!("You can now run komorebic start --whkd --bar");
+                println!("Example komorebi.json, komorebi.bar.json, whkdrc and latest applications.json files created");
+                println!("You can now run komorebic start --whkd --bar");
+            } else {
+                println!("Example komorebi.json, komorebi.bar.json and latest applications.json files created");
+                println!("You can now run komorebic start --bar");
+            }
         }
         SubCommand::EnableAutostart(args) => {
             let mut current_exe = std::env::current_exe().expect("unable to get exec path");
@@ -1681,6 +2249,18 @@ fn main() -> Result<()> {
                 // errors
                 let _ = serde_json::from_str::<StaticConfig>(&config_source)?;
 
+                for key in unknown_config_keys(&config_source) {
+                    println!("Unknown key '{key}' will be ignored; check for typos or options which have been removed\n");
+                }
+
+                if let Ok(parsed_config) = &parsed_config {
+                    let mut invalid = vec![];
+                    invalid_regex_identifiers(parsed_config, &mut invalid);
+                    for (id, error) in invalid {
+                        println!("Identifier '{id}' has the Regex matching strategy but is not a valid regular expression: {error}\n");
+                    }
+                }
+
                 let path = resolve_home_path(static_config)?;
                 let raw = std::fs::read_to_string(path)?;
                 StaticConfig::aliases(&raw);
@@ -1709,6 +2289,20 @@ fn main() -> Result<()> {
                 println!("If running 'komorebic start --await-configuration', you will manually have to call the following command to begin tiling: komorebic complete-configuration\n");
             }
 
+            let socket = komorebi_client::socket_path();
+            if !DATA_DIR.is_dir() {
+                println!("The data directory {} does not exist yet; it will be created when komorebi is started\n", DATA_DIR.display());
+            } else if send_query(&SocketMessage::Ping).is_ok() {
+                println!(
+                    "komorebi is running and listening on {}\n",
+                    socket.display()
+                );
+            } else if socket.exists() {
+                println!("{} exists but komorebi is not responding to commands on it; if komorebi is not running, this socket was left behind by a previous instance and will be replaced when it is started\n", socket.display());
+            } else {
+                println!("komorebi is not running; it will listen for commands on {} once it is started\n", socket.display());
+            }
+
             let client = reqwest::blocking::Client::new();
 
             if let Ok(response) = client
@@ -1755,8 +2349,20 @@ fn main() -> Result<()> {
             }
         }
         SubCommand::Log => {
-            let timestamp = Utc::now().format("%Y-%m-%d").to_string();
-            let color_log = std::env::temp_dir().join(format!("komorebi.log.{timestamp}"));
+            // The name of the current log file depends on the rotation that
+            // komorebi was started with, so just follow the most recent one
+            let color_log = std::fs::read_dir(komorebi_client::log_dir())?
+                .flatten()
+                .filter(|entry| {
+                    entry
+                        .file_name()
+                        .to_string_lossy()
+                        .starts_with("komorebi.log")
+                })
+                .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+                .ok_or_else(|| anyhow!("could not find a komorebi log file"))?
+                .path();
+
             let file = TailedFile::new(File::open(color_log)?);
             let locked = file.lock();
             #[allow(clippy::significant_drop_in_scrutinee, clippy::lines_filter_map_ok)]
@@ -1764,114 +2370,152 @@ fn main() -> Result<()> {
                 println!("{line}");
             }
         }
+        SubCommand::LogLevel(arg) => {
+            send_message_and_wait(&SocketMessage::LogLevel(arg.level))?;
+        }
         SubCommand::Focus(arg) => {
-            send_message(&SocketMessage::FocusWindow(arg.operation_direction))?;
+            send_message_and_wait(&SocketMessage::FocusWindow(arg.operation_direction))?;
         }
         SubCommand::ForceFocus => {
-            send_message(&SocketMessage::ForceFocus)?;
+            send_message_and_wait(&SocketMessage::ForceFocus)?;
         }
         SubCommand::Close => {
-            send_message(&SocketMessage::Close)?;
+            send_message_and_wait(&SocketMessage::Close)?;
         }
         SubCommand::Minimize => {
-            send_message(&SocketMessage::Minimize)?;
+            send_message_and_wait(&SocketMessage::Minimize)?;
         }
         SubCommand::Promote => {
-            send_message(&SocketMessage::Promote)?;
+            send_message_and_wait(&SocketMessage::Promote)?;
         }
         SubCommand::PromoteFocus => {
-            send_message(&SocketMessage::PromoteFocus)?;
+            send_message_and_wait(&SocketMessage::PromoteFocus)?;
         }
         SubCommand::PromoteWindow(arg) => {
-            send_message(&SocketMessage::PromoteWindow(arg.operation_direction))?;
+            send_message_and_wait(&SocketMessage::PromoteWindow(arg.operation_direction))?;
         }
         SubCommand::TogglePause => {
-            send_message(&SocketMessage::TogglePause)?;
+            send_message_and_wait(&SocketMessage::TogglePause)?;
+        }
+        SubCommand::ToggleSoftStop => {
+            send_message_and_wait(&SocketMessage::ToggleSoftStop)?;
+        }
+        SubCommand::Overview => {
+            send_message_and_wait(&SocketMessage::Overview)?;
         }
         SubCommand::Retile => {
-            send_message(&SocketMessage::Retile)?;
+            send_message_and_wait(&SocketMessage::Retile)?;
+        }
+        SubCommand::BalanceWorkspace => {
+            send_message_and_wait(&SocketMessage::BalanceWorkspace)?;
         }
         SubCommand::Move(arg) => {
-            send_message(&SocketMessage::MoveWindow(arg.operation_direction))?;
+            send_message_and_wait(&SocketMessage::MoveWindow(arg.operation_direction))?;
         }
         SubCommand::CycleFocus(arg) => {
-            send_message(&SocketMessage::CycleFocusWindow(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleFocusWindow(arg.cycle_direction))?;
         }
         SubCommand::CycleMove(arg) => {
-            send_message(&SocketMessage::CycleMoveWindow(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleMoveWindow(arg.cycle_direction))?;
+        }
+        SubCommand::FocusWindowUnderCursor => {
+            send_message_and_wait(&SocketMessage::FocusWindowUnderCursor)?;
         }
         SubCommand::EagerFocus(arg) => {
-            send_message(&SocketMessage::EagerFocus(arg.exe))?;
+            send_message_and_wait(&SocketMessage::EagerFocus(arg.exe))?;
         }
         SubCommand::MoveToMonitor(arg) => {
-            send_message(&SocketMessage::MoveContainerToMonitorNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::MoveContainerToMonitorNumber(arg.target))?;
+        }
+        SubCommand::MoveToMonitorAlias(arg) => {
+            send_message_and_wait(&SocketMessage::MoveContainerToMonitorAlias(arg.alias))?;
         }
         SubCommand::CycleMoveToMonitor(arg) => {
-            send_message(&SocketMessage::CycleMoveContainerToMonitor(
+            send_message_and_wait(&SocketMessage::CycleMoveContainerToMonitor(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::MoveToWorkspace(arg) => {
-            send_message(&SocketMessage::MoveContainerToWorkspaceNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::MoveContainerToWorkspaceNumber(arg.target))?;
         }
         SubCommand::MoveToNamedWorkspace(arg) => {
-            send_message(&SocketMessage::MoveContainerToNamedWorkspace(arg.workspace))?;
+            send_message_and_wait(&SocketMessage::MoveContainerToNamedWorkspace(arg.workspace))?;
         }
         SubCommand::CycleMoveToWorkspace(arg) => {
-            send_message(&SocketMessage::CycleMoveContainerToWorkspace(
+            send_message_and_wait(&SocketMessage::CycleMoveContainerToWorkspace(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::SendToMonitor(arg) => {
-            send_message(&SocketMessage::SendContainerToMonitorNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::SendContainerToMonitorNumber(arg.target))?;
+        }
+        SubCommand::SendToMonitorAlias(arg) => {
+            send_message_and_wait(&SocketMessage::SendContainerToMonitorAlias(arg.alias))?;
         }
         SubCommand::CycleSendToMonitor(arg) => {
-            send_message(&SocketMessage::CycleSendContainerToMonitor(
+            send_message_and_wait(&SocketMessage::CycleSendContainerToMonitor(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::SendToWorkspace(arg) => {
-            send_message(&SocketMessage::SendContainerToWorkspaceNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::SendContainerToWorkspaceNumber(arg.target))?;
         }
         SubCommand::SendToNamedWorkspace(arg) => {
-            send_message(&SocketMessage::SendContainerToNamedWorkspace(arg.workspace))?;
+            send_message_and_wait(&SocketMessage::SendContainerToNamedWorkspace(arg.workspace))?;
         }
         SubCommand::CycleSendToWorkspace(arg) => {
-            send_message(&SocketMessage::CycleSendContainerToWorkspace(
+            send_message_and_wait(&SocketMessage::CycleSendContainerToWorkspace(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::SendToMonitorWorkspace(arg) => {
-            send_message(&SocketMessage::SendContainerToMonitorWorkspaceNumber(
+            send_message_and_wait(&SocketMessage::SendContainerToMonitorWorkspaceNumber(
                 arg.target_monitor,
                 arg.target_workspace,
             ))?;
         }
         SubCommand::MoveToMonitorWorkspace(arg) => {
-            send_message(&SocketMessage::MoveContainerToMonitorWorkspaceNumber(
+            send_message_and_wait(&SocketMessage::MoveContainerToMonitorWorkspaceNumber(
+                arg.target_monitor,
+                arg.target_workspace,
+            ))?;
+        }
+        SubCommand::MoveWindowToWorkspace(arg) => {
+            send_message_and_wait(&SocketMessage::MoveWindowToWorkspace(
+                arg.hwnd,
                 arg.target_monitor,
                 arg.target_workspace,
             ))?;
         }
         SubCommand::MoveWorkspaceToMonitor(arg) => {
-            send_message(&SocketMessage::MoveWorkspaceToMonitorNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::MoveWorkspaceToMonitorNumber(arg.target))?;
         }
         SubCommand::CycleMoveWorkspaceToMonitor(arg) => {
-            send_message(&SocketMessage::CycleMoveWorkspaceToMonitor(
+            send_message_and_wait(&SocketMessage::CycleMoveWorkspaceToMonitor(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::MoveToLastWorkspace => {
-            send_message(&SocketMessage::MoveContainerToLastWorkspace)?;
+            send_message_and_wait(&SocketMessage::MoveContainerToLastWorkspace)?;
+        }
+        SubCommand::SendToVirtualDesktop(arg) => {
+            send_message_and_wait(&SocketMessage::SendContainerToVirtualDesktopNumber(
+                arg.target,
+            ))?;
+        }
+        SubCommand::MoveToVirtualDesktop(arg) => {
+            send_message_and_wait(&SocketMessage::MoveContainerToVirtualDesktopNumber(
+                arg.target,
+            ))?;
         }
         SubCommand::SendToLastWorkspace => {
-            send_message(&SocketMessage::SendContainerToLastWorkspace)?;
+            send_message_and_wait(&SocketMessage::SendContainerToLastWorkspace)?;
         }
         SubCommand::SwapWorkspacesWithMonitor(arg) => {
-            send_message(&SocketMessage::SwapWorkspacesToMonitorNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::SwapWorkspacesToMonitorNumber(arg.target))?;
         }
         SubCommand::InvisibleBorders(arg) => {
-            send_message(&SocketMessage::InvisibleBorders(Rect {
+            send_message_and_wait(&SocketMessage::InvisibleBorders(Rect {
                 left: arg.left,
                 top: arg.top,
                 right: arg.right,
@@ -1879,7 +2523,7 @@ fn main() -> Result<()> {
             }))?;
         }
         SubCommand::MonitorWorkAreaOffset(arg) => {
-            send_message(&SocketMessage::MonitorWorkAreaOffset(
+            send_message_and_wait(&SocketMessage::MonitorWorkAreaOffset(
                 arg.monitor,
                 Rect {
                     left: arg.left,
@@ -1890,7 +2534,7 @@ fn main() -> Result<()> {
             ))?;
         }
         SubCommand::GlobalWorkAreaOffset(arg) => {
-            send_message(&SocketMessage::WorkAreaOffset(Rect {
+            send_message_and_wait(&SocketMessage::WorkAreaOffset(Rect {
                 left: arg.left,
                 top: arg.top,
                 right: arg.right,
@@ -1898,98 +2542,139 @@ fn main() -> Result<()> {
             }))?;
         }
         SubCommand::ToggleWindowBasedWorkAreaOffset => {
-            send_message(&SocketMessage::ToggleWindowBasedWorkAreaOffset)?;
+            send_message_and_wait(&SocketMessage::ToggleWindowBasedWorkAreaOffset)?;
         }
         SubCommand::ContainerPadding(arg) => {
-            send_message(&SocketMessage::ContainerPadding(
+            send_message_and_wait(&SocketMessage::ContainerPadding(
                 arg.monitor,
                 arg.workspace,
                 arg.size,
             ))?;
         }
         SubCommand::NamedWorkspaceContainerPadding(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceContainerPadding(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceContainerPadding(
                 arg.workspace,
                 arg.size,
             ))?;
         }
         SubCommand::WorkspacePadding(arg) => {
-            send_message(&SocketMessage::WorkspacePadding(
+            send_message_and_wait(&SocketMessage::WorkspacePadding(
                 arg.monitor,
                 arg.workspace,
                 arg.size,
             ))?;
         }
         SubCommand::NamedWorkspacePadding(arg) => {
-            send_message(&SocketMessage::NamedWorkspacePadding(
+            send_message_and_wait(&SocketMessage::NamedWorkspacePadding(
                 arg.workspace,
                 arg.size,
             ))?;
         }
+        SubCommand::MonitorContainerPadding(arg) => {
+            send_message_and_wait(&SocketMessage::MonitorContainerPadding(
+                arg.monitor,
+                arg.size,
+            ))?;
+        }
+        SubCommand::MonitorWorkspacePadding(arg) => {
+            send_message_and_wait(&SocketMessage::MonitorWorkspacePadding(
+                arg.monitor,
+                arg.size,
+            ))?;
+        }
+        SubCommand::DefaultContainerPadding(arg) => {
+            send_message_and_wait(&SocketMessage::DefaultContainerPadding(arg.size))?;
+        }
+        SubCommand::DefaultWorkspacePadding(arg) => {
+            send_message_and_wait(&SocketMessage::DefaultWorkspacePadding(arg.size))?;
+        }
         SubCommand::FocusedWorkspacePadding(arg) => {
-            send_message(&SocketMessage::FocusedWorkspacePadding(arg.size))?;
+            send_message_and_wait(&SocketMessage::FocusedWorkspacePadding(arg.size))?;
         }
         SubCommand::FocusedWorkspaceContainerPadding(arg) => {
-            send_message(&SocketMessage::FocusedWorkspaceContainerPadding(arg.size))?;
+            send_message_and_wait(&SocketMessage::FocusedWorkspaceContainerPadding(arg.size))?;
         }
         SubCommand::AdjustWorkspacePadding(arg) => {
-            send_message(&SocketMessage::AdjustWorkspacePadding(
+            send_message_and_wait(&SocketMessage::AdjustWorkspacePadding(
                 arg.sizing,
                 arg.adjustment,
             ))?;
         }
         SubCommand::AdjustContainerPadding(arg) => {
-            send_message(&SocketMessage::AdjustContainerPadding(
+            send_message_and_wait(&SocketMessage::AdjustContainerPadding(
                 arg.sizing,
                 arg.adjustment,
             ))?;
         }
         SubCommand::ToggleFocusFollowsMouse(arg) => {
-            send_message(&SocketMessage::ToggleFocusFollowsMouse(arg.implementation))?;
+            send_message_and_wait(&SocketMessage::ToggleFocusFollowsMouse(arg.implementation))?;
+        }
+        SubCommand::FocusFollowsMouseHoverDelay(arg) => {
+            send_message_and_wait(&SocketMessage::FocusFollowsMouseHoverDelay(arg.delay))?;
+        }
+        SubCommand::FocusFollowsMouseExclusionZone(arg) => {
+            send_message_and_wait(&SocketMessage::FocusFollowsMouseExclusionZone(Rect {
+                left: arg.left,
+                top: arg.top,
+                right: arg.right,
+                bottom: arg.bottom,
+            }))?;
+        }
+        SubCommand::FocusFollowsMouseIgnoreRule(arg) => {
+            send_message_and_wait(&SocketMessage::FocusFollowsMouseIgnoreRule(
+                arg.identifier,
+                arg.id,
+            ))?;
+        }
+        SubCommand::ClearFocusFollowsMouseExclusions => {
+            send_message_and_wait(&SocketMessage::ClearFocusFollowsMouseExclusions)?;
         }
         SubCommand::ToggleTiling => {
-            send_message(&SocketMessage::ToggleTiling)?;
+            send_message_and_wait(&SocketMessage::ToggleTiling)?;
         }
         SubCommand::ToggleFloat => {
-            send_message(&SocketMessage::ToggleFloat)?;
+            send_message_and_wait(&SocketMessage::ToggleFloat)?;
+        }
+        SubCommand::ToggleWorkspaceFloat => {
+            send_message_and_wait(&SocketMessage::ToggleWorkspaceFloat)?;
         }
         SubCommand::ToggleMonocle => {
-            send_message(&SocketMessage::ToggleMonocle)?;
+            send_message_and_wait(&SocketMessage::ToggleMonocle)?;
         }
         SubCommand::ToggleMaximize => {
-            send_message(&SocketMessage::ToggleMaximize)?;
+            send_message_and_wait(&SocketMessage::ToggleMaximize)?;
         }
         SubCommand::ToggleLock => {
-            send_message(&SocketMessage::ToggleLock)?;
+            send_message_and_wait(&SocketMessage::ToggleLock)?;
         }
         SubCommand::WorkspaceLayout(arg) => {
-            send_message(&SocketMessage::WorkspaceLayout(
+            send_message_and_wait(&SocketMessage::WorkspaceLayout(
                 arg.monitor,
                 arg.workspace,
                 arg.value,
             ))?;
         }
         SubCommand::NamedWorkspaceLayout(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceLayout(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceLayout(
                 arg.workspace,
                 arg.value,
             ))?;
         }
         SubCommand::WorkspaceCustomLayout(arg) => {
-            send_message(&SocketMessage::WorkspaceLayoutCustom(
+            send_message_and_wait(&SocketMessage::WorkspaceLayoutCustom(
                 arg.monitor,
                 arg.workspace,
                 resolve_home_path(arg.path)?,
             ))?;
         }
         SubCommand::NamedWorkspaceCustomLayout(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceLayoutCustom(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceLayoutCustom(
                 arg.workspace,
                 resolve_home_path(arg.path)?,
             ))?;
         }
         SubCommand::WorkspaceLayoutRule(arg) => {
-            send_message(&SocketMessage::WorkspaceLayoutRule(
+            send_message_and_wait(&SocketMessage::WorkspaceLayoutRule(
                 arg.monitor,
                 arg.workspace,
                 arg.at_container_count,
@@ -1997,14 +2682,14 @@ fn main() -> Result<()> {
             ))?;
         }
         SubCommand::NamedWorkspaceLayoutRule(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceLayoutRule(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceLayoutRule(
                 arg.workspace,
                 arg.at_container_count,
                 arg.layout,
             ))?;
         }
         SubCommand::WorkspaceCustomLayoutRule(arg) => {
-            send_message(&SocketMessage::WorkspaceLayoutCustomRule(
+            send_message_and_wait(&SocketMessage::WorkspaceLayoutCustomRule(
                 arg.monitor,
                 arg.workspace,
                 arg.at_container_count,
@@ -2012,32 +2697,32 @@ fn main() -> Result<()> {
             ))?;
         }
         SubCommand::NamedWorkspaceCustomLayoutRule(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceLayoutCustomRule(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceLayoutCustomRule(
                 arg.workspace,
                 arg.at_container_count,
                 resolve_home_path(arg.path)?,
             ))?;
         }
         SubCommand::ClearWorkspaceLayoutRules(arg) => {
-            send_message(&SocketMessage::ClearWorkspaceLayoutRules(
+            send_message_and_wait(&SocketMessage::ClearWorkspaceLayoutRules(
                 arg.monitor,
                 arg.workspace,
             ))?;
         }
         SubCommand::ClearNamedWorkspaceLayoutRules(arg) => {
-            send_message(&SocketMessage::ClearNamedWorkspaceLayoutRules(
+            send_message_and_wait(&SocketMessage::ClearNamedWorkspaceLayoutRules(
                 arg.workspace,
             ))?;
         }
         SubCommand::WorkspaceTiling(arg) => {
-            send_message(&SocketMessage::WorkspaceTiling(
+            send_message_and_wait(&SocketMessage::WorkspaceTiling(
                 arg.monitor,
                 arg.workspace,
                 arg.value.into(),
             ))?;
         }
         SubCommand::NamedWorkspaceTiling(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceTiling(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceTiling(
                 arg.workspace,
                 arg.value.into(),
             ))?;
@@ -2113,6 +2798,38 @@ fn main() -> Result<()> {
                 flags.push("'--clean-state'".to_string());
             }
 
+            if let Some(rotation) = arg.log_rotation {
+                flags.push(format!(
+                    "'--log-rotation={}'",
+                    rotation.to_string().to_lowercase()
+                ));
+            }
+
+            if let Some(retention) = arg.log_retention {
+                flags.push(format!("'--log-retention={retention}'"));
+            }
+
+            if let Some(max_size) = arg.log_max_size {
+                flags.push(format!("'--log-max-size={max_size}'"));
+            }
+
+            if let Some(socket_name) = &opts.socket_name {
+                flags.push(format!("'--socket-name={socket_name}'"));
+            }
+
+            // Another instance may already be running when starting one with its own socket name,
+            // so whether the new instance has started is checked by pinging its socket instead
+            let is_running = |system: &sysinfo::System| {
+                if opts.socket_name.is_some() {
+                    send_query(&SocketMessage::Ping).is_ok()
+                } else {
+                    system
+                        .processes_by_name("komorebi.exe".as_ref())
+                        .next()
+                        .is_some()
+                }
+            };
+
             let script = if flags.is_empty() {
                 format!(
                     "Start-Process '{}' -WindowStyle hidden",
@@ -2130,10 +2847,7 @@ fn main() -> Result<()> {
             system.refresh_processes(ProcessesToUpdate::All, true);
 
             let mut attempts = 0;
-            let mut running = system
-                .processes_by_name("komorebi.exe".as_ref())
-                .next()
-                .is_some();
+            let mut running = is_running(&system);
 
             while !running && attempts <= 2 {
                 match powershell_script::run(&script) {
@@ -2150,11 +2864,7 @@ fn main() -> Result<()> {
 
                 system.refresh_processes(ProcessesToUpdate::All, true);
 
-                if system
-                    .processes_by_name("komorebi.exe".as_ref())
-                    .next()
-                    .is_some()
-                {
+                if is_running(&system) {
                     println!("Started!");
                     running = true;
                 } else {
@@ -2421,9 +3131,9 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             }
 
             if arg.ignore_restore {
-                send_message(&SocketMessage::StopIgnoreRestore)?;
+                send_message_and_wait(&SocketMessage::StopIgnoreRestore)?;
             } else {
-                send_message(&SocketMessage::Stop)?;
+                send_message_and_wait(&SocketMessage::Stop)?;
             }
             let mut system = sysinfo::System::new_all();
             system.refresh_processes(ProcessesToUpdate::All, true);
@@ -2438,7 +3148,7 @@ Stop-Process -Name:komorebi -ErrorAction SilentlyContinue
                     Ok(_) => {
                         println!("{script}");
 
-                        let hwnd_json = DATA_DIR.join("komorebi.hwnd.json");
+                        let hwnd_json = komorebi_client::hwnd_json_path();
 
                         let file = File::open(hwnd_json)?;
                         let reader = BufReader::new(file);
@@ -2526,14 +3236,23 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 }
             }
         }
-        SubCommand::IgnoreRule(arg) => {
-            send_message(&SocketMessage::IgnoreRule(arg.identifier, arg.id))?;
-        }
+        SubCommand::IgnoreRule(arg) => match arg.matching_strategy {
+            None => {
+                send_message_and_wait(&SocketMessage::IgnoreRule(arg.identifier, arg.id))?;
+            }
+            Some(matching_strategy) => {
+                send_message_and_wait(&SocketMessage::IgnoreRuleWithMatchingStrategy(
+                    arg.identifier,
+                    arg.id,
+                    matching_strategy,
+                ))?;
+            }
+        },
         SubCommand::ManageRule(arg) => {
-            send_message(&SocketMessage::ManageRule(arg.identifier, arg.id))?;
+            send_message_and_wait(&SocketMessage::ManageRule(arg.identifier, arg.id))?;
         }
         SubCommand::InitialWorkspaceRule(arg) => {
-            send_message(&SocketMessage::InitialWorkspaceRule(
+            send_message_and_wait(&SocketMessage::InitialWorkspaceRule(
                 arg.identifier,
                 arg.id,
                 arg.monitor,
@@ -2541,14 +3260,14 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             ))?;
         }
         SubCommand::InitialNamedWorkspaceRule(arg) => {
-            send_message(&SocketMessage::InitialNamedWorkspaceRule(
+            send_message_and_wait(&SocketMessage::InitialNamedWorkspaceRule(
                 arg.identifier,
                 arg.id,
                 arg.workspace,
             ))?;
         }
         SubCommand::WorkspaceRule(arg) => {
-            send_message(&SocketMessage::WorkspaceRule(
+            send_message_and_wait(&SocketMessage::WorkspaceRule(
                 arg.identifier,
                 arg.id,
                 arg.monitor,
@@ -2556,112 +3275,142 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             ))?;
         }
         SubCommand::NamedWorkspaceRule(arg) => {
-            send_message(&SocketMessage::NamedWorkspaceRule(
+            send_message_and_wait(&SocketMessage::NamedWorkspaceRule(
                 arg.identifier,
                 arg.id,
                 arg.workspace,
             ))?;
         }
         SubCommand::ClearWorkspaceRules(arg) => {
-            send_message(&SocketMessage::ClearWorkspaceRules(
+            send_message_and_wait(&SocketMessage::ClearWorkspaceRules(
                 arg.monitor,
                 arg.workspace,
             ))?;
         }
         SubCommand::ClearNamedWorkspaceRules(arg) => {
-            send_message(&SocketMessage::ClearNamedWorkspaceRules(arg.workspace))?;
+            send_message_and_wait(&SocketMessage::ClearNamedWorkspaceRules(arg.workspace))?;
         }
         SubCommand::ClearAllWorkspaceRules => {
-            send_message(&SocketMessage::ClearAllWorkspaceRules)?;
+            send_message_and_wait(&SocketMessage::ClearAllWorkspaceRules)?;
         }
         SubCommand::EnforceWorkspaceRules => {
-            send_message(&SocketMessage::EnforceWorkspaceRules)?;
+            send_message_and_wait(&SocketMessage::EnforceWorkspaceRules)?;
+        }
+        SubCommand::Exec(arg) => {
+            send_message_and_wait(&SocketMessage::Exec(arg.command, arg.workspace, arg.float))?;
+        }
+        SubCommand::WaitForWindow(arg) => {
+            // komorebi only replies once the window is managed or the timeout has passed
+            send_message_and_wait_with_timeout(
+                &SocketMessage::WaitForWindow(arg.identifier, arg.id, arg.timeout),
+                Duration::from_millis(arg.timeout) + komorebi_client::MESSAGE_PROCESSING_TIMEOUT,
+            )?;
         }
         SubCommand::Stack(arg) => {
-            send_message(&SocketMessage::StackWindow(arg.operation_direction))?;
+            send_message_and_wait(&SocketMessage::StackWindow(arg.operation_direction))?;
         }
         SubCommand::StackAll => {
-            send_message(&SocketMessage::StackAll)?;
+            send_message_and_wait(&SocketMessage::StackAll)?;
         }
         SubCommand::Unstack => {
-            send_message(&SocketMessage::UnstackWindow)?;
+            send_message_and_wait(&SocketMessage::UnstackWindow)?;
         }
         SubCommand::UnstackAll => {
-            send_message(&SocketMessage::UnstackAll)?;
+            send_message_and_wait(&SocketMessage::UnstackAll)?;
         }
         SubCommand::FocusStackWindow(arg) => {
-            send_message(&SocketMessage::FocusStackWindow(arg.target))?;
+            send_message_and_wait(&SocketMessage::FocusStackWindow(arg.target))?;
         }
         SubCommand::CycleStack(arg) => {
-            send_message(&SocketMessage::CycleStack(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleStack(arg.cycle_direction))?;
         }
         SubCommand::CycleStackIndex(arg) => {
-            send_message(&SocketMessage::CycleStackIndex(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleStackIndex(arg.cycle_direction))?;
         }
         SubCommand::ChangeLayout(arg) => {
-            send_message(&SocketMessage::ChangeLayout(arg.default_layout))?;
+            send_message_and_wait(&SocketMessage::ChangeLayout(arg.default_layout))?;
         }
         SubCommand::CycleLayout(arg) => {
-            send_message(&SocketMessage::CycleLayout(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleLayout(arg.cycle_direction))?;
         }
         SubCommand::LoadCustomLayout(arg) => {
-            send_message(&SocketMessage::ChangeLayoutCustom(resolve_home_path(
+            send_message_and_wait(&SocketMessage::ChangeLayoutCustom(resolve_home_path(
+                arg.path,
+            )?))?;
+        }
+        SubCommand::SaveCustomLayout(arg) => {
+            send_message_and_wait(&SocketMessage::SaveCustomLayout(resolve_home_path(
                 arg.path,
             )?))?;
         }
         SubCommand::FlipLayout(arg) => {
-            send_message(&SocketMessage::FlipLayout(arg.axis))?;
+            send_message_and_wait(&SocketMessage::FlipLayout(arg.axis))?;
+        }
+        SubCommand::ToggleSplitDirection => {
+            send_message_and_wait(&SocketMessage::ToggleSplitDirection)?;
+        }
+        SubCommand::SplitHorizontal => {
+            send_message_and_wait(&SocketMessage::SplitHorizontal)?;
+        }
+        SubCommand::SplitVertical => {
+            send_message_and_wait(&SocketMessage::SplitVertical)?;
         }
         SubCommand::FocusMonitor(arg) => {
-            send_message(&SocketMessage::FocusMonitorNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::FocusMonitorNumber(arg.target))?;
+        }
+        SubCommand::FocusMonitorAlias(arg) => {
+            send_message_and_wait(&SocketMessage::FocusMonitorAlias(arg.alias))?;
         }
         SubCommand::FocusMonitorAtCursor => {
-            send_message(&SocketMessage::FocusMonitorAtCursor)?;
+            send_message_and_wait(&SocketMessage::FocusMonitorAtCursor)?;
         }
         SubCommand::FocusLastWorkspace => {
-            send_message(&SocketMessage::FocusLastWorkspace)?;
+            send_message_and_wait(&SocketMessage::FocusLastWorkspace)?;
         }
         SubCommand::FocusWorkspace(arg) => {
-            send_message(&SocketMessage::FocusWorkspaceNumber(arg.target))?;
+            send_message_and_wait(&SocketMessage::FocusWorkspaceNumber(arg.target))?;
+        }
+        SubCommand::FocusWorkspaceWithMouse(arg) => {
+            send_message_and_wait(&SocketMessage::FocusWorkspaceNumberWithMouse(arg.target))?;
         }
         SubCommand::FocusWorkspaces(arg) => {
-            send_message(&SocketMessage::FocusWorkspaceNumbers(arg.target))?;
+            send_message_and_wait(&SocketMessage::FocusWorkspaceNumbers(arg.target))?;
         }
         SubCommand::FocusMonitorWorkspace(arg) => {
-            send_message(&SocketMessage::FocusMonitorWorkspaceNumber(
+            send_message_and_wait(&SocketMessage::FocusMonitorWorkspaceNumber(
                 arg.target_monitor,
                 arg.target_workspace,
             ))?;
         }
         SubCommand::FocusNamedWorkspace(arg) => {
-            send_message(&SocketMessage::FocusNamedWorkspace(arg.workspace))?;
+            send_message_and_wait(&SocketMessage::FocusNamedWorkspace(arg.workspace))?;
         }
         SubCommand::CloseWorkspace => {
-            send_message(&SocketMessage::CloseWorkspace)?;
+            send_message_and_wait(&SocketMessage::CloseWorkspace)?;
         }
         SubCommand::CycleMonitor(arg) => {
-            send_message(&SocketMessage::CycleFocusMonitor(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleFocusMonitor(arg.cycle_direction))?;
         }
         SubCommand::CycleWorkspace(arg) => {
-            send_message(&SocketMessage::CycleFocusWorkspace(arg.cycle_direction))?;
+            send_message_and_wait(&SocketMessage::CycleFocusWorkspace(arg.cycle_direction))?;
         }
         SubCommand::CycleEmptyWorkspace(arg) => {
-            send_message(&SocketMessage::CycleFocusEmptyWorkspace(
+            send_message_and_wait(&SocketMessage::CycleFocusEmptyWorkspace(
                 arg.cycle_direction,
             ))?;
         }
         SubCommand::NewWorkspace => {
-            send_message(&SocketMessage::NewWorkspace)?;
+            send_message_and_wait(&SocketMessage::NewWorkspace)?;
         }
         SubCommand::WorkspaceName(name) => {
-            send_message(&SocketMessage::WorkspaceName(
+            send_message_and_wait(&SocketMessage::WorkspaceName(
                 name.monitor,
                 name.workspace,
                 name.value,
             ))?;
         }
         SubCommand::MonitorIndexPreference(arg) => {
-            send_message(&SocketMessage::MonitorIndexPreference(
+            send_message_and_wait(&SocketMessage::MonitorIndexPreference(
                 arg.index_preference,
                 arg.left,
                 arg.top,
@@ -2670,19 +3419,19 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             ))?;
         }
         SubCommand::DisplayIndexPreference(arg) => {
-            send_message(&SocketMessage::DisplayIndexPreference(
+            send_message_and_wait(&SocketMessage::DisplayIndexPreference(
                 arg.index_preference,
                 arg.display,
             ))?;
         }
         SubCommand::EnsureWorkspaces(workspaces) => {
-            send_message(&SocketMessage::EnsureWorkspaces(
+            send_message_and_wait(&SocketMessage::EnsureWorkspaces(
                 workspaces.monitor,
                 workspaces.workspace_count,
             ))?;
         }
         SubCommand::EnsureNamedWorkspaces(arg) => {
-            send_message(&SocketMessage::EnsureNamedWorkspaces(
+            send_message_and_wait(&SocketMessage::EnsureNamedWorkspaces(
                 arg.monitor,
                 arg.names,
             ))?;
@@ -2690,23 +3439,46 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::State => {
             print_query(&SocketMessage::State);
         }
+        SubCommand::Inspect => {
+            inspect::inspect()?;
+        }
         SubCommand::GlobalState => {
             print_query(&SocketMessage::GlobalState);
         }
+        SubCommand::Ping => {
+            print_query(&SocketMessage::Ping);
+        }
         SubCommand::Gui => {
             Command::new("komorebi-gui").spawn()?;
         }
-        SubCommand::VisibleWindows => {
-            print_query(&SocketMessage::VisibleWindows);
+        SubCommand::VisibleWindows(args) => {
+            if args.all {
+                print_query(&SocketMessage::DebugVisibleWindows);
+            } else {
+                print_query(&SocketMessage::VisibleWindows);
+            }
+        }
+        SubCommand::FocusedWindow(args) => {
+            std::thread::sleep(Duration::from_secs(args.delay));
+            let hwnd = komorebi_client::WindowsApi::foreground_window()?;
+            print_query(&SocketMessage::DebugWindow(hwnd));
         }
         SubCommand::MonitorInformation => {
             print_query(&SocketMessage::MonitorInformation);
         }
+        SubCommand::PreviewLayout(arg) => {
+            print_query(&SocketMessage::PreviewLayout(arg.default_layout));
+        }
+        SubCommand::PreviewLayoutCustom(arg) => {
+            print_query(&SocketMessage::PreviewLayoutCustom(resolve_home_path(
+                arg.path,
+            )?));
+        }
         SubCommand::Query(arg) => {
             print_query(&SocketMessage::Query(arg.state_query));
         }
         SubCommand::RestoreWindows => {
-            let hwnd_json = DATA_DIR.join("komorebi.hwnd.json");
+            let hwnd_json = komorebi_client::hwnd_json_path();
 
             let file = File::open(hwnd_json)?;
             let reader = BufReader::new(file);
@@ -2717,47 +3489,91 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             }
         }
         SubCommand::ResizeEdge(resize) => {
-            send_message(&SocketMessage::ResizeWindowEdge(resize.edge, resize.sizing))?;
+            send_message_and_wait(&SocketMessage::ResizeWindowEdge(resize.edge, resize.sizing))?;
         }
         SubCommand::ResizeAxis(arg) => {
-            send_message(&SocketMessage::ResizeWindowAxis(arg.axis, arg.sizing))?;
+            send_message_and_wait(&SocketMessage::ResizeWindowAxis(arg.axis, arg.sizing))?;
         }
         SubCommand::FocusFollowsMouse(arg) => {
-            send_message(&SocketMessage::FocusFollowsMouse(
+            send_message_and_wait(&SocketMessage::FocusFollowsMouse(
                 arg.implementation,
                 arg.boolean_state.into(),
             ))?;
         }
         SubCommand::ReplaceConfiguration(arg) => {
-            send_message(&SocketMessage::ReplaceConfiguration(arg.path))?;
+            send_message_and_wait(&SocketMessage::ReplaceConfiguration(arg.path))?;
+        }
+        SubCommand::ApplyAppSpecificConfiguration(arg) => {
+            send_message_and_wait(&SocketMessage::ApplySpecificConfiguration(
+                resolve_home_path(arg.path)?,
+            ))?;
+        }
+        SubCommand::DumpRules(arg) => {
+            send_message_and_wait(&SocketMessage::DumpRules(resolve_home_path(arg.path)?))?;
+        }
+        SubCommand::LoadRules(arg) => {
+            send_message_and_wait(&SocketMessage::LoadRules(resolve_home_path(arg.path)?))?;
         }
         SubCommand::ReloadConfiguration => {
-            send_message(&SocketMessage::ReloadConfiguration)?;
+            send_message_and_wait(&SocketMessage::ReloadConfiguration)?;
+        }
+        SubCommand::ReloadBindings => {
+            send_message_and_wait(&SocketMessage::ReloadBindings)?;
+        }
+        SubCommand::EnterKeybindingMode(arg) => {
+            send_message_and_wait(&SocketMessage::EnterKeybindingMode(arg.mode))?;
+        }
+        SubCommand::ExitKeybindingMode => {
+            send_message_and_wait(&SocketMessage::ExitKeybindingMode)?;
         }
         SubCommand::WatchConfiguration(arg) => {
-            send_message(&SocketMessage::WatchConfiguration(arg.boolean_state.into()))?;
+            send_message_and_wait(&SocketMessage::WatchConfiguration(arg.boolean_state.into()))?;
         }
         SubCommand::CompleteConfiguration => {
-            send_message(&SocketMessage::CompleteConfiguration)?;
+            send_message_and_wait(&SocketMessage::CompleteConfiguration)?;
         }
         SubCommand::IdentifyObjectNameChangeApplication(target) => {
-            send_message(&SocketMessage::IdentifyObjectNameChangeApplication(
+            send_message_and_wait(&SocketMessage::IdentifyObjectNameChangeApplication(
                 target.identifier,
                 target.id,
             ))?;
         }
         SubCommand::IdentifyTrayApplication(target) => {
-            send_message(&SocketMessage::IdentifyTrayApplication(
+            send_message_and_wait(&SocketMessage::IdentifyTrayApplication(
+                target.identifier,
+                target.id,
+            ))?;
+        }
+        SubCommand::IdentifyStackApplication(target) => {
+            send_message_and_wait(&SocketMessage::IdentifyStackApplication(
                 target.identifier,
                 target.id,
             ))?;
         }
         SubCommand::IdentifyLayeredApplication(target) => {
-            send_message(&SocketMessage::IdentifyLayeredApplication(
+            send_message_and_wait(&SocketMessage::IdentifyLayeredApplication(
                 target.identifier,
                 target.id,
             ))?;
         }
+        SubCommand::IdentifyBorderOverflowApplication(target) => {
+            send_message_and_wait(&SocketMessage::IdentifyBorderOverflowApplication(
+                target.identifier,
+                target.id,
+            ))?;
+        }
+        SubCommand::IdentifyBorderOverflowWithOffset(target) => {
+            send_message_and_wait(&SocketMessage::IdentifyBorderOverflowWithOffset(
+                target.identifier,
+                target.id,
+                Rect {
+                    left: target.left,
+                    top: target.top,
+                    right: target.right,
+                    bottom: target.bottom,
+                },
+            ))?;
+        }
         SubCommand::RemoveTitleBar(target) => {
             match target.identifier {
                 ApplicationIdentifier::Exe => {}
@@ -2766,52 +3582,96 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
                 }
             }
 
-            send_message(&SocketMessage::RemoveTitleBar(target.identifier, target.id))?;
+            send_message_and_wait(&SocketMessage::RemoveTitleBar(target.identifier, target.id))?;
         }
         SubCommand::ToggleTitleBars => {
-            send_message(&SocketMessage::ToggleTitleBars)?;
-        }
-        SubCommand::Manage => {
-            send_message(&SocketMessage::ManageFocusedWindow)?;
+            send_message_and_wait(&SocketMessage::ToggleTitleBars)?;
         }
-        SubCommand::Unmanage => {
-            send_message(&SocketMessage::UnmanageFocusedWindow)?;
+        SubCommand::IdentifyPresentationApplication(target) => {
+            send_message_and_wait(&SocketMessage::IdentifyPresentationApplication(
+                target.identifier,
+                target.id,
+            ))?;
         }
-        SubCommand::QuickSaveResize => {
-            send_message(&SocketMessage::QuickSave)?;
+        SubCommand::TogglePresentationMode => {
+            send_message_and_wait(&SocketMessage::TogglePresentationMode)?;
+        }
+        SubCommand::Manage(arg) => match arg.hwnd {
+            Some(hwnd) => send_message_and_wait(&SocketMessage::ManageWindowByHwnd(hwnd))?,
+            None => send_message_and_wait(&SocketMessage::ManageFocusedWindow)?,
+        },
+        SubCommand::Unmanage(arg) => match arg.hwnd {
+            Some(hwnd) => send_message_and_wait(&SocketMessage::UnmanageWindowByHwnd(hwnd))?,
+            None => send_message_and_wait(&SocketMessage::UnmanageFocusedWindow)?,
+        },
+        SubCommand::QuickSaveResize(arg) => {
+            if arg.include.is_empty() {
+                send_message_and_wait(&SocketMessage::QuickSave)?;
+            } else {
+                send_message_and_wait(&SocketMessage::QuickSaveWithOptions(arg.include))?;
+            }
         }
         SubCommand::QuickLoadResize => {
-            send_message(&SocketMessage::QuickLoad)?;
+            send_message_and_wait(&SocketMessage::QuickLoad)?;
         }
         SubCommand::SaveResize(arg) => {
-            send_message(&SocketMessage::Save(resolve_home_path(arg.path)?))?;
+            let path = resolve_home_path(arg.path)?;
+            if arg.include.is_empty() {
+                send_message_and_wait(&SocketMessage::Save(path))?;
+            } else {
+                send_message_and_wait(&SocketMessage::SaveWithOptions(path, arg.include))?;
+            }
         }
         SubCommand::LoadResize(arg) => {
-            send_message(&SocketMessage::Load(resolve_home_path(arg.path)?))?;
+            send_message_and_wait(&SocketMessage::Load(resolve_home_path(arg.path)?))?;
         }
         SubCommand::SubscribeSocket(arg) => {
-            send_message(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            if !arg.filter_state_changes && arg.max_notifications_per_second.is_none() {
+                send_message_and_wait(&SocketMessage::AddSubscriberSocket(arg.socket))?;
+            } else {
+                send_message_and_wait(&SocketMessage::AddSubscriberSocketWithOptions(
+                    arg.socket,
+                    SubscribeOptions {
+                        filter_state_changes: arg.filter_state_changes,
+                        max_notifications_per_second: arg.max_notifications_per_second,
+                    },
+                ))?;
+            }
         }
         SubCommand::UnsubscribeSocket(arg) => {
-            send_message(&SocketMessage::RemoveSubscriberSocket(arg.socket))?;
+            send_message_and_wait(&SocketMessage::RemoveSubscriberSocket(arg.socket))?;
         }
         SubCommand::SubscribePipe(arg) => {
-            send_message(&SocketMessage::AddSubscriberPipe(arg.named_pipe))?;
+            send_message_and_wait(&SocketMessage::AddSubscriberPipe(arg.named_pipe))?;
         }
         SubCommand::UnsubscribePipe(arg) => {
-            send_message(&SocketMessage::RemoveSubscriberPipe(arg.named_pipe))?;
+            send_message_and_wait(&SocketMessage::RemoveSubscriberPipe(arg.named_pipe))?;
+        }
+        SubCommand::ListSubscribers => {
+            print_query(&SocketMessage::ListSubscribers);
         }
         SubCommand::ToggleMouseFollowsFocus => {
-            send_message(&SocketMessage::ToggleMouseFollowsFocus)?;
+            send_message_and_wait(&SocketMessage::ToggleMouseFollowsFocus)?;
         }
         SubCommand::MouseFollowsFocus(arg) => {
-            send_message(&SocketMessage::MouseFollowsFocus(arg.boolean_state.into()))?;
+            send_message_and_wait(&SocketMessage::MouseFollowsFocus(arg.boolean_state.into()))?;
+        }
+        SubCommand::VirtualDesktopAwareness(arg) => {
+            send_message_and_wait(&SocketMessage::VirtualDesktopAwareness(
+                arg.boolean_state.into(),
+            ))?;
+        }
+        SubCommand::StackBySameExe(arg) => {
+            send_message_and_wait(&SocketMessage::StackBySameExe(arg.boolean_state.into()))?;
+        }
+        SubCommand::MaxContainerWindows(arg) => {
+            send_message_and_wait(&SocketMessage::MaxContainerWindows(arg.max_windows))?;
         }
         SubCommand::Border(arg) => {
-            send_message(&SocketMessage::Border(arg.boolean_state.into()))?;
+            send_message_and_wait(&SocketMessage::Border(arg.boolean_state.into()))?;
         }
         SubCommand::BorderColour(arg) => {
-            send_message(&SocketMessage::BorderColour(
+            send_message_and_wait(&SocketMessage::BorderColour(
                 arg.window_kind,
                 arg.r,
                 arg.g,
@@ -2819,82 +3679,95 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
             ))?;
         }
         SubCommand::BorderWidth(arg) => {
-            send_message(&SocketMessage::BorderWidth(arg.width))?;
+            send_message_and_wait(&SocketMessage::BorderWidth(arg.width))?;
         }
         SubCommand::BorderOffset(arg) => {
-            send_message(&SocketMessage::BorderOffset(arg.offset))?;
+            send_message_and_wait(&SocketMessage::BorderOffset(arg.offset))?;
         }
         SubCommand::BorderStyle(arg) => {
-            send_message(&SocketMessage::BorderStyle(arg.style))?;
+            send_message_and_wait(&SocketMessage::BorderStyle(arg.style))?;
         }
         SubCommand::BorderImplementation(arg) => {
-            send_message(&SocketMessage::BorderImplementation(arg.style))?;
+            send_message_and_wait(&SocketMessage::BorderImplementation(arg.style))?;
         }
         SubCommand::StackbarMode(arg) => {
-            send_message(&SocketMessage::StackbarMode(arg.mode))?;
+            send_message_and_wait(&SocketMessage::StackbarMode(arg.mode))?;
         }
         SubCommand::Transparency(arg) => {
-            send_message(&SocketMessage::Transparency(arg.boolean_state.into()))?;
+            send_message_and_wait(&SocketMessage::Transparency(arg.boolean_state.into()))?;
         }
         SubCommand::TransparencyAlpha(arg) => {
-            send_message(&SocketMessage::TransparencyAlpha(arg.alpha))?;
+            send_message_and_wait(&SocketMessage::TransparencyAlpha(arg.alpha))?;
         }
         SubCommand::ToggleTransparency => {
-            send_message(&SocketMessage::ToggleTransparency)?;
+            send_message_and_wait(&SocketMessage::ToggleTransparency)?;
         }
         SubCommand::Animation(arg) => {
-            send_message(&SocketMessage::Animation(
+            send_message_and_wait(&SocketMessage::Animation(
                 arg.boolean_state.into(),
                 arg.animation_type,
             ))?;
         }
         SubCommand::AnimationDuration(arg) => {
-            send_message(&SocketMessage::AnimationDuration(
+            send_message_and_wait(&SocketMessage::AnimationDuration(
                 arg.duration,
                 arg.animation_type,
             ))?;
         }
         SubCommand::AnimationFps(arg) => {
-            send_message(&SocketMessage::AnimationFps(arg.fps))?;
+            send_message_and_wait(&SocketMessage::AnimationFps(arg.fps))?;
         }
         SubCommand::AnimationStyle(arg) => {
-            send_message(&SocketMessage::AnimationStyle(
+            send_message_and_wait(&SocketMessage::AnimationStyle(
                 arg.style,
                 arg.animation_type,
             ))?;
         }
 
         SubCommand::ResizeDelta(arg) => {
-            send_message(&SocketMessage::ResizeDelta(arg.pixels))?;
+            send_message_and_wait(&SocketMessage::ResizeDelta(arg.pixels))?;
         }
         SubCommand::ToggleWindowContainerBehaviour => {
-            send_message(&SocketMessage::ToggleWindowContainerBehaviour)?;
+            send_message_and_wait(&SocketMessage::ToggleWindowContainerBehaviour)?;
         }
         SubCommand::ToggleFloatOverride => {
-            send_message(&SocketMessage::ToggleFloatOverride)?;
+            send_message_and_wait(&SocketMessage::ToggleFloatOverride)?;
         }
         SubCommand::ToggleWorkspaceWindowContainerBehaviour => {
-            send_message(&SocketMessage::ToggleWorkspaceWindowContainerBehaviour)?;
+            send_message_and_wait(&SocketMessage::ToggleWorkspaceWindowContainerBehaviour)?;
         }
         SubCommand::ToggleWorkspaceFloatOverride => {
-            send_message(&SocketMessage::ToggleWorkspaceFloatOverride)?;
+            send_message_and_wait(&SocketMessage::ToggleWorkspaceFloatOverride)?;
         }
         SubCommand::ToggleWorkspaceLayer => {
-            send_message(&SocketMessage::ToggleWorkspaceLayer)?;
+            send_message_and_wait(&SocketMessage::ToggleWorkspaceLayer)?;
         }
         SubCommand::WindowHidingBehaviour(arg) => {
-            send_message(&SocketMessage::WindowHidingBehaviour(arg.hiding_behaviour))?;
+            send_message_and_wait(&SocketMessage::WindowHidingBehaviour(arg.hiding_behaviour))?;
+        }
+        SubCommand::FloatingWindowZOrder(arg) => {
+            send_message_and_wait(&SocketMessage::FloatingWindowZOrder(arg.floating_z_order))?;
         }
         SubCommand::CrossMonitorMoveBehaviour(arg) => {
-            send_message(&SocketMessage::CrossMonitorMoveBehaviour(
+            send_message_and_wait(&SocketMessage::CrossMonitorMoveBehaviour(
                 arg.move_behaviour,
             ))?;
         }
         SubCommand::ToggleCrossMonitorMoveBehaviour => {
-            send_message(&SocketMessage::ToggleCrossMonitorMoveBehaviour)?;
+            send_message_and_wait(&SocketMessage::ToggleCrossMonitorMoveBehaviour)?;
+        }
+        SubCommand::DirectionalFocusBehaviour(arg) => {
+            send_message_and_wait(&SocketMessage::DirectionalFocusBehaviour(
+                arg.directional_focus_behaviour,
+            ))?;
+        }
+        SubCommand::MonocleNewWindowBehaviour(arg) => {
+            send_message_and_wait(&SocketMessage::MonocleWithNewWindowBehaviour(
+                arg.monocle_with_new_window_behaviour,
+            ))?;
         }
         SubCommand::UnmanagedWindowOperationBehaviour(arg) => {
-            send_message(&SocketMessage::UnmanagedWindowOperationBehaviour(
+            send_message_and_wait(&SocketMessage::UnmanagedWindowOperationBehaviour(
                 arg.operation_behaviour,
             ))?;
         }
@@ -3014,8 +3887,8 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::SocketSchema => {
             #[cfg(feature = "schemars")]
             {
-                let socket_message = schemars::schema_for!(SocketMessage);
-                let schema = serde_json::to_string_pretty(&socket_message)?;
+                let schema =
+                    serde_json::to_string_pretty(&komorebi_client::socket_protocol_schema())?;
                 println!("{schema}");
             }
         }
@@ -3037,8 +3910,41 @@ if (Get-Command Get-CimInstance -ErrorAction SilentlyContinue) {
         SubCommand::GenerateStaticConfig => {
             print_query(&SocketMessage::GenerateStaticConfig);
         }
+        SubCommand::Completions(arg) => {
+            let mut cli = Opts::command();
+            let mut stdout = std::io::stdout();
+
+            if arg.module {
+                if arg.shell != CompletionShell::Powershell {
+                    bail!("modules can only be generated for powershell");
+                }
+
+                print!("{}", powershell_module(&cli));
+            } else {
+                match arg.shell {
+                    CompletionShell::Bash => {
+                        generate(Shell::Bash, &mut cli, "komorebic", &mut stdout);
+                    }
+                    CompletionShell::Elvish => {
+                        generate(Shell::Elvish, &mut cli, "komorebic", &mut stdout);
+                    }
+                    CompletionShell::Fish => {
+                        generate(Shell::Fish, &mut cli, "komorebic", &mut stdout);
+                    }
+                    CompletionShell::Nushell => {
+                        generate(Nushell, &mut cli, "komorebic", &mut stdout);
+                    }
+                    CompletionShell::Powershell => {
+                        generate(Shell::PowerShell, &mut cli, "komorebic", &mut stdout);
+                    }
+                    CompletionShell::Zsh => {
+                        generate(Shell::Zsh, &mut cli, "komorebic", &mut stdout);
+                    }
+                }
+            }
+        }
         // Deprecated
-        SubCommand::AltFocusHack(_) | SubCommand::IdentifyBorderOverflowApplication(_) => {
+        SubCommand::AltFocusHack(_) => {
             println!("Command deprecated - this is now automatically handled by komorebi! 🎉");
         }
     }